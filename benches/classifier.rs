@@ -0,0 +1,93 @@
+extern crate classifier;
+#[macro_use]
+extern crate criterion;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use criterion::{black_box, Criterion, Throughput};
+use classifier::{CharNgramTokenizer, NaiveBayes, NaiveBayesVariant, Tokenizer, WhitespaceTokenizer};
+use classifier::dataset;
+
+// counts the bytes currently allocated, so the memory a model holds on to
+// can be reported next to the timings
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+static CORPUS: &str = include_str!("../tests/data/foods.jsonl");
+
+// the bundled corpus repeated until it is big enough to time
+fn corpus() -> Vec<(String, String)> {
+    let examples: Vec<(String, String)> = dataset::from_jsonl(CORPUS.as_bytes())
+        .map(|record| record.unwrap())
+        .collect();
+    examples.iter().cycle().take(examples.len() * 20).cloned().collect()
+}
+
+fn trained(examples: &[(String, String)], variant: NaiveBayesVariant) -> NaiveBayes {
+    let mut nb = NaiveBayes::new();
+    nb.set_variant(variant);
+    nb.add_documents(examples);
+    nb.train();
+    nb
+}
+
+fn training(c: &mut Criterion) {
+    let examples = corpus();
+    let mut group = c.benchmark_group("training");
+    group.throughput(Throughput::Elements(examples.len() as u64));
+    for &variant in [NaiveBayesVariant::Multinomial, NaiveBayesVariant::Bernoulli, NaiveBayesVariant::Complement].iter() {
+        group.bench_function(format!("{:?}", variant), |b| b.iter(|| trained(&examples, variant)));
+    }
+    group.finish();
+}
+
+fn classification(c: &mut Criterion) {
+    let examples = corpus();
+    let documents: Vec<String> = examples.iter().map(|(document, _)| document.clone()).collect();
+    let mut group = c.benchmark_group("classification");
+    for &variant in [NaiveBayesVariant::Multinomial, NaiveBayesVariant::Bernoulli, NaiveBayesVariant::Complement].iter() {
+        let nb = trained(&examples, variant);
+        group.bench_function(format!("{:?}", variant), |b| b.iter(|| nb.classify(black_box(&documents[0]))));
+    }
+    let nb = trained(&examples, NaiveBayesVariant::Multinomial);
+    group.throughput(Throughput::Elements(documents.len() as u64));
+    group.bench_function("batch", |b| b.iter(|| nb.classify_batch(black_box(&documents))));
+    group.finish();
+}
+
+fn tokenization(c: &mut Criterion) {
+    let examples = corpus();
+    let document = &examples[0].0;
+    let mut group = c.benchmark_group("tokenization");
+    group.bench_function("whitespace", |b| b.iter(|| WhitespaceTokenizer.tokenize(black_box(document))));
+    let char_ngrams = CharNgramTokenizer::default();
+    group.bench_function("char_ngrams", |b| b.iter(|| char_ngrams.tokenize(black_box(document))));
+    group.finish();
+}
+
+fn memory(_: &mut Criterion) {
+    let examples = corpus();
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    let nb = trained(&examples, NaiveBayesVariant::Multinomial);
+    let after = ALLOCATED.load(Ordering::Relaxed);
+    println!("memory: a model of {} words holds {} bytes", nb.vocab_size(), after.saturating_sub(before));
+}
+
+criterion_group!(benches, tokenization, training, classification, memory);
+criterion_main!(benches);
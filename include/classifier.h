@@ -0,0 +1,49 @@
+/* C interface to the classifier crate, built with its `ffi` feature.
+ * See the documentation of the crate's `ffi` module. */
+
+#ifndef CLASSIFIER_H
+#define CLASSIFIER_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* A naive Bayes classifier, only ever handled through a pointer */
+typedef struct NaiveBayes NaiveBayes;
+
+/* Creates a new classifier, free it with classifier_free */
+NaiveBayes *classifier_new(void);
+
+/* Builds a classifier from a JSON string, NULL on failure */
+NaiveBayes *classifier_from_json(const char *json);
+
+/* Encodes the classifier as JSON, NULL on failure. Free the string with
+ * classifier_string_free. */
+char *classifier_to_json(const NaiveBayes *nb);
+
+/* Adds a labeled document, returns 0 or -1 on failure */
+int classifier_add_document(NaiveBayes *nb, const char *document, const char *label);
+
+/* Trains the classifier on the documents added so far, returns 0 or -1 on
+ * failure */
+int classifier_train(NaiveBayes *nb);
+
+/* Computes a classifying label for the document, NULL on failure. Free the
+ * string with classifier_string_free. */
+char *classifier_classify(const NaiveBayes *nb, const char *document);
+
+/* Frees a classifier, does nothing for NULL */
+void classifier_free(NaiveBayes *nb);
+
+/* Frees a string handed back by the library, does nothing for NULL */
+void classifier_string_free(char *s);
+
+/* Describes the last failure on the calling thread, NULL if nothing has
+ * failed. Valid until the next failure on the same thread. */
+const char *classifier_last_error(void);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CLASSIFIER_H */
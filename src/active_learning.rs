@@ -0,0 +1,56 @@
+//! Choosing which unlabeled documents are most worth labeling by hand
+//!
+//! A classifier learns the most from the documents it is least sure about,
+//! so labeling those first builds a good training set from fewer labels:
+//!
+//! ```rust
+//! use classifier::NaiveBayes;
+//! use classifier::active_learning::Uncertainty;
+//!
+//! let mut nb = NaiveBayes::new();
+//! nb.add_document("pork belly ribs", "meat");
+//! nb.add_document("kale okra spinach", "veggie");
+//! nb.train();
+//!
+//! let unlabeled = ["pork ribs", "pork kale", "okra spinach"];
+//! let ranked = nb.most_uncertain(&unlabeled, 1).unwrap();
+//! assert_eq!(ranked[0].0, 1);
+//! let ranked = nb.most_uncertain_by(&unlabeled, 3, Uncertainty::Margin).unwrap();
+//! assert_eq!(ranked.len(), 3);
+//! ```
+
+/// How uncertain a classifier is about a document, measured from the
+/// posterior probabilities of its labels. Higher is more uncertain.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Uncertainty {
+    /// The entropy, in bits, of the posteriors (the default), which takes
+    /// every label into account
+    #[default]
+    Entropy,
+    /// One minus the difference between the posteriors of the two most
+    /// likely labels, for documents that fall between two labels
+    Margin,
+    /// One minus the posterior of the most likely label
+    LeastConfidence,
+}
+
+impl Uncertainty {
+
+    /// Measures the uncertainty of the posterior probabilities of every
+    /// label, which should sum to 1
+    pub fn score(&self, posteriors: &[f64]) -> f64 {
+        match *self {
+            Uncertainty::Entropy => posteriors.iter()
+                .filter(|&&p| p > 0.0)
+                .map(|&p| -p * p.log2())
+                .sum(),
+            Uncertainty::Margin => {
+                let (first, second) = posteriors.iter().fold((0.0, 0.0), |(first, second), &p| {
+                    if p > first { (p, first) } else if p > second { (first, p) } else { (first, second) }
+                });
+                1.0 - (first - second)
+            },
+            Uncertainty::LeastConfidence => 1.0 - posteriors.iter().fold(0.0, |acc: f64, &p| acc.max(p)),
+        }
+    }
+}
@@ -0,0 +1,215 @@
+//! Trains, uses, and inspects naive Bayes models from the shell
+//!
+//! Datasets are CSV files with `text` and `label` columns or JSONL files
+//! with a `{"text": ..., "label": ...}` record per line, told apart by
+//! their extension. Models are saved as JSON.
+
+extern crate classifier;
+extern crate csv;
+#[macro_use]
+extern crate serde_derive;
+
+use std::env;
+use std::fs::File;
+use std::io::{self, BufRead};
+use std::path::Path;
+use std::process;
+use classifier::{ClassifierError, NaiveBayes, NaiveBayesVariant};
+use classifier::dataset;
+use classifier::evaluation::ConfusionMatrix;
+
+static USAGE: &str = "\
+usage: classifier-cli <command> [options]
+
+commands:
+  train <dataset> <model> [--variant multinomial|bernoulli|complement] [--smoothing <value>]
+        trains a model on a dataset and saves it
+  classify <model> [--posteriors] [document]
+        classifies the document, or every line of standard input
+  evaluate <model> <dataset>
+        reports the accuracy of a model on a labeled dataset
+  inspect <model> [--top <n>]
+        lists the labels of a model and their most probable words
+
+datasets are .csv files with text and label columns, or .jsonl files";
+
+// the positional arguments and the (name, value) options of a command
+type Arguments<'a> = (Vec<&'a str>, Vec<(&'a str, &'a str)>);
+
+#[derive(Deserialize)]
+struct CsvRecord {
+    text: String,
+    label: String,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.is_empty() || args[0] == "-h" || args[0] == "--help" {
+        println!("{}", USAGE);
+        return;
+    }
+    let result = match args[0].as_str() {
+        "train" => train(&args[1..]),
+        "classify" => classify(&args[1..]),
+        "evaluate" => evaluate(&args[1..]),
+        "inspect" => inspect(&args[1..]),
+        command => Err(format!("unknown command {}\n\n{}", command, USAGE)),
+    };
+    if let Err(message) = result {
+        eprintln!("error: {}", message);
+        process::exit(1);
+    }
+}
+
+fn train(args: &[String]) -> Result<(), String> {
+    let (positional, options) = parse(args, &["--variant", "--smoothing"], &[])?;
+    let (dataset_path, model_path) = match positional.as_slice() {
+        [dataset, model] => (dataset, model),
+        _ => return Err(format!("train takes a dataset and a model\n\n{}", USAGE)),
+    };
+
+    let mut nb = NaiveBayes::new();
+    if let Some(variant) = option(&options, "--variant") {
+        nb.set_variant(match variant {
+            "multinomial" => NaiveBayesVariant::Multinomial,
+            "bernoulli" => NaiveBayesVariant::Bernoulli,
+            "complement" => NaiveBayesVariant::Complement,
+            _ => return Err(format!("unknown variant {}", variant)),
+        });
+    }
+    if let Some(smoothing) = option(&options, "--smoothing") {
+        let smoothing = smoothing.parse().map_err(|_| format!("invalid smoothing {}", smoothing))?;
+        nb.set_smoothing(smoothing).map_err(|e| e.to_string())?;
+    }
+
+    let examples = read_dataset(Path::new(dataset_path))?;
+    nb.add_documents(&examples);
+    nb.train();
+    nb.save_to_file(model_path).map_err(|e| e.to_string())?;
+    println!("trained on {} documents, {} labels, {} words", examples.len(), nb.get_labels().len(), nb.vocab_size());
+    Ok(())
+}
+
+fn classify(args: &[String]) -> Result<(), String> {
+    let (positional, options) = parse(args, &[], &["--posteriors"])?;
+    let nb = match positional.first() {
+        Some(model) => load(model)?,
+        None => return Err(format!("classify takes a model\n\n{}", USAGE)),
+    };
+    let posteriors = options.iter().any(|&(name, _)| name == "--posteriors");
+    let print = |document: &str| -> Result<(), String> {
+        if posteriors {
+            let mut scores = nb.get_document_posteriors(document).map_err(|e| e.to_string())?;
+            scores.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            let scores: Vec<String> = scores.iter().map(|(label, p)| format!("{}\t{:.4}", label, p)).collect();
+            println!("{}", scores.join("\t"));
+        } else {
+            println!("{}", nb.classify(document).map_err(|e| e.to_string())?);
+        }
+        Ok(())
+    };
+
+    if positional.len() > 1 {
+        return print(&positional[1..].join(" "));
+    }
+    let stdin = io::stdin();
+    for line in stdin.lock().lines() {
+        print(&line.map_err(|e| e.to_string())?)?;
+    }
+    Ok(())
+}
+
+fn evaluate(args: &[String]) -> Result<(), String> {
+    let (positional, _) = parse(args, &[], &[])?;
+    let (model_path, dataset_path) = match positional.as_slice() {
+        [model, dataset] => (model, dataset),
+        _ => return Err(format!("evaluate takes a model and a dataset\n\n{}", USAGE)),
+    };
+    let nb = load(model_path)?;
+    let examples = read_dataset(Path::new(dataset_path))?;
+
+    let results: Vec<(String, String)> = nb.classify_batch(&examples.iter().map(|(text, _)| text.clone()).collect::<Vec<_>>())
+        .into_iter()
+        .zip(examples.into_iter().map(|(_, label)| label))
+        .map(|(predicted, label)| predicted.map(|predicted| (predicted, label)))
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.to_string())?;
+    let matrix = ConfusionMatrix::from_pairs(&results);
+
+    println!("accuracy\t{:.4}", matrix.accuracy());
+    println!("label\tprecision\trecall\tf1\tsupport");
+    for report in matrix.report() {
+        println!("{}\t{:.4}\t{:.4}\t{:.4}\t{}", report.label, report.precision, report.recall, report.f1, report.support);
+    }
+    Ok(())
+}
+
+fn inspect(args: &[String]) -> Result<(), String> {
+    let (positional, options) = parse(args, &["--top"], &[])?;
+    let nb = match positional.as_slice() {
+        [model] => load(model)?,
+        _ => return Err(format!("inspect takes a model\n\n{}", USAGE)),
+    };
+    let top = match option(&options, "--top") {
+        Some(n) => n.parse().map_err(|_| format!("invalid number of words {}", n))?,
+        None => 10,
+    };
+
+    println!("{} words", nb.vocab_size());
+    let mut labels = nb.get_labels();
+    labels.sort();
+    for label in labels.iter() {
+        let words: Vec<String> = nb.top_words_for_label(label, top).into_iter().map(|(word, _)| word).collect();
+        println!("{}\t{}", label, words.join(" "));
+    }
+    Ok(())
+}
+
+// splits the arguments into positional ones and options, flags have an
+// empty value
+fn parse<'a>(args: &'a [String], valued: &[&str], flags: &[&str]) -> Result<Arguments<'a>, String> {
+    let mut positional = Vec::new();
+    let mut options = Vec::new();
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if valued.contains(&arg.as_str()) {
+            let value = args.next().ok_or_else(|| format!("{} needs a value", arg))?;
+            options.push((arg.as_str(), value.as_str()));
+        } else if flags.contains(&arg.as_str()) {
+            options.push((arg.as_str(), ""));
+        } else if arg.starts_with("--") {
+            return Err(format!("unknown option {}\n\n{}", arg, USAGE));
+        } else {
+            positional.push(arg.as_str());
+        }
+    }
+    Ok((positional, options))
+}
+
+fn option<'a>(options: &[(&str, &'a str)], name: &str) -> Option<&'a str> {
+    options.iter().rev().find(|&&(option, _)| option == name).map(|&(_, value)| value)
+}
+
+fn load(path: &str) -> Result<NaiveBayes, String> {
+    NaiveBayes::load_from_file(path).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn read_dataset(path: &Path) -> Result<Vec<(String, String)>, String> {
+    let context = |error: String| format!("{}: {}", path.display(), error);
+    let file = File::open(path).map_err(|e| context(e.to_string()))?;
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("csv") => {
+            let mut reader = csv::Reader::from_reader(file);
+            let mut examples = Vec::new();
+            for record in reader.deserialize() {
+                let record: CsvRecord = record.map_err(|e| context(e.to_string()))?;
+                examples.push((record.text, record.label));
+            }
+            Ok(examples)
+        },
+        Some("jsonl") | Some("ndjson") => {
+            dataset::from_jsonl(file).collect::<Result<_, _>>().map_err(|e: ClassifierError| context(e.to_string()))
+        },
+        _ => Err(context("datasets must be .csv or .jsonl files".to_string())),
+    }
+}
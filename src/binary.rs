@@ -0,0 +1,60 @@
+use std::io::Write;
+use bincode;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use error::ClassifierError;
+
+// every binary model starts with these bytes followed by the format version
+// as a little endian u16, so readers can reject models they don't understand.
+// bincode writes fields by position, so the version is increased with every
+// change to the layout of a model, and older layouts are still decoded (see
+// `NaiveBayes::from_bytes`), tests/data holds a model of every version. The
+// layouts of the naive Bayes classifier have added:
+// 1. nothing, the classifier itself is encoded
+// 2. weighted counts, priors, and feature hashing
+// 3. model versions, label smoothing and metadata, unknown word policies,
+//    and token filters
+// 4. calibration and document IDs
+// 5. skip-grams, word count limits, fixed vocabularies, punctuation
+//    stripping, and token length limits
+static MAGIC: &[u8; 4] = b"NBCL";
+pub static VERSION: u16 = 5;
+static HEADER_LEN: usize = 6;
+
+// encodes a model with bincode behind the versioned header
+pub fn encode<S: Serialize>(model: &S) -> Result<Vec<u8>, ClassifierError> {
+    let mut bytes = Vec::new();
+    bytes.write_all(MAGIC).and_then(|_| bytes.write_all(&VERSION.to_le_bytes()))
+        .map_err(|e| ClassifierError::Encode(e.to_string()))?;
+    bincode::serialize_into(&mut bytes, model).map_err(|e| ClassifierError::Encode(e.to_string()))?;
+    Ok(bytes)
+}
+
+// checks the header, returning the format version and the bincode model
+// after it, and refuses models from newer versions of the format
+pub fn split(bytes: &[u8]) -> Result<(u16, &[u8]), ClassifierError> {
+    if bytes.len() < HEADER_LEN || &bytes[..4] != MAGIC {
+        return Err(ClassifierError::Decode("not a binary classifier model".to_string()));
+    }
+    let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+    if version == 0 || version > VERSION {
+        return Err(unsupported(version));
+    }
+    Ok((version, &bytes[HEADER_LEN..]))
+}
+
+// decodes a bincode model, the bytes after the header
+pub fn decode_payload<D: DeserializeOwned>(payload: &[u8]) -> Result<D, ClassifierError> {
+    bincode::deserialize(payload).map_err(|e| ClassifierError::Decode(e.to_string()))
+}
+
+// checks the header and decodes a model whose layout is the same in every
+// version of the format
+pub fn decode<D: DeserializeOwned>(bytes: &[u8]) -> Result<D, ClassifierError> {
+    decode_payload(split(bytes)?.1)
+}
+
+// the error for a version of the format a model can't be read from
+pub fn unsupported(version: u16) -> ClassifierError {
+    ClassifierError::UnsupportedModelVersion(u32::from(version), u32::from(VERSION))
+}
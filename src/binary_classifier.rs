@@ -0,0 +1,153 @@
+use error::ClassifierError;
+use evaluation::{roc_curve, RocCurve};
+use naive_bayes::Classifier;
+use tokenizer::{Tokenizer, WhitespaceTokenizer};
+
+/// A naive Bayes classifier for the common case of exactly two labels, such
+/// as spam and ham, which scores documents by the log odds of the positive
+/// label and can be evaluated by the area under its ROC curve
+///
+/// ```rust
+/// use classifier::BinaryClassifier;
+///
+/// let mut spam = BinaryClassifier::new("spam", "ham");
+/// spam.add_document("win free money now", true);
+/// spam.add_document("lunch at noon tomorrow", false);
+/// spam.train();
+///
+/// assert!(spam.score("free money").unwrap() > 0.0);
+/// assert!(spam.classify_bool("free money", 0.0).unwrap());
+/// assert_eq!(spam.classify("lunch tomorrow").unwrap(), "ham");
+/// ```
+#[derive(Debug, Clone)]
+pub struct BinaryClassifier<T = WhitespaceTokenizer> {
+    classifier: Classifier<T>,
+    positive: String,
+    negative: String,
+}
+
+impl BinaryClassifier {
+
+    /// Creates a new classifier with the given labels that tokenizes
+    /// documents by breaking on whitespace characters
+    pub fn new(positive: &str, negative: &str) -> BinaryClassifier {
+        BinaryClassifier::from_classifier(Classifier::new(), positive, negative)
+    }
+}
+
+impl<T: Tokenizer> BinaryClassifier<T> {
+
+    /// Wraps a classifier, which may have been configured (with a tokenizer,
+    /// smoothing value, etc.) or trained already, in which case it should
+    /// not have labels other than `positive` and `negative`
+    pub fn from_classifier(classifier: Classifier<T>, positive: &str, negative: &str) -> BinaryClassifier<T> {
+        BinaryClassifier { classifier, positive: positive.to_string(), negative: negative.to_string() }
+    }
+
+    /// Gets the classifier, for everything the binary classifier leaves out
+    pub fn classifier(&self) -> &Classifier<T> {
+        &self.classifier
+    }
+
+    /// Gets the classifier mutably, e.g. to change its settings
+    pub fn classifier_mut(&mut self) -> &mut Classifier<T> {
+        &mut self.classifier
+    }
+
+    /// Unwraps the classifier
+    pub fn into_classifier(self) -> Classifier<T> {
+        self.classifier
+    }
+
+    /// The label of positive documents
+    pub fn positive_label(&self) -> &str {
+        &self.positive
+    }
+
+    /// The label of negative documents
+    pub fn negative_label(&self) -> &str {
+        &self.negative
+    }
+
+    /// Adds a document with the positive label if `positive` is true, or
+    /// the negative label otherwise
+    pub fn add_document(&mut self, document: &str, positive: bool) {
+        let label = self.label(positive).to_string();
+        self.classifier.add_document(document, &label);
+    }
+
+    /// Adds a tokenized document with the positive or negative label, see
+    /// `add_document`
+    pub fn add_document_tokenized<S: AsRef<str>>(&mut self, document: &[S], positive: bool) {
+        let label = self.label(positive).to_string();
+        self.classifier.add_document_tokenized(document, &label);
+    }
+
+    /// Trains the classifier on the documents that have been added so far
+    pub fn train(&mut self) {
+        self.classifier.train();
+    }
+
+    /// Takes a document that has been tokenized into a vector of strings and
+    /// computes the log odds of the positive label given the document, the
+    /// natural log of its posterior probability over that of the negative
+    /// label. Positive scores favor the positive label, and unlike the
+    /// posteriors the scores don't saturate at 0 or 1 for long documents.
+    /// Fails as `NaiveBayes::classify` would, or if the classifier has not
+    /// been trained on documents with both labels.
+    pub fn score_tokenized<S: AsRef<str>>(&self, document: &[S]) -> Result<f64, ClassifierError> {
+        let scores = self.classifier.log_scores_tokenized(document)?;
+        let score = |label: &str| scores.iter()
+            .find(|&(l, _)| l == label)
+            .map(|&(_, score)| score)
+            .ok_or_else(|| ClassifierError::MissingLabel(label.to_string()));
+        Ok(score(&self.positive)? - score(&self.negative)?)
+    }
+
+    /// Tokenizes the document with the classifier's tokenizer and computes
+    /// the log odds of the positive label, see `score_tokenized`
+    pub fn score(&self, document: &str) -> Result<f64, ClassifierError> {
+        self.score_tokenized(&self.classifier.tokenize(document))
+    }
+
+    /// Whether the document is positive, that is its score is at least
+    /// `threshold`. A threshold of 0 picks the more likely label, a higher
+    /// one trades recall of the positive label for precision.
+    pub fn classify_bool(&self, document: &str, threshold: f64) -> Result<bool, ClassifierError> {
+        Ok(self.score(document)? >= threshold)
+    }
+
+    /// Computes the more likely of the two labels for the document
+    pub fn classify(&self, document: &str) -> Result<&str, ClassifierError> {
+        self.classify_bool(document, 0.0).map(|positive| self.label(positive))
+    }
+
+    /// Scores each of the (document, whether it is positive) examples and
+    /// computes the ROC curve, to choose a threshold for `classify_bool`
+    /// from the precision and recall of each one. Fails if a document can't
+    /// be scored or the examples don't include both positive and negative
+    /// ones.
+    pub fn roc_curve<D: AsRef<str>>(&self, examples: &[(D, bool)]) -> Result<RocCurve, ClassifierError> {
+        let scores = examples.iter()
+            .map(|(document, _)| self.score(document.as_ref()))
+            .collect::<Result<Vec<f64>, _>>()?;
+        let truths: Vec<bool> = examples.iter().map(|&(_, positive)| positive).collect();
+        for &positive in [true, false].iter() {
+            if !truths.contains(&positive) {
+                return Err(ClassifierError::MissingLabel(self.label(positive).to_string()));
+            }
+        }
+        Ok(roc_curve(&scores, &truths))
+    }
+
+    /// Computes the area under the ROC curve of the examples, see
+    /// `roc_curve` and `RocCurve::auc`. 1 is a perfect ranking and 0.5 is no
+    /// better than chance.
+    pub fn roc_auc<D: AsRef<str>>(&self, examples: &[(D, bool)]) -> Result<f64, ClassifierError> {
+        Ok(self.roc_curve(examples)?.auc())
+    }
+
+    fn label(&self, positive: bool) -> &str {
+        if positive { &self.positive } else { &self.negative }
+    }
+}
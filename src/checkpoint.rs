@@ -0,0 +1,105 @@
+use std::fs;
+use std::path::Path;
+use error::ClassifierError;
+use naive_bayes::Classifier;
+use persistence::{self, Compression};
+use tokenizer::Tokenizer;
+
+// the file in the checkpoint directory the checkpoints are written to
+static CHECKPOINT_FILE: &str = "checkpoint.json";
+
+// a snapshot of a classifier partway through a stream of examples, along
+// with how many examples of the stream it has taken in
+#[derive(Serialize)]
+#[serde(bound = "")]
+struct Checkpoint<'a, T: 'a> {
+    documents: usize,
+    classifier: &'a Classifier<T>,
+}
+
+#[derive(Deserialize)]
+struct SavedCheckpoint {
+    documents: usize,
+    classifier: Classifier,
+}
+
+impl<T: Tokenizer> Classifier<T> {
+
+    /// Adds every (document, label) pair of an iterator to the classifier
+    /// and trains it, like `add_documents_iter` followed by `train`, but
+    /// saves a checkpoint to `checkpoint.json` in `dir` after every
+    /// `every_n_docs` documents and once more when training is done. The
+    /// directory is created if it doesn't exist. If the run is cut short,
+    /// `resume_from_checkpoint` picks it up from the last checkpoint.
+    ///
+    /// Fails if `every_n_docs` is 0 or a checkpoint can't be written.
+    ///
+    /// ```rust,no_run
+    /// use classifier::NaiveBayes;
+    ///
+    /// let examples = vec![("pork belly ribs", "meat"), ("kale okra spinach", "veggie")];
+    /// let mut nb = NaiveBayes::new();
+    /// nb.train_with_checkpoints(examples.clone(), "checkpoints", 1000).unwrap();
+    ///
+    /// // after a crash, a classifier set up the same way carries on from
+    /// // the last checkpoint, skipping the documents it already has
+    /// let mut nb = NaiveBayes::new();
+    /// nb.resume_from_checkpoint("checkpoints/checkpoint.json", examples, 1000).unwrap();
+    /// ```
+    pub fn train_with_checkpoints<I, D, L, P>(&mut self, examples: I, dir: P, every_n_docs: usize)
+        -> Result<(), ClassifierError>
+        where I: IntoIterator<Item = (D, L)>, D: AsRef<str>, L: AsRef<str>, P: AsRef<Path>
+    {
+        if every_n_docs == 0 {
+            return Err(ClassifierError::InvalidCheckpointInterval(every_n_docs));
+        }
+        fs::create_dir_all(dir.as_ref()).map_err(|e| ClassifierError::Io(e.to_string()))?;
+        self.add_checkpointed(examples, &dir.as_ref().join(CHECKPOINT_FILE), every_n_docs, 0)
+    }
+
+    /// Carries on a run of `train_with_checkpoints` from the checkpoint
+    /// file at `path`. The classifier's model is replaced with the one in
+    /// the checkpoint, and `examples` must be the same stream the run was
+    /// given from its start: the documents the checkpoint already has are
+    /// skipped, and the rest are added and trained on while checkpoints
+    /// are written to the same file.
+    ///
+    /// The tokenizer and sanitizer aren't saved in checkpoints, so the
+    /// classifier should have been created and set up with the same ones
+    /// the interrupted run used. Fails if `every_n_docs` is 0 or the
+    /// checkpoint can't be read or written, and nothing changes if it can't
+    /// be read.
+    pub fn resume_from_checkpoint<P, I, D, L>(&mut self, path: P, examples: I, every_n_docs: usize)
+        -> Result<(), ClassifierError>
+        where P: AsRef<Path>, I: IntoIterator<Item = (D, L)>, D: AsRef<str>, L: AsRef<str>
+    {
+        if every_n_docs == 0 {
+            return Err(ClassifierError::InvalidCheckpointInterval(every_n_docs));
+        }
+        let checkpoint: SavedCheckpoint = persistence::load(path.as_ref())?;
+        self.restore(checkpoint.classifier);
+        let examples = examples.into_iter().skip(checkpoint.documents);
+        self.add_checkpointed(examples, path.as_ref(), every_n_docs, checkpoint.documents)
+    }
+
+    // adds the examples that follow the first `documents` of a stream,
+    // checkpointing to `path`, and trains
+    fn add_checkpointed<I, D, L>(&mut self, examples: I, path: &Path, every_n_docs: usize, mut documents: usize)
+        -> Result<(), ClassifierError>
+        where I: IntoIterator<Item = (D, L)>, D: AsRef<str>, L: AsRef<str>
+    {
+        for (document, label) in examples {
+            self.add_document(document.as_ref(), label.as_ref());
+            documents += 1;
+            if documents.is_multiple_of(every_n_docs) {
+                self.save_checkpoint(path, documents)?;
+            }
+        }
+        self.train();
+        self.save_checkpoint(path, documents)
+    }
+
+    fn save_checkpoint(&self, path: &Path, documents: usize) -> Result<(), ClassifierError> {
+        persistence::save(&Checkpoint { documents, classifier: self }, path, Compression::None)
+    }
+}
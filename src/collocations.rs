@@ -0,0 +1,162 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use error::ClassifierError;
+use tokenizer::{Tokenizer, WhitespaceTokenizer};
+
+/// Word pairs that appear together far more often than chance, such as
+/// "hot dog" or "green bean", found in a training corpus by `learn` and
+/// merged into single tokens ("hot_dog") by `merge` or a
+/// `CollocationTokenizer`
+///
+/// A pair is a collocation when it appears at least a minimum number of
+/// times and its normalized pointwise mutual information is at least a
+/// threshold. The pointwise mutual information of "hot dog" is the log of
+/// how much more often "hot" is followed by "dog" than it would be if the
+/// words were independent, and normalizing it divides that by minus the log
+/// probability of the pair, giving 1 for words that only ever appear
+/// together and 0 for independent ones.
+///
+/// ```rust
+/// use classifier::{Collocations, CollocationTokenizer, NaiveBayes, Tokenizer, WhitespaceTokenizer};
+///
+/// let corpus = ["a hot dog with mustard", "hot dog and fries", "grilled hot dog", "a cold drink"];
+/// let documents: Vec<Vec<String>> = corpus.iter().map(|document| WhitespaceTokenizer.tokenize(document)).collect();
+/// let collocations = Collocations::learn(&documents, 3, 0.5).unwrap();
+/// assert_eq!(collocations.pairs(), vec![("hot", "dog")]);
+///
+/// let tokenizer = CollocationTokenizer::new(WhitespaceTokenizer, collocations);
+/// assert_eq!(tokenizer.tokenize("a hot dog"), vec!["a", "hot_dog"]);
+/// let mut nb = NaiveBayes::with_tokenizer(tokenizer);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Collocations {
+    // the words that follow each first word of a collocation
+    pairs: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Collocations {
+
+    /// Creates an empty set of collocations, which merges nothing
+    pub fn new() -> Collocations {
+        Collocations::default()
+    }
+
+    /// Finds the collocations of tokenized documents, the pairs of adjacent
+    /// tokens that appear at least `min_count` times with a normalized
+    /// pointwise mutual information of at least `threshold`, which must be
+    /// greater than 0 and at most 1
+    pub fn learn<S: AsRef<str>>(documents: &[Vec<S>], min_count: usize, threshold: f64)
+        -> Result<Collocations, ClassifierError>
+    {
+        if !(threshold > 0.0 && threshold <= 1.0) {
+            return Err(ClassifierError::InvalidThreshold(threshold));
+        }
+        let mut words: HashMap<&str, usize> = HashMap::new();
+        let mut bigrams: HashMap<(&str, &str), usize> = HashMap::new();
+        let mut total = 0;
+        for document in documents.iter() {
+            for token in document.iter() {
+                *words.entry(token.as_ref()).or_insert(0) += 1;
+                total += 1;
+            }
+            for pair in document.windows(2) {
+                *bigrams.entry((pair[0].as_ref(), pair[1].as_ref())).or_insert(0) += 1;
+            }
+        }
+
+        let mut collocations = Collocations::new();
+        let total = total as f64;
+        for (&(first, second), &count) in bigrams.iter() {
+            if count < min_count.max(1) {
+                continue;
+            }
+            let p_pair = count as f64 / total;
+            let p_first = words[first] as f64 / total;
+            let p_second = words[second] as f64 / total;
+            // a pair making up the whole corpus has nothing to normalize by
+            let npmi = if p_pair >= 1.0 { 1.0 } else { (p_pair / (p_first * p_second)).ln() / -p_pair.ln() };
+            if npmi >= threshold {
+                collocations.insert(first, second);
+            }
+        }
+        Ok(collocations)
+    }
+
+    /// Adds a pair of words to merge
+    pub fn insert(&mut self, first: &str, second: &str) {
+        self.pairs.entry(first.to_string()).or_default().insert(second.to_string());
+    }
+
+    /// Whether the pair of words is a collocation
+    pub fn contains(&self, first: &str, second: &str) -> bool {
+        self.pairs.get(first).is_some_and(|seconds| seconds.contains(second))
+    }
+
+    /// Gets every collocation, sorted
+    pub fn pairs(&self) -> Vec<(&str, &str)> {
+        self.pairs.iter()
+            .flat_map(|(first, seconds)| seconds.iter().map(move |second| (first.as_str(), second.as_str())))
+            .collect()
+    }
+
+    /// The number of collocations
+    pub fn len(&self) -> usize {
+        self.pairs.values().map(|seconds| seconds.len()).sum()
+    }
+
+    /// Whether there are no collocations
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Joins every collocation in the tokens into a single token, with an
+    /// underscore between its words. Tokens are merged from the start, so
+    /// in "a b c" with both "a b" and "b c" collocations only "a_b" is.
+    pub fn merge<S: AsRef<str>>(&self, tokens: &[S]) -> Vec<String> {
+        let mut merged = Vec::with_capacity(tokens.len());
+        let mut i = 0;
+        while i < tokens.len() {
+            let token = tokens[i].as_ref();
+            match tokens.get(i + 1) {
+                Some(next) if self.contains(token, next.as_ref()) => {
+                    merged.push(format!("{}_{}", token, next.as_ref()));
+                    i += 2;
+                },
+                _ => {
+                    merged.push(token.to_string());
+                    i += 1;
+                },
+            }
+        }
+        merged
+    }
+}
+
+/// A tokenizer that merges the collocations in the tokens produced by
+/// another tokenizer, see `Collocations`. The collocations should be
+/// learned from tokens made the same way, after any preprocessing the
+/// classifier does.
+#[derive(Debug, Clone, Default)]
+pub struct CollocationTokenizer<T = WhitespaceTokenizer> {
+    tokenizer: T,
+    collocations: Collocations,
+}
+
+impl<T: Tokenizer> CollocationTokenizer<T> {
+
+    /// Creates a tokenizer that merges the collocations in the tokens of
+    /// `tokenizer`
+    pub fn new(tokenizer: T, collocations: Collocations) -> CollocationTokenizer<T> {
+        CollocationTokenizer { tokenizer, collocations }
+    }
+
+    /// Gets the collocations the tokenizer merges
+    pub fn collocations(&self) -> &Collocations {
+        &self.collocations
+    }
+}
+
+impl<T: Tokenizer> Tokenizer for CollocationTokenizer<T> {
+    fn tokenize(&self, document: &str) -> Vec<String> {
+        self.collocations.merge(&self.tokenizer.tokenize(document))
+    }
+}
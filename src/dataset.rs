@@ -0,0 +1,350 @@
+//! Loading, saving, and splitting labeled examples
+//!
+//! ```rust
+//! use classifier::dataset;
+//!
+//! let examples: Vec<(String, String)> = vec![
+//!     ("ham hock pork belly".to_string(), "meat".to_string()),
+//!     ("kale okra spinach".to_string(), "veggie".to_string()),
+//!     ("ball tip short ribs".to_string(), "meat".to_string()),
+//!     ("water chestnut kale".to_string(), "veggie".to_string()),
+//! ];
+//!
+//! let (train, test) = dataset::stratified_train_test_split(&examples, 0.5, 42).unwrap();
+//! assert_eq!(train.len(), 2);
+//! assert_eq!(test.len(), 2);
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use serde_json;
+#[cfg(feature = "encoding-detection")]
+use chardetng::EncodingDetector;
+#[cfg(feature = "encoding-detection")]
+use encoding_rs::Encoding;
+use error::ClassifierError;
+#[cfg(feature = "rand")]
+use rand::Rng;
+use random::{Shuffle, SplitMix64};
+
+/// A (training set, testing set) pair of (document, label) examples
+pub type Split = (Vec<(String, String)>, Vec<(String, String)>);
+
+/// Shuffles the (document, label) examples and splits them into a training
+/// set and a testing set, with `test_ratio` of the examples in the testing
+/// set. The same seed always gives the same split.
+pub fn train_test_split<D, L>(examples: &[(D, L)], test_ratio: f64, seed: u64) -> Result<Split, ClassifierError>
+    where D: AsRef<str>, L: AsRef<str>
+{
+    split(examples, test_ratio, &mut SplitMix64::new(seed))
+}
+
+/// Same as `train_test_split`, but shuffles with the given generator from
+/// the `rand` crate. Requires the `rand` feature.
+#[cfg(feature = "rand")]
+pub fn train_test_split_with_rng<D, L, R>(examples: &[(D, L)], test_ratio: f64, rng: &mut R)
+                                         -> Result<Split, ClassifierError>
+    where D: AsRef<str>, L: AsRef<str>, R: Rng + ?Sized
+{
+    split(examples, test_ratio, rng)
+}
+
+/// Same as `train_test_split`, but the examples of each label are split
+/// separately, so every label that has at least two examples appears in
+/// both sets
+pub fn stratified_train_test_split<D, L>(examples: &[(D, L)], test_ratio: f64, seed: u64)
+                                         -> Result<Split, ClassifierError>
+    where D: AsRef<str>, L: AsRef<str>
+{
+    stratified_split(examples, test_ratio, &mut SplitMix64::new(seed))
+}
+
+/// Same as `stratified_train_test_split`, but shuffles with the given
+/// generator from the `rand` crate. Requires the `rand` feature.
+#[cfg(feature = "rand")]
+pub fn stratified_train_test_split_with_rng<D, L, R>(examples: &[(D, L)], test_ratio: f64, rng: &mut R)
+                                                    -> Result<Split, ClassifierError>
+    where D: AsRef<str>, L: AsRef<str>, R: Rng + ?Sized
+{
+    stratified_split(examples, test_ratio, rng)
+}
+
+fn split<D, L, S>(examples: &[(D, L)], test_ratio: f64, rng: &mut S) -> Result<Split, ClassifierError>
+    where D: AsRef<str>, L: AsRef<str>, S: Shuffle + ?Sized
+{
+    check_ratio(test_ratio)?;
+
+    let mut indices: Vec<usize> = (0..examples.len()).collect();
+    rng.shuffle(&mut indices);
+
+    let num_test = test_size(indices.len(), test_ratio);
+    Ok(collect(examples, &indices[num_test..], &indices[..num_test]))
+}
+
+fn stratified_split<D, L, S>(examples: &[(D, L)], test_ratio: f64, rng: &mut S) -> Result<Split, ClassifierError>
+    where D: AsRef<str>, L: AsRef<str>, S: Shuffle + ?Sized
+{
+    check_ratio(test_ratio)?;
+
+    let mut by_label: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (i, (_, label)) in examples.iter().enumerate() {
+        by_label.entry(label.as_ref()).or_default().push(i);
+    }
+
+    let mut train = Vec::new();
+    let mut test = Vec::new();
+    for indices in by_label.values_mut() {
+        rng.shuffle(indices);
+        let num_test = test_size(indices.len(), test_ratio);
+        test.extend_from_slice(&indices[..num_test]);
+        train.extend_from_slice(&indices[num_test..]);
+    }
+
+    // mix the labels back together so neither set is ordered by label
+    rng.shuffle(&mut train);
+    rng.shuffle(&mut test);
+    Ok(collect(examples, &train, &test))
+}
+
+/// Balances the labels of the (document, label) examples by repeating the
+/// examples of every label until it has as many as the most common label.
+/// A label gets as many whole copies of its examples as fit and then a
+/// random sample of them for the rest, and the result is shuffled. The same
+/// seed always gives the same result.
+pub fn oversample<D, L>(examples: &[(D, L)], seed: u64) -> Vec<(String, String)>
+    where D: AsRef<str>, L: AsRef<str>
+{
+    resample(examples, true, &mut SplitMix64::new(seed))
+}
+
+/// Same as `oversample`, but samples with the given generator from the
+/// `rand` crate. Requires the `rand` feature.
+#[cfg(feature = "rand")]
+pub fn oversample_with_rng<D, L, R>(examples: &[(D, L)], rng: &mut R) -> Vec<(String, String)>
+    where D: AsRef<str>, L: AsRef<str>, R: Rng + ?Sized
+{
+    resample(examples, true, rng)
+}
+
+/// Balances the labels of the (document, label) examples by keeping a
+/// random sample of the examples of every label, as many as the least
+/// common label has, and shuffles the result. The same seed always gives
+/// the same result.
+pub fn undersample<D, L>(examples: &[(D, L)], seed: u64) -> Vec<(String, String)>
+    where D: AsRef<str>, L: AsRef<str>
+{
+    resample(examples, false, &mut SplitMix64::new(seed))
+}
+
+/// Same as `undersample`, but samples with the given generator from the
+/// `rand` crate. Requires the `rand` feature.
+#[cfg(feature = "rand")]
+pub fn undersample_with_rng<D, L, R>(examples: &[(D, L)], rng: &mut R) -> Vec<(String, String)>
+    where D: AsRef<str>, L: AsRef<str>, R: Rng + ?Sized
+{
+    resample(examples, false, rng)
+}
+
+// samples every label up to the size of the largest or down to the size of
+// the smallest
+fn resample<D, L, S>(examples: &[(D, L)], up: bool, rng: &mut S) -> Vec<(String, String)>
+    where D: AsRef<str>, L: AsRef<str>, S: Shuffle + ?Sized
+{
+    let mut by_label: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (i, (_, label)) in examples.iter().enumerate() {
+        by_label.entry(label.as_ref()).or_default().push(i);
+    }
+    let sizes = by_label.values().map(|indices| indices.len());
+    let target = if up { sizes.max() } else { sizes.min() }.unwrap_or(0);
+
+    let mut sampled = Vec::with_capacity(target * by_label.len());
+    for indices in by_label.values_mut() {
+        for _ in 0..target / indices.len() {
+            sampled.extend_from_slice(indices);
+        }
+        rng.shuffle(indices);
+        sampled.extend_from_slice(&indices[..target % indices.len()]);
+    }
+    rng.shuffle(&mut sampled);
+    collect(examples, &sampled, &[]).0
+}
+
+/// Reads (document, label) examples from newline-delimited JSON, one
+/// `{"text": ..., "label": ...}` record per line (other fields are ignored).
+/// The records are read as they are needed. A malformed line gives a
+/// `MalformedLine` error and reading carries on with the next line, so bad
+/// lines can be skipped:
+///
+/// ```rust
+/// use classifier::dataset;
+///
+/// let jsonl = "{\"text\": \"pork belly\", \"label\": \"meat\"}\n\
+///              not json\n\
+///              {\"text\": \"kale\", \"label\": \"veggie\"}\n";
+///
+/// let examples: Vec<(String, String)> = dataset::from_jsonl(jsonl.as_bytes())
+///     .filter_map(|record| record.ok())
+///     .collect();
+/// assert_eq!(examples.len(), 2);
+/// ```
+pub fn from_jsonl<R: Read>(reader: R) -> JsonlRecords<BufReader<R>> {
+    JsonlRecords {
+        reader: BufReader::new(reader),
+        line_number: 0,
+        failed: false,
+    }
+}
+
+/// Writes (document, label) examples as newline-delimited JSON, one
+/// `{"text": ..., "label": ...}` record per line
+pub fn to_jsonl<W, D, L>(mut writer: W, examples: &[(D, L)]) -> Result<(), ClassifierError>
+    where W: Write, D: AsRef<str>, L: AsRef<str>
+{
+    for (text, label) in examples.iter() {
+        let record = Record { text: text.as_ref(), label: label.as_ref() };
+        serde_json::to_writer(&mut writer, &record).map_err(|e| ClassifierError::Encode(e.to_string()))?;
+        writer.write_all(b"\n").map_err(io_error)?;
+    }
+    writer.flush().map_err(io_error)
+}
+
+/// The examples of a newline-delimited JSON reader, see `from_jsonl`
+#[derive(Debug)]
+pub struct JsonlRecords<R> {
+    reader: R,
+    line_number: usize,
+    // set once reading fails, nothing more can be read after that
+    failed: bool,
+}
+
+impl<R: BufRead> Iterator for JsonlRecords<R> {
+    type Item = Result<(String, String), ClassifierError>;
+
+    fn next(&mut self) -> Option<Result<(String, String), ClassifierError>> {
+        let mut line = String::new();
+        while !self.failed {
+            line.clear();
+            match self.reader.read_line(&mut line) {
+                Ok(0) => return None,
+                Ok(_) => {},
+                Err(e) => {
+                    self.failed = true;
+                    return Some(Err(io_error(e)));
+                },
+            }
+            self.line_number += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            return Some(serde_json::from_str::<Record<String>>(&line)
+                .map(|record| (record.text, record.label))
+                .map_err(|e| ClassifierError::MalformedLine(self.line_number, e.to_string())));
+        }
+        None
+    }
+}
+
+/// Reads (document, label) examples from a directory with a subdirectory
+/// for each label, every file of which is a document with that label (the
+/// layout of corpora like 20 Newsgroups). Hidden files and directories are
+/// skipped and the examples are read in order of label and then file name.
+/// Every file must be UTF-8, see `from_label_dirs_detecting_encoding` for
+/// corpora that are not.
+pub fn from_label_dirs<P: AsRef<Path>>(path: P) -> Result<Vec<(String, String)>, ClassifierError> {
+    read_label_dirs(path.as_ref(), decode_utf8)
+}
+
+/// Same as `from_label_dirs`, but the encoding of each file is guessed from
+/// its byte order mark or, failing that, from its contents. Requires the
+/// `encoding-detection` feature.
+#[cfg(feature = "encoding-detection")]
+pub fn from_label_dirs_detecting_encoding<P: AsRef<Path>>(path: P) -> Result<Vec<(String, String)>, ClassifierError> {
+    read_label_dirs(path.as_ref(), decode_detected)
+}
+
+fn read_label_dirs(path: &Path, decode: fn(&[u8]) -> Option<String>) -> Result<Vec<(String, String)>, ClassifierError> {
+    let mut examples = Vec::new();
+    for label_dir in sorted_entries(path)? {
+        if !label_dir.is_dir() {
+            continue;
+        }
+        let label = label_dir.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        for file in sorted_entries(&label_dir)? {
+            if !file.is_file() {
+                continue;
+            }
+            let bytes = fs::read(&file).map_err(io_error)?;
+            let text = decode(&bytes).ok_or_else(|| {
+                ClassifierError::Decode(format!("{} is not valid UTF-8", file.display()))
+            })?;
+            examples.push((text, label.clone()));
+        }
+    }
+    Ok(examples)
+}
+
+// the visible entries of a directory, sorted so that examples are always
+// read in the same order
+fn sorted_entries(path: &Path) -> Result<Vec<PathBuf>, ClassifierError> {
+    let mut entries = Vec::new();
+    for entry in fs::read_dir(path).map_err(io_error)? {
+        let entry = entry.map_err(io_error)?;
+        if !entry.file_name().to_string_lossy().starts_with('.') {
+            entries.push(entry.path());
+        }
+    }
+    entries.sort();
+    Ok(entries)
+}
+
+fn decode_utf8(bytes: &[u8]) -> Option<String> {
+    let bytes = if bytes.starts_with(b"\xef\xbb\xbf") { &bytes[3..] } else { bytes };
+    String::from_utf8(bytes.to_vec()).ok()
+}
+
+#[cfg(feature = "encoding-detection")]
+fn decode_detected(bytes: &[u8]) -> Option<String> {
+    let (encoding, bytes) = match Encoding::for_bom(bytes) {
+        Some((encoding, bom_length)) => (encoding, &bytes[bom_length..]),
+        None => {
+            let mut detector = EncodingDetector::new();
+            detector.feed(bytes, true);
+            (detector.guess(None, true), bytes)
+        },
+    };
+    Some(encoding.decode_without_bom_handling(bytes).0.into_owned())
+}
+
+fn io_error(error: io::Error) -> ClassifierError {
+    ClassifierError::Io(error.to_string())
+}
+
+#[derive(Serialize, Deserialize)]
+struct Record<S> {
+    text: S,
+    label: S,
+}
+
+fn check_ratio(test_ratio: f64) -> Result<(), ClassifierError> {
+    if !(test_ratio > 0.0 && test_ratio < 1.0) {
+        return Err(ClassifierError::InvalidSplitRatio(test_ratio));
+    }
+    Ok(())
+}
+
+// the number of the `n` examples to put in the testing set, keeping at
+// least one example in each set whenever there are enough of them
+fn test_size(n: usize, test_ratio: f64) -> usize {
+    if n < 2 {
+        return 0;
+    }
+    let size = (n as f64 * test_ratio).round() as usize;
+    size.max(1).min(n - 1)
+}
+
+fn collect<D: AsRef<str>, L: AsRef<str>>(examples: &[(D, L)], train: &[usize], test: &[usize]) -> Split {
+    let example = |&i: &usize| (examples[i].0.as_ref().to_string(), examples[i].1.as_ref().to_string());
+    (train.iter().map(example).collect(), test.iter().map(example).collect())
+}
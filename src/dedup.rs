@@ -0,0 +1,166 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use error::ClassifierError;
+use random::SplitMix64;
+
+// the number of hash functions in a MinHash signature, and how many of them
+// make up each band of the signature that candidates are looked up by
+static NUM_HASHES: usize = 64;
+static ROWS_PER_BAND: usize = 2;
+
+/// Spots duplicate training documents so they can be skipped, since a
+/// document added many times biases the classifier toward its label
+///
+/// Documents are compared by their words, ignoring case and spacing. By
+/// default only exact duplicates are caught. With `near_duplicates`, a
+/// document is also a duplicate when the overlap (Jaccard similarity) of
+/// its shingles, runs of consecutive words, with those of a document seen
+/// before is at least a threshold. The similarity is estimated from MinHash
+/// signatures, and only documents sharing part of a signature are compared.
+///
+/// ```rust
+/// use classifier::{Deduplicator, NaiveBayes};
+///
+/// let examples = [
+///     ("pork belly and ribs", "meat"),
+///     ("Pork  belly and RIBS", "meat"),
+///     ("kale okra spinach", "veggie"),
+/// ];
+///
+/// let mut nb = NaiveBayes::new();
+/// let mut deduplicator = Deduplicator::new();
+/// let skipped = nb.add_documents_deduplicated(examples.iter().cloned(), &mut deduplicator);
+/// assert_eq!(skipped, 1);
+/// assert_eq!(deduplicator.kept(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Deduplicator {
+    // the hashes of the words of every document kept
+    exact: HashSet<u64>,
+    near: Option<NearDuplicates>,
+    kept: usize,
+    skipped: usize,
+}
+
+#[derive(Debug, Clone)]
+struct NearDuplicates {
+    threshold: f64,
+    shingle_size: usize,
+    // the signature of every document kept
+    signatures: Vec<Vec<u64>>,
+    // the documents with each (band, hash of the band's rows)
+    bands: HashMap<(usize, u64), Vec<usize>>,
+}
+
+impl Deduplicator {
+
+    /// Creates a deduplicator that catches exact duplicates
+    pub fn new() -> Deduplicator {
+        Deduplicator::default()
+    }
+
+    /// Also catches near duplicates, documents whose shingles of
+    /// `shingle_size` words overlap those of a document seen before by at
+    /// least `threshold` (greater than 0 and at most 1). Documents shorter
+    /// than a shingle are a single shingle. Fails if the threshold or
+    /// shingle size is invalid.
+    pub fn near_duplicates(mut self, threshold: f64, shingle_size: usize) -> Result<Deduplicator, ClassifierError> {
+        if !(threshold > 0.0 && threshold <= 1.0) {
+            return Err(ClassifierError::InvalidThreshold(threshold));
+        }
+        if shingle_size == 0 {
+            return Err(ClassifierError::InvalidNgramRange(shingle_size, shingle_size));
+        }
+        self.near = Some(NearDuplicates {
+            threshold,
+            shingle_size,
+            signatures: Vec::new(),
+            bands: HashMap::new(),
+        });
+        Ok(self)
+    }
+
+    /// Whether the document duplicates one seen before. A document that
+    /// isn't is remembered, so that later copies of it are caught.
+    pub fn is_duplicate(&mut self, document: &str) -> bool {
+        let words: Vec<String> = document.split_whitespace().map(|word| word.to_lowercase()).collect();
+        // a near duplicate's hash is remembered too, which is harmless as
+        // any exact copy of it is a near duplicate as well
+        let duplicate = !self.exact.insert(hash(&words))
+            || self.near.as_mut().is_some_and(|near| near.is_duplicate(&words));
+        if duplicate {
+            self.skipped += 1;
+        } else {
+            self.kept += 1;
+        }
+        duplicate
+    }
+
+    /// Keeps the (document, label) examples whose documents are not
+    /// duplicates, see `is_duplicate`
+    pub fn deduplicate<D, L>(&mut self, examples: &[(D, L)]) -> Vec<(String, String)>
+        where D: AsRef<str>, L: AsRef<str>
+    {
+        examples.iter()
+            .filter(|(document, _)| !self.is_duplicate(document.as_ref()))
+            .map(|(document, label)| (document.as_ref().to_string(), label.as_ref().to_string()))
+            .collect()
+    }
+
+    /// The number of documents that were not duplicates
+    pub fn kept(&self) -> usize {
+        self.kept
+    }
+
+    /// The number of documents that were duplicates
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+}
+
+impl NearDuplicates {
+
+    // compares the document with the documents sharing a band of its
+    // signature, and remembers it if it is not a near duplicate of them
+    fn is_duplicate(&mut self, words: &[String]) -> bool {
+        let signature = self.signature(words);
+        let bands: Vec<(usize, u64)> = signature.chunks(ROWS_PER_BAND).map(hash).enumerate().collect();
+        let duplicate = bands.iter()
+            .filter_map(|band| self.bands.get(band))
+            .flat_map(|documents| documents.iter())
+            .any(|&other| similarity(&signature, &self.signatures[other]) >= self.threshold);
+        if !duplicate {
+            let id = self.signatures.len();
+            for band in bands {
+                self.bands.entry(band).or_default().push(id);
+            }
+            self.signatures.push(signature);
+        }
+        duplicate
+    }
+
+    // the smallest value of each hash function over the document's shingles
+    fn signature(&self, words: &[String]) -> Vec<u64> {
+        let size = self.shingle_size.min(words.len()).max(1);
+        let shingles: Vec<u64> = words.windows(size).map(hash).collect();
+        (0..NUM_HASHES as u64).map(|i| {
+            shingles.iter()
+                .map(|&shingle| SplitMix64::new(shingle ^ i.wrapping_mul(0x9E37_79B9_7F4A_7C15)).next())
+                .min()
+                .unwrap_or(0)
+        }).collect()
+    }
+}
+
+// the fraction of the hash functions whose smallest values agree, which
+// estimates the Jaccard similarity of the shingles
+fn similarity(a: &[u64], b: &[u64]) -> f64 {
+    a.iter().zip(b.iter()).filter(|&(x, y)| x == y).count() as f64 / a.len() as f64
+}
+
+fn hash<H: Hash + ?Sized>(value: &H) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::path::Path;
+use sled;
+use error::ClassifierError;
+use naive_bayes::Classifier;
+use tokenizer::{Tokenizer, WhitespaceTokenizer};
+
+// the keys of the database: the count of a word with a label and the number
+// of the label's documents it appeared in are kept under
+// `WORD_COUNTS word \0 label`, so every label of a word is next to the
+// others, and the number of documents of a label under `EXAMPLES label`
+static WORD_COUNTS: u8 = b'w';
+static EXAMPLES: u8 = b'e';
+
+/// Trains a naive Bayes classifier on more documents than fit in memory, by
+/// keeping the word counts in an on-disk database rather than in memory.
+/// Requires the `disk` feature.
+///
+/// Documents are tokenized and turned into features with the settings of the
+/// classifier the trainer was created with. Once every document has been
+/// added, `into_classifier` builds the trained classifier from the counts,
+/// leaving out rare words so that the result fits in memory. The counts stay
+/// in the database, so opening it again carries on where training left off.
+///
+/// ```rust,no_run
+/// use classifier::DiskTrainer;
+///
+/// let mut trainer = DiskTrainer::open("counts.db").unwrap();
+/// trainer.add_document("sirloin pastrami ham hock", "meat").unwrap();
+/// trainer.add_document("okra kale spinach", "veggie").unwrap();
+///
+/// // keep the words seen at least twice
+/// let nb = trainer.into_classifier(2.0).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct DiskTrainer<T = WhitespaceTokenizer> {
+    classifier: Classifier<T>,
+    db: sled::Db,
+}
+
+impl DiskTrainer {
+
+    /// Opens (or creates) the database at `path`, counting documents the way
+    /// a new `NaiveBayes` would
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<DiskTrainer, ClassifierError> {
+        DiskTrainer::with_classifier(path, Classifier::new())
+    }
+}
+
+impl<T: Tokenizer> DiskTrainer<T> {
+
+    /// Opens (or creates) the database at `path`, counting documents with the
+    /// settings (tokenizer, n-grams, stop words, etc.) of `classifier`, which
+    /// `into_classifier` then adds the counts to
+    pub fn with_classifier<P: AsRef<Path>>(path: P, classifier: Classifier<T>) -> Result<DiskTrainer<T>, ClassifierError> {
+        let db = sled::open(path).map_err(db_error)?;
+        db.set_merge_operator(add_counts);
+        Ok(DiskTrainer { classifier, db })
+    }
+
+    /// Takes a document that has been tokenized into a vector of strings and
+    /// a label and adds the document's word counts to the database
+    pub fn add_document_tokenized<S: AsRef<str>>(&mut self, document: &[S], label: &str) -> Result<(), ClassifierError> {
+        if document.is_empty() { return Ok(()); }
+
+        let mut counts: HashMap<String, f64> = HashMap::new();
+        for feature in self.classifier.training_features(document) {
+            *counts.entry(feature.into_owned()).or_insert(0.0) += 1.0;
+        }
+        for (word, count) in counts.iter() {
+            self.db.merge(word_key(word, label), encode(&[*count, 1.0])).map_err(db_error)?;
+        }
+        self.db.merge(examples_key(label), encode(&[1.0])).map_err(db_error)?;
+        Ok(())
+    }
+
+    /// Takes a document and a label, tokenizes the document with the
+    /// classifier's tokenizer and adds its word counts to the database
+    pub fn add_document(&mut self, document: &str, label: &str) -> Result<(), ClassifierError> {
+        let tokens = self.classifier.tokenize(document);
+        self.add_document_tokenized(&tokens, label)
+    }
+
+    /// Makes sure every document added so far has been written to disk
+    pub fn flush(&self) -> Result<(), ClassifierError> {
+        self.db.flush().map(|_| ()).map_err(db_error)
+    }
+
+    /// Builds the trained classifier from the counts in the database, keeping
+    /// only the words seen at least `min_count` times in total
+    pub fn into_classifier(mut self, min_count: f64) -> Result<Classifier<T>, ClassifierError> {
+        // the counts of one word with each of its labels, added once
+        // every label of the word has been seen
+        let mut word: Option<String> = None;
+        let mut labels: Vec<(String, f64, f64)> = Vec::new();
+        for entry in self.db.scan_prefix([WORD_COUNTS]) {
+            let (key, value) = entry.map_err(db_error)?;
+            let (next_word, label) = decode_word_key(&key)?;
+            if word.as_ref() != Some(&next_word) {
+                if let Some(word) = word.take() {
+                    add_word(&mut self.classifier, &word, &labels, min_count);
+                }
+                labels.clear();
+                word = Some(next_word);
+            }
+            let counts = decode(&value, 2)?;
+            labels.push((label, counts[0], counts[1]));
+        }
+        if let Some(word) = word {
+            add_word(&mut self.classifier, &word, &labels, min_count);
+        }
+
+        for entry in self.db.scan_prefix([EXAMPLES]) {
+            let (key, value) = entry.map_err(db_error)?;
+            let label = String::from_utf8(key[1..].to_vec()).map_err(|e| ClassifierError::Decode(e.to_string()))?;
+            self.classifier.add_label_examples(&label, decode(&value, 1)?[0]);
+        }
+        self.classifier.train();
+        Ok(self.classifier)
+    }
+}
+
+fn add_word<T: Tokenizer>(classifier: &mut Classifier<T>, word: &str, labels: &[(String, f64, f64)], min_count: f64) {
+    let total: f64 = labels.iter().map(|&(_, count, _)| count).sum();
+    if total < min_count {
+        return;
+    }
+    for (label, count, documents) in labels.iter() {
+        classifier.add_word_counts(label, word, *count, *documents);
+    }
+}
+
+// sums the f64s of a value with those being merged into it, leaving a
+// corrupt value as it is for `into_classifier` to report
+fn add_counts(_key: &[u8], old: Option<&[u8]>, new: &[u8]) -> Option<Vec<u8>> {
+    let len = new.len() / 8;
+    let mut counts = match decode(new, len) {
+        Ok(counts) => counts,
+        Err(_) => return old.map(|old| old.to_vec()),
+    };
+    if let Some(old) = old {
+        match decode(old, len) {
+            Ok(old) => for (count, old) in counts.iter_mut().zip(old) {
+                *count += old;
+            },
+            Err(_) => return Some(old.to_vec()),
+        }
+    }
+    Some(encode(&counts))
+}
+
+fn encode(counts: &[f64]) -> Vec<u8> {
+    counts.iter().flat_map(|count| count.to_le_bytes()).collect()
+}
+
+// the `len` f64s of a value, failing if it is not exactly that long, as the
+// database may have been damaged on disk
+fn decode(bytes: &[u8], len: usize) -> Result<Vec<f64>, ClassifierError> {
+    if bytes.len() != len * 8 {
+        return Err(ClassifierError::Decode(format!("count value of {} bytes, expected {}", bytes.len(), len * 8)));
+    }
+    Ok(bytes.chunks(8).map(|chunk| {
+        let mut buffer = [0u8; 8];
+        buffer.copy_from_slice(chunk);
+        f64::from_le_bytes(buffer)
+    }).collect())
+}
+
+fn word_key(word: &str, label: &str) -> Vec<u8> {
+    let mut key = vec![WORD_COUNTS];
+    key.extend_from_slice(word.as_bytes());
+    key.push(0);
+    key.extend_from_slice(label.as_bytes());
+    key
+}
+
+fn decode_word_key(key: &[u8]) -> Result<(String, String), ClassifierError> {
+    let key = String::from_utf8(key[1..].to_vec()).map_err(|e| ClassifierError::Decode(e.to_string()))?;
+    match key.find('\0') {
+        Some(split) => Ok((key[..split].to_string(), key[split + 1..].to_string())),
+        None => Err(ClassifierError::Decode(format!("malformed word count key {:?}", key))),
+    }
+}
+
+fn examples_key(label: &str) -> Vec<u8> {
+    let mut key = vec![EXAMPLES];
+    key.extend_from_slice(label.as_bytes());
+    key
+}
+
+fn db_error(error: sled::Error) -> ClassifierError {
+    ClassifierError::Io(error.to_string())
+}
@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+use std::f64;
+use std::fmt;
+use error::ClassifierError;
+use text_classifier::TextClassifier;
+
+/// How an `Ensemble` combines the predictions of its models
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Voting {
+    /// Every model votes for the label it gives the highest probability,
+    /// and the label with the most votes wins. Ties go to the tied label with
+    /// the highest averaged log-probability. The probabilities are the
+    /// shares of the votes.
+    #[default]
+    Majority,
+    /// The log-probabilities the models give each label are averaged, and
+    /// the label with the highest average wins. The probabilities are the
+    /// normalized geometric means of the models' probabilities.
+    AverageLogProbability,
+}
+
+/// Several trained classifiers classifying documents together, which is
+/// often more accurate than any one of them, especially when they read
+/// documents differently (e.g. with different tokenizers or n-gram ranges)
+///
+/// The models can be any `TextClassifier`s, and should have been trained on
+/// the same labels: a label a model doesn't know gets no votes from it and
+/// a probability of 0. An ensemble is a `TextClassifier` itself, adding
+/// documents to and training every one of its models.
+///
+/// ```rust
+/// use classifier::{CharNgramTokenizer, Ensemble, LogisticRegression, NaiveBayes, TextClassifier, Voting};
+///
+/// let mut ensemble = Ensemble::new(Voting::AverageLogProbability);
+/// ensemble.add_model(NaiveBayes::new());
+/// ensemble.add_model(NaiveBayes::with_tokenizer(CharNgramTokenizer::new(3, 4).unwrap()));
+/// ensemble.add_model(LogisticRegression::new());
+/// ensemble.add_document("pork belly ribs", "meat");
+/// ensemble.add_document("kale okra spinach", "veggie");
+/// ensemble.train();
+///
+/// assert_eq!(ensemble.classify("pork ribs").unwrap(), "meat");
+/// ```
+pub struct Ensemble {
+    models: Vec<Box<dyn TextClassifier>>,
+    voting: Voting,
+}
+
+impl Ensemble {
+
+    /// Creates an ensemble without models that combines their predictions
+    /// the given way
+    pub fn new(voting: Voting) -> Ensemble {
+        Ensemble { models: Vec::new(), voting }
+    }
+
+    /// Adds a model, which may have been trained already
+    pub fn add_model<C: TextClassifier + 'static>(&mut self, model: C) {
+        self.models.push(Box::new(model));
+    }
+
+    /// Gets the models in the order they were added
+    pub fn models(&self) -> &[Box<dyn TextClassifier>] {
+        &self.models
+    }
+
+    /// Gets the number of models
+    pub fn len(&self) -> usize {
+        self.models.len()
+    }
+
+    /// Whether the ensemble has no models
+    pub fn is_empty(&self) -> bool {
+        self.models.is_empty()
+    }
+
+    /// Gets the way predictions are combined
+    pub fn voting(&self) -> Voting {
+        self.voting
+    }
+
+    /// Sets the way predictions are combined
+    pub fn set_voting(&mut self, voting: Voting) {
+        self.voting = voting;
+    }
+
+    /// Adds a labeled document to every model for the next call to `train()`
+    pub fn add_document(&mut self, document: &str, label: &str) {
+        for model in self.models.iter_mut() {
+            model.add_document(document, label);
+        }
+    }
+
+    /// Trains every model on the documents that have been added
+    pub fn train(&mut self) {
+        for model in self.models.iter_mut() {
+            model.train();
+        }
+    }
+
+    /// Computes a classifying label for the document by combining the
+    /// models' predictions. Fails with `Untrained` if the ensemble has no
+    /// models, or as the first model that can't give the probabilities of
+    /// the labels given the document does.
+    pub fn classify(&self, document: &str) -> Result<String, ClassifierError> {
+        let probabilities = self.model_probabilities(document)?;
+        let averages = self.average_log_probabilities(&probabilities);
+        let votes = match self.voting {
+            Voting::Majority => votes(&probabilities),
+            Voting::AverageLogProbability => BTreeMap::new(),
+        };
+        averages.into_iter()
+            .map(|(label, average)| (votes.get(&label).cloned().unwrap_or(0), average, label))
+            .max_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)).then(b.2.cmp(&a.2)))
+            .map(|(_, _, label)| label)
+            .ok_or(ClassifierError::Untrained)
+    }
+
+    /// Gets every label any model knows, sorted, and its probability given
+    /// the document: the share of the models voting for it, or the
+    /// normalized geometric mean of the models' probabilities, depending on
+    /// the voting. Fails as `classify` would.
+    pub fn get_document_posteriors(&self, document: &str) -> Result<Vec<(String, f64)>, ClassifierError> {
+        let probabilities = self.model_probabilities(document)?;
+        match self.voting {
+            Voting::Majority => {
+                let mut votes = votes(&probabilities);
+                let labels = self.labels();
+                Ok(labels.into_iter().map(|label| {
+                    let count = votes.remove(&label).unwrap_or(0);
+                    (label, count as f64 / self.models.len() as f64)
+                }).collect())
+            },
+            Voting::AverageLogProbability => {
+                let averages = self.average_log_probabilities(&probabilities);
+                let max = averages.values().cloned().fold(f64::NEG_INFINITY, f64::max);
+                let total: f64 = averages.values().map(|average| (average - max).exp()).sum();
+                Ok(averages.into_iter().map(|(label, average)| (label, (average - max).exp() / total)).collect())
+            },
+        }
+    }
+
+    /// Gets every label any model knows, sorted
+    pub fn labels(&self) -> Vec<String> {
+        let mut labels: Vec<String> = self.models.iter().flat_map(|model| model.labels()).collect();
+        labels.sort();
+        labels.dedup();
+        labels
+    }
+
+    // the probabilities every model gives each label, asking each model once
+    fn model_probabilities(&self, document: &str) -> Result<Vec<BTreeMap<String, f64>>, ClassifierError> {
+        if self.models.is_empty() {
+            return Err(ClassifierError::Untrained);
+        }
+        self.models.iter()
+            .map(|model| model.probabilities(document).map(|probabilities| probabilities.into_iter().collect()))
+            .collect()
+    }
+
+    // the mean over the models of the log-probability of every label, a
+    // probability of 0 counting as the smallest positive one so that a label
+    // missing from one model can still be compared with the others
+    fn average_log_probabilities(&self, probabilities: &[BTreeMap<String, f64>]) -> BTreeMap<String, f64> {
+        let mut sums: BTreeMap<String, f64> = self.labels().into_iter().map(|label| (label, 0.0)).collect();
+        for model_probabilities in probabilities.iter() {
+            for (label, sum) in sums.iter_mut() {
+                let probability = model_probabilities.get(label).cloned().unwrap_or(0.0);
+                *sum += probability.max(f64::MIN_POSITIVE).ln();
+            }
+        }
+        let num_models = probabilities.len() as f64;
+        sums.into_iter().map(|(label, sum)| (label, sum / num_models)).collect()
+    }
+}
+
+// the number of models voting for each label, every model voting for the
+// label it gives the highest probability, the first of them on a tie
+fn votes(probabilities: &[BTreeMap<String, f64>]) -> BTreeMap<String, usize> {
+    let mut votes = BTreeMap::new();
+    for model_probabilities in probabilities.iter() {
+        let vote = model_probabilities.iter()
+            .max_by(|a, b| a.1.total_cmp(b.1).then(b.0.cmp(a.0)))
+            .map(|(label, _)| label.clone());
+        if let Some(label) = vote {
+            *votes.entry(label).or_insert(0) += 1;
+        }
+    }
+    votes
+}
+
+impl fmt::Debug for Ensemble {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Ensemble")
+            .field("models", &self.models.len())
+            .field("voting", &self.voting)
+            .finish()
+    }
+}
+
+impl TextClassifier for Ensemble {
+    fn add_document(&mut self, document: &str, label: &str) {
+        Ensemble::add_document(self, document, label);
+    }
+
+    fn train(&mut self) {
+        Ensemble::train(self);
+    }
+
+    fn classify(&self, document: &str) -> Result<String, ClassifierError> {
+        Ensemble::classify(self, document)
+    }
+
+    fn probabilities(&self, document: &str) -> Result<Vec<(String, f64)>, ClassifierError> {
+        self.get_document_posteriors(document)
+    }
+
+    fn labels(&self) -> Vec<String> {
+        Ensemble::labels(self)
+    }
+}
@@ -0,0 +1,171 @@
+use alloc::fmt;
+use alloc::string::String;
+
+/// The errors that can occur when building, training, or using a classifier
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClassifierError {
+    /// A classifier could not be encoded or written, holds a description
+    /// of what went wrong
+    Encode(String),
+    /// An encoded classifier could not be decoded, holds a description
+    /// of what went wrong
+    Decode(String),
+    /// A classifier was used before it was trained on any documents
+    Untrained,
+    /// A smoothing value that is not a positive number was given
+    InvalidSmoothing(f64),
+    /// An n-gram range that does not satisfy `0 < min <= max` was given
+    InvalidNgramRange(usize, usize),
+    /// A sample with the wrong number of features was given, holds the
+    /// expected and the actual number of features
+    DimensionMismatch(usize, usize),
+    /// A number of cross-validation folds that is less than 2 or more than
+    /// the number of examples was given
+    InvalidFoldCount(usize),
+    /// A train/test split ratio that is not strictly between 0 and 1 was given
+    InvalidSplitRatio(f64),
+    /// A document was removed from a label it was never added to, holds
+    /// the label
+    DocumentNotFound(String),
+    /// A maximum document frequency that is not greater than 0 and at most 1
+    /// was given
+    InvalidDocumentFrequency(f64),
+    /// A model file could not be read or written, holds a description of
+    /// what went wrong
+    Io(String),
+    /// A number of hash buckets of 0 was given
+    InvalidBucketCount(usize),
+    /// A custom prior that is not a positive number was given, holds the
+    /// label and the prior
+    InvalidPrior(String, f64),
+    /// A document weight that is not a positive number was given
+    InvalidWeight(f64),
+    /// A line of a dataset file could not be read as an example, holds the
+    /// line number (starting from 1) and a description of what went wrong
+    MalformedLine(usize, String),
+    /// A learning rate that is not a positive number was given
+    InvalidLearningRate(f64),
+    /// A regularization strength that is negative was given
+    InvalidRegularization(f64),
+    /// A penalty for unknown words that is negative or not finite was given
+    InvalidPenalty(f64),
+    /// A regular expression that could not be compiled was given, holds a
+    /// description of what went wrong
+    InvalidPattern(String),
+    /// Classifiers that cannot be combined were given, holds a description
+    /// of why
+    IncompatibleModels(String),
+    /// A document with no features to classify it by was given, e.g. one
+    /// that is empty or made up only of stop words
+    EmptyDocument,
+    /// A classifier with no words in its vocabulary was used to classify
+    /// a document
+    EmptyVocabulary,
+    /// A classifier uses a setting an `InferenceModel` cannot reproduce,
+    /// holds the setting
+    UnsupportedSetting(String),
+    /// A serialized model has a version this release cannot read, holds
+    /// the model's version and the newest version this release reads
+    UnsupportedModelVersion(u32, u32),
+    /// Training was cancelled before it finished
+    Cancelled,
+    /// A label of the classifier inside a `TypedNaiveBayes` doesn't stand
+    /// for any of its labels, holds the label
+    InvalidLabel(String),
+    /// A label no documents have was given, or a `BinaryClassifier` was
+    /// trained or evaluated without any documents with one of its two
+    /// labels, holds the label
+    MissingLabel(String),
+    /// A similarity or association threshold that is not greater than 0 and
+    /// at most 1 was given, holds the threshold
+    InvalidThreshold(f64),
+    /// A document was looked up by an ID it was never added with, holds
+    /// the ID
+    UnknownDocumentId(String),
+    /// A limit of 0 on how many times a word counts in a document was given
+    InvalidWordCount(usize),
+    /// A checkpoint interval of 0 documents was given
+    InvalidCheckpointInterval(usize),
+    /// An empty list of labels was given where at least one is needed
+    NoLabels,
+    /// A minimum token length above the maximum was given, holds the
+    /// minimum and the maximum
+    InvalidTokenLengths(usize, usize),
+}
+
+impl fmt::Display for ClassifierError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ClassifierError::Encode(ref reason) =>
+                write!(f, "encoding classifier failed: {}", reason),
+            ClassifierError::Decode(ref reason) =>
+                write!(f, "decoding classifier failed: {}", reason),
+            ClassifierError::Untrained =>
+                write!(f, "classifier has not been trained on any documents"),
+            ClassifierError::InvalidSmoothing(smoothing) =>
+                write!(f, "smoothing value must be a positive number, got {}", smoothing),
+            ClassifierError::InvalidNgramRange(min, max) =>
+                write!(f, "n-gram range must satisfy 0 < min <= max, got ({}, {})", min, max),
+            ClassifierError::DimensionMismatch(expected, found) =>
+                write!(f, "expected a sample with {} features, got {}", expected, found),
+            ClassifierError::InvalidFoldCount(k) =>
+                write!(f, "number of folds must be at least 2 and at most the number of examples, got {}", k),
+            ClassifierError::InvalidSplitRatio(ratio) =>
+                write!(f, "split ratio must be between 0 and 1, got {}", ratio),
+            ClassifierError::DocumentNotFound(ref label) =>
+                write!(f, "no such document was added with the label {}", label),
+            ClassifierError::InvalidDocumentFrequency(frequency) =>
+                write!(f, "document frequency must be greater than 0 and at most 1, got {}", frequency),
+            ClassifierError::Io(ref reason) =>
+                write!(f, "reading or writing model file failed: {}", reason),
+            ClassifierError::InvalidBucketCount(buckets) =>
+                write!(f, "number of hash buckets must be greater than 0, got {}", buckets),
+            ClassifierError::InvalidPrior(ref label, prior) =>
+                write!(f, "prior of label {} must be a positive number, got {}", label, prior),
+            ClassifierError::InvalidWeight(weight) =>
+                write!(f, "document weight must be a positive number, got {}", weight),
+            ClassifierError::MalformedLine(line, ref reason) =>
+                write!(f, "malformed line {}: {}", line, reason),
+            ClassifierError::InvalidLearningRate(rate) =>
+                write!(f, "learning rate must be a positive number, got {}", rate),
+            ClassifierError::InvalidRegularization(strength) =>
+                write!(f, "regularization strength must not be negative, got {}", strength),
+            ClassifierError::InvalidPenalty(penalty) =>
+                write!(f, "unknown word penalty must be a non-negative number, got {}", penalty),
+            ClassifierError::InvalidPattern(ref reason) =>
+                write!(f, "invalid regular expression: {}", reason),
+            ClassifierError::IncompatibleModels(ref reason) =>
+                write!(f, "classifiers cannot be merged: {}", reason),
+            ClassifierError::EmptyDocument =>
+                write!(f, "document has no features to classify it by"),
+            ClassifierError::EmptyVocabulary =>
+                write!(f, "classifier has no words in its vocabulary"),
+            ClassifierError::UnsupportedSetting(ref setting) =>
+                write!(f, "inference models do not support {}", setting),
+            ClassifierError::UnsupportedModelVersion(version, newest) =>
+                write!(f, "model version {} is not supported, this release reads models up to version {}",
+                       version, newest),
+            ClassifierError::Cancelled =>
+                write!(f, "training was cancelled"),
+            ClassifierError::InvalidLabel(ref label) =>
+                write!(f, "label {} does not stand for any typed label", label),
+            ClassifierError::MissingLabel(ref label) =>
+                write!(f, "no documents have the label {}", label),
+            ClassifierError::InvalidThreshold(threshold) =>
+                write!(f, "threshold must be greater than 0 and at most 1, got {}", threshold),
+            ClassifierError::UnknownDocumentId(ref id) =>
+                write!(f, "no document was added with the ID {}", id),
+            ClassifierError::InvalidWordCount(max_count) =>
+                write!(f, "word count limit must be at least 1, got {}", max_count),
+            ClassifierError::InvalidCheckpointInterval(every_n_docs) =>
+                write!(f, "checkpoint interval must be at least 1 document, got {}", every_n_docs),
+            ClassifierError::NoLabels =>
+                write!(f, "no labels were given"),
+            ClassifierError::InvalidTokenLengths(min, max) =>
+                write!(f, "token lengths must satisfy min <= max, got ({}, {})", min, max),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl ::std::error::Error for ClassifierError {}
@@ -0,0 +1,316 @@
+//! Measuring the quality of a classifier's predictions
+//!
+//! ```rust
+//! use classifier::evaluation::ConfusionMatrix;
+//!
+//! // (predicted, actual) labels
+//! let results = vec![
+//!     ("meat".to_string(), "meat".to_string()),
+//!     ("veggie".to_string(), "meat".to_string()),
+//!     ("veggie".to_string(), "veggie".to_string()),
+//! ];
+//!
+//! let matrix = ConfusionMatrix::from_pairs(&results);
+//! assert_eq!(matrix.precision("meat"), 1.0);
+//! assert_eq!(matrix.recall("meat"), 0.5);
+//! ```
+//!
+//! A binary classifier that scores documents, such as a `BinaryClassifier`,
+//! can be judged over every threshold at once by its ROC curve, and the
+//! threshold chosen from the precision and recall of each one:
+//!
+//! ```rust
+//! use classifier::evaluation::roc_curve;
+//!
+//! let scores = [2.5, 1.0, 0.5, -1.0];
+//! let truths = [true, false, true, false];
+//!
+//! let curve = roc_curve(&scores, &truths);
+//! assert_eq!(curve.auc(), 0.75);
+//! let best = curve.best_f1().unwrap();
+//! assert_eq!((best.threshold, best.precision, best.recall), (0.5, 2.0 / 3.0, 1.0));
+//! ```
+
+use std::collections::{BTreeSet, HashMap};
+
+/// Counts of how often each actual label was predicted as each label
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConfusionMatrix {
+    labels: BTreeSet<String>,
+    // keyed by (actual, predicted)
+    counts: HashMap<(String, String), usize>,
+    total: usize,
+}
+
+/// The scores of a single label
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelReport {
+    /// The label being scored
+    pub label: String,
+    /// The fraction of the documents predicted as this label that have it
+    pub precision: f64,
+    /// The fraction of the documents with this label that were predicted as it
+    pub recall: f64,
+    /// The harmonic mean of precision and recall
+    pub f1: f64,
+    /// The number of documents that actually have this label
+    pub support: usize,
+}
+
+impl ConfusionMatrix {
+
+    /// Creates an empty confusion matrix
+    pub fn new() -> ConfusionMatrix {
+        ConfusionMatrix::default()
+    }
+
+    /// Builds a confusion matrix from a list of (predicted, actual) labels
+    pub fn from_pairs<P: AsRef<str>, A: AsRef<str>>(results: &[(P, A)]) -> ConfusionMatrix {
+        let mut matrix = ConfusionMatrix::new();
+        for (predicted, actual) in results.iter() {
+            matrix.add(predicted.as_ref(), actual.as_ref());
+        }
+        matrix
+    }
+
+    /// Records a single prediction
+    pub fn add(&mut self, predicted: &str, actual: &str) {
+        self.labels.insert(predicted.to_string());
+        self.labels.insert(actual.to_string());
+        *self.counts.entry((actual.to_string(), predicted.to_string())).or_insert(0) += 1;
+        self.total += 1;
+    }
+
+    /// Gets every label that was either predicted or expected, in sorted order
+    pub fn labels(&self) -> Vec<String> {
+        self.labels.iter().cloned().collect()
+    }
+
+    /// The number of documents with the `actual` label that were predicted
+    /// as the `predicted` label
+    pub fn count(&self, actual: &str, predicted: &str) -> usize {
+        let key = (actual.to_string(), predicted.to_string());
+        self.counts.get(&key).cloned().unwrap_or(0)
+    }
+
+    /// The total number of predictions recorded
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// The fraction of all predictions that were correct
+    pub fn accuracy(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+        self.true_positives_total() as f64 / self.total as f64
+    }
+
+    /// The fraction of the documents predicted as `label` that have it
+    pub fn precision(&self, label: &str) -> f64 {
+        ratio(self.count(label, label), self.predicted(label))
+    }
+
+    /// The fraction of the documents with `label` that were predicted as it
+    pub fn recall(&self, label: &str) -> f64 {
+        ratio(self.count(label, label), self.support(label))
+    }
+
+    /// The harmonic mean of the precision and recall of `label`
+    pub fn f1(&self, label: &str) -> f64 {
+        f1(self.precision(label), self.recall(label))
+    }
+
+    /// The number of documents that actually have `label`
+    pub fn support(&self, label: &str) -> usize {
+        self.counts.iter()
+            .filter(|&((actual, _), _)| actual == label)
+            .map(|(_, &count)| count)
+            .sum()
+    }
+
+    /// The unweighted mean of every label's precision
+    pub fn macro_precision(&self) -> f64 {
+        self.macro_average(|label| self.precision(label))
+    }
+
+    /// The unweighted mean of every label's recall
+    pub fn macro_recall(&self) -> f64 {
+        self.macro_average(|label| self.recall(label))
+    }
+
+    /// The unweighted mean of every label's F1 score
+    pub fn macro_f1(&self) -> f64 {
+        self.macro_average(|label| self.f1(label))
+    }
+
+    /// Precision computed over the counts of every label together. With a
+    /// single label per document this is the same as the accuracy.
+    pub fn micro_precision(&self) -> f64 {
+        ratio(self.true_positives_total(), self.total)
+    }
+
+    /// Recall computed over the counts of every label together. With a
+    /// single label per document this is the same as the accuracy.
+    pub fn micro_recall(&self) -> f64 {
+        ratio(self.true_positives_total(), self.total)
+    }
+
+    /// The harmonic mean of the micro precision and micro recall
+    pub fn micro_f1(&self) -> f64 {
+        f1(self.micro_precision(), self.micro_recall())
+    }
+
+    /// Gets the scores of every label, in sorted order of the labels
+    pub fn report(&self) -> Vec<LabelReport> {
+        self.labels.iter().map(|label| LabelReport {
+            label: label.clone(),
+            precision: self.precision(label),
+            recall: self.recall(label),
+            f1: self.f1(label),
+            support: self.support(label),
+        }).collect()
+    }
+
+    // the number of documents predicted as `label`
+    fn predicted(&self, label: &str) -> usize {
+        self.counts.iter()
+            .filter(|&((_, predicted), _)| predicted == label)
+            .map(|(_, &count)| count)
+            .sum()
+    }
+
+    fn true_positives_total(&self) -> usize {
+        self.counts.iter()
+            .filter(|&((actual, predicted), _)| actual == predicted)
+            .map(|(_, &count)| count)
+            .sum()
+    }
+
+    fn macro_average<F: Fn(&str) -> f64>(&self, score: F) -> f64 {
+        if self.labels.is_empty() {
+            return 0.0;
+        }
+        let total: f64 = self.labels.iter().map(|label| score(label)).sum();
+        total / self.labels.len() as f64
+    }
+}
+
+/// How a binary classifier does when the documents scoring at least
+/// `threshold` are predicted positive
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThresholdPoint {
+    /// The lowest score predicted positive
+    pub threshold: f64,
+    /// The number of positive documents predicted positive
+    pub true_positives: usize,
+    /// The number of negative documents predicted positive
+    pub false_positives: usize,
+    /// The fraction of the documents predicted positive that are positive
+    pub precision: f64,
+    /// The fraction of the positive documents predicted positive, also
+    /// called the true positive rate
+    pub recall: f64,
+    /// The fraction of the negative documents predicted positive
+    pub false_positive_rate: f64,
+    /// The harmonic mean of precision and recall
+    pub f1: f64,
+}
+
+/// The receiver operating characteristic of a binary classifier, the true
+/// positive rate against the false positive rate over every threshold, made
+/// with `roc_curve`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RocCurve {
+    // one per distinct score, highest threshold first
+    points: Vec<ThresholdPoint>,
+}
+
+/// Computes the ROC curve of a binary classifier from the scores it gave
+/// documents and whether each document is actually positive, in the same
+/// order. There is a point for each distinct score, as the threshold.
+///
+/// # Panics
+///
+/// Panics if there are not as many truths as scores.
+pub fn roc_curve(scores: &[f64], truths: &[bool]) -> RocCurve {
+    assert_eq!(scores.len(), truths.len(), "every score needs a truth");
+    let mut scored: Vec<(f64, bool)> = scores.iter().cloned().zip(truths.iter().cloned()).collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    let positives = truths.iter().filter(|&&truth| truth).count();
+    let negatives = truths.len() - positives;
+
+    // lowering the threshold past each run of tied scores predicts all of
+    // them positive at once
+    let (mut true_positives, mut false_positives) = (0, 0);
+    let points = scored.chunk_by(|a, b| a.0 == b.0).map(|run| {
+        let run_positives = run.iter().filter(|&&(_, truth)| truth).count();
+        true_positives += run_positives;
+        false_positives += run.len() - run_positives;
+        let precision = ratio(true_positives, true_positives + false_positives);
+        let recall = ratio(true_positives, positives);
+        ThresholdPoint {
+            threshold: run[0].0,
+            true_positives,
+            false_positives,
+            precision,
+            recall,
+            false_positive_rate: ratio(false_positives, negatives),
+            f1: f1(precision, recall),
+        }
+    }).collect();
+    RocCurve { points }
+}
+
+impl RocCurve {
+
+    /// Gets the point of every threshold, from the highest to the lowest.
+    /// This is also the sweep of the precision and recall trade-off.
+    pub fn points(&self) -> &[ThresholdPoint] {
+        &self.points
+    }
+
+    /// The area under the curve, the probability that a random positive
+    /// document scores higher than a random negative one (ties count half).
+    /// 1 is a perfect ranking and 0.5 is no better than chance. It is 0 if
+    /// there were no positive or no negative documents.
+    pub fn auc(&self) -> f64 {
+        let mut area = 0.0;
+        let (mut x, mut y) = (0.0, 0.0);
+        for point in self.points.iter() {
+            area += (point.false_positive_rate - x) * (point.recall + y) / 2.0;
+            x = point.false_positive_rate;
+            y = point.recall;
+        }
+        area
+    }
+
+    /// The point with the highest F1 score, the highest threshold of any
+    /// that tie, or `None` if the curve has no points
+    pub fn best_f1(&self) -> Option<&ThresholdPoint> {
+        self.points.iter().fold(None, |best: Option<&ThresholdPoint>, point| match best {
+            Some(best) if best.f1 >= point.f1 => Some(best),
+            _ => Some(point),
+        })
+    }
+
+    /// The point with the highest threshold whose precision is at least
+    /// `precision` and recall is highest, or `None` if no threshold is
+    /// that precise
+    pub fn threshold_for_precision(&self, precision: f64) -> Option<&ThresholdPoint> {
+        self.points.iter()
+            .filter(|point| point.precision >= precision)
+            .fold(None, |best: Option<&ThresholdPoint>, point| match best {
+                Some(best) if best.recall >= point.recall => Some(best),
+                _ => Some(point),
+            })
+    }
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    if denominator == 0 { 0.0 } else { numerator as f64 / denominator as f64 }
+}
+
+fn f1(precision: f64, recall: f64) -> f64 {
+    if precision + recall == 0.0 { 0.0 } else { 2.0 * precision * recall / (precision + recall) }
+}
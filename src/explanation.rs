@@ -0,0 +1,40 @@
+/// How a classifier arrived at the scores of a document, see
+/// `NaiveBayes::explain`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    /// The explanation of every label's score, highest scoring label first
+    pub labels: Vec<LabelExplanation>,
+}
+
+/// The terms that add up to one label's log score for a document
+///
+/// `score` is `prior + baseline` plus every contribution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelExplanation {
+    /// The label being scored
+    pub label: String,
+    /// The log probability of the label, zero for the complement variant
+    /// which does not use priors
+    pub prior: f64,
+    /// The part of the score that does not depend on the document's words.
+    /// For the Bernoulli variant this is the log probability of a document
+    /// with none of the words in the vocabulary, otherwise it is zero.
+    pub baseline: f64,
+    /// The amount each feature of the document added to the score, in the
+    /// order of the document. Features that are not in the vocabulary come
+    /// last, and are left out when the unknown word policy ignores them. For
+    /// the Bernoulli variant each distinct feature is listed once.
+    pub contributions: Vec<(String, f64)>,
+    /// The label's log score for the document
+    pub score: f64,
+    /// The posterior probability of the label given the document
+    pub posterior: f64,
+}
+
+impl Explanation {
+
+    /// Gets the explanation of the given label's score
+    pub fn label(&self, label: &str) -> Option<&LabelExplanation> {
+        self.labels.iter().find(|explanation| explanation.label == label)
+    }
+}
@@ -0,0 +1,76 @@
+//! Scoring how much the words of a document tell about its label
+//!
+//! Each (word, label) pair is scored from the number of documents that do
+//! and do not contain the word and do and do not have the label. The most
+//! informative words can be kept and the rest dropped before training:
+//!
+//! ```rust
+//! use classifier::NaiveBayes;
+//! use classifier::feature_selection::FeatureScore;
+//!
+//! let mut nb = NaiveBayes::new();
+//! nb.add_document("the pork the beef", "meat");
+//! nb.add_document("the kale okra", "veggie");
+//!
+//! // "the" is in every document so it says nothing about the label
+//! nb.select_top_features(4, FeatureScore::ChiSquared);
+//! assert_eq!(nb.vocab_size(), 4);
+//! nb.train();
+//! assert_eq!(nb.classify("pork").unwrap(), "meat");
+//! ```
+
+/// How informative a word is about a label
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum FeatureScore {
+    /// The chi-squared statistic of the word and the label occurring
+    /// independently (the default)
+    #[default]
+    ChiSquared,
+    /// The mutual information, in bits, between the word occurring in a
+    /// document and the document having the label
+    MutualInformation,
+}
+
+impl FeatureScore {
+
+    /// Scores a word and a label from document counts: `n11` documents with
+    /// the word and the label, `n10` with the word but not the label, `n01`
+    /// with the label but not the word, and `n00` with neither. Weighted
+    /// documents count as their weight.
+    pub fn score(&self, n11: f64, n10: f64, n01: f64, n00: f64) -> f64 {
+        match *self {
+            FeatureScore::ChiSquared => chi_squared(n11, n10, n01, n00),
+            FeatureScore::MutualInformation => mutual_information(n11, n10, n01, n00),
+        }
+    }
+}
+
+/// The chi-squared statistic of a 2x2 table of document counts, see
+/// `FeatureScore::score` for the order of the counts. 0 when the word or
+/// the label is in every document or in none.
+pub fn chi_squared(n11: f64, n10: f64, n01: f64, n00: f64) -> f64 {
+    let denominator = (n11 + n01) * (n11 + n10) * (n10 + n00) * (n01 + n00);
+    if denominator == 0.0 {
+        return 0.0;
+    }
+    let n = n11 + n10 + n01 + n00;
+    n * (n11 * n00 - n10 * n01).powi(2) / denominator
+}
+
+/// The mutual information, in bits, of a 2x2 table of document counts, see
+/// `FeatureScore::score` for the order of the counts
+pub fn mutual_information(n11: f64, n10: f64, n01: f64, n00: f64) -> f64 {
+    let n = n11 + n10 + n01 + n00;
+    let with_word = n11 + n10;
+    let without_word = n01 + n00;
+    let with_label = n11 + n01;
+    let without_label = n10 + n00;
+
+    // each cell contributes p(cell) * log(p(cell) / (p(row) * p(column))),
+    // cells with no documents contribute nothing
+    let cell = |count: f64, row: f64, column: f64| {
+        if count == 0.0 { 0.0 } else { count / n * (n * count / (row * column)).log2() }
+    };
+    cell(n11, with_word, with_label) + cell(n10, with_word, without_label)
+        + cell(n01, without_word, with_label) + cell(n00, without_word, without_label)
+}
@@ -0,0 +1,197 @@
+//! A C interface to the naive Bayes classifier, for embedding trained
+//! classifiers in programs written in other languages. Requires the `ffi`
+//! feature, and `include/classifier.h` declares the functions for C.
+//!
+//! Strings passed in must be NUL-terminated UTF-8. Strings handed back
+//! belong to the caller, who frees them with `classifier_string_free`.
+//! A function that fails returns NULL or -1, and `classifier_last_error`
+//! then describes what went wrong.
+//!
+//! ```c
+//! #include "classifier.h"
+//!
+//! NaiveBayes *nb = classifier_new();
+//! classifier_add_document(nb, "pork belly ribs", "meat");
+//! classifier_add_document(nb, "kale okra spinach", "veggie");
+//! classifier_train(nb);
+//!
+//! char *label = classifier_classify(nb, "pork");
+//! if (label == NULL) {
+//!     fprintf(stderr, "%s\n", classifier_last_error());
+//! } else {
+//!     printf("%s\n", label);  /* meat */
+//!     classifier_string_free(label);
+//! }
+//! classifier_free(nb);
+//! ```
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+use naive_bayes::Classifier;
+
+thread_local! {
+    // the message of the last failure on each thread
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Creates a new classifier that tokenizes documents by breaking on
+/// whitespace characters. Free it with `classifier_free`.
+#[no_mangle]
+pub extern "C" fn classifier_new() -> *mut Classifier {
+    Box::into_raw(Box::new(Classifier::new()))
+}
+
+/// Builds a classifier from a JSON string, NULL if it cannot be decoded.
+/// Free it with `classifier_free`.
+///
+/// # Safety
+///
+/// `json` must be NULL or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn classifier_from_json(json: *const c_char) -> *mut Classifier {
+    let classifier = read_str(json).and_then(|json| Classifier::from_json(json).map_err(|e| e.to_string()));
+    match classifier {
+        Ok(classifier) => Box::into_raw(Box::new(classifier)),
+        Err(message) => {
+            set_last_error(message);
+            ptr::null_mut()
+        },
+    }
+}
+
+/// Encodes the classifier as a JSON string, NULL on failure
+///
+/// # Safety
+///
+/// `nb` must be NULL or a classifier that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn classifier_to_json(nb: *const Classifier) -> *mut c_char {
+    match nb.as_ref() {
+        Some(nb) => into_c_string(nb.to_json()),
+        None => fail_null("null classifier"),
+    }
+}
+
+/// Adds a labeled document to the classifier. Returns 0, or -1 on failure.
+///
+/// # Safety
+///
+/// `nb` must be NULL or a classifier that has not been freed, and `document`
+/// and `label` must be NULL or NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn classifier_add_document(nb: *mut Classifier, document: *const c_char,
+                                                 label: *const c_char) -> c_int {
+    let nb = match nb.as_mut() {
+        Some(nb) => nb,
+        None => return fail("null classifier"),
+    };
+    match (read_str(document), read_str(label)) {
+        (Ok(document), Ok(label)) => {
+            nb.add_document(document, label);
+            0
+        },
+        (Err(message), _) | (_, Err(message)) => fail(&message),
+    }
+}
+
+/// Trains the classifier on the documents added so far. Returns 0, or -1
+/// on failure.
+///
+/// # Safety
+///
+/// `nb` must be NULL or a classifier that has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn classifier_train(nb: *mut Classifier) -> c_int {
+    match nb.as_mut() {
+        Some(nb) => {
+            nb.train();
+            0
+        },
+        None => fail("null classifier"),
+    }
+}
+
+/// Computes a classifying label for the document, NULL on failure (e.g. an
+/// untrained classifier or a document with no features)
+///
+/// # Safety
+///
+/// `nb` must be NULL or a classifier that has not been freed, and `document`
+/// must be NULL or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn classifier_classify(nb: *const Classifier, document: *const c_char) -> *mut c_char {
+    let nb = match nb.as_ref() {
+        Some(nb) => nb,
+        None => return fail_null("null classifier"),
+    };
+    match read_str(document).and_then(|document| nb.classify(document).map_err(|e| e.to_string())) {
+        Ok(label) => into_c_string(label),
+        Err(message) => fail_null(&message),
+    }
+}
+
+/// Frees a classifier, doing nothing for NULL
+///
+/// # Safety
+///
+/// `nb` must be NULL or a classifier made by this library that has not
+/// already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn classifier_free(nb: *mut Classifier) {
+    if !nb.is_null() {
+        drop(Box::from_raw(nb));
+    }
+}
+
+/// Frees a string handed back by this library, doing nothing for NULL
+///
+/// # Safety
+///
+/// `s` must be NULL or a string handed back by this library that has not
+/// already been freed.
+#[no_mangle]
+pub unsafe extern "C" fn classifier_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Describes the last failure on the calling thread, NULL if nothing has
+/// failed. The string belongs to the library and stays valid until the
+/// next failure on the same thread.
+#[no_mangle]
+pub extern "C" fn classifier_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
+
+unsafe fn read_str<'a>(s: *const c_char) -> Result<&'a str, String> {
+    if s.is_null() {
+        return Err("null string".to_string());
+    }
+    CStr::from_ptr(s).to_str().map_err(|e| format!("invalid UTF-8: {}", e))
+}
+
+// hands a string over to the caller, labels can't hold NUL characters in C
+fn into_c_string(s: String) -> *mut c_char {
+    match CString::new(s) {
+        Ok(s) => s.into_raw(),
+        Err(_) => fail_null("string contains a NUL character"),
+    }
+}
+
+fn set_last_error(message: String) {
+    let message = CString::new(message.replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+}
+
+fn fail(message: &str) -> c_int {
+    set_last_error(message.to_string());
+    -1
+}
+
+fn fail_null<T>(message: &str) -> *mut T {
+    set_last_error(message.to_string());
+    ptr::null_mut()
+}
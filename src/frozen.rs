@@ -0,0 +1,263 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::mem;
+use serde_json;
+use error::ClassifierError;
+use naive_bayes::features;
+use preprocessing::Preprocessor;
+use tokenizer::{Tokenizer, WhitespaceTokenizer};
+
+/// A trained naive Bayes classifier frozen into flat, sorted arrays, for
+/// classifying with a large vocabulary in as little memory as possible,
+/// made with `NaiveBayes::freeze`
+///
+/// The words are stored end to end in a single string in sorted order and
+/// found by binary search, and what every word adds to the score of every
+/// label is precomputed into one array with a row per word. There is no
+/// hash map and no per-word allocation, so a frozen model takes a fraction
+/// of the memory of the classifier it was made from, and classifying is
+/// only lookups and additions. It gives the same labels and posteriors as
+/// the classifier, but it cannot be trained any further.
+///
+/// The model can be serialized with serde. Like the classifier, the
+/// tokenizer and sanitizer are not part of the serialized model. A model made with
+/// `NaiveBayes::freeze_quantized` keeps the word scores in single precision,
+/// which halves its size in memory and roughly halves it serialized.
+///
+/// ```rust
+/// use classifier::NaiveBayes;
+///
+/// let mut nb = NaiveBayes::new();
+/// nb.add_document("pork belly ribs", "meat");
+/// nb.add_document("kale okra spinach", "veggie");
+/// let model = nb.freeze();
+/// assert_eq!(model.classify("pork ribs").unwrap(), "meat");
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrozenModel<T = WhitespaceTokenizer> {
+    pub(crate) labels: Vec<String>,
+    // the score of each label for a document with no features
+    pub(crate) biases: Vec<f64>,
+    // what each feature not in the vocabulary adds to the score of each label
+    pub(crate) unknown: Vec<f64>,
+    // every word of the vocabulary, sorted and written end to end
+    pub(crate) text: String,
+    // where each word ends in `text`, each one starts where the one before
+    // it ends
+    pub(crate) ends: Vec<u32>,
+    // what each occurrence of a word adds to the score of each label, one
+    // row of a score per label for each word, in the order of the words.
+    // Empty when the model is quantized.
+    pub(crate) scores: Vec<f64>,
+    // the scores above rounded to single precision, only when the model is
+    // quantized
+    #[serde(default)]
+    pub(crate) quantized_scores: Vec<f32>,
+    // whether a feature only counts once however often it appears, as in
+    // the Bernoulli variant
+    pub(crate) distinct: bool,
+    pub(crate) preprocessor: Preprocessor,
+    #[serde(skip)]
+    pub(crate) sanitizer: Option<fn(&str) -> String>,
+    pub(crate) stop_words: HashSet<String>,
+    pub(crate) ngram_range: (usize, usize),
+    #[serde(default)]
+    pub(crate) skip_window: usize,
+    #[serde(default)]
+    pub(crate) max_word_count: Option<usize>,
+    pub(crate) hash_buckets: Option<usize>,
+    #[serde(skip)]
+    pub(crate) tokenizer: T,
+}
+
+impl FrozenModel {
+
+    /// Builds a frozen model from a JSON string
+    pub fn from_json(encoded: &str) -> Result<FrozenModel, ClassifierError> {
+        serde_json::from_str(encoded).map_err(|e| ClassifierError::Decode(e.to_string()))
+    }
+}
+
+impl<T: Tokenizer> FrozenModel<T> {
+
+    /// Builds a frozen model from a JSON string, using the given tokenizer
+    /// (which should be the one the classifier was trained with)
+    pub fn from_json_with_tokenizer(encoded: &str, tokenizer: T) -> Result<FrozenModel<T>, ClassifierError> {
+        let model: FrozenModel<WhitespaceTokenizer> = FrozenModel::from_json(encoded)?;
+        Ok(FrozenModel {
+            labels: model.labels,
+            biases: model.biases,
+            unknown: model.unknown,
+            text: model.text,
+            ends: model.ends,
+            scores: model.scores,
+            quantized_scores: model.quantized_scores,
+            distinct: model.distinct,
+            preprocessor: model.preprocessor,
+            sanitizer: model.sanitizer,
+            stop_words: model.stop_words,
+            ngram_range: model.ngram_range,
+            skip_window: model.skip_window,
+            max_word_count: model.max_word_count,
+            hash_buckets: model.hash_buckets,
+            tokenizer,
+        })
+    }
+
+    /// Sets the function documents are passed through before they are
+    /// tokenized, which should be the classifier's, see
+    /// `NaiveBayes::set_sanitizer`. It is kept when the classifier is frozen
+    /// but, like the tokenizer, not serialized.
+    pub fn set_sanitizer(&mut self, sanitizer: fn(&str) -> String) {
+        self.sanitizer = Some(sanitizer);
+    }
+
+    /// Encodes the model as a JSON string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("encoding JSON failed")
+    }
+
+    /// Gets all of the labels the model knows, sorted
+    pub fn get_labels(&self) -> Vec<String> {
+        self.labels.clone()
+    }
+
+    /// The number of words in the model's vocabulary
+    pub fn vocab_size(&self) -> usize {
+        self.ends.len()
+    }
+
+    /// Whether the word scores are kept in single precision, see
+    /// `NaiveBayes::freeze_quantized`
+    pub fn is_quantized(&self) -> bool {
+        !self.quantized_scores.is_empty()
+    }
+
+    /// Roughly how many bytes the model takes up
+    pub fn memory_bytes(&self) -> usize {
+        mem::size_of::<FrozenModel<T>>() + self.text.capacity()
+            + self.ends.capacity() * mem::size_of::<u32>()
+            + self.quantized_scores.capacity() * mem::size_of::<f32>()
+            + (self.scores.capacity() + self.biases.capacity() + self.unknown.capacity()) * mem::size_of::<f64>()
+            + self.labels.iter().map(|label| mem::size_of::<String>() + label.len()).sum::<usize>()
+            + self.stop_words.capacity() * mem::size_of::<String>()
+            + self.stop_words.iter().map(|word| word.len()).sum::<usize>()
+    }
+
+    /// Takes a document that has been tokenized into a vector of strings and
+    /// computes a classifying label for it, failing as `NaiveBayes::classify`
+    /// would. Of labels with the same score, the first in sorted order wins.
+    pub fn classify_tokenized<S: AsRef<str>>(&self, document: &[S]) -> Result<String, ClassifierError> {
+        let scores = self.scores(document)?;
+        let mut best = 0;
+        for (i, &score) in scores.iter().enumerate() {
+            if score > scores[best] {
+                best = i;
+            }
+        }
+        Ok(self.labels[best].clone())
+    }
+
+    /// Tokenizes the document with the model's tokenizer and computes a
+    /// classifying label for it
+    pub fn classify(&self, document: &str) -> Result<String, ClassifierError> {
+        self.classify_tokenized(&self.tokenize(document))
+    }
+
+    /// Takes a document that has been tokenized into a vector of strings and
+    /// gets every label and its posterior probability given the document,
+    /// see `NaiveBayes::get_document_posteriors`
+    pub fn get_document_posteriors_tokenized<S: AsRef<str>>(&self, document: &[S])
+        -> Result<Vec<(String, f64)>, ClassifierError>
+    {
+        let scores = self.scores(document)?;
+        // shift by the largest score first so exponentiating can't underflow
+        let max_score = scores.iter().fold(f64::NEG_INFINITY, |acc, &s| acc.max(s));
+        let total: f64 = scores.iter().map(|&s| (s - max_score).exp()).sum();
+        Ok(self.labels.iter().zip(scores.iter())
+            .map(|(label, &s)| (label.clone(), (s - max_score).exp() / total))
+            .collect())
+    }
+
+    /// Tokenizes the document with the model's tokenizer and gets every
+    /// label and its posterior probability given the document
+    pub fn get_document_posteriors(&self, document: &str) -> Result<Vec<(String, f64)>, ClassifierError> {
+        self.get_document_posteriors_tokenized(&self.tokenize(document))
+    }
+
+    fn tokenize(&self, document: &str) -> Vec<String> {
+        match self.sanitizer {
+            Some(sanitizer) => self.preprocessor.tokenize(&self.tokenizer, &sanitizer(document)),
+            None => self.preprocessor.tokenize(&self.tokenizer, document),
+        }
+    }
+
+    // the word at the given position of the sorted vocabulary
+    fn word(&self, i: usize) -> &str {
+        let start = if i == 0 { 0 } else { self.ends[i - 1] as usize };
+        &self.text[start..self.ends[i] as usize]
+    }
+
+    // the position of the word in the sorted vocabulary, by binary search
+    fn position(&self, word: &str) -> Option<usize> {
+        let (mut low, mut high) = (0, self.ends.len());
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match self.word(middle).cmp(word) {
+                Ordering::Less => low = middle + 1,
+                Ordering::Greater => high = middle,
+                Ordering::Equal => return Some(middle),
+            }
+        }
+        None
+    }
+
+    // the score of every label, in the order of `labels`
+    fn scores<S: AsRef<str>>(&self, document: &[S]) -> Result<Vec<f64>, ClassifierError> {
+        if self.labels.is_empty() {
+            return Err(ClassifierError::Untrained);
+        }
+        if self.ends.is_empty() {
+            return Err(ClassifierError::EmptyVocabulary);
+        }
+        let mut features = features(document, &self.stop_words, self.ngram_range, self.skip_window, self.hash_buckets,
+                                    self.max_word_count);
+        if features.is_empty() {
+            return Err(ClassifierError::EmptyDocument);
+        }
+
+        // each distinct feature is looked up once and counted as often as
+        // it appears
+        features.sort_unstable();
+        let num_labels = self.labels.len();
+        let mut scores = self.biases.clone();
+        for run in features.chunk_by(|a, b| a == b) {
+            let count = if self.distinct { 1.0 } else { run.len() as f64 };
+            let row = self.position(&run[0]).map(|i| i * num_labels..(i + 1) * num_labels);
+            match row {
+                Some(row) if self.is_quantized() => {
+                    for (score, &term) in scores.iter_mut().zip(self.quantized_scores[row].iter()) {
+                        *score += count * term as f64;
+                    }
+                },
+                Some(row) => {
+                    for (score, term) in scores.iter_mut().zip(self.scores[row].iter()) {
+                        *score += count * term;
+                    }
+                },
+                None => {
+                    for (score, term) in scores.iter_mut().zip(self.unknown.iter()) {
+                        *score += count * term;
+                    }
+                },
+            }
+        }
+        Ok(scores)
+    }
+
+    // rounds the word scores to single precision
+    pub(crate) fn quantize(&mut self) {
+        self.quantized_scores = self.scores.iter().map(|&score| score as f32).collect();
+        self.scores = Vec::new();
+    }
+}
@@ -0,0 +1,257 @@
+use std::collections::BTreeMap;
+use std::f64;
+use std::io::{Read, Write};
+use std::path::Path;
+use serde_json;
+use error::ClassifierError;
+use persistence::{self, Compression};
+#[cfg(feature = "binary")]
+use binary;
+
+static DEFAULT_VARIANCE_SMOOTHING: f64 = 1e-9f64;
+
+/// Gaussian naive Bayes classifier for fixed-length numeric feature vectors
+///
+/// Every feature is modeled as normally distributed within each label. The
+/// number of features is fixed by the first sample that is added.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GaussianNaiveBayes {
+    num_features: usize,
+    num_examples: u32,
+    variance_smoothing: f64,
+    // ordered by label, so labels come out in the same order every time
+    classifications: BTreeMap<String, GaussianClassification>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GaussianClassification {
+    label: String,
+    num_examples: u32,
+    probability: f64,
+    // running means and sums of squared differences from the mean
+    // (Welford's method), one per feature
+    means: Vec<f64>,
+    squared_differences: Vec<f64>,
+    variances: Vec<f64>,
+}
+
+impl GaussianNaiveBayes {
+
+    /// Creates a new classifier
+    pub fn new() -> GaussianNaiveBayes {
+        GaussianNaiveBayes {
+            num_features: 0,
+            num_examples: 0u32,
+            variance_smoothing: DEFAULT_VARIANCE_SMOOTHING,
+            classifications: BTreeMap::new(),
+        }
+    }
+
+    /// Takes a sample and a label and adds the sample to the list of samples
+    /// that the classifier is aware of and will train on next time the
+    /// `train()` method is called. Every sample must have the same number of
+    /// features.
+    pub fn add_sample(&mut self, sample: &[f64], label: &str) -> Result<(), ClassifierError> {
+        if self.num_features == 0 {
+            self.num_features = sample.len();
+        }
+        self.check_sample(sample)?;
+
+        if !self.classifications.contains_key(label) {
+            let c = GaussianClassification::new(label, self.num_features);
+            self.classifications.insert(label.to_string(), c);
+        }
+        self.classifications.get_mut(label).unwrap().add_sample(sample);
+        self.num_examples += 1;
+        Ok(())
+    }
+
+    /// Adds a list of (sample, label) tuples to the classifier
+    pub fn add_samples(&mut self, examples: &[(Vec<f64>, String)]) -> Result<(), ClassifierError> {
+        for (sample, label) in examples.iter() {
+            self.add_sample(sample, label)?;
+        }
+        Ok(())
+    }
+
+    /// Gets a vector of all of the labels that the classifier has seen so
+    /// far, sorted
+    pub fn get_labels(&self) -> Vec<String> {
+        self.classifications.values().map(|c| c.label.clone()).collect()
+    }
+
+    /// Sets the portion of the largest feature variance that is added to
+    /// every variance for numerical stability (must not be negative,
+    /// defaults to 1e-9)
+    pub fn set_variance_smoothing(&mut self, smoothing: f64) -> Result<(), ClassifierError> {
+        if smoothing.is_nan() || smoothing < 0.0 {
+            return Err(ClassifierError::InvalidSmoothing(smoothing));
+        }
+        self.variance_smoothing = smoothing;
+        Ok(())
+    }
+
+    /// Trains the classifier on the samples that have been observed so far
+    pub fn train(&mut self) {
+        // smooth with a portion of the largest variance of any feature, so
+        // that features which never vary don't divide by zero
+        let mut max_variance = 0.0f64;
+        for classification in self.classifications.values() {
+            for &squared_difference in classification.squared_differences.iter() {
+                let variance = squared_difference / classification.num_examples as f64;
+                max_variance = max_variance.max(variance);
+            }
+        }
+        if max_variance == 0.0 {
+            max_variance = 1.0;
+        }
+        let epsilon = self.variance_smoothing * max_variance;
+
+        for classification in self.classifications.values_mut() {
+            classification.train(self.num_examples, epsilon);
+        }
+    }
+
+    /// Computes a classifying label for a sample
+    pub fn classify(&self, sample: &[f64]) -> Result<String, ClassifierError> {
+        self.check_sample(sample)?;
+        let mut max_score = f64::NEG_INFINITY;
+        let mut max_classification = None;
+
+        for classification in self.classifications.values() {
+            let score = classification.score_sample(sample);
+            if score > max_score {
+                max_classification = Some(classification);
+                max_score = score;
+            }
+        }
+
+        max_classification.map(|c| c.label.clone()).ok_or(ClassifierError::Untrained)
+    }
+
+    /// Similar to classify but instead of returning a single label, returns all
+    /// labels and the probabilities of each one given the sample
+    pub fn get_sample_probabilities(&self, sample: &[f64]) -> Result<Vec<(String, f64)>, ClassifierError> {
+        self.check_sample(sample)?;
+        let scores: Vec<(String, f64)> = self.classifications.values()
+            .map(|c| (c.label.clone(), c.score_sample(sample)))
+            .collect();
+
+        // normalize the log likelihoods, shifting by the largest one so
+        // that exponentiating them can't underflow to zero
+        let max_score = scores.iter().fold(f64::NEG_INFINITY, |acc, &(_, s)| acc.max(s));
+        let total: f64 = scores.iter().map(|&(_, s)| (s - max_score).exp()).sum();
+        Ok(scores.into_iter().map(|(c, s)| (c, (s - max_score).exp() / total)).collect())
+    }
+
+    /// Encodes the classifier as a JSON string.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("encoding JSON failed")
+    }
+
+    /// Encodes the classifier as JSON and writes it to the given writer
+    pub fn to_writer<W: Write>(&self, writer: W) -> Result<(), ClassifierError> {
+        serde_json::to_writer(writer, self).map_err(|e| ClassifierError::Encode(e.to_string()))
+    }
+
+    /// Builds a new classifier from a JSON string
+    pub fn from_json(encoded: &str) -> Result<GaussianNaiveBayes, ClassifierError> {
+        serde_json::from_str(encoded).map_err(|e| ClassifierError::Decode(e.to_string()))
+    }
+
+    /// Builds a new classifier from JSON read from the given reader
+    pub fn from_reader<R: Read>(reader: R) -> Result<GaussianNaiveBayes, ClassifierError> {
+        serde_json::from_reader(reader).map_err(|e| ClassifierError::Decode(e.to_string()))
+    }
+
+    /// Saves the classifier to a JSON file, see `NaiveBayes::save_to_file`
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ClassifierError> {
+        self.save_to_file_compressed(path, Compression::None)
+    }
+
+    /// Same as `save_to_file`, but compresses the file, see `Compression`
+    pub fn save_to_file_compressed<P: AsRef<Path>>(&self, path: P, compression: Compression)
+                                                   -> Result<(), ClassifierError> {
+        persistence::save(self, path.as_ref(), compression)
+    }
+
+    /// Loads a classifier from a file written by `save_to_file` or
+    /// `save_to_file_compressed`
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<GaussianNaiveBayes, ClassifierError> {
+        persistence::load(path.as_ref())
+    }
+
+    /// Encodes the classifier in a compact binary format, see
+    /// `NaiveBayes::to_bytes`. Requires the `binary` feature.
+    #[cfg(feature = "binary")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ClassifierError> {
+        binary::encode(self)
+    }
+
+    /// Builds a new classifier from bytes written by `to_bytes`. Requires
+    /// the `binary` feature.
+    #[cfg(feature = "binary")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<GaussianNaiveBayes, ClassifierError> {
+        binary::decode(bytes)
+    }
+
+    fn check_sample(&self, sample: &[f64]) -> Result<(), ClassifierError> {
+        if sample.len() != self.num_features {
+            return Err(ClassifierError::DimensionMismatch(self.num_features, sample.len()));
+        }
+        Ok(())
+    }
+
+}
+
+impl Default for GaussianNaiveBayes {
+    fn default() -> GaussianNaiveBayes {
+        GaussianNaiveBayes::new()
+    }
+}
+
+impl GaussianClassification {
+
+    fn new(label: &str, num_features: usize) -> GaussianClassification {
+        GaussianClassification {
+            label: label.to_string(),
+            num_examples: 0u32,
+            probability: 0.0f64,
+            means: vec![0.0; num_features],
+            squared_differences: vec![0.0; num_features],
+            variances: vec![0.0; num_features],
+        }
+    }
+
+    fn add_sample(&mut self, sample: &[f64]) {
+        self.num_examples += 1;
+        let n = self.num_examples as f64;
+        for (i, &x) in sample.iter().enumerate() {
+            let delta = x - self.means[i];
+            self.means[i] += delta / n;
+            self.squared_differences[i] += delta * (x - self.means[i]);
+        }
+    }
+
+    // trains this classification
+    fn train(&mut self, total_examples: u32, epsilon: f64) {
+        self.probability = self.num_examples as f64 / total_examples as f64;
+        let n = self.num_examples as f64;
+        self.variances = self.squared_differences.iter()
+            .map(|&squared_difference| squared_difference / n + epsilon)
+            .collect();
+    }
+
+    // retrieves the log probability of the sample given this classification
+    // plus the log probability of this classification
+    fn score_sample(&self, sample: &[f64]) -> f64 {
+        let mut total = 0.0f64;
+        for (i, &x) in sample.iter().enumerate() {
+            let variance = self.variances[i];
+            let difference = x - self.means[i];
+            total -= 0.5 * (2.0 * f64::consts::PI * variance).ln() +
+                difference * difference / (2.0 * variance);
+        }
+        self.probability.ln() + total
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::Path;
+use serde_json;
+use error::ClassifierError;
+use naive_bayes::Classifier;
+use persistence::{self, Compression};
+use tokenizer::{Tokenizer, WhitespaceTokenizer};
+
+static DEFAULT_SEPARATOR: &str = "/";
+
+/// A classifier for hierarchical labels such as `"food/meat/pork"`
+///
+/// The labels' paths declare the label tree. Every node of the tree has a
+/// naive Bayes classifier choosing between its children, and a document is
+/// classified level by level: first among the top level labels, then among
+/// the children of the chosen label, and so on until a leaf is reached.
+/// A label that has documents of its own as well as children is never
+/// chosen over its children.
+///
+/// ```rust
+/// use classifier::HierarchicalClassifier;
+///
+/// let mut hc = HierarchicalClassifier::new();
+/// hc.add_document("pork chop pork belly", "food/meat/pork");
+/// hc.add_document("beef brisket ribeye", "food/meat/beef");
+/// hc.add_document("kale okra spinach", "food/veggie");
+/// hc.add_document("hammer nails saw", "tools");
+/// hc.train();
+///
+/// assert_eq!(hc.classify("pork belly").unwrap(), "food/meat/pork");
+/// let levels = hc.classify_levels("pork belly").unwrap();
+/// assert_eq!(levels[0].0, "food");
+/// assert_eq!(levels[1].0, "food/meat");
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HierarchicalClassifier<T = WhitespaceTokenizer> {
+    separator: String,
+    // the classifier choosing between the children of each node of the
+    // tree, by the node's path ("" for the root)
+    nodes: HashMap<String, Classifier>,
+    #[serde(skip)]
+    tokenizer: T,
+}
+
+impl HierarchicalClassifier {
+
+    /// Creates a new classifier that tokenizes documents by breaking on
+    /// whitespace characters, and separates the levels of labels with `/`
+    pub fn new() -> HierarchicalClassifier {
+        HierarchicalClassifier::with_tokenizer(WhitespaceTokenizer)
+    }
+
+    /// Builds a new classifier from a JSON string
+    pub fn from_json(encoded: &str) -> Result<HierarchicalClassifier, ClassifierError> {
+        serde_json::from_str(encoded).map_err(|e| ClassifierError::Decode(e.to_string()))
+    }
+
+    /// Builds a new classifier from JSON read from the given reader
+    pub fn from_reader<R: Read>(reader: R) -> Result<HierarchicalClassifier, ClassifierError> {
+        serde_json::from_reader(reader).map_err(|e| ClassifierError::Decode(e.to_string()))
+    }
+
+    /// Loads a classifier from a file written by `save_to_file` or
+    /// `save_to_file_compressed`
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<HierarchicalClassifier, ClassifierError> {
+        persistence::load(path.as_ref())
+    }
+}
+
+impl Default for HierarchicalClassifier {
+    fn default() -> HierarchicalClassifier {
+        HierarchicalClassifier::new()
+    }
+}
+
+impl<T: Tokenizer> HierarchicalClassifier<T> {
+
+    /// Creates a new classifier that uses the given tokenizer to break
+    /// documents into words, both when training and when classifying
+    pub fn with_tokenizer(tokenizer: T) -> HierarchicalClassifier<T> {
+        HierarchicalClassifier {
+            separator: DEFAULT_SEPARATOR.to_string(),
+            nodes: HashMap::new(),
+            tokenizer,
+        }
+    }
+
+    /// Builds a new classifier from a JSON string, using the given tokenizer
+    /// (which should be the one the classifier was trained with)
+    pub fn from_json_with_tokenizer(encoded: &str, tokenizer: T) -> Result<HierarchicalClassifier<T>, ClassifierError> {
+        let classifier = HierarchicalClassifier::from_json(encoded)?;
+        Ok(HierarchicalClassifier {
+            separator: classifier.separator,
+            nodes: classifier.nodes,
+            tokenizer,
+        })
+    }
+
+    /// Sets the string separating the levels of a label (`/` by default).
+    /// This should be set before any documents are added.
+    pub fn set_separator(&mut self, separator: &str) {
+        self.separator = separator.to_string();
+    }
+
+    /// Takes a document that has been tokenized into a vector of strings and
+    /// a hierarchical label, and adds the document to the classifier of
+    /// every level of the label
+    pub fn add_document_tokenized<S: AsRef<str>>(&mut self, document: &[S], label: &str) {
+        let levels: Vec<&str> = label.split(self.separator.as_str()).filter(|level| !level.is_empty()).collect();
+        for (depth, level) in levels.iter().enumerate() {
+            let path = levels[..depth].join(&self.separator);
+            self.nodes.entry(path).or_default().add_document_tokenized(document, level);
+        }
+    }
+
+    /// Takes a document and a hierarchical label, tokenizes the document
+    /// with the classifier's tokenizer and adds it to the classifier
+    pub fn add_document(&mut self, document: &str, label: &str) {
+        let tokens = self.tokenizer.tokenize(document);
+        self.add_document_tokenized(&tokens, label);
+    }
+
+    /// Trains the classifier of every level
+    pub fn train(&mut self) {
+        for node in self.nodes.values_mut() {
+            node.train();
+        }
+    }
+
+    /// Gets the full path of every leaf label the classifier has seen
+    pub fn get_labels(&self) -> Vec<String> {
+        let mut labels: Vec<String> = self.nodes.iter()
+            .flat_map(|(path, node)| node.get_labels().into_iter().map(move |child| (path, child)))
+            .map(|(path, child)| self.child_path(path, &child))
+            .filter(|label| !self.nodes.contains_key(label))
+            .collect();
+        labels.sort();
+        labels
+    }
+
+    /// Gets the names of the children of a label, `""` for the top level
+    /// labels. Returns nothing for a leaf or unknown label.
+    pub fn get_children(&self, label: &str) -> Vec<String> {
+        let mut children = self.nodes.get(label).map(|node| node.get_labels()).unwrap_or_default();
+        children.sort();
+        children
+    }
+
+    /// Takes a document that has been tokenized into a vector of strings and
+    /// classifies it level by level, returning the path chosen at every
+    /// level (most general first) with the posterior probability of that
+    /// choice given the level above it. Fails if the classifier has not
+    /// been trained or the document cannot be classified.
+    pub fn classify_levels_tokenized<S: AsRef<str>>(&self, document: &[S]) -> Result<Vec<(String, f64)>, ClassifierError> {
+        let mut levels = Vec::new();
+        let mut path = String::new();
+        while let Some(node) = self.nodes.get(&path) {
+            let (child, posterior) = node.classify_top_k_tokenized(document, 1)?.into_iter().next()
+                .ok_or(ClassifierError::Untrained)?;
+            path = self.child_path(&path, &child);
+            levels.push((path.clone(), posterior));
+        }
+        if levels.is_empty() {
+            return Err(ClassifierError::Untrained);
+        }
+        Ok(levels)
+    }
+
+    /// Tokenizes the document with the classifier's tokenizer and
+    /// classifies it level by level, see `classify_levels_tokenized`
+    pub fn classify_levels(&self, document: &str) -> Result<Vec<(String, f64)>, ClassifierError> {
+        self.classify_levels_tokenized(&self.tokenizer.tokenize(document))
+    }
+
+    /// Takes a document that has been tokenized into a vector of strings and
+    /// computes the full path of its leaf label
+    pub fn classify_tokenized<S: AsRef<str>>(&self, document: &[S]) -> Result<String, ClassifierError> {
+        let mut levels = self.classify_levels_tokenized(document)?;
+        Ok(levels.pop().map(|(path, _)| path).unwrap_or_default())
+    }
+
+    /// Tokenizes the document with the classifier's tokenizer and computes
+    /// the full path of its leaf label
+    pub fn classify(&self, document: &str) -> Result<String, ClassifierError> {
+        self.classify_tokenized(&self.tokenizer.tokenize(document))
+    }
+
+    /// Encodes the classifier as a JSON string.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("encoding JSON failed")
+    }
+
+    /// Encodes the classifier as JSON and writes it to the given writer
+    pub fn to_writer<W: Write>(&self, writer: W) -> Result<(), ClassifierError> {
+        serde_json::to_writer(writer, self).map_err(|e| ClassifierError::Encode(e.to_string()))
+    }
+
+    /// Saves the classifier to a JSON file, see `NaiveBayes::save_to_file`
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ClassifierError> {
+        self.save_to_file_compressed(path, Compression::None)
+    }
+
+    /// Same as `save_to_file`, but compresses the file, see `Compression`
+    pub fn save_to_file_compressed<P: AsRef<Path>>(&self, path: P, compression: Compression)
+                                                   -> Result<(), ClassifierError> {
+        persistence::save(self, path.as_ref(), compression)
+    }
+
+    // the path of a child of the node at `path`
+    fn child_path(&self, path: &str, child: &str) -> String {
+        if path.is_empty() {
+            return child.to_string();
+        }
+        format!("{}{}{}", path, self.separator, child)
+    }
+}
@@ -0,0 +1,328 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use error::ClassifierError;
+use tokenizer::{bucket, ngrams, Tokenizer, WhitespaceTokenizer};
+
+// an encoded model starts with these bytes and then the encoding's version
+static MAGIC: &[u8] = b"NBIM";
+static VERSION: u8 = 1;
+
+/// A trained naive Bayes classifier cut down to what classifying documents
+/// needs, for running a model on devices without the standard library
+///
+/// An inference model is made from a trained classifier with
+/// `NaiveBayes::to_inference_model` and encoded with `to_bytes`, then
+/// decoded on the device with `from_bytes`. It gives the same labels and
+/// posteriors as the classifier it was made from, with the score of every
+/// word precomputed so that classifying is only lookups and additions.
+///
+/// It is the only classifier available without the `std` feature, in which
+/// case the crate is `no_std` and only needs `alloc`. Like the other
+/// classifiers, the tokenizer is not part of the encoded model.
+///
+/// ```rust
+/// use classifier::{InferenceModel, NaiveBayes};
+///
+/// let mut nb = NaiveBayes::new();
+/// nb.add_document("pork belly ribs", "meat");
+/// nb.add_document("kale okra spinach", "veggie");
+/// nb.train();
+/// let bytes = nb.to_inference_model().unwrap().to_bytes();
+///
+/// // on the device
+/// let model = InferenceModel::from_bytes(&bytes).unwrap();
+/// assert_eq!(model.classify("pork ribs").unwrap(), "meat");
+/// ```
+#[derive(Debug, Clone)]
+pub struct InferenceModel<T = WhitespaceTokenizer> {
+    pub(crate) labels: Vec<String>,
+    // the score of each label for a document with no features
+    pub(crate) biases: Vec<f64>,
+    // what each feature not in the vocabulary adds to the score of each label
+    pub(crate) unknown: Vec<f64>,
+    // what each occurrence of a word adds to the score of each label
+    pub(crate) words: BTreeMap<String, Vec<f64>>,
+    // whether a feature only counts once however often it appears, as in
+    // the Bernoulli variant
+    pub(crate) distinct: bool,
+    pub(crate) lowercase: bool,
+    pub(crate) stop_words: BTreeSet<String>,
+    pub(crate) ngram_range: (usize, usize),
+    pub(crate) hash_buckets: Option<usize>,
+    pub(crate) tokenizer: T,
+}
+
+impl InferenceModel {
+
+    /// Decodes a model encoded with `to_bytes` that tokenizes documents by
+    /// breaking on whitespace characters
+    pub fn from_bytes(bytes: &[u8]) -> Result<InferenceModel, ClassifierError> {
+        InferenceModel::from_bytes_with_tokenizer(bytes, WhitespaceTokenizer)
+    }
+}
+
+impl<T: Tokenizer> InferenceModel<T> {
+
+    /// Decodes a model encoded with `to_bytes`, using the given tokenizer
+    /// (which should be the one the classifier was trained with)
+    pub fn from_bytes_with_tokenizer(bytes: &[u8], tokenizer: T) -> Result<InferenceModel<T>, ClassifierError> {
+        let mut decoder = Decoder { bytes };
+        if decoder.take(MAGIC.len())? != MAGIC {
+            return Err(ClassifierError::Decode("not an encoded inference model".to_string()));
+        }
+        let version = decoder.u8()?;
+        if version != VERSION {
+            return Err(ClassifierError::Decode(alloc::format!("unsupported model version {}", version)));
+        }
+        let distinct = decoder.u8()? != 0;
+        let lowercase = decoder.u8()? != 0;
+        let ngram_range = (decoder.u32()?, decoder.u32()?);
+        let hash_buckets = match decoder.u32()? {
+            0 => None,
+            num_buckets => Some(num_buckets),
+        };
+
+        let mut stop_words = BTreeSet::new();
+        for _ in 0..decoder.count(4)? {
+            stop_words.insert(decoder.string()?);
+        }
+        let num_labels = decoder.count(4 + 8 + 8)?;
+        let mut labels = Vec::with_capacity(num_labels);
+        let mut biases = Vec::with_capacity(num_labels);
+        let mut unknown = Vec::with_capacity(num_labels);
+        for _ in 0..num_labels {
+            labels.push(decoder.string()?);
+            biases.push(decoder.f64()?);
+            unknown.push(decoder.f64()?);
+        }
+        let mut words = BTreeMap::new();
+        for _ in 0..decoder.count(4 + 8 * num_labels)? {
+            let word = decoder.string()?;
+            let scores = (0..num_labels).map(|_| decoder.f64()).collect::<Result<Vec<f64>, ClassifierError>>()?;
+            words.insert(word, scores);
+        }
+        if !decoder.bytes.is_empty() {
+            return Err(ClassifierError::Decode("trailing bytes after the model".to_string()));
+        }
+
+        Ok(InferenceModel {
+            labels, biases, unknown, words, distinct, lowercase, stop_words, ngram_range, hash_buckets, tokenizer,
+        })
+    }
+
+    /// Encodes the model into a compact binary form, little-endian on every
+    /// platform
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = MAGIC.to_vec();
+        bytes.push(VERSION);
+        bytes.push(self.distinct as u8);
+        bytes.push(self.lowercase as u8);
+        put_u32(&mut bytes, self.ngram_range.0);
+        put_u32(&mut bytes, self.ngram_range.1);
+        put_u32(&mut bytes, self.hash_buckets.unwrap_or(0));
+
+        put_u32(&mut bytes, self.stop_words.len());
+        for word in self.stop_words.iter() {
+            put_str(&mut bytes, word);
+        }
+        put_u32(&mut bytes, self.labels.len());
+        for ((label, bias), unknown) in self.labels.iter().zip(self.biases.iter()).zip(self.unknown.iter()) {
+            put_str(&mut bytes, label);
+            bytes.extend_from_slice(&bias.to_le_bytes());
+            bytes.extend_from_slice(&unknown.to_le_bytes());
+        }
+        put_u32(&mut bytes, self.words.len());
+        for (word, scores) in self.words.iter() {
+            put_str(&mut bytes, word);
+            for score in scores.iter() {
+                bytes.extend_from_slice(&score.to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Gets all of the labels the model knows
+    pub fn get_labels(&self) -> Vec<String> {
+        self.labels.clone()
+    }
+
+    /// The number of words in the model's vocabulary
+    pub fn vocab_size(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Takes a document that has been tokenized into a vector of strings and
+    /// computes a classifying label for it. Fails if the document has no
+    /// features.
+    pub fn classify_tokenized<S: AsRef<str>>(&self, document: &[S]) -> Result<String, ClassifierError> {
+        let scores = self.scores(document)?;
+        let mut best = 0;
+        for (i, &score) in scores.iter().enumerate() {
+            if score > scores[best] {
+                best = i;
+            }
+        }
+        Ok(self.labels[best].clone())
+    }
+
+    /// Tokenizes the document with the model's tokenizer and computes a
+    /// classifying label for it
+    pub fn classify(&self, document: &str) -> Result<String, ClassifierError> {
+        self.classify_tokenized(&self.tokenize(document))
+    }
+
+    /// Takes a document that has been tokenized into a vector of strings and
+    /// gets every label and its posterior probability given the document,
+    /// see `NaiveBayes::get_document_posteriors`
+    pub fn get_document_posteriors_tokenized<S: AsRef<str>>(&self, document: &[S])
+        -> Result<Vec<(String, f64)>, ClassifierError>
+    {
+        let scores = self.scores(document)?;
+        // shift by the largest score first so exponentiating can't underflow
+        let max_score = scores.iter().fold(f64::NEG_INFINITY, |acc, &s| acc.max(s));
+        let total: f64 = scores.iter().map(|&s| exp(s - max_score)).sum();
+        Ok(self.labels.iter().zip(scores.iter())
+            .map(|(label, &s)| (label.clone(), exp(s - max_score) / total))
+            .collect())
+    }
+
+    /// Tokenizes the document with the model's tokenizer and gets every
+    /// label and its posterior probability given the document
+    pub fn get_document_posteriors(&self, document: &str) -> Result<Vec<(String, f64)>, ClassifierError> {
+        self.get_document_posteriors_tokenized(&self.tokenize(document))
+    }
+
+    fn tokenize(&self, document: &str) -> Vec<String> {
+        if self.lowercase {
+            self.tokenizer.tokenize(&document.to_lowercase())
+        } else {
+            self.tokenizer.tokenize(document)
+        }
+    }
+
+    // turns tokens into features the way the classifier did, see
+    // `NaiveBayes::features`
+    fn features<S: AsRef<str>>(&self, tokens: &[S]) -> Vec<String> {
+        let tokens: Vec<String> = tokens.iter()
+            .map(AsRef::as_ref)
+            .filter(|token| !token.is_empty() && !self.stop_words.contains(*token))
+            .map(ToString::to_string)
+            .collect();
+        let (min, max) = self.ngram_range;
+        let features = if min == 1 && max == 1 { tokens } else { ngrams(&tokens, min, max) };
+        match self.hash_buckets {
+            Some(num_buckets) => features.iter().map(|feature| bucket(feature, num_buckets)).collect(),
+            None => features,
+        }
+    }
+
+    // the score of every label, in the order of `labels`
+    fn scores<S: AsRef<str>>(&self, document: &[S]) -> Result<Vec<f64>, ClassifierError> {
+        let mut features = self.features(document);
+        if features.is_empty() {
+            return Err(ClassifierError::EmptyDocument);
+        }
+        if self.distinct {
+            features.sort();
+            features.dedup();
+        }
+        let mut scores = self.biases.clone();
+        for feature in features.iter() {
+            let weights = self.words.get(feature).unwrap_or(&self.unknown);
+            for (score, weight) in scores.iter_mut().zip(weights.iter()) {
+                *score += weight;
+            }
+        }
+        Ok(scores)
+    }
+}
+
+// reads the parts of an encoded model in order
+struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+
+    fn take(&mut self, n: usize) -> Result<&'a [u8], ClassifierError> {
+        if self.bytes.len() < n {
+            return Err(ClassifierError::Decode("unexpected end of the model".to_string()));
+        }
+        let (taken, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, ClassifierError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<usize, ClassifierError> {
+        let mut buffer = [0u8; 4];
+        buffer.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(buffer) as usize)
+    }
+
+    // reads the number of items that follow, each at least `min_size` bytes
+    // long, refusing counts the rest of the model is too short to hold so
+    // that a corrupt count can't make the decoder allocate beyond the input
+    fn count(&mut self, min_size: usize) -> Result<usize, ClassifierError> {
+        let count = self.u32()?;
+        if count.saturating_mul(min_size) > self.bytes.len() {
+            return Err(ClassifierError::Decode("unexpected end of the model".to_string()));
+        }
+        Ok(count)
+    }
+
+    fn f64(&mut self) -> Result<f64, ClassifierError> {
+        let mut buffer = [0u8; 8];
+        buffer.copy_from_slice(self.take(8)?);
+        Ok(f64::from_le_bytes(buffer))
+    }
+
+    fn string(&mut self) -> Result<String, ClassifierError> {
+        let len = self.u32()?;
+        String::from_utf8(self.take(len)?.to_vec()).map_err(|e| ClassifierError::Decode(e.to_string()))
+    }
+}
+
+fn put_u32(bytes: &mut Vec<u8>, n: usize) {
+    bytes.extend_from_slice(&(n as u32).to_le_bytes());
+}
+
+fn put_str(bytes: &mut Vec<u8>, s: &str) {
+    put_u32(bytes, s.len());
+    bytes.extend_from_slice(s.as_bytes());
+}
+
+#[cfg(feature = "std")]
+fn exp(x: f64) -> f64 {
+    x.exp()
+}
+
+// e^x for x <= 0, as `core` has no `f64::exp`. x is split into k ln 2 + r
+// with |r| < ln 2, and e^r summed from its Taylor series.
+#[cfg(not(feature = "std"))]
+fn exp(x: f64) -> f64 {
+    use core::f64::consts::LN_2;
+
+    if x < -745.2 {
+        return 0.0;
+    }
+    let k = (x / LN_2) as i64;
+    let r = x - k as f64 * LN_2;
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    for n in 1..25 {
+        term *= r / n as f64;
+        sum += term;
+    }
+    // 2^k in two steps when it is too small to be a normal number
+    let pow2 = |k: i64| f64::from_bits(((k + 1023) as u64) << 52);
+    if k < -1022 {
+        sum * pow2(-1022) * pow2(k + 1022)
+    } else {
+        sum * pow2(k)
+    }
+}
@@ -0,0 +1,188 @@
+//! Identifying the language of documents from their character n-grams,
+//! e.g. to route text to a classifier trained for its language
+//!
+//! `LanguageIdentifier::pretrained` comes trained on a small bundled sample
+//! of English, French, German, Spanish, Italian, Portuguese, and Dutch, which
+//! is enough for sentences of a few words. For other languages, or better
+//! accuracy on short or specialized text, train one on samples of your own:
+//!
+//! ```rust
+//! use classifier::langid::LanguageIdentifier;
+//!
+//! let identifier = LanguageIdentifier::pretrained();
+//! assert_eq!(identifier.detect("where is the train station").unwrap(), "en");
+//! assert_eq!(identifier.detect("où est la gare").unwrap(), "fr");
+//!
+//! let mut custom = LanguageIdentifier::new();
+//! custom.add_sample("the cat sat on the mat with the hat", "english");
+//! custom.add_sample("die katze sitzt auf der matte mit dem hut", "german");
+//! custom.train();
+//! assert_eq!(custom.detect("the hat").unwrap(), "english");
+//! ```
+
+use error::ClassifierError;
+use naive_bayes::Classifier;
+use preprocessing::Preprocessor;
+use tokenizer::CharNgramTokenizer;
+
+/// The (ISO 639-1 language code, text) samples `LanguageIdentifier::pretrained`
+/// is trained on
+pub static SAMPLES: &[(&str, &str)] = &[
+    ("en", "the quick brown fox jumps over the lazy dog. where is the train station? \
+            i would like a cup of coffee with milk, please. we went to the market this morning \
+            and bought some bread and cheese. it is raining again, so they stayed at home and \
+            watched a film. what time does the shop open tomorrow? my brother works in a hospital \
+            near the river. thank you very much for your help, it was very kind of you. \
+            i do not know where he lives, but she says that it is not far from here. the children \
+            are playing in the garden with their friends. this is the first time that we have seen \
+            such a beautiful day."),
+    ("fr", "le renard brun rapide saute par-dessus le chien paresseux. où est la gare ? \
+            je voudrais une tasse de café au lait, s'il vous plaît. nous sommes allés au marché \
+            ce matin et nous avons acheté du pain et du fromage. il pleut encore, alors ils sont \
+            restés à la maison pour regarder un film. à quelle heure ouvre le magasin demain ? \
+            mon frère travaille dans un hôpital près de la rivière. merci beaucoup pour votre aide. \
+            je ne sais pas où il habite, mais elle dit que ce n'est pas loin d'ici. les enfants \
+            jouent dans le jardin avec leurs amis. c'est la première fois que nous voyons une si \
+            belle journée."),
+    ("de", "der schnelle braune fuchs springt über den faulen hund. wo ist der bahnhof? \
+            ich hätte gern eine tasse kaffee mit milch, bitte. wir sind heute morgen auf den markt \
+            gegangen und haben brot und käse gekauft. es regnet schon wieder, also sind sie zu hause \
+            geblieben und haben einen film gesehen. wann öffnet das geschäft morgen? mein bruder \
+            arbeitet in einem krankenhaus in der nähe des flusses. vielen dank für ihre hilfe. \
+            ich weiß nicht, wo er wohnt, aber sie sagt, dass es nicht weit von hier ist. die \
+            kinder spielen mit ihren freunden im garten. das ist das erste mal, dass wir so einen \
+            schönen tag gesehen haben."),
+    ("es", "el rápido zorro marrón salta sobre el perro perezoso. ¿dónde está la estación de tren? \
+            quisiera una taza de café con leche, por favor. esta mañana fuimos al mercado y compramos \
+            pan y queso. está lloviendo otra vez, así que se quedaron en casa y vieron una película. \
+            ¿a qué hora abre la tienda mañana? mi hermano trabaja en un hospital cerca del río. \
+            muchas gracias por su ayuda, ha sido muy amable. \
+            no sé dónde vive, pero ella dice que no está lejos de aquí. los niños juegan en el \
+            jardín con sus amigos. es la primera vez que vemos un día tan bonito."),
+    ("it", "la veloce volpe marrone salta sopra il cane pigro. dov'è la stazione dei treni? \
+            vorrei una tazza di caffè con il latte, per favore. stamattina siamo andati al mercato e \
+            abbiamo comprato pane e formaggio. sta piovendo di nuovo, quindi sono rimasti a casa e \
+            hanno guardato un film. a che ora apre il negozio domani? mio fratello lavora in un \
+            ospedale vicino al fiume. grazie mille per il vostro aiuto, siete stati molto gentili. \
+            non so dove abita, ma lei dice che non è lontano da qui. i bambini giocano in giardino \
+            con i loro amici. è la prima volta che vediamo una giornata così bella."),
+    ("pt", "a rápida raposa marrom pula sobre o cão preguiçoso. onde fica a estação de comboios? \
+            eu gostaria de uma chávena de café com leite, por favor. hoje de manhã fomos ao mercado \
+            e comprámos pão e queijo. está a chover outra vez, então eles ficaram em casa e viram um \
+            filme. a que horas abre a loja amanhã? o meu irmão trabalha num hospital perto do rio. \
+            muito obrigado pela sua ajuda, foi muito simpático. \
+            não sei onde ele mora, mas ela diz que não é longe daqui. as crianças brincam no \
+            jardim com os seus amigos. é a primeira vez que vemos um dia tão bonito."),
+    ("nl", "de snelle bruine vos springt over de luie hond. waar is het treinstation? \
+            ik wil graag een kopje koffie met melk, alstublieft. we zijn vanochtend naar de markt \
+            gegaan en hebben brood en kaas gekocht. het regent weer, dus ze zijn thuis gebleven en \
+            hebben een film gekeken. hoe laat gaat de winkel morgen open? mijn broer werkt in een \
+            ziekenhuis bij de rivier. heel erg bedankt voor je hulp, dat was erg aardig van je. \
+            ik weet niet waar hij woont, maar zij zegt dat het niet ver van hier is. de kinderen \
+            spelen met hun vrienden in de tuin. het is de eerste keer dat we zo'n mooie dag hebben \
+            gezien."),
+];
+
+/// Identifies the language of documents with a naive Bayes classifier over
+/// the character n-grams of their words, see the `langid` module
+#[derive(Debug, Clone)]
+pub struct LanguageIdentifier {
+    classifier: Classifier<CharNgramTokenizer>,
+}
+
+impl LanguageIdentifier {
+
+    /// Creates an untrained identifier over the character 1, 2, and 3-grams
+    /// of lowercased words
+    pub fn new() -> LanguageIdentifier {
+        LanguageIdentifier::with_tokenizer(CharNgramTokenizer::new(1, 3).unwrap())
+    }
+
+    /// Creates an untrained identifier over the character n-grams the given
+    /// tokenizer produces from lowercased words
+    pub fn with_tokenizer(tokenizer: CharNgramTokenizer) -> LanguageIdentifier {
+        let mut classifier = Classifier::with_tokenizer(tokenizer);
+        classifier.set_preprocessor(Preprocessor::lowercasing());
+        LanguageIdentifier { classifier }
+    }
+
+    /// Creates an identifier trained on the bundled `SAMPLES`, which
+    /// identifies languages by their ISO 639-1 codes
+    pub fn pretrained() -> LanguageIdentifier {
+        let mut identifier = LanguageIdentifier::new();
+        for &(language, text) in SAMPLES.iter() {
+            for sentence in text.split_terminator(['.', '?', '!']) {
+                identifier.add_sample(sentence, language);
+            }
+        }
+        identifier.train();
+        identifier
+    }
+
+    /// Builds an identifier made with `new` or `pretrained` from a JSON
+    /// string written by `to_json`
+    pub fn from_json(encoded: &str) -> Result<LanguageIdentifier, ClassifierError> {
+        LanguageIdentifier::from_json_with_tokenizer(encoded, CharNgramTokenizer::new(1, 3).unwrap())
+    }
+
+    /// Builds an identifier from a JSON string written by `to_json`, using
+    /// the given tokenizer (which should be the one it was trained with)
+    pub fn from_json_with_tokenizer(encoded: &str, tokenizer: CharNgramTokenizer)
+        -> Result<LanguageIdentifier, ClassifierError>
+    {
+        Ok(LanguageIdentifier { classifier: Classifier::from_json_with_tokenizer(encoded, tokenizer)? })
+    }
+
+    /// Adds a sample of text in the given language, for the next call to
+    /// `train()`
+    pub fn add_sample(&mut self, text: &str, language: &str) {
+        self.classifier.add_document(text, language);
+    }
+
+    /// Trains the identifier on the samples that have been added
+    pub fn train(&mut self) {
+        self.classifier.train();
+    }
+
+    /// Gets every language the identifier knows, sorted
+    pub fn languages(&self) -> Vec<String> {
+        self.classifier.get_labels()
+    }
+
+    /// Identifies the language of the document. Fails if the identifier has
+    /// not been trained or the document has no words.
+    pub fn detect(&self, document: &str) -> Result<String, ClassifierError> {
+        self.classifier.classify(document)
+    }
+
+    /// Identifies the language of the document, or returns `None` if the
+    /// posterior probability of the most likely language is less than
+    /// `min_confidence`
+    pub fn detect_with_threshold(&self, document: &str, min_confidence: f64)
+        -> Result<Option<String>, ClassifierError>
+    {
+        self.classifier.classify_with_threshold(document, min_confidence)
+    }
+
+    /// Gets every language and its posterior probability given the document,
+    /// most likely first
+    pub fn probabilities(&self, document: &str) -> Result<Vec<(String, f64)>, ClassifierError> {
+        self.classifier.classify_top_k(document, usize::MAX)
+    }
+
+    /// Gets the classifier behind the identifier
+    pub fn classifier(&self) -> &Classifier<CharNgramTokenizer> {
+        &self.classifier
+    }
+
+    /// Encodes the identifier as a JSON string
+    pub fn to_json(&self) -> String {
+        self.classifier.to_json()
+    }
+}
+
+impl Default for LanguageIdentifier {
+    fn default() -> LanguageIdentifier {
+        LanguageIdentifier::new()
+    }
+}
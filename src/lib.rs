@@ -24,7 +24,7 @@
 //! 
 //! // add each example document to the classifier
 //! for &(document, label) in examples.iter() {
-//!     nb.add_document(&document.to_string(), &label.to_string());
+//!     nb.add_document(document, label);
 //! }
 //! 
 //! // train the classifier
@@ -33,12 +33,194 @@
 //! // now try to classify a new document with the classifier
 //! let food_document = "salami pancetta beef ribs".to_string();
 //! 
-//! assert_eq!( nb.classify(&food_document), "meat" );
+//! assert_eq!( nb.classify(&food_document).unwrap(), "meat" );
 //! ```
 //! meat examples from [baconipsum](http://baconipsum.com/), veggie examples from: [veggieipsum](http://veggieipsum.com/)
+//!
+//! ## Without the standard library
+//!
+//! Building without the default `std` feature leaves a `no_std` crate that
+//! only needs `alloc`, with `InferenceModel` for classifying documents with a
+//! model trained elsewhere, the tokenizers and `ClassifierError`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 
-extern crate rustc_serialize;
+extern crate alloc;
+#[cfg(feature = "binary")]
+extern crate bincode;
+#[cfg(feature = "encoding-detection")]
+extern crate chardetng;
+// PyO3's macros refer to `::core`
+#[cfg(feature = "python")]
+extern crate core;
+#[cfg(feature = "encoding-detection")]
+extern crate encoding_rs;
+#[cfg(feature = "gzip")]
+extern crate flate2;
+#[cfg(feature = "ndarray")]
+extern crate ndarray;
+#[cfg(feature = "python")]
+extern crate pyo3;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "std")]
 extern crate regex;
+#[cfg(feature = "stemming")]
+extern crate rust_stemmers;
+#[cfg(feature = "std")]
+extern crate serde;
+#[cfg(feature = "std")]
+#[macro_use]
+extern crate serde_derive;
+#[cfg(feature = "std")]
+extern crate serde_json;
+#[cfg(feature = "disk")]
+extern crate sled;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(feature = "std")]
+extern crate unicode_normalization;
+#[cfg(feature = "wasm")]
+extern crate wasm_bindgen;
+#[cfg(feature = "zstandard")]
+extern crate zstd;
 
+#[cfg(feature = "binary")]
+mod binary;
+#[cfg(feature = "std")]
+mod binary_classifier;
+#[cfg(feature = "std")]
+mod checkpoint;
+#[cfg(feature = "std")]
+mod collocations;
+#[cfg(feature = "std")]
+mod dedup;
+#[cfg(feature = "disk")]
+mod disk;
+mod error;
+#[cfg(feature = "std")]
+mod ensemble;
+#[cfg(feature = "std")]
+mod explanation;
+#[cfg(feature = "std")]
+mod frozen;
+#[cfg(feature = "std")]
+mod gaussian;
+#[cfg(feature = "std")]
+mod hierarchy;
+mod inference;
+#[cfg(feature = "std")]
+mod logistic;
+#[cfg(feature = "ndarray")]
+mod matrix;
+#[cfg(feature = "std")]
+mod model;
+#[cfg(feature = "std")]
 mod naive_bayes;
-pub use self::naive_bayes::Classifier as NaiveBayes;
\ No newline at end of file
+#[cfg(feature = "std")]
+mod observer;
+#[cfg(feature = "std")]
+mod onnx;
+#[cfg(feature = "std")]
+mod persistence;
+#[cfg(feature = "std")]
+mod pipeline;
+#[cfg(feature = "std")]
+mod preprocessing;
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "std")]
+mod random;
+#[cfg(feature = "std")]
+mod sklearn;
+#[cfg(feature = "std")]
+mod stats;
+#[cfg(feature = "std")]
+mod stemmer;
+#[cfg(feature = "std")]
+mod text_classifier;
+mod tokenizer;
+#[cfg(feature = "std")]
+mod typed;
+#[cfg(feature = "std")]
+mod vectorizer;
+#[cfg(feature = "std")]
+mod vocabulary;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "stop-words")]
+pub mod stop_words;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod active_learning;
+#[cfg(feature = "std")]
+pub mod dataset;
+#[cfg(feature = "std")]
+pub mod evaluation;
+#[cfg(feature = "std")]
+pub mod feature_selection;
+#[cfg(feature = "langid")]
+pub mod langid;
+#[cfg(feature = "std")]
+pub mod sanitize;
+#[cfg(feature = "sentiment")]
+pub mod sentiment;
+#[cfg(feature = "std")]
+pub mod tuning;
+#[cfg(feature = "std")]
+pub mod validation;
+#[cfg(feature = "std")]
+pub use self::binary_classifier::BinaryClassifier;
+#[cfg(feature = "std")]
+pub use self::collocations::{CollocationTokenizer, Collocations};
+#[cfg(feature = "std")]
+pub use self::dedup::Deduplicator;
+#[cfg(feature = "disk")]
+pub use self::disk::DiskTrainer;
+pub use self::error::ClassifierError;
+#[cfg(feature = "std")]
+pub use self::ensemble::{Ensemble, Voting};
+#[cfg(feature = "std")]
+pub use self::explanation::{Explanation, LabelExplanation};
+#[cfg(feature = "std")]
+pub use self::frozen::FrozenModel;
+#[cfg(feature = "std")]
+pub use self::gaussian::GaussianNaiveBayes;
+#[cfg(feature = "std")]
+pub use self::hierarchy::HierarchicalClassifier;
+pub use self::inference::InferenceModel;
+#[cfg(feature = "std")]
+pub use self::logistic::LogisticRegression;
+#[cfg(feature = "std")]
+pub use self::model::{NaiveBayesModel, NaiveBayesTrainer};
+#[cfg(feature = "std")]
+pub use self::naive_bayes::{Classifier as NaiveBayes, FeatureWeighting, NaiveBayesVariant, PriorMode,
+                             UnknownWordPolicy};
+#[cfg(feature = "std")]
+pub use self::observer::TrainingObserver;
+#[cfg(feature = "std")]
+pub use self::persistence::Compression;
+#[cfg(feature = "std")]
+pub use self::pipeline::{Pipeline, PipelineBuilder, TokenizerStage};
+#[cfg(feature = "std")]
+pub use self::preprocessing::{Preprocessor, PreprocessorBuilder, TokenFilter, UnicodeNormalization};
+#[cfg(feature = "python")]
+pub use self::python::PyClassifier;
+#[cfg(feature = "std")]
+pub use self::stats::{LabelStats, ModelStats};
+#[cfg(feature = "std")]
+pub use self::stemmer::{Stemmer, StemmingTokenizer};
+#[cfg(feature = "stemming")]
+pub use self::stemmer::SnowballStemmer;
+#[cfg(feature = "stemming")]
+pub use rust_stemmers::Algorithm as StemmingAlgorithm;
+#[cfg(feature = "std")]
+pub use self::text_classifier::TextClassifier;
+pub use self::tokenizer::{CharNgramTokenizer, Tokenizer, WhitespaceTokenizer};
+#[cfg(feature = "std")]
+pub use self::typed::TypedNaiveBayes;
+#[cfg(feature = "std")]
+pub use self::vectorizer::{DocumentVector, Vectorizer};
+#[cfg(feature = "wasm")]
+pub use self::wasm::WasmClassifier;
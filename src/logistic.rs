@@ -0,0 +1,453 @@
+use std::collections::{HashMap, HashSet};
+use std::f64;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use serde_json;
+use tokenizer::{ngrams, Tokenizer, WhitespaceTokenizer};
+use preprocessing::Preprocessor;
+use observer::TrainingObserver;
+#[cfg(feature = "rand")]
+use rand::Rng;
+use random::{Shuffle, SplitMix64};
+use error::ClassifierError;
+use persistence::{self, Compression};
+
+static DEFAULT_LEARNING_RATE: f64 = 0.5f64;
+static DEFAULT_REGULARIZATION: f64 = 1e-4f64;
+static DEFAULT_EPOCHS: usize = 20;
+
+/// Multinomial logistic regression text classifier
+///
+/// Documents are turned into bags of words the same way `NaiveBayes` does
+/// it (tokenizer, preprocessing, stop words and n-grams), and a weight per
+/// word and label is learned by stochastic gradient descent with L2
+/// regularization. This usually classifies better than naive Bayes once
+/// there are enough training documents, at the cost of slower training.
+///
+/// The added documents are kept so that the classifier can be trained
+/// again, but they are not part of the serialized model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogisticRegression<T = WhitespaceTokenizer> {
+    // the index of each word's weight
+    vocab: HashMap<String, usize>,
+    labels: Vec<String>,
+    // one weight per word for each label
+    weights: Vec<Vec<f64>>,
+    biases: Vec<f64>,
+    learning_rate: f64,
+    regularization: f64,
+    epochs: usize,
+    seed: u64,
+    ngram_range: (usize, usize),
+    stop_words: HashSet<String>,
+    preprocessor: Preprocessor,
+    // the (word index, count) pairs and the label index of each document
+    #[serde(skip)]
+    documents: Vec<(Vec<(usize, f64)>, usize)>,
+    #[serde(skip)]
+    tokenizer: T,
+}
+
+impl LogisticRegression {
+
+    /// Creates a new classifier that tokenizes documents by breaking on
+    /// whitespace characters
+    pub fn new() -> LogisticRegression {
+        LogisticRegression::with_tokenizer(WhitespaceTokenizer)
+    }
+
+    /// Builds a new classifier from a JSON string
+    pub fn from_json(encoded: &str) -> Result<LogisticRegression, ClassifierError> {
+        serde_json::from_str(encoded).map_err(|e| ClassifierError::Decode(e.to_string()))
+    }
+
+    /// Builds a new classifier from JSON read from the given reader
+    pub fn from_reader<R: Read>(reader: R) -> Result<LogisticRegression, ClassifierError> {
+        serde_json::from_reader(reader).map_err(|e| ClassifierError::Decode(e.to_string()))
+    }
+
+    /// Loads a classifier from a file written by `save_to_file` or
+    /// `save_to_file_compressed`
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<LogisticRegression, ClassifierError> {
+        persistence::load(path.as_ref())
+    }
+
+}
+
+impl Default for LogisticRegression {
+    fn default() -> LogisticRegression {
+        LogisticRegression::new()
+    }
+}
+
+impl<T: Tokenizer> LogisticRegression<T> {
+
+    /// Creates a new classifier that uses the given tokenizer to break
+    /// documents into words, both when training and when classifying
+    pub fn with_tokenizer(tokenizer: T) -> LogisticRegression<T> {
+        LogisticRegression {
+            vocab: HashMap::new(),
+            labels: Vec::new(),
+            weights: Vec::new(),
+            biases: Vec::new(),
+            learning_rate: DEFAULT_LEARNING_RATE,
+            regularization: DEFAULT_REGULARIZATION,
+            epochs: DEFAULT_EPOCHS,
+            seed: 0,
+            ngram_range: (1, 1),
+            stop_words: HashSet::new(),
+            preprocessor: Preprocessor::new(),
+            documents: Vec::new(),
+            tokenizer,
+        }
+    }
+
+    /// Builds a new classifier from a JSON string, using the given tokenizer
+    /// (which should be the one the classifier was trained with)
+    pub fn from_json_with_tokenizer(encoded: &str, tokenizer: T) -> Result<LogisticRegression<T>, ClassifierError> {
+        Ok(LogisticRegression::retokenized(LogisticRegression::from_json(encoded)?, tokenizer))
+    }
+
+    /// Loads a classifier from a file written by `save_to_file`, using the
+    /// given tokenizer (which should be the one the classifier was trained with)
+    pub fn load_from_file_with_tokenizer<P: AsRef<Path>>(path: P, tokenizer: T) -> Result<LogisticRegression<T>, ClassifierError> {
+        Ok(LogisticRegression::retokenized(LogisticRegression::load_from_file(path)?, tokenizer))
+    }
+
+    /// Takes a document that has been tokenized into a vector of strings
+    /// and a label and adds the document to the list of documents that the
+    /// classifier will train on next time the `train()` method is called
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", name = "add_document", skip_all,
+                                                         fields(label = label, tokens = document.len())))]
+    pub fn add_document_tokenized<S: AsRef<str>>(&mut self, document: &[S], label: &str) {
+        if document.is_empty() { return; }
+
+        let mut counts: HashMap<usize, f64> = HashMap::new();
+        for feature in self.features(document) {
+            let next_index = self.vocab.len();
+            let index = *self.vocab.entry(feature).or_insert(next_index);
+            *counts.entry(index).or_insert(0.0) += 1.0;
+        }
+        let label_index = match self.labels.iter().position(|l| l == label) {
+            Some(index) => index,
+            None => {
+                self.labels.push(label.to_string());
+                self.labels.len() - 1
+            },
+        };
+        let mut counts: Vec<(usize, f64)> = counts.into_iter().collect();
+        counts.sort_by_key(|&(index, _)| index);
+        self.documents.push((counts, label_index));
+    }
+
+    /// Takes a document and a label and tokenizes the document with the
+    /// classifier's tokenizer, see `add_document_tokenized`
+    pub fn add_document(&mut self, document: &str, label: &str) {
+        let tokens = self.tokenize(document);
+        self.add_document_tokenized(&tokens, label);
+    }
+
+    /// Adds a list of (document, label) tuples to the classifier
+    pub fn add_documents<D: AsRef<str>, L: AsRef<str>>(&mut self, examples: &[(D, L)]) {
+        for (document, label) in examples.iter() {
+            self.add_document(document.as_ref(), label.as_ref());
+        }
+    }
+
+    /// Gets a vector of all of the labels that the classifier has seen so far
+    pub fn get_labels(&self) -> Vec<String> {
+        self.labels.clone()
+    }
+
+    /// Gets the number of distinct words (features) the classifier knows
+    pub fn vocab_size(&self) -> usize {
+        self.vocab.len()
+    }
+
+    /// Sets the step size of gradient descent (must be greater than 0.0,
+    /// defaults to 0.5)
+    pub fn set_learning_rate(&mut self, learning_rate: f64) -> Result<(), ClassifierError> {
+        if learning_rate.is_nan() || learning_rate <= 0.0 {
+            return Err(ClassifierError::InvalidLearningRate(learning_rate));
+        }
+        self.learning_rate = learning_rate;
+        Ok(())
+    }
+
+    /// Sets the strength of the L2 regularization, which keeps the weights
+    /// small so rare words can't dominate (must not be negative, defaults
+    /// to 1e-4)
+    pub fn set_regularization(&mut self, regularization: f64) -> Result<(), ClassifierError> {
+        if regularization.is_nan() || regularization < 0.0 {
+            return Err(ClassifierError::InvalidRegularization(regularization));
+        }
+        self.regularization = regularization;
+        Ok(())
+    }
+
+    /// Sets the number of passes over the documents that training makes
+    /// (defaults to 20)
+    pub fn set_epochs(&mut self, epochs: usize) {
+        self.epochs = epochs;
+    }
+
+    /// Sets the seed of the order documents are visited in while training,
+    /// the same seed always gives the same model
+    pub fn set_seed(&mut self, seed: u64) {
+        self.seed = seed;
+    }
+
+    /// Sets the range of n-gram sizes to use as features, see
+    /// `NaiveBayes::set_ngram_range`
+    pub fn set_ngram_range(&mut self, min: usize, max: usize) -> Result<(), ClassifierError> {
+        if min == 0 || min > max {
+            return Err(ClassifierError::InvalidNgramRange(min, max));
+        }
+        self.ngram_range = (min, max);
+        Ok(())
+    }
+
+    /// Sets the stop words, see `NaiveBayes::set_stop_words`
+    pub fn set_stop_words<I, S>(&mut self, stop_words: I)
+        where I: IntoIterator<Item = S>, S: AsRef<str>
+    {
+        self.stop_words = stop_words.into_iter().map(|word| word.as_ref().to_string()).collect();
+    }
+
+    /// Sets the preprocessing applied to documents before they are
+    /// tokenized, see `Preprocessor`
+    pub fn set_preprocessor(&mut self, preprocessor: Preprocessor) {
+        self.preprocessor = preprocessor;
+    }
+
+    /// Trains the classifier on the documents that have been added so far,
+    /// starting over from scratch every time
+    pub fn train(&mut self) {
+        self.train_observed(&mut ());
+    }
+
+    /// Same as `train`, but tells the observer how much of the training is
+    /// done after each pass over the documents
+    pub fn train_observed<O: TrainingObserver + ?Sized>(&mut self, observer: &mut O) {
+        let mut rng = SplitMix64::new(self.seed);
+        self.train_shuffled(&mut rng, observer, None);
+    }
+
+    /// Same as `train`, but gives up with a `Cancelled` error if `cancel` is
+    /// set, which is checked before each document is visited. A cancelled
+    /// classifier is left part way through training and needs to be trained
+    /// again.
+    pub fn train_with_cancel(&mut self, cancel: &AtomicBool) -> Result<(), ClassifierError> {
+        self.train_observed_with_cancel(&mut (), cancel)
+    }
+
+    /// Same as `train_with_cancel`, but tells the observer how much of the
+    /// training is done after each pass over the documents
+    pub fn train_observed_with_cancel<O: TrainingObserver + ?Sized>(&mut self, observer: &mut O, cancel: &AtomicBool)
+                                                                   -> Result<(), ClassifierError> {
+        let mut rng = SplitMix64::new(self.seed);
+        if self.train_shuffled(&mut rng, observer, Some(cancel)) { Ok(()) } else { Err(ClassifierError::Cancelled) }
+    }
+
+    /// Same as `train`, but shuffles the documents with the given generator
+    /// from the `rand` crate rather than the classifier's seed. Requires the
+    /// `rand` feature.
+    #[cfg(feature = "rand")]
+    pub fn train_with_rng<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.train_shuffled(rng, &mut (), None);
+    }
+
+    // trains the classifier, returning false if it was cancelled
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", name = "train", skip_all,
+                                                         fields(labels = self.labels.len(), vocab = self.vocab.len(),
+                                                                epochs = self.epochs)))]
+    fn train_shuffled<S, O>(&mut self, rng: &mut S, observer: &mut O, cancel: Option<&AtomicBool>) -> bool
+        where S: Shuffle + ?Sized, O: TrainingObserver + ?Sized
+    {
+        let num_labels = self.labels.len();
+        let num_words = self.vocab.len();
+        self.biases = vec![0.0; num_labels];
+        self.weights = vec![vec![0.0; num_words]; num_labels];
+
+        // documents are scaled to unit length so long ones don't take
+        // bigger steps than short ones
+        let documents: Vec<(Vec<(usize, f64)>, usize)> = self.documents.iter()
+            .map(|(counts, label)| (normalized(counts), *label))
+            .collect();
+        let mut order: Vec<usize> = (0..documents.len()).collect();
+
+        for epoch in 0..self.epochs {
+            rng.shuffle(&mut order);
+            let learning_rate = self.learning_rate / (1.0 + epoch as f64 * 0.1);
+            for &i in order.iter() {
+                if cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(epoch, "training cancelled");
+                    return false;
+                }
+                let (ref features, label) = documents[i];
+                let probabilities = self.probabilities(features);
+                for (k, &p) in probabilities.iter().enumerate() {
+                    let error = p - if k == label { 1.0 } else { 0.0 };
+                    self.biases[k] -= learning_rate * error;
+                    // only the weights of words in the document are
+                    // regularized, which keeps each step cheap
+                    let weights = &mut self.weights[k];
+                    for &(j, x) in features.iter() {
+                        weights[j] -= learning_rate * (error * x + self.regularization * weights[j]);
+                    }
+                }
+            }
+            #[cfg(feature = "tracing")]
+            tracing::trace!(epoch, learning_rate, "finished epoch");
+            observer.on_train_progress((epoch + 1) as f64 / self.epochs as f64);
+        }
+        if self.epochs == 0 {
+            observer.on_train_progress(1.0);
+        }
+        true
+    }
+
+    /// Takes an unlabeled document that has been tokenized into a vector of
+    /// strings and computes a classifying label for it. Fails if the
+    /// classifier has not been trained, has an empty vocabulary, or the
+    /// document has no features.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", name = "classify", skip_all,
+                                                         fields(tokens = document.len())))]
+    pub fn classify_tokenized<S: AsRef<str>>(&self, document: &[S]) -> Result<String, ClassifierError> {
+        let posteriors = self.get_document_posteriors_tokenized(document)?;
+        let best = posteriors.into_iter().fold(None, |best: Option<(String, f64)>, (label, p)| match best {
+            Some(ref b) if b.1 >= p => best,
+            _ => Some((label, p)),
+        });
+        best.map(|(label, _)| label).ok_or(ClassifierError::Untrained)
+    }
+
+    /// Takes an unlabeled document and tokenizes it with the classifier's
+    /// tokenizer, see `classify_tokenized`
+    pub fn classify(&self, document: &str) -> Result<String, ClassifierError> {
+        self.classify_tokenized(&self.tokenize(document))
+    }
+
+    /// Similar to `classify_tokenized` but instead of returning a single
+    /// label, returns all labels and the probability of each one given the
+    /// document
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", name = "posteriors", skip_all,
+                                                         fields(tokens = document.len())))]
+    pub fn get_document_posteriors_tokenized<S: AsRef<str>>(&self, document: &[S]) -> Result<Vec<(String, f64)>, ClassifierError> {
+        if self.biases.is_empty() {
+            return Err(ClassifierError::Untrained);
+        }
+        if self.vocab.is_empty() {
+            return Err(ClassifierError::EmptyVocabulary);
+        }
+        let features = self.features(document);
+        if features.is_empty() {
+            return Err(ClassifierError::EmptyDocument);
+        }
+        let mut counts: HashMap<usize, f64> = HashMap::new();
+        for feature in features {
+            if let Some(&index) = self.vocab.get(&feature) {
+                *counts.entry(index).or_insert(0.0) += 1.0;
+            }
+        }
+        let mut counts: Vec<(usize, f64)> = counts.into_iter().collect();
+        counts.sort_by_key(|&(index, _)| index);
+        let features = normalized(&counts);
+        Ok(self.labels.iter().cloned().zip(self.probabilities(&features)).collect())
+    }
+
+    /// Tokenizes the document with the classifier's tokenizer, see
+    /// `get_document_posteriors_tokenized`
+    pub fn get_document_posteriors(&self, document: &str) -> Result<Vec<(String, f64)>, ClassifierError> {
+        self.get_document_posteriors_tokenized(&self.tokenize(document))
+    }
+
+    /// Encodes the classifier as a JSON string.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("encoding JSON failed")
+    }
+
+    /// Encodes the classifier as JSON and writes it to the given writer
+    pub fn to_writer<W: Write>(&self, writer: W) -> Result<(), ClassifierError> {
+        serde_json::to_writer(writer, self).map_err(|e| ClassifierError::Encode(e.to_string()))
+    }
+
+    /// Saves the classifier to a JSON file, see `NaiveBayes::save_to_file`
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ClassifierError> {
+        self.save_to_file_compressed(path, Compression::None)
+    }
+
+    /// Same as `save_to_file`, but compresses the file, see `Compression`
+    pub fn save_to_file_compressed<P: AsRef<Path>>(&self, path: P, compression: Compression)
+                                                   -> Result<(), ClassifierError> {
+        persistence::save(self, path.as_ref(), compression)
+    }
+
+    // swaps in a tokenizer for a freshly deserialized classifier
+    fn retokenized(classifier: LogisticRegression, tokenizer: T) -> LogisticRegression<T> {
+        LogisticRegression {
+            vocab: classifier.vocab,
+            labels: classifier.labels,
+            weights: classifier.weights,
+            biases: classifier.biases,
+            learning_rate: classifier.learning_rate,
+            regularization: classifier.regularization,
+            epochs: classifier.epochs,
+            seed: classifier.seed,
+            ngram_range: classifier.ngram_range,
+            stop_words: classifier.stop_words,
+            preprocessor: classifier.preprocessor,
+            documents: classifier.documents,
+            tokenizer,
+        }
+    }
+
+    // the softmax of each label's score for the (word index, value) features
+    fn probabilities(&self, features: &[(usize, f64)]) -> Vec<f64> {
+        let scores: Vec<f64> = self.weights.iter().zip(self.biases.iter())
+            .map(|(weights, &bias)| {
+                bias + features.iter().map(|&(j, x)| weights[j] * x).sum::<f64>()
+            })
+            .collect();
+        let max_score = scores.iter().fold(f64::NEG_INFINITY, |acc, &s| acc.max(s));
+        let total: f64 = scores.iter().map(|&s| (s - max_score).exp()).sum();
+        scores.iter().map(|&s| (s - max_score).exp() / total).collect()
+    }
+
+    // preprocesses and tokenizes a document, then filters its tokens
+    fn tokenize(&self, document: &str) -> Vec<String> {
+        let tokens = if self.preprocessor.is_identity() {
+            self.tokenizer.tokenize(document)
+        } else {
+            self.tokenizer.tokenize(&self.preprocessor.process(document))
+        };
+        self.preprocessor.filter_tokens(tokens)
+    }
+
+    // turns the tokens of a document into the words the classifier weighs,
+    // skipping empty tokens
+    fn features<S: AsRef<str>>(&self, tokens: &[S]) -> Vec<String> {
+        let tokens: Vec<String> = tokens.iter()
+            .map(AsRef::as_ref)
+            .filter(|token| !token.is_empty() && !self.stop_words.contains(*token))
+            .map(ToString::to_string)
+            .collect();
+        let (min, max) = self.ngram_range;
+        if min == 1 && max == 1 {
+            return tokens;
+        }
+        ngrams(&tokens, min, max)
+    }
+
+}
+
+// scales the (word index, count) pairs of a document to unit length
+fn normalized(counts: &[(usize, f64)]) -> Vec<(usize, f64)> {
+    let length = counts.iter().map(|&(_, x)| x * x).sum::<f64>().sqrt();
+    if length == 0.0 {
+        return Vec::new();
+    }
+    counts.iter().map(|&(j, x)| (j, x / length)).collect()
+}
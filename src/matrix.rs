@@ -0,0 +1,43 @@
+use std::collections::HashMap;
+use ndarray::Array2;
+use naive_bayes::Classifier;
+use tokenizer::Tokenizer;
+
+impl<T: Tokenizer> Classifier<T> {
+
+    /// Vectorizes the documents into a matrix with a row for each document
+    /// and a column for each word of the vocabulary, so the classifier's
+    /// features can be fed into other machine learning crates such as
+    /// linfa. Also gives the word of each column, which are sorted. An entry
+    /// is the word's count in the document times its weight, which makes it
+    /// a TF-IDF value when the classifier weights features by TF-IDF, see
+    /// `FeatureWeighting`. Words that are not in the vocabulary are left out.
+    /// Requires the `ndarray` feature.
+    ///
+    /// ```rust
+    /// use classifier::NaiveBayes;
+    ///
+    /// let mut nb = NaiveBayes::new();
+    /// nb.add_document("pork belly ribs", "meat");
+    /// nb.add_document("kale okra", "veggie");
+    /// nb.train();
+    ///
+    /// let (matrix, words) = nb.to_feature_matrix(&["pork pork kale", "okra"]);
+    /// assert_eq!(words, vec!["belly", "kale", "okra", "pork", "ribs"]);
+    /// assert_eq!(matrix.row(0).to_vec(), vec![0.0, 1.0, 0.0, 2.0, 0.0]);
+    /// ```
+    pub fn to_feature_matrix<D: AsRef<str>>(&self, documents: &[D]) -> (Array2<f64>, Vec<String>) {
+        let vectorizer = self.vectorizer();
+        let mut mapping = vectorizer.vocabulary_mapping();
+        mapping.sort_unstable_by(|a, b| a.1.cmp(b.1));
+        let columns: HashMap<u32, usize> = mapping.iter().enumerate().map(|(column, &(id, _))| (id, column)).collect();
+
+        let mut matrix = Array2::zeros((documents.len(), mapping.len()));
+        for (row, document) in documents.iter().enumerate() {
+            for (id, value) in vectorizer.vectorize(document.as_ref()).to_sparse() {
+                matrix[[row, columns[&id]]] = value;
+            }
+        }
+        (matrix, mapping.into_iter().map(|(_, word)| word.to_string()).collect())
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use error::ClassifierError;
+use explanation::Explanation;
+use naive_bayes::Classifier;
+use persistence::Compression;
+use tokenizer::{Tokenizer, WhitespaceTokenizer};
+
+/// The naive Bayes classifier seen as the trainer of a `NaiveBayesModel`:
+/// documents are added to it and its settings are changed, and then
+/// `into_model` trains it and produces the model
+pub type NaiveBayesTrainer<T = WhitespaceTokenizer> = Classifier<T>;
+
+/// A trained naive Bayes classifier that can no longer be changed, made with
+/// `NaiveBayesTrainer::into_model`
+///
+/// The model is serialized the same way as the classifier it was made from,
+/// so either one can load what the other saved.
+///
+/// Classifying only reads the model, so it needs no locking. The model
+/// shares one copy of the classifier between all of its clones, which makes
+/// cloning it cheap, and it is `Send` and `Sync` whenever its tokenizer is,
+/// so a clone can be handed to every thread of a server:
+///
+/// ```rust
+/// use std::thread;
+/// use classifier::NaiveBayes;
+///
+/// let mut nb = NaiveBayes::new();
+/// nb.add_document("pork belly ribs", "meat");
+/// nb.add_document("kale okra spinach", "veggie");
+/// let model = nb.into_model();
+///
+/// let handles: Vec<_> = (0..4).map(|_| {
+///     let model = model.clone();
+///     thread::spawn(move || model.classify("pork").unwrap())
+/// }).collect();
+/// for handle in handles {
+///     assert_eq!(handle.join().unwrap(), "meat");
+/// }
+/// ```
+#[derive(Debug)]
+pub struct NaiveBayesModel<T = WhitespaceTokenizer> {
+    classifier: Arc<Classifier<T>>,
+}
+
+// a derived Clone would needlessly require the tokenizer to be Clone
+impl<T> Clone for NaiveBayesModel<T> {
+    fn clone(&self) -> NaiveBayesModel<T> {
+        NaiveBayesModel { classifier: Arc::clone(&self.classifier) }
+    }
+}
+
+impl<T: Tokenizer> Classifier<T> {
+
+    /// Trains the classifier and turns it into an immutable model that can be
+    /// cloned cheaply and shared between threads, see `NaiveBayesModel`
+    pub fn into_model(mut self) -> NaiveBayesModel<T> {
+        self.train();
+        NaiveBayesModel::trained(self)
+    }
+}
+
+impl NaiveBayesModel {
+
+    /// Builds a model from a JSON string
+    pub fn from_json(encoded: &str) -> Result<NaiveBayesModel, ClassifierError> {
+        Classifier::from_json(encoded).map(NaiveBayesModel::trained)
+    }
+
+    /// Builds a model from JSON read from the given reader
+    pub fn from_reader<R: Read>(reader: R) -> Result<NaiveBayesModel, ClassifierError> {
+        Classifier::from_reader(reader).map(NaiveBayesModel::trained)
+    }
+
+    /// Loads a model from a file, see `NaiveBayes::load_from_file`
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<NaiveBayesModel, ClassifierError> {
+        Classifier::load_from_file(path).map(NaiveBayesModel::trained)
+    }
+}
+
+impl<T: Tokenizer> NaiveBayesModel<T> {
+
+    // wraps a classifier that was saved after it was trained
+    fn trained(classifier: Classifier<T>) -> NaiveBayesModel<T> {
+        NaiveBayesModel { classifier: Arc::new(classifier) }
+    }
+
+    /// Builds a model from a JSON string, using the given tokenizer (which
+    /// should be the one the model was trained with)
+    pub fn from_json_with_tokenizer(encoded: &str, tokenizer: T) -> Result<NaiveBayesModel<T>, ClassifierError> {
+        Classifier::from_json_with_tokenizer(encoded, tokenizer).map(NaiveBayesModel::trained)
+    }
+
+    /// Loads a model from a file, using the given tokenizer (which should be
+    /// the one the model was trained with)
+    pub fn load_from_file_with_tokenizer<P: AsRef<Path>>(path: P, tokenizer: T)
+        -> Result<NaiveBayesModel<T>, ClassifierError>
+    {
+        Classifier::load_from_file_with_tokenizer(path, tokenizer).map(NaiveBayesModel::trained)
+    }
+
+    /// Encodes the model as a JSON string
+    pub fn to_json(&self) -> String {
+        self.classifier.to_json()
+    }
+
+    /// Encodes the model as JSON and writes it to the given writer
+    pub fn to_writer<W: Write>(&self, writer: W) -> Result<(), ClassifierError> {
+        self.classifier.to_writer(writer)
+    }
+
+    /// Saves the model to a file, see `NaiveBayes::save_to_file`
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ClassifierError> {
+        self.classifier.save_to_file(path)
+    }
+
+    /// Saves the model to a compressed file, see `Compression`
+    pub fn save_to_file_compressed<P: AsRef<Path>>(&self, path: P, compression: Compression)
+        -> Result<(), ClassifierError>
+    {
+        self.classifier.save_to_file_compressed(path, compression)
+    }
+
+    /// Gets the classifier the model was made from, for everything that
+    /// only reads it (inspecting words, saving, etc.)
+    pub fn classifier(&self) -> &Classifier<T> {
+        &self.classifier
+    }
+
+    /// Computes a classifying label for the document, see `NaiveBayes::classify`
+    pub fn classify(&self, document: &str) -> Result<String, ClassifierError> {
+        self.classifier.classify(document)
+    }
+
+    /// Computes a classifying label for the tokenized document, see
+    /// `NaiveBayes::classify_tokenized`
+    pub fn classify_tokenized<S: AsRef<str>>(&self, document: &[S]) -> Result<String, ClassifierError> {
+        self.classifier.classify_tokenized(document)
+    }
+
+    /// Computes a classifying label for the document and gets the metadata
+    /// attached to it, see `NaiveBayes::classify_with_metadata`
+    pub fn classify_with_metadata(&self, document: &str)
+        -> Result<(String, HashMap<String, String>), ClassifierError>
+    {
+        self.classifier.classify_with_metadata(document)
+    }
+
+    /// See `NaiveBayes::classify_with_threshold`
+    pub fn classify_with_threshold(&self, document: &str, min_confidence: f64)
+        -> Result<Option<String>, ClassifierError>
+    {
+        self.classifier.classify_with_threshold(document, min_confidence)
+    }
+
+    /// See `NaiveBayes::classify_top_k`
+    pub fn classify_top_k(&self, document: &str, k: usize) -> Result<Vec<(String, f64)>, ClassifierError> {
+        self.classifier.classify_top_k(document, k)
+    }
+
+    /// Gets every label and its posterior probability given the document,
+    /// see `NaiveBayes::get_document_posteriors`
+    pub fn get_document_posteriors(&self, document: &str) -> Result<Vec<(String, f64)>, ClassifierError> {
+        self.classifier.get_document_posteriors(document)
+    }
+
+    /// See `NaiveBayes::get_document_posteriors_tokenized`
+    pub fn get_document_posteriors_tokenized<S: AsRef<str>>(&self, document: &[S])
+        -> Result<Vec<(String, f64)>, ClassifierError>
+    {
+        self.classifier.get_document_posteriors_tokenized(document)
+    }
+
+    /// Breaks the score of every label down, see `NaiveBayes::explain`
+    pub fn explain(&self, document: &str) -> Result<Explanation, ClassifierError> {
+        self.classifier.explain(document)
+    }
+
+    /// Gets all of the labels the model knows
+    pub fn get_labels(&self) -> Vec<String> {
+        self.classifier.get_labels()
+    }
+}
+
+impl<T: Tokenizer + Clone> NaiveBayesModel<T> {
+
+    /// Turns the model back into a trainer so more documents can be added.
+    /// The classifier is only copied if other clones of the model exist.
+    pub fn into_trainer(self) -> NaiveBayesTrainer<T> {
+        Arc::try_unwrap(self.classifier).unwrap_or_else(|shared| (*shared).clone())
+    }
+}
+
+impl<T: Tokenizer + Sync> NaiveBayesModel<T> {
+
+    /// Classifies the documents across all of the available threads, see
+    /// `NaiveBayes::classify_batch`
+    pub fn classify_batch<D: AsRef<str> + Sync>(&self, documents: &[D]) -> Vec<Result<String, ClassifierError>> {
+        self.classifier.classify_batch(documents)
+    }
+
+    /// See `NaiveBayes::get_document_posteriors_batch`
+    pub fn get_document_posteriors_batch<D: AsRef<str> + Sync>(&self, documents: &[D])
+        -> Vec<Result<Vec<(String, f64)>, ClassifierError>>
+    {
+        self.classifier.get_document_posteriors_batch(documents)
+    }
+}
+
+impl<T> Serialize for NaiveBayesModel<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.classifier.serialize(serializer)
+    }
+}
+
+impl<'de, T: Default> Deserialize<'de> for NaiveBayesModel<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<NaiveBayesModel<T>, D::Error> {
+        let classifier = Classifier::deserialize(deserializer)?;
+        Ok(NaiveBayesModel { classifier: Arc::new(classifier) })
+    }
+}
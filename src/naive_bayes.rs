@@ -1,230 +1,3380 @@
-use std::collections::{HashMap, HashSet};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::Hash;
 use std::f64;
-use regex::Regex;
-use rustc_serialize::json;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::mem;
+use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::Error;
+use serde_json;
+use tokenizer::{bucket, ngrams, skip_grams, Tokenizer, WhitespaceTokenizer};
+use preprocessing::Preprocessor;
+#[cfg(feature = "binary")]
+use preprocessing::{PreprocessorV2, PreprocessorV4};
+use observer::TrainingObserver;
+use vocabulary::{Vocabulary, WordId};
+use error::ClassifierError;
+use persistence::{self, Compression};
+use onnx;
+#[cfg(feature = "binary")]
+use binary;
+use explanation::{Explanation, LabelExplanation};
+use stats::{LabelStats, ModelStats};
+use inference::InferenceModel;
+use frozen::FrozenModel;
+use active_learning::Uncertainty;
+use dedup::Deduplicator;
+use feature_selection::FeatureScore;
+use vectorizer::{DocumentVector, Vectorizer};
 
-static DEFAULT_SMOOTHING: f64 = 1.0f64;
+pub(crate) static DEFAULT_SMOOTHING: f64 = 1.0f64;
+// the version of the serialized model, increased whenever a change to it
+// needs more than the defaults of new fields to read older models, see
+// `migrate`
+static MODEL_VERSION: u32 = 1;
+
+// the largest calibration scale `calibrate` fits
+static MAX_CALIBRATION: f64 = 1024.0;
+
+// how much of a document's weight may be left of a count it is removed
+// from, relative to the weight, and still be rounding error rather than
+// another document
+static REMOVAL_TOLERANCE: f64 = 1e-9;
 
 /// Naive Bayes classifier
-#[derive(Debug, Clone, RustcDecodable, RustcEncodable)]
-pub struct Classifier {
+///
+/// The tokenizer used to break documents into words is a type parameter
+/// and defaults to `WhitespaceTokenizer`. The tokenizer is not part of the
+/// serialized model, so a classifier using a custom tokenizer must be given
+/// it again when it is deserialized (see `from_json_with_tokenizer`).
+///
+/// The classifier implements serde's `Serialize` and `Deserialize`, so it
+/// can be persisted with any serde data format.
+///
+/// Classifying a document only reads the classifier, and the classifier is
+/// `Send` and `Sync` whenever its tokenizer is. To share a trained classifier
+/// between threads without locking, turn it into a `NaiveBayesModel` with
+/// `into_model`.
+#[derive(Debug, Clone)]
+pub struct Classifier<T = WhitespaceTokenizer> {
+    vocab: Vocabulary,
+    // the total weight of the documents, each document counts as its weight
+    num_examples: f64,
+    smoothing: f64,
+    // smoothing values that replace `smoothing` for particular labels
+    label_smoothing: HashMap<String, f64>,
+    // key/value pairs attached to labels, they play no part in classification
+    label_metadata: HashMap<String, HashMap<String, String>>,
+    ngram_range: (usize, usize),
+    // the most tokens a skip-gram skips, 0 when there are none
+    skip_window: usize,
+    // the most times a feature counts in one document, if there is a limit
+    max_word_count: Option<usize>,
+    // the only features that are counted when training, if they are fixed
+    fixed_vocabulary: Option<HashSet<String>>,
+    online: bool,
+    stop_words: HashSet<String>,
+    variant: NaiveBayesVariant,
+    weighting: FeatureWeighting,
+    priors: PriorMode,
+    unknown_words: UnknownWordPolicy,
+    // the number of documents each word has appeared in
+    document_frequencies: HashMap<WordId, f64>,
+    preprocessor: Preprocessor,
+    // masks sensitive text in documents before they are preprocessed, it
+    // is not serialized
+    sanitizer: Option<fn(&str) -> String>,
+    // the number of buckets features are hashed into, if they are hashed
+    hash_buckets: Option<usize>,
+    // the factor the log scores are multiplied by before they are
+    // normalized into posteriors, fitted by `calibrate`
+    calibration: Option<f64>,
+    // the label and features of every document added with an ID, by ID
+    documents: BTreeMap<String, (String, Vec<String>)>,
+    // ordered by label, so that labels come out in the same order every time
+    // and ties go to the first label
+    classifications: BTreeMap<String, Classification>,
+    tokenizer: T,
+}
+
+/// The event model the classifier uses for documents
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum NaiveBayesVariant {
+    /// Documents are bags of word occurrences, every occurrence of a word
+    /// counts (the default)
+    #[default]
+    Multinomial,
+    /// Documents are sets of words, only the presence or absence of each
+    /// word in the vocabulary counts. The absence of a word is scored
+    /// explicitly, which often works better for short documents.
+    /// Feature weighting does not apply to this variant.
+    Bernoulli,
+    /// Complement naive Bayes, each label's word probabilities are estimated
+    /// from the documents of every other label, and a document is scored by
+    /// how poorly it fits those. This is far less biased toward labels with
+    /// many examples, which makes it a good choice for imbalanced data.
+    /// Class priors are not used by this variant.
+    Complement,
+}
+
+/// Where the prior probability of each label comes from
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum PriorMode {
+    /// The fraction of the training documents with the label (the default)
+    #[default]
+    Learned,
+    /// Every label is equally likely, so labels with many training
+    /// documents are not favored
+    Uniform,
+    /// The given prior of each label. Labels missing from the map use their
+    /// learned prior.
+    Custom(HashMap<String, f64>),
+}
+
+/// How the words of a document that are not in the vocabulary are scored
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum UnknownWordPolicy {
+    /// Unknown words play no part in the score (the default)
+    #[default]
+    Ignore,
+    /// Every unknown word is scored as a word never seen with the label,
+    /// using the smoothed probability of an unseen word. Labels with fewer
+    /// words give unseen words more probability, so documents full of new
+    /// words lean toward those labels.
+    DefaultProbability,
+    /// Every unknown word subtracts the given (non-negative) penalty from
+    /// the log score of every label. This lowers the scores without
+    /// changing which label wins or the posteriors.
+    PenalizePerToken(f64),
+}
+
+/// How the occurrences of words in documents are weighted when training
+/// and when scoring documents
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum FeatureWeighting {
+    /// Every occurrence of a word counts once (the default)
+    #[default]
+    Counts,
+    /// Every occurrence of a word is weighted by the word's inverse document
+    /// frequency, so words that appear in many documents count for less
+    TfIdf,
+}
+
+// the words of a classification are kept by their id in the vocabulary
+#[derive(Debug, Clone)]
+struct Classification {
+    label: String,
+    num_examples: f64,
+    num_words: f64,
+    probability: f64,
+    default_word_probability: f64,
+    // the log probability of a document containing none of the vocabulary,
+    // only used by the Bernoulli variant
+    absent_log_probability: f64,
+    words: HashMap<WordId, (f64, f64)>,
+    // the number of documents with this label each word has appeared in
+    document_counts: HashMap<WordId, f64>,
+    // the probability of each word given every other classification, only
+    // used by the complement variant
+    complement_probabilities: HashMap<WordId, f64>,
+    // the logarithms of the probabilities above, computed by train so that
+    // scoring a document needs no `ln()`: the log prior, what each
+    // occurrence of a word adds to the score under the variant, and what a
+    // word never seen with this classification adds
+    log_prior: f64,
+    word_scores: HashMap<WordId, f64>,
+    default_word_score: f64,
+}
+
+// the encoded form of a classifier, which keeps the words themselves rather
+// than their ids. Serialized with `&str` and deserialized with `String`.
+#[derive(Serialize, Deserialize)]
+struct EncodedClassifier<S: Eq + Hash> {
+    // models serialized before versioning have none and are version 0
+    #[serde(default)]
+    model_version: u32,
+    vocab: Vec<S>,
+    num_examples: f64,
+    smoothing: f64,
+    #[serde(default)]
+    label_smoothing: HashMap<S, f64>,
+    #[serde(default)]
+    label_metadata: HashMap<S, HashMap<S, S>>,
+    // models serialized before n-gram support have no range
+    #[serde(default = "default_ngram_range")]
+    ngram_range: (usize, usize),
+    #[serde(default)]
+    skip_window: usize,
+    #[serde(default)]
+    max_word_count: Option<usize>,
+    #[serde(default)]
+    fixed_vocabulary: Option<HashSet<S>>,
+    #[serde(default)]
+    online: bool,
+    #[serde(default)]
+    stop_words: HashSet<S>,
+    #[serde(default)]
+    variant: NaiveBayesVariant,
+    #[serde(default)]
+    weighting: FeatureWeighting,
+    #[serde(default)]
+    priors: PriorMode,
+    #[serde(default)]
+    unknown_words: UnknownWordPolicy,
+    #[serde(default)]
+    document_frequencies: HashMap<S, f64>,
+    #[serde(default)]
+    preprocessor: Preprocessor,
+    #[serde(default)]
+    hash_buckets: Option<usize>,
+    #[serde(default)]
+    calibration: Option<f64>,
+    #[serde(default)]
+    documents: HashMap<S, (S, Vec<S>)>,
+    classifications: HashMap<S, EncodedClassification<S>>,
+}
+
+// the layout of a classifier in version 1 of the binary format, when the
+// classifier itself was encoded and counted documents and words in whole
+// numbers
+#[cfg(feature = "binary")]
+#[derive(Deserialize)]
+struct EncodedClassifierV1 {
     vocab: HashSet<String>,
     num_examples: u32,
     smoothing: f64,
-    classifications: HashMap<String, Classification>
+    ngram_range: (usize, usize),
+    online: bool,
+    stop_words: HashSet<String>,
+    variant: NaiveBayesVariant,
+    weighting: FeatureWeighting,
+    document_frequencies: HashMap<String, u32>,
+    preprocessor: PreprocessorV2,
+    classifications: HashMap<String, EncodedClassificationV1>,
 }
 
-#[derive(Debug, Clone, RustcDecodable, RustcEncodable)]
-struct Classification {
+#[cfg(feature = "binary")]
+#[derive(Deserialize)]
+struct EncodedClassificationV1 {
     label: String,
     num_examples: u32,
     num_words: u32,
     probability: f64,
     default_word_probability: f64,
+    absent_log_probability: f64,
     words: HashMap<String, (u32, f64)>,
+    document_counts: HashMap<String, u32>,
+    complement_probabilities: HashMap<String, f64>,
+}
+
+// the layout of a classifier in version 2 of the binary format, from before
+// model versions, per-label smoothing, label metadata, and unknown word
+// policies
+#[cfg(feature = "binary")]
+#[derive(Deserialize)]
+struct EncodedClassifierV2 {
+    vocab: Vec<String>,
+    num_examples: f64,
+    smoothing: f64,
+    ngram_range: (usize, usize),
+    online: bool,
+    stop_words: HashSet<String>,
+    variant: NaiveBayesVariant,
+    weighting: FeatureWeighting,
+    priors: PriorMode,
+    document_frequencies: HashMap<String, f64>,
+    preprocessor: PreprocessorV2,
+    hash_buckets: Option<usize>,
+    classifications: HashMap<String, EncodedClassification<String>>,
+}
+
+// the layout of a classifier in version 3 of the binary format, from before
+// calibration and document IDs
+#[cfg(feature = "binary")]
+#[derive(Deserialize)]
+struct EncodedClassifierV3 {
+    model_version: u32,
+    vocab: Vec<String>,
+    num_examples: f64,
+    smoothing: f64,
+    label_smoothing: HashMap<String, f64>,
+    label_metadata: HashMap<String, HashMap<String, String>>,
+    ngram_range: (usize, usize),
+    online: bool,
+    stop_words: HashSet<String>,
+    variant: NaiveBayesVariant,
+    weighting: FeatureWeighting,
+    priors: PriorMode,
+    unknown_words: UnknownWordPolicy,
+    document_frequencies: HashMap<String, f64>,
+    preprocessor: PreprocessorV4,
+    hash_buckets: Option<usize>,
+    classifications: HashMap<String, EncodedClassification<String>>,
+}
+
+#[cfg(feature = "binary")]
+impl EncodedClassifierV1 {
+
+    // the model in the layout of the next version, with the settings it
+    // predates off
+    fn upgrade(self) -> EncodedClassifierV2 {
+        let counts = |counts: HashMap<String, u32>| counts.into_iter()
+            .map(|(word, count)| (word, f64::from(count)))
+            .collect();
+        EncodedClassifierV2 {
+            vocab: self.vocab.into_iter().collect(),
+            num_examples: f64::from(self.num_examples),
+            smoothing: self.smoothing,
+            ngram_range: self.ngram_range,
+            online: self.online,
+            stop_words: self.stop_words,
+            variant: self.variant,
+            weighting: self.weighting,
+            priors: PriorMode::Learned,
+            document_frequencies: counts(self.document_frequencies),
+            preprocessor: self.preprocessor,
+            hash_buckets: None,
+            classifications: self.classifications.into_iter()
+                .map(|(label, classification)| {
+                    let classification = EncodedClassification {
+                        label: classification.label,
+                        num_examples: f64::from(classification.num_examples),
+                        num_words: f64::from(classification.num_words),
+                        probability: classification.probability,
+                        default_word_probability: classification.default_word_probability,
+                        absent_log_probability: classification.absent_log_probability,
+                        words: classification.words.into_iter()
+                            .map(|(word, (count, probability))| (word, (f64::from(count), probability)))
+                            .collect(),
+                        document_counts: counts(classification.document_counts),
+                        complement_probabilities: classification.complement_probabilities,
+                    };
+                    (label, classification)
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(feature = "binary")]
+impl EncodedClassifierV2 {
+
+    // the model in the layout of the next version, with the settings it
+    // predates off
+    fn upgrade(self) -> EncodedClassifierV3 {
+        EncodedClassifierV3 {
+            model_version: 0,
+            vocab: self.vocab,
+            num_examples: self.num_examples,
+            smoothing: self.smoothing,
+            label_smoothing: HashMap::new(),
+            label_metadata: HashMap::new(),
+            ngram_range: self.ngram_range,
+            online: self.online,
+            stop_words: self.stop_words,
+            variant: self.variant,
+            weighting: self.weighting,
+            priors: self.priors,
+            unknown_words: UnknownWordPolicy::Ignore,
+            document_frequencies: self.document_frequencies,
+            preprocessor: self.preprocessor.upgrade(),
+            hash_buckets: self.hash_buckets,
+            classifications: self.classifications,
+        }
+    }
+}
+
+#[cfg(feature = "binary")]
+impl EncodedClassifierV3 {
+
+    // the model in the layout of the next version, with the settings it
+    // predates off
+    fn upgrade(self) -> EncodedClassifierV4 {
+        EncodedClassifierV4 {
+            model_version: self.model_version,
+            vocab: self.vocab,
+            num_examples: self.num_examples,
+            smoothing: self.smoothing,
+            label_smoothing: self.label_smoothing,
+            label_metadata: self.label_metadata,
+            ngram_range: self.ngram_range,
+            online: self.online,
+            stop_words: self.stop_words,
+            variant: self.variant,
+            weighting: self.weighting,
+            priors: self.priors,
+            unknown_words: self.unknown_words,
+            document_frequencies: self.document_frequencies,
+            preprocessor: self.preprocessor,
+            hash_buckets: self.hash_buckets,
+            calibration: None,
+            documents: HashMap::new(),
+            classifications: self.classifications,
+        }
+    }
+}
+
+// the layout of a classifier in version 4 of the binary format, which is
+// read by position, from before skip-grams, word count limits, and fixed
+// vocabularies
+#[cfg(feature = "binary")]
+#[derive(Deserialize)]
+struct EncodedClassifierV4 {
+    model_version: u32,
+    vocab: Vec<String>,
+    num_examples: f64,
+    smoothing: f64,
+    label_smoothing: HashMap<String, f64>,
+    label_metadata: HashMap<String, HashMap<String, String>>,
+    ngram_range: (usize, usize),
+    online: bool,
+    stop_words: HashSet<String>,
+    variant: NaiveBayesVariant,
+    weighting: FeatureWeighting,
+    priors: PriorMode,
+    unknown_words: UnknownWordPolicy,
+    document_frequencies: HashMap<String, f64>,
+    preprocessor: PreprocessorV4,
+    hash_buckets: Option<usize>,
+    calibration: Option<f64>,
+    documents: HashMap<String, (String, Vec<String>)>,
+    classifications: HashMap<String, EncodedClassification<String>>,
+}
+
+#[cfg(feature = "binary")]
+impl EncodedClassifierV4 {
+
+    // the model in the current layout, with the settings it predates off
+    fn upgrade(self) -> EncodedClassifier<String> {
+        EncodedClassifier {
+            model_version: self.model_version,
+            vocab: self.vocab,
+            num_examples: self.num_examples,
+            smoothing: self.smoothing,
+            label_smoothing: self.label_smoothing,
+            label_metadata: self.label_metadata,
+            ngram_range: self.ngram_range,
+            skip_window: 0,
+            max_word_count: None,
+            fixed_vocabulary: None,
+            online: self.online,
+            stop_words: self.stop_words,
+            variant: self.variant,
+            weighting: self.weighting,
+            priors: self.priors,
+            unknown_words: self.unknown_words,
+            document_frequencies: self.document_frequencies,
+            preprocessor: self.preprocessor.upgrade(),
+            hash_buckets: self.hash_buckets,
+            calibration: self.calibration,
+            documents: self.documents,
+            classifications: self.classifications,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct EncodedClassification<S: Eq + Hash> {
+    label: S,
+    num_examples: f64,
+    num_words: f64,
+    probability: f64,
+    default_word_probability: f64,
+    #[serde(default)]
+    absent_log_probability: f64,
+    words: HashMap<S, (f64, f64)>,
+    #[serde(default)]
+    document_counts: HashMap<S, f64>,
+    #[serde(default)]
+    complement_probabilities: HashMap<S, f64>,
 }
 
 impl Classifier {
     
-    /// Creates a new classifier
+    /// Creates a new classifier that tokenizes documents by breaking on
+    /// whitespace characters
     pub fn new() -> Classifier {
+        Classifier::with_tokenizer(WhitespaceTokenizer)
+    }
+
+    /// Creates a new classifier that uses the given naive Bayes variant and
+    /// tokenizes documents by breaking on whitespace characters
+    pub fn with_variant(variant: NaiveBayesVariant) -> Classifier {
+        let mut classifier = Classifier::new();
+        classifier.set_variant(variant);
+        classifier
+    }
+
+    /// Creates a new classifier that hashes every feature into one of
+    /// `num_buckets` buckets rather than keeping the features themselves
+    /// (the hashing trick), see `set_hashed_features`
+    pub fn with_hashed_features(num_buckets: usize) -> Result<Classifier, ClassifierError> {
+        let mut classifier = Classifier::new();
+        classifier.set_hashed_features(num_buckets)?;
+        Ok(classifier)
+    }
+
+    /// Creates a new classifier that only ever counts the given words, see
+    /// `set_fixed_vocabulary`
+    pub fn with_fixed_vocabulary<I, S>(words: I) -> Classifier
+        where I: IntoIterator<Item = S>, S: AsRef<str>
+    {
+        let mut classifier = Classifier::new();
+        classifier.set_fixed_vocabulary(words);
+        classifier
+    }
+
+    /// Builds a new classifier from a JSON string
+    pub fn from_json(encoded: &str) -> Result<Classifier, ClassifierError> {
+        serde_json::from_str(encoded).map_err(|e| ClassifierError::Decode(e.to_string()))
+    }
+
+    /// Builds a new classifier from JSON read from the given reader
+    pub fn from_reader<R: Read>(reader: R) -> Result<Classifier, ClassifierError> {
+        serde_json::from_reader(reader).map_err(|e| ClassifierError::Decode(e.to_string()))
+    }
+
+    /// Loads a classifier from a file written by `save_to_file` or
+    /// `save_to_file_compressed`, the compression is detected automatically
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Classifier, ClassifierError> {
+        persistence::load(path.as_ref())
+    }
+
+    /// Builds a new classifier from bytes written by `to_bytes`, either by
+    /// this release or an older one, whose models are read with the
+    /// settings added since then turned off. Fails with
+    /// `UnsupportedModelVersion` for a version of the format this release
+    /// can't read. Requires the `binary` feature.
+    #[cfg(feature = "binary")]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Classifier, ClassifierError> {
+        let (version, payload) = binary::split(bytes)?;
+        let encoded = match version {
+            1 => binary::decode_payload::<EncodedClassifierV1>(payload)?.upgrade().upgrade().upgrade().upgrade(),
+            2 => binary::decode_payload::<EncodedClassifierV2>(payload)?.upgrade().upgrade().upgrade(),
+            3 => binary::decode_payload::<EncodedClassifierV3>(payload)?.upgrade().upgrade(),
+            4 => binary::decode_payload::<EncodedClassifierV4>(payload)?.upgrade(),
+            version if version == binary::VERSION => binary::decode_payload(payload)?,
+            version => return Err(binary::unsupported(version)),
+        };
+        Classifier::decode(encoded)
+    }
+
+}
+
+impl Default for Classifier {
+    fn default() -> Classifier {
+        Classifier::new()
+    }
+}
+
+impl<T: Tokenizer> Classifier<T> {
+
+    /// Creates a new classifier that uses the given tokenizer to break
+    /// documents into words, both when training and when classifying
+    pub fn with_tokenizer(tokenizer: T) -> Classifier<T> {
         Classifier {
-            vocab: HashSet::new(),
-            num_examples: 0u32,
+            vocab: Vocabulary::new(),
+            num_examples: 0.0f64,
             smoothing: DEFAULT_SMOOTHING,
-            classifications: HashMap::new(),
+            label_smoothing: HashMap::new(),
+            label_metadata: HashMap::new(),
+            ngram_range: (1, 1),
+            skip_window: 0,
+            max_word_count: None,
+            fixed_vocabulary: None,
+            online: false,
+            stop_words: HashSet::new(),
+            variant: NaiveBayesVariant::Multinomial,
+            weighting: FeatureWeighting::Counts,
+            priors: PriorMode::Learned,
+            unknown_words: UnknownWordPolicy::Ignore,
+            document_frequencies: HashMap::new(),
+            preprocessor: Preprocessor::new(),
+            sanitizer: None,
+            hash_buckets: None,
+            calibration: None,
+            documents: BTreeMap::new(),
+            classifications: BTreeMap::new(),
+            tokenizer,
         }
     }
 
+    /// Builds a new classifier from a JSON string, using the given tokenizer
+    /// (which should be the one the classifier was trained with)
+    pub fn from_json_with_tokenizer(encoded: &str, tokenizer: T) -> Result<Classifier<T>, ClassifierError> {
+        Ok(Classifier::retokenized(Classifier::from_json(encoded)?, tokenizer))
+    }
+
+    /// Loads a classifier from a file written by `save_to_file`, using the
+    /// given tokenizer (which should be the one the classifier was trained with)
+    pub fn load_from_file_with_tokenizer<P: AsRef<Path>>(path: P, tokenizer: T) -> Result<Classifier<T>, ClassifierError> {
+        Ok(Classifier::retokenized(Classifier::load_from_file(path)?, tokenizer))
+    }
+
+    /// Builds a new classifier from bytes written by `to_bytes`, using the
+    /// given tokenizer (which should be the one the classifier was trained
+    /// with). Requires the `binary` feature.
+    #[cfg(feature = "binary")]
+    pub fn from_bytes_with_tokenizer(bytes: &[u8], tokenizer: T) -> Result<Classifier<T>, ClassifierError> {
+        Ok(Classifier::retokenized(Classifier::from_bytes(bytes)?, tokenizer))
+    }
+
     /// Takes a document that has been tokenized into a vector of strings
     /// and a label and adds the document to the list of documents that the
     /// classifier is aware of and will train on next time the `train()` method is called
-    pub fn add_document_tokenized(&mut self, document: &Vec<String>, label: &String) {
-        if document.len() == 0 { return; }
-        
+    pub fn add_document_tokenized<S: AsRef<str>>(&mut self, document: &[S], label: &str) {
+        self.add_weighted(document, label, 1.0);
+    }
+
+    /// Same as `add_document_tokenized`, but takes the tokens as string
+    /// slices from any iterator, e.g. slices of a buffer the caller already
+    /// holds. Only words the classifier has not seen before are copied.
+    pub fn add_document_tokens<'a, I: IntoIterator<Item = &'a str>>(&mut self, tokens: I, label: &str) {
+        let tokens: Vec<&str> = tokens.into_iter().collect();
+        self.add_document_tokenized(&tokens, label);
+    }
+
+    /// Takes a document and a label and tokenizes the document with the
+    /// classifier's tokenizer. The document is added to the list
+    /// of documents that the classifier is aware of and will train on next time
+    /// the `train()` method is called 
+    pub fn add_document(&mut self, document: &str, label: &str) {
+        let tokens = self.tokenize(document);
+        self.add_document_tokenized(&tokens, label);
+    }
+
+    /// Adds a tokenized document that counts as `weight` documents (a
+    /// positive number, which need not be whole) when training. Every word
+    /// of the document counts `weight` times as well. The document is
+    /// removed by `remove_document_tokenized_weighted` with the same weight.
+    pub fn add_document_tokenized_weighted<S: AsRef<str>>(&mut self, document: &[S], label: &str, weight: f64)
+                                           -> Result<(), ClassifierError> {
+        if !(weight > 0.0 && weight.is_finite()) {
+            return Err(ClassifierError::InvalidWeight(weight));
+        }
+        self.add_weighted(document, label, weight);
+        Ok(())
+    }
+
+    /// Tokenizes the document with the classifier's tokenizer and adds it
+    /// with the given weight, see `add_document_tokenized_weighted`
+    pub fn add_document_weighted(&mut self, document: &str, label: &str, weight: f64) -> Result<(), ClassifierError> {
+        let tokens = self.tokenize(document);
+        self.add_document_tokenized_weighted(&tokens, label, weight)
+    }
+
+    /// Adds a document given as the number of times each of its words
+    /// appears, for pipelines that count words themselves. The words are
+    /// taken as they are, without being tokenized or preprocessed, and as
+    /// the counts carry no word order they are counted as single words even
+    /// if the classifier builds n-grams or skip-grams. Stop words, feature
+    /// hashing, the fixed vocabulary, and the word count limit apply as
+    /// they do to any other document, and a document with no words left
+    /// after them is not added.
+    pub fn add_document_counts(&mut self, counts: &HashMap<String, u32>, label: &str) {
+        let mut features: HashMap<Cow<str>, f64> = HashMap::new();
+        for (word, &count) in counts.iter() {
+            if count == 0 || word.is_empty() || self.stop_words.contains(word) {
+                continue;
+            }
+            let feature = match self.hash_buckets {
+                Some(num_buckets) => Cow::Owned(bucket(word, num_buckets)),
+                None => Cow::Borrowed(word.as_str()),
+            };
+            if self.fixed_vocabulary.as_ref().is_none_or(|words| words.contains(feature.as_ref())) {
+                *features.entry(feature).or_insert(0.0) += f64::from(count);
+            }
+        }
+        if features.is_empty() { return; }
+
+        let max_count = self.max_word_count.map_or(f64::INFINITY, |max_count| max_count as f64);
+        let mut ids: Vec<(WordId, f64)> = features.into_iter()
+            .map(|(feature, count)| (self.vocab.intern(&feature), count.min(max_count)))
+            .collect();
+        ids.sort_unstable_by_key(|&(id, _)| id);
+
+        self.classifications.entry(label.to_string()).or_insert_with(|| Classification::new(label))
+            .add_counts(&ids, 1.0);
+        for &(id, _) in ids.iter() {
+            *self.document_frequencies.entry(id).or_insert(0.0) += 1.0;
+        }
+        self.num_examples += 1.0;
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", name = "add_document", skip_all,
+                                                         fields(label = label, tokens = document.len(), weight = weight)))]
+    fn add_weighted<S: AsRef<str>>(&mut self, document: &[S], label: &str, weight: f64) {
+        if document.is_empty() { return; }
+
+        let features = self.training_features(document);
+        let ids: Vec<WordId> = features.iter().map(|word| self.vocab.intern(word)).collect();
+
         // make sure the classification already exists
         if !self.classifications.contains_key(label) {
             let c = Classification::new(label);
-            self.classifications.insert(label.clone(), c);
+            self.classifications.insert(label.to_string(), c);
         }
 
-        let mut classification = self.classifications.get_mut(label).unwrap();
-                
-        for word in document.iter() {
-            classification.add_word(word);
-            self.vocab.insert(word.to_string());
+        let classification = self.classifications.get_mut(label).unwrap();
+        classification.add_document(&ids, weight);
+
+        let unique_ids: HashSet<WordId> = ids.into_iter().collect();
+        for id in unique_ids {
+            *self.document_frequencies.entry(id).or_insert(0.0) += weight;
         }
 
-        self.num_examples += 1;
-        classification.num_examples += 1;
+        self.num_examples += weight;
     }
 
-    /// Takes a document and a label and tokenizes the document by
-    /// breaking on whitespace characters. The document is added to the list
-    /// of documents that the classifier is aware of and will train on next time
-    /// the `train()` method is called 
-    pub fn add_document(&mut self, document: &String, label: &String) {
-        self.add_document_tokenized(&split_document(document), label);
+    /// Combines classifiers that were trained on separate shards of the data
+    /// into one, as if every document had been added to a single classifier.
+    /// The word counts, document counts, and vocabularies are summed and the
+    /// result is trained. Every classifier must have the same settings
+    /// (smoothing, variant, n-gram range, stop words, etc.), and the result
+    /// uses the first one's tokenizer. Metadata attached to a label by more
+    /// than one classifier keeps the first value of each key, and a document
+    /// ID used by more than one keeps the first one's document.
+    pub fn merge(classifiers: &[Classifier<T>]) -> Result<Classifier<T>, ClassifierError>
+        where T: Clone
+    {
+        let (first, rest) = classifiers.split_first()
+            .ok_or_else(|| ClassifierError::IncompatibleModels("no classifiers to merge".to_string()))?;
+        let mut merged = first.clone();
+        for other in rest.iter() {
+            merged.absorb(other)?;
+        }
+        // the calibration was fitted to the scores of the first classifier
+        merged.calibration = None;
+        merged.train();
+        Ok(merged)
     }
 
     /// Adds a list of (document, label) tuples to the classifier
-    pub fn add_documents(&mut self, examples: &Vec<(String, String)>) {
-        for &(ref document, ref label) in examples.iter() {
-            self.add_document(document, label);
+    pub fn add_documents<D: AsRef<str>, L: AsRef<str>>(&mut self, examples: &[(D, L)]) {
+        for (document, label) in examples.iter() {
+            self.add_document(document.as_ref(), label.as_ref());
+        }
+    }
+
+    /// Adds every (document, label) pair of an iterator to the classifier,
+    /// one at a time, so a corpus can be streamed through the classifier
+    /// without holding it all in memory
+    pub fn add_documents_iter<I, D, L>(&mut self, examples: I)
+        where I: IntoIterator<Item = (D, L)>, D: AsRef<str>, L: AsRef<str>
+    {
+        for (document, label) in examples {
+            self.add_document(document.as_ref(), label.as_ref());
+        }
+    }
+
+    /// Same as `add_documents_iter`, but skips the documents the
+    /// deduplicator finds to be duplicates, see `Deduplicator`. Returns the
+    /// number of documents skipped.
+    pub fn add_documents_deduplicated<I, D, L>(&mut self, examples: I, deduplicator: &mut Deduplicator) -> usize
+        where I: IntoIterator<Item = (D, L)>, D: AsRef<str>, L: AsRef<str>
+    {
+        let mut skipped = 0;
+        for (document, label) in examples {
+            if deduplicator.is_duplicate(document.as_ref()) {
+                skipped += 1;
+            } else {
+                self.add_document(document.as_ref(), label.as_ref());
+            }
+        }
+        skipped
+    }
+
+    /// Same as `add_documents_iter`, but tells the observer about every
+    /// document as it is added
+    pub fn add_documents_observed<I, D, L, O>(&mut self, examples: I, observer: &mut O)
+        where I: IntoIterator<Item = (D, L)>, D: AsRef<str>, L: AsRef<str>, O: TrainingObserver + ?Sized
+    {
+        for (document, label) in examples {
+            self.add_document(document.as_ref(), label.as_ref());
+            observer.on_document_added(label.as_ref());
         }
     }
 
     /// Adds a list of (tokenized document, label) tuples to the classifier
-    pub fn add_documents_tokenized(&mut self, examples: &Vec<(Vec<String>, String)>) {
-        for &(ref document, ref label) in examples.iter() {
-            self.add_document_tokenized(document, label);
+    pub fn add_documents_tokenized<S: AsRef<str>, L: AsRef<str>>(&mut self, examples: &[(Vec<S>, L)]) {
+        for (document, label) in examples.iter() {
+            self.add_document_tokenized(document, label.as_ref());
+        }
+    }
+
+    /// Takes a tokenized document and the label it was added with and removes
+    /// the document's words and counts from the classifier, forgetting the
+    /// document as if it had never been added. A label left with no documents
+    /// is removed, as are words that no longer appear in any document. Fails
+    /// if the label has no document with these words, in which case nothing
+    /// changes. The classifier needs to be trained again afterwards (unless
+    /// it is online). A document added with a weight must be removed with
+    /// `remove_document_tokenized_weighted`.
+    pub fn remove_document_tokenized<S: AsRef<str>>(&mut self, document: &[S], label: &str) -> Result<(), ClassifierError> {
+        self.remove_document_tokenized_weighted(document, label, 1.0)
+    }
+
+    /// Removes a tokenized document that was added with the given weight,
+    /// subtracting exactly what `add_document_tokenized_weighted` added, see
+    /// `remove_document_tokenized`
+    pub fn remove_document_tokenized_weighted<S: AsRef<str>>(&mut self, document: &[S], label: &str, weight: f64)
+                                              -> Result<(), ClassifierError> {
+        if !(weight > 0.0 && weight.is_finite()) {
+            return Err(ClassifierError::InvalidWeight(weight));
+        }
+        if document.is_empty() { return Ok(()); }
+
+        let features = self.training_features(document);
+        self.remove_features(&features, label, weight)
+    }
+
+    /// Tokenizes the document with the classifier's tokenizer and removes it
+    /// with the given weight, see `remove_document_tokenized_weighted`
+    pub fn remove_document_weighted(&mut self, document: &str, label: &str, weight: f64) -> Result<(), ClassifierError> {
+        let tokens = self.tokenize(document);
+        self.remove_document_tokenized_weighted(&tokens, label, weight)
+    }
+
+    // removes a document by the features it was trained on and its weight,
+    // see `remove_document_tokenized`. A document without features can't be
+    // told apart from any other, and matching it would take every document
+    // of the label with it.
+    fn remove_features<S: AsRef<str>>(&mut self, features: &[S], label: &str, weight: f64)
+                                      -> Result<(), ClassifierError> {
+        if features.is_empty() {
+            return Err(ClassifierError::DocumentNotFound(label.to_string()));
+        }
+        let ids: Option<Vec<WordId>> = features.iter().map(|word| self.vocab.id(word.as_ref())).collect();
+        let ids = match ids {
+            Some(ids) => ids,
+            None => return Err(ClassifierError::DocumentNotFound(label.to_string())),
+        };
+
+        let label_emptied = match self.classifications.get_mut(label) {
+            Some(ref mut classification) if classification.contains_document(&ids, weight) => {
+                classification.remove_document(&ids, weight);
+                is_removed(classification.num_examples, weight)
+            },
+            _ => return Err(ClassifierError::DocumentNotFound(label.to_string())),
+        };
+        if label_emptied {
+            self.classifications.remove(label);
+        }
+
+        let unique_ids: HashSet<WordId> = ids.into_iter().collect();
+        for id in unique_ids {
+            let forgotten = match self.document_frequencies.get_mut(&id) {
+                Some(frequency) => {
+                    *frequency -= weight;
+                    is_removed(*frequency, weight)
+                },
+                None => false,
+            };
+            if forgotten {
+                self.document_frequencies.remove(&id);
+            }
+            if !self.classifications.values().any(|c| c.words.contains_key(&id)) {
+                self.vocab.remove(id);
+            }
+        }
+
+        self.num_examples -= weight;
+        if self.classifications.is_empty() {
+            self.num_examples = 0.0;
+        }
+        Ok(())
+    }
+
+    /// Tokenizes the document with the classifier's tokenizer and removes
+    /// it from the classifier, see `remove_document_tokenized`
+    pub fn remove_document(&mut self, document: &str, label: &str) -> Result<(), ClassifierError> {
+        let tokens = self.tokenize(document);
+        self.remove_document_tokenized(&tokens, label)
+    }
+
+    /// Same as `add_document_tokenized`, but remembers the document under
+    /// the given ID, so that it can be listed by `documents_for_label` and
+    /// removed by `remove_document_by_id`. A document already added with
+    /// the ID is removed first, and if that fails because it has already
+    /// been removed some other way, nothing changes and the error is
+    /// returned. Fails with `EmptyDocument` if the document has no features
+    /// to remember it by, in which case nothing changes either.
+    pub fn add_document_tokenized_with_id<S: AsRef<str>>(&mut self, id: &str, document: &[S], label: &str)
+        -> Result<(), ClassifierError>
+    {
+        let features: Vec<String> = self.training_features(document).into_iter().map(Cow::into_owned).collect();
+        if features.is_empty() {
+            return Err(ClassifierError::EmptyDocument);
+        }
+        match self.remove_document_by_id(id) {
+            Ok(()) | Err(ClassifierError::UnknownDocumentId(_)) => {},
+            Err(e) => return Err(e),
+        }
+        self.add_document_tokenized(document, label);
+        self.documents.insert(id.to_string(), (label.to_string(), features));
+        Ok(())
+    }
+
+    /// Tokenizes the document with the classifier's tokenizer and adds it
+    /// under the given ID, see `add_document_tokenized_with_id`
+    pub fn add_document_with_id(&mut self, id: &str, document: &str, label: &str) -> Result<(), ClassifierError> {
+        let tokens = self.tokenize(document);
+        self.add_document_tokenized_with_id(id, &tokens, label)
+    }
+
+    /// Removes the document added with the given ID, see
+    /// `remove_document_tokenized`. Fails if no document was added with the
+    /// ID, or if it has already been removed some other way, in which case
+    /// nothing changes.
+    pub fn remove_document_by_id(&mut self, id: &str) -> Result<(), ClassifierError> {
+        let (label, features) = match self.documents.get(id) {
+            Some(document) => document.clone(),
+            None => return Err(ClassifierError::UnknownDocumentId(id.to_string())),
+        };
+        self.remove_features(&features, &label, 1.0)?;
+        self.documents.remove(id);
+        Ok(())
+    }
+
+    /// Gets the IDs of the documents added with the label by
+    /// `add_document_with_id`, sorted. Documents added without an ID are
+    /// counted but not listed.
+    pub fn documents_for_label(&self, label: &str) -> Vec<&str> {
+        self.documents.iter()
+            .filter(|&(_, (l, _))| l == label)
+            .map(|(id, _)| id.as_str())
+            .collect()
+    }
+
+    /// Gets the label the document with the given ID was added with, if
+    /// there is one
+    pub fn document_label(&self, id: &str) -> Option<&str> {
+        self.documents.get(id).map(|(label, _)| label.as_str())
+    }
+
+    /// Renames a label, keeping its documents, smoothing value, metadata,
+    /// and custom prior, and trains the classifier. If `new` is already a
+    /// label, the two are merged, see `merge_labels`. Fails if `old` has no
+    /// documents.
+    pub fn rename_label(&mut self, old: &str, new: &str) -> Result<(), ClassifierError> {
+        self.merge_labels(&[old], new)
+    }
+
+    /// Combines the labels into `new_label`, which may be one of them or
+    /// another label the classifier already has, as if all of their
+    /// documents had been added with `new_label`, and trains the classifier.
+    /// The combined label keeps the first smoothing value and the first
+    /// value of each metadata key found among `new_label` and then the
+    /// labels in order, and the sum of their custom priors. Fails with
+    /// `NoLabels` if no labels are given, or with `MissingLabel` if one of
+    /// them has no documents, in which case nothing changes.
+    pub fn merge_labels<L: AsRef<str>>(&mut self, labels: &[L], new_label: &str) -> Result<(), ClassifierError> {
+        if labels.is_empty() {
+            return Err(ClassifierError::NoLabels);
+        }
+        for label in labels.iter() {
+            if !self.classifications.contains_key(label.as_ref()) {
+                return Err(ClassifierError::MissingLabel(label.as_ref().to_string()));
+            }
+        }
+        let mut merged = vec![new_label];
+        for label in labels.iter().map(|label| label.as_ref()) {
+            if !merged.contains(&label) {
+                merged.push(label);
+            }
+        }
+
+        let mut combined = self.classifications.remove(new_label)
+            .unwrap_or_else(|| Classification::new(new_label));
+        for &label in merged[1..].iter() {
+            let classification = self.classifications.remove(label).expect("label was checked");
+            let ids: HashMap<WordId, WordId> = classification.words.keys()
+                .chain(classification.document_counts.keys())
+                .map(|&id| (id, id))
+                .collect();
+            combined.absorb(&classification, &ids);
+        }
+        self.classifications.insert(new_label.to_string(), combined);
+
+        let smoothing = merged.iter().filter_map(|&label| self.label_smoothing.get(label).cloned()).next();
+        for &label in merged.iter() {
+            self.label_smoothing.remove(label);
+        }
+        if let Some(smoothing) = smoothing {
+            self.label_smoothing.insert(new_label.to_string(), smoothing);
+        }
+        let mut metadata = HashMap::new();
+        for &label in merged.iter() {
+            for (key, value) in self.label_metadata.remove(label).unwrap_or_default() {
+                metadata.entry(key).or_insert(value);
+            }
+        }
+        if !metadata.is_empty() {
+            self.label_metadata.insert(new_label.to_string(), metadata);
+        }
+        if let PriorMode::Custom(ref mut custom) = self.priors {
+            let priors: Vec<f64> = merged.iter().filter_map(|&label| custom.remove(label)).collect();
+            if !priors.is_empty() {
+                custom.insert(new_label.to_string(), priors.iter().sum());
+            }
         }
+        for (label, _) in self.documents.values_mut() {
+            if merged.contains(&label.as_str()) {
+                *label = new_label.to_string();
+            }
+        }
+
+        self.train();
+        Ok(())
+    }
+
+    /// Removes a label and all of its documents from the classifier, as if
+    /// they had never been added, along with its smoothing value, metadata,
+    /// custom prior, and document IDs. Words that no longer appear with any
+    /// label are removed from the vocabulary. Fails if the label has no
+    /// documents. The classifier needs to be trained again afterwards
+    /// (unless it is online).
+    pub fn remove_label(&mut self, label: &str) -> Result<(), ClassifierError> {
+        let classification = match self.classifications.remove(label) {
+            Some(classification) => classification,
+            None => return Err(ClassifierError::MissingLabel(label.to_string())),
+        };
+        self.num_examples -= classification.num_examples;
+        for (id, &count) in classification.document_counts.iter() {
+            let forgotten = match self.document_frequencies.get_mut(id) {
+                Some(frequency) => {
+                    *frequency -= count;
+                    is_removed(*frequency, count)
+                },
+                None => false,
+            };
+            if forgotten {
+                self.document_frequencies.remove(id);
+            }
+        }
+        let unused: Vec<WordId> = classification.words.keys()
+            .filter(|id| !self.classifications.values().any(|c| c.words.contains_key(id)))
+            .cloned()
+            .collect();
+        self.drop_words(&unused);
+        if self.classifications.is_empty() {
+            self.num_examples = 0.0;
+        }
+
+        self.label_smoothing.remove(label);
+        self.label_metadata.remove(label);
+        if let PriorMode::Custom(ref mut custom) = self.priors {
+            custom.remove(label);
+        }
+        self.documents.retain(|_, (l, _)| l != label);
+        Ok(())
     }
 
-    /// Gets a vector of all of the labels that the classifier has seen so far
+    /// Gets a vector of all of the labels that the classifier has seen so
+    /// far, sorted
     pub fn get_labels(&self) -> Vec<String> {
         let labels: Vec<String> =
             self.classifications.values().map(|c| c.label.clone()).collect();
         labels
     }
 
-    /// Sets the [smoothing](http://en.wikipedia.org/wiki/Additive_smoothing)
-    /// value (must be greater than 0.0)
-    pub fn set_smoothing(&mut self, smoothing: f64) {
-        if smoothing <= 0.0 {
-            panic!("smoothing value must be a positive number");
+    /// Gets the number of distinct words (features) the classifier knows
+    pub fn vocab_size(&self) -> usize {
+        self.vocab.len()
+    }
+
+    /// Gets every word (feature) the classifier knows, sorted
+    pub fn vocabulary(&self) -> impl Iterator<Item = &str> {
+        let mut words: Vec<&str> = self.vocab.iter().map(|(_, word)| word).collect();
+        words.sort_unstable();
+        words.into_iter()
+    }
+
+    /// Writes every word (feature) the classifier knows to the given
+    /// writer, sorted and one per line
+    pub fn export_vocabulary<W: Write>(&self, mut writer: W) -> Result<(), ClassifierError> {
+        for word in self.vocabulary() {
+            writer.write_all(word.as_bytes())
+                .and_then(|_| writer.write_all(b"\n"))
+                .map_err(|e| ClassifierError::Io(e.to_string()))?;
         }
-        self.smoothing = smoothing;
+        writer.flush().map_err(|e| ClassifierError::Io(e.to_string()))
     }
 
-    /// Trains the classifier on the documents that have been observed so far
-    pub fn train(&mut self) {
-        for (_, classification) in self.classifications.iter_mut() {
-            classification.train(&self.vocab, self.num_examples, self.smoothing);
+    /// Summarizes the classifier: the size of its vocabulary, the examples
+    /// and words of each label, its smoothing, and roughly how much memory
+    /// it takes up
+    pub fn stats(&self) -> ModelStats {
+        let labels: Vec<LabelStats> = self.classifications.iter().map(|(label, classification)| LabelStats {
+            label: label.clone(),
+            examples: classification.num_examples,
+            tokens: classification.num_words,
+            distinct_words: classification.words.len(),
+            smoothing: self.label_smoothing.get(label).cloned().unwrap_or(self.smoothing),
+        }).collect();
+        ModelStats {
+            vocab_size: self.vocab.len(),
+            num_labels: self.classifications.len(),
+            num_examples: self.num_examples,
+            labels,
+            smoothing: self.smoothing,
+            memory_bytes: self.estimated_memory_bytes(),
         }
     }
 
-    /// Takes an unlabeled document that has been tokenized into a vector of strings
-    /// and then computes a classifying label for the document
-    pub fn classify_tokenized(&self, document: &Vec<String>) -> String {
-        let mut max_score = f64::NEG_INFINITY;
-        let mut max_classification = None;
-        
+    /// Estimates how many bytes the classifier takes up, by walking its
+    /// vocabulary, the counts and probabilities of every label, the
+    /// documents added with an ID, and the strings of its stop words, fixed
+    /// vocabulary, and label settings. Hash maps are counted at their
+    /// capacity. Allocator overhead and whatever the tokenizer and the
+    /// preprocessor hold are left out, so the real figure is somewhat higher.
+    pub fn estimated_memory_bytes(&self) -> usize {
+        let custom_priors = match self.priors {
+            PriorMode::Custom(ref priors) => map_size(priors, |_| 0),
+            _ => 0,
+        };
+        mem::size_of::<Classifier<T>>() + self.vocab.heap_size()
+            + self.document_frequencies.capacity() * mem::size_of::<(WordId, f64)>()
+            + self.classifications.iter()
+                .map(|(label, classification)| mem::size_of::<String>() + label.len() + classification.heap_size())
+                .sum::<usize>()
+            + map_size(&self.label_smoothing, |_| 0)
+            + map_size(&self.label_metadata, |metadata| map_size(metadata, |value| value.len()))
+            + set_size(&self.stop_words)
+            + self.fixed_vocabulary.as_ref().map_or(0, set_size)
+            + custom_priors
+            + self.documents.iter().map(|(id, (label, features))| {
+                mem::size_of::<(String, (String, Vec<String>))>() + id.len() + label.len()
+                    + features.capacity() * mem::size_of::<String>()
+                    + features.iter().map(|feature| feature.len()).sum::<usize>()
+            }).sum::<usize>()
+    }
+
+    /// Drops rare and overly common words from the vocabulary and every
+    /// classification: words seen fewer than `min_count` times in total, and
+    /// words that appear in more than `max_doc_frequency` (a fraction between
+    /// 0 and 1) of the documents. Returns the number of words dropped. This
+    /// should be called after the documents are added and before training.
+    pub fn prune_vocabulary(&mut self, min_count: u32, max_doc_frequency: f64) -> Result<usize, ClassifierError> {
+        if !(max_doc_frequency > 0.0 && max_doc_frequency <= 1.0) {
+            return Err(ClassifierError::InvalidDocumentFrequency(max_doc_frequency));
+        }
+
+        let mut counts: HashMap<WordId, f64> = HashMap::new();
         for classification in self.classifications.values() {
-            let score = classification.score_document(document, &self.vocab);
-            if score > max_score {
-                max_classification = Some(classification);
-                max_score = score;
+            for (&id, &(count, _)) in classification.words.iter() {
+                *counts.entry(id).or_insert(0.0) += count;
             }
         }
 
-        max_classification.expect("no classification found").label.clone()
+        let num_examples = self.num_examples;
+        let document_frequencies = &self.document_frequencies;
+        let pruned: Vec<WordId> = self.vocab.iter()
+            .map(|(id, _)| id)
+            .filter(|id| {
+                let count = counts.get(id).cloned().unwrap_or(0.0);
+                // models serialized before document frequencies were kept
+                // can only be pruned by count
+                let frequency = document_frequencies.get(id)
+                    .map_or(0.0, |&frequency| frequency / num_examples);
+                count < min_count as f64 || frequency > max_doc_frequency
+            })
+            .collect();
+
+        self.drop_words(&pruned);
+        Ok(pruned.len())
     }
 
-    /// Takes an unlabeled document and tokenizes it by breaking on spaces and
-    /// then computes a classifying label for the document
-    pub fn classify(&self, document: &String) -> String {
-        self.classify_tokenized(&split_document(document))
+    /// Scores every word in the vocabulary against every label, see
+    /// `FeatureScore`. The (word, label, score) triples are sorted from
+    /// highest to lowest score.
+    pub fn feature_scores(&self, score: FeatureScore) -> Vec<(String, String, f64)> {
+        let mut scores = Vec::new();
+        for classification in self.classifications.values() {
+            for (id, word) in self.vocab.iter() {
+                let (n11, n10, n01, n00) = self.contingency(id, classification);
+                let value = score.score(n11, n10, n01, n00);
+                scores.push((word.to_string(), classification.label.clone(), value));
+            }
+        }
+        scores.sort_by(|a, b| {
+            b.2.total_cmp(&a.2).then_with(|| a.0.cmp(&b.0)).then_with(|| a.1.cmp(&b.1))
+        });
+        scores
     }
 
-    /// Similar to classify but instead of returning a single label, returns all
-    /// labels and the probabilities of each one given the document
-    pub fn get_document_probabilities_tokenized(&self, document: &Vec<String>) -> Vec<(String, f64)> {        
-        
-        let all_probs:Vec<(String, f64)> = self.classifications.values().map(|classification| {
-            let score = classification.score_document(document, &self.vocab);
-            (classification.label.clone(), score)
-        }).collect();
+    /// Keeps only the `n` words that are most informative about some label
+    /// (by their highest score against any label, see `FeatureScore`) and
+    /// drops every other word from the vocabulary and every classification.
+    /// Returns the number of words dropped. This should be called after the
+    /// documents are added and before training.
+    pub fn select_top_features(&mut self, n: usize, score: FeatureScore) -> usize {
+        let mut best: HashMap<WordId, f64> = HashMap::new();
+        for classification in self.classifications.values() {
+            for (id, _) in self.vocab.iter() {
+                let (n11, n10, n01, n00) = self.contingency(id, classification);
+                let value = score.score(n11, n10, n01, n00);
+                let entry = best.entry(id).or_insert(value);
+                *entry = entry.max(value);
+            }
+        }
 
-        let total_prob = all_probs.iter()
-            .map(|&(_, s)| s)
-            .fold(0.0, |acc, s| acc + s);
+        let vocab = &self.vocab;
+        let mut ranked: Vec<(WordId, f64)> = best.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| vocab.word(a.0).cmp(vocab.word(b.0))));
+        let dropped: Vec<WordId> = ranked.into_iter().skip(n).map(|(id, _)| id).collect();
 
-        all_probs.into_iter().map(|(c, s)| (c, 1.0 - s/total_prob) ).collect()
+        self.drop_words(&dropped);
+        dropped.len()
     }
 
-    /// Similar to classify but instead of returning a single label, returns all
-    /// labels and the probabilities of each one given the document
-    pub fn get_document_probabilities(&self, document: &String) -> Vec<(String, f64)> {
-        self.get_document_probabilities_tokenized(&split_document(document))
+    /// Sets the [smoothing](http://en.wikipedia.org/wiki/Additive_smoothing)
+    /// value (must be greater than 0.0)
+    pub fn set_smoothing(&mut self, smoothing: f64) -> Result<(), ClassifierError> {
+        if smoothing.is_nan() || smoothing <= 0.0 {
+            return Err(ClassifierError::InvalidSmoothing(smoothing));
+        }
+        self.smoothing = smoothing;
+        Ok(())
     }
 
-    /// Encodes the classifier as a JSON string.
-    pub fn to_json(&self) -> String {
-        json::encode(self).ok().expect("encoding JSON failed")
+    /// Sets the smoothing value of one label, replacing the classifier's
+    /// smoothing for that label only (must be greater than 0.0). The
+    /// classifier needs to be trained again after this is changed.
+    pub fn set_label_smoothing(&mut self, label: &str, smoothing: f64) -> Result<(), ClassifierError> {
+        if smoothing.is_nan() || smoothing <= 0.0 {
+            return Err(ClassifierError::InvalidSmoothing(smoothing));
+        }
+        self.label_smoothing.insert(label.to_string(), smoothing);
+        Ok(())
     }
 
-    /// Builds a new classifier from a JSON string
-    pub fn from_json(encoded: &str) -> Classifier {
-        let classifier: Classifier = json::decode(encoded).ok().expect("decoding JSON failed");
-        classifier
+    /// Removes the smoothing value of a label set by `set_label_smoothing`,
+    /// so the label uses the classifier's smoothing again
+    pub fn clear_label_smoothing(&mut self, label: &str) {
+        self.label_smoothing.remove(label);
     }
 
-}
+    /// Attaches a key/value pair, such as a display name or a database id, to
+    /// a label, replacing any value the key already had. The metadata is
+    /// saved with the classifier but plays no part in classification. It can
+    /// be attached to a label before any documents are added with it.
+    pub fn set_label_metadata(&mut self, label: &str, key: &str, value: &str) {
+        self.label_metadata.entry(label.to_string()).or_default()
+            .insert(key.to_string(), value.to_string());
+    }
 
+    /// Gets the metadata attached to a label, if any has been
+    pub fn get_label_metadata(&self, label: &str) -> Option<&HashMap<String, String>> {
+        self.label_metadata.get(label)
+    }
 
-impl Classification {
-    
-    fn new(label: &String) -> Classification {
-        Classification {
-            label: label.clone(),
-            num_examples: 0u32,
-            num_words: 0u32,
-            probability: 0.0f64,
-            default_word_probability: 0.0f64,
-            words: HashMap::new(),
+    /// Removes a key from the metadata of a label, returning its value
+    pub fn remove_label_metadata(&mut self, label: &str, key: &str) -> Option<String> {
+        let metadata = self.label_metadata.get_mut(label)?;
+        let value = metadata.remove(key);
+        if metadata.is_empty() {
+            self.label_metadata.remove(label);
         }
+        value
     }
 
-    fn add_word(&mut self, word: &String) {
-        self.num_words += 1;
-        if self.words.contains_key(word) {
-            self.words.get_mut(word).unwrap().0 += 1;
-        } else {
-            self.words.insert(word.clone(), (1, 0.0f64));
+    /// Trains the classifier with each of the candidate smoothing values and
+    /// keeps the one that classifies the (document, label) validation
+    /// examples most accurately (the first of any that tie). The classifier
+    /// is left trained with the chosen smoothing, which is returned. With no
+    /// candidates or no validation examples the smoothing is left as it is.
+    pub fn tune_smoothing<D, L>(&mut self, validation: &[(D, L)], candidates: &[f64]) -> Result<f64, ClassifierError>
+        where D: AsRef<str>, L: AsRef<str>
+    {
+        if self.classifications.is_empty() {
+            return Err(ClassifierError::Untrained);
+        }
+        if let Some(&invalid) = candidates.iter().find(|&&smoothing| smoothing.is_nan() || smoothing <= 0.0) {
+            return Err(ClassifierError::InvalidSmoothing(invalid));
         }
-    }
 
-    // trains this classification
-    fn train(&mut self, vocab: &HashSet<String>, total_examples: u32, smoothing: f64) {
-        // the probability of this classification
-        self.probability = self.num_examples as f64 / total_examples as f64;
-        // the probability of any word that has not been seen in a document
-        // labeled with this classification's label
-        self.default_word_probability = smoothing /
-            (self.num_words as f64 + smoothing * vocab.len() as f64);
-        
-        for word in vocab.iter() {
-            if self.words.contains_key(word) {
-                let word_entry = self.words.get_mut(word).unwrap();
-                let word_count = word_entry.0;
-                let p_word_given_label =
-                    (word_count as f64 + smoothing) /
-                    (self.num_words as f64 + smoothing * vocab.len() as f64);
-                word_entry.1 = p_word_given_label;
+        let mut best = (self.smoothing, -1.0f64);
+        if !validation.is_empty() {
+            for &smoothing in candidates.iter() {
+                self.smoothing = smoothing;
+                self.train();
+                let correct = validation.iter()
+                    .filter(|&(document, label)| {
+                        self.classify(document.as_ref()).is_ok_and(|predicted| predicted == label.as_ref())
+                    })
+                    .count();
+                let accuracy = correct as f64 / validation.len() as f64;
+                if accuracy > best.1 {
+                    best = (smoothing, accuracy);
+                }
             }
         }
+        self.smoothing = best.0;
+        self.train();
+        Ok(best.0)
     }
 
-    // retrieves the probability of the document given this classification
-    // times the probability of this classification
-    fn score_document(&self, document: &Vec<String>, vocab: &HashSet<String>) -> f64 {
-        let mut total = 0.0f64;
-        for word in document.iter() {
-            if vocab.contains(word) {
-                let word_probability = match self.words.get(word) {
-                    Some( &(_, p) ) => p,
-                    None => self.default_word_probability,
-                };
-                total += word_probability.ln();
-            }
+    /// Calibrates the posterior probabilities of the trained classifier on
+    /// the (document, label) validation examples, which should not be ones
+    /// it was trained on, and returns the fitted scale
+    ///
+    /// Naive Bayes treats words as independent when they are not, so its
+    /// posteriors are usually far too close to 0 and 1. Calibration fits a
+    /// single scale that the log scores of every label are multiplied by
+    /// before they are normalized into posteriors, chosen to minimize the
+    /// log loss of the validation labels. This is Platt scaling for any
+    /// number of labels (also called temperature scaling), and the scale is
+    /// usually well below 1, which softens the posteriors. Which label is
+    /// most likely never changes. Examples with a label the classifier
+    /// doesn't know or a document without features are skipped, and if
+    /// none are left the classifier is left uncalibrated.
+    ///
+    /// The calibration is kept when the classifier is retrained, serialized,
+    /// or frozen, and applies to `get_document_posteriors` and everything
+    /// built on it, such as `classify_with_threshold` and `classify_top_k`.
+    pub fn calibrate<D, L>(&mut self, validation: &[(D, L)]) -> Result<f64, ClassifierError>
+        where D: AsRef<str>, L: AsRef<str>
+    {
+        self.check_trained()?;
+        let complement = if self.online { self.complement_counts() } else { None };
+        let parameters = self.parameters(complement.as_ref());
+        let examples: Vec<(Vec<f64>, usize)> = validation.iter()
+            .filter_map(|(document, label)| {
+                let truth = self.classifications.keys().position(|l| l == label.as_ref())?;
+                let tokens = self.tokenize(document.as_ref());
+                let features = self.features(&tokens);
+                if features.is_empty() {
+                    return None;
+                }
+                let scores: Vec<f64> = self.scores_with(&features, &parameters).into_iter()
+                    .map(|(_, score)| score)
+                    .collect();
+                // a true label that can't be scored can't be calibrated for
+                if scores[truth].is_finite() { Some((scores, truth)) } else { None }
+            })
+            .collect();
+        if examples.is_empty() {
+            self.calibration = None;
+            return Ok(1.0);
         }
-        self.probability.ln() + total
+        let scale = fit_calibration(&examples);
+        self.calibration = Some(scale);
+        Ok(scale)
+    }
+
+    /// The scale fitted by `calibrate`, or `None` if the classifier has not
+    /// been calibrated
+    pub fn calibration(&self) -> Option<f64> {
+        self.calibration
     }
-}
 
-// splits a String on whitespaces
-fn split_document(document: &String) -> Vec<String> {
-    let re = Regex::new(r"(\s)").unwrap();
-    re.split(document).map(|s| s.to_string()).collect()
-}
\ No newline at end of file
+    /// Removes the calibration, so the posteriors are those of the model
+    pub fn clear_calibration(&mut self) {
+        self.calibration = None;
+    }
+
+    /// Sets the range of n-gram sizes to use as features, for example `(1, 3)`
+    /// uses every unigram, bigram, and trigram of each document. The words
+    /// of an n-gram are joined by a single space. The default is `(1, 1)`,
+    /// single words only. This should be set before any documents are added.
+    pub fn set_ngram_range(&mut self, min: usize, max: usize) -> Result<(), ClassifierError> {
+        if min == 0 || min > max {
+            return Err(ClassifierError::InvalidNgramRange(min, max));
+        }
+        self.ngram_range = (min, max);
+        Ok(())
+    }
+
+    /// Adds skip-grams to the features, pairs of words with between 1 and
+    /// `window` words between them, so "pork belly ribs" also has the
+    /// feature "pork _ ribs". Each skipped word is written as an underscore,
+    /// so "pork _ ribs" and "pork _ _ ribs" are different features. This
+    /// helps when the words of a phrase are sometimes split up, as in "pork
+    /// spare ribs". A window of 0 (the default) turns skip-grams off. This
+    /// should be set before any documents are added.
+    pub fn set_skip_grams(&mut self, window: usize) {
+        self.skip_window = window;
+    }
+
+    /// Fixes the vocabulary to the given words, so that no other word is
+    /// ever counted or stored in the model, e.g. to make sure no personal
+    /// information a document might hold can end up in it. Documents are
+    /// still trained on the words they have from the list, and when
+    /// classifying every other word is unknown, see `set_unknown_word_policy`.
+    /// The words are matched against features, so with n-grams or hashed
+    /// features the list should hold n-grams or buckets. This should be set
+    /// before any documents are added.
+    pub fn set_fixed_vocabulary<I, S>(&mut self, words: I)
+        where I: IntoIterator<Item = S>, S: AsRef<str>
+    {
+        self.fixed_vocabulary = Some(words.into_iter().map(|word| word.as_ref().to_string()).collect());
+    }
+
+    /// Removes the fixed vocabulary, so every word is counted again
+    pub fn clear_fixed_vocabulary(&mut self) {
+        self.fixed_vocabulary = None;
+    }
+
+    /// Limits how many times a word counts in a single document, when
+    /// training and when classifying, so that a word repeated over and over
+    /// (as in spam) can't outweigh the rest of the document. A limit of 1
+    /// only counts whether each word is present. Fails if the limit is 0.
+    /// This should be set before any documents are added.
+    pub fn set_max_word_count(&mut self, max_count: usize) -> Result<(), ClassifierError> {
+        if max_count == 0 {
+            return Err(ClassifierError::InvalidWordCount(max_count));
+        }
+        self.max_word_count = Some(max_count);
+        Ok(())
+    }
+
+    /// Removes the limit set by `set_max_word_count`, so every occurrence
+    /// of a word counts again
+    pub fn clear_max_word_count(&mut self) {
+        self.max_word_count = None;
+    }
+
+    /// Sets the stop words, tokens that are dropped from documents before
+    /// they are counted or classified. Stop words must match tokens exactly.
+    /// A list of common English stop words is available from the `stop_words`
+    /// module when the `stop-words` feature is enabled. This should be set
+    /// before any documents are added.
+    pub fn set_stop_words<I, S>(&mut self, stop_words: I)
+        where I: IntoIterator<Item = S>, S: AsRef<str>
+    {
+        self.stop_words = stop_words.into_iter().map(|word| word.as_ref().to_string()).collect();
+    }
+
+    /// Sets the preprocessing (lowercasing, Unicode normalization, accent
+    /// stripping) applied to documents before they are tokenized, see
+    /// `Preprocessor`. Tokenized documents are used as they are. This should
+    /// be set before any documents are added.
+    pub fn set_preprocessor(&mut self, preprocessor: Preprocessor) {
+        self.preprocessor = preprocessor;
+    }
+
+    /// Sets a function every document is passed through before it is
+    /// preprocessed and tokenized, when training and when classifying, e.g.
+    /// one of the scrubbers of the `sanitize` module to mask personal
+    /// information so it is never stored in the model. Tokenized documents
+    /// are used as they are. Like the tokenizer, the sanitizer is not part
+    /// of the serialized classifier and has to be set again after loading.
+    pub fn set_sanitizer(&mut self, sanitizer: fn(&str) -> String) {
+        self.sanitizer = Some(sanitizer);
+    }
+
+    /// Removes the sanitizer, so documents are used as they are again
+    pub fn clear_sanitizer(&mut self) {
+        self.sanitizer = None;
+    }
+
+    /// Hashes every feature into one of `num_buckets` buckets, so the
+    /// vocabulary never holds more than `num_buckets` words however many
+    /// distinct features are seen. Features that land in the same bucket
+    /// are counted as one word. Buckets are named by their number (`"#17"`),
+    /// which is what word inspection and explanations report. This should
+    /// be set before any documents are added.
+    pub fn set_hashed_features(&mut self, num_buckets: usize) -> Result<(), ClassifierError> {
+        if num_buckets == 0 {
+            return Err(ClassifierError::InvalidBucketCount(num_buckets));
+        }
+        self.hash_buckets = Some(num_buckets);
+        Ok(())
+    }
+
+    /// Sets the naive Bayes variant, see `NaiveBayesVariant`. The classifier
+    /// needs to be trained again after this is changed.
+    pub fn set_variant(&mut self, variant: NaiveBayesVariant) {
+        self.variant = variant;
+    }
+
+    /// Sets how the occurrences of words are weighted, see `FeatureWeighting`.
+    /// The classifier needs to be trained again after this is changed.
+    pub fn set_feature_weighting(&mut self, weighting: FeatureWeighting) {
+        self.weighting = weighting;
+    }
+
+    /// Sets where the prior probability of each label comes from, see
+    /// `PriorMode`. The complement variant does not use priors. The
+    /// classifier needs to be trained again after this is changed.
+    pub fn set_priors(&mut self, priors: PriorMode) -> Result<(), ClassifierError> {
+        if let PriorMode::Custom(ref custom) = priors {
+            for (label, &prior) in custom.iter() {
+                if !(prior > 0.0 && prior.is_finite()) {
+                    return Err(ClassifierError::InvalidPrior(label.clone(), prior));
+                }
+            }
+        }
+        self.priors = priors;
+        Ok(())
+    }
+
+    /// Sets how the words of a document that are not in the vocabulary are
+    /// scored, see `UnknownWordPolicy`. Takes effect without training again.
+    pub fn set_unknown_word_policy(&mut self, policy: UnknownWordPolicy) -> Result<(), ClassifierError> {
+        if let UnknownWordPolicy::PenalizePerToken(penalty) = policy {
+            if !(penalty >= 0.0 && penalty.is_finite()) {
+                return Err(ClassifierError::InvalidPenalty(penalty));
+            }
+        }
+        self.unknown_words = policy;
+        Ok(())
+    }
+
+    /// Turns online learning on or off. An online classifier scores documents
+    /// straight from the word counts it has observed, so every added document
+    /// is reflected immediately and `train()` never needs to be called. This
+    /// makes adding a document cheap at the cost of slightly slower
+    /// classification. Call `train()` after turning online learning off.
+    pub fn set_online(&mut self, online: bool) {
+        self.online = online;
+    }
+
+    /// Trains the classifier on the documents that have been observed so far
+    pub fn train(&mut self) {
+        self.train_observed(&mut ());
+    }
+
+    /// Same as `train`, but tells the observer how much of the training is
+    /// done after each label
+    pub fn train_observed<O: TrainingObserver + ?Sized>(&mut self, observer: &mut O) {
+        self.train_until(observer, None);
+    }
+
+    /// Same as `train`, but gives up with a `Cancelled` error if `cancel` is
+    /// set, which is checked before each label is trained, e.g. by another
+    /// thread shutting down. A cancelled classifier is left part way through
+    /// training and needs to be trained again.
+    pub fn train_with_cancel(&mut self, cancel: &AtomicBool) -> Result<(), ClassifierError> {
+        self.train_observed_with_cancel(&mut (), cancel)
+    }
+
+    /// Same as `train_with_cancel`, but tells the observer how much of the
+    /// training is done after each label
+    pub fn train_observed_with_cancel<O: TrainingObserver + ?Sized>(&mut self, observer: &mut O, cancel: &AtomicBool)
+                                                                   -> Result<(), ClassifierError> {
+        if self.train_until(observer, Some(cancel)) { Ok(()) } else { Err(ClassifierError::Cancelled) }
+    }
+
+    // trains the classifier, returning false if it was cancelled
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", name = "train", skip_all,
+                                                         fields(labels = self.classifications.len(),
+                                                                vocab = self.vocab.len())))]
+    fn train_until<O: TrainingObserver + ?Sized>(&mut self, observer: &mut O, cancel: Option<&AtomicBool>) -> bool {
+        let complement = self.complement_counts();
+        let parameters = Parameters {
+            vocab: &self.vocab,
+            total_examples: self.num_examples,
+            smoothing: self.smoothing,
+            label_smoothing: &self.label_smoothing,
+            variant: self.variant,
+            weighting: self.weighting,
+            priors: &self.priors,
+            unknown_words: self.unknown_words,
+            num_labels: self.classifications.len(),
+            document_frequencies: &self.document_frequencies,
+            complement: complement.as_ref(),
+        };
+        let num_labels = self.classifications.len();
+        for (i, classification) in self.classifications.values_mut().enumerate() {
+            if cancel.is_some_and(|cancel| cancel.load(Ordering::Relaxed)) {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(trained = i, "training cancelled");
+                return false;
+            }
+            classification.train(&parameters);
+            observer.on_train_progress((i + 1) as f64 / num_labels as f64);
+        }
+        if num_labels == 0 {
+            observer.on_train_progress(1.0);
+        }
+        true
+    }
+
+    /// Takes an unlabeled document that has been tokenized into a vector of strings
+    /// and then computes a classifying label for the document. Fails if the
+    /// classifier has not been trained, has an empty vocabulary, or the
+    /// document has no features. Of labels with the same score, the first
+    /// in sorted order wins.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", name = "classify", skip_all,
+                                                         fields(tokens = document.len())))]
+    pub fn classify_tokenized<S: AsRef<str>>(&self, document: &[S]) -> Result<String, ClassifierError> {
+        let features = self.features(document);
+        self.check_ready(&features)?;
+        Ok(best_label(self.scores(&features)))
+    }
+
+    /// Same as `classify_tokenized`, but takes the tokens as string slices
+    /// from any iterator, without copying them (unless n-grams or hashed
+    /// features are used)
+    pub fn classify_tokens<'a, I: IntoIterator<Item = &'a str>>(&self, tokens: I) -> Result<String, ClassifierError> {
+        let tokens: Vec<&str> = tokens.into_iter().collect();
+        self.classify_tokenized(&tokens)
+    }
+
+    /// Takes an unlabeled document and tokenizes it with the classifier's
+    /// tokenizer and then computes a classifying label for the document
+    pub fn classify(&self, document: &str) -> Result<String, ClassifierError> {
+        self.classify_tokenized(&self.tokenize(document))
+    }
+
+    /// Same as `classify`, but also returns the metadata attached to the
+    /// label (empty if there is none), see `set_label_metadata`
+    pub fn classify_with_metadata(&self, document: &str)
+        -> Result<(String, HashMap<String, String>), ClassifierError>
+    {
+        let label = self.classify(document)?;
+        let metadata = self.label_metadata.get(&label).cloned().unwrap_or_default();
+        Ok((label, metadata))
+    }
+
+    /// Takes an unlabeled document that has been tokenized into a vector of strings
+    /// and computes a classifying label for it, or returns `None` if the
+    /// posterior probability of the best label is less than `min_confidence`
+    pub fn classify_with_threshold_tokenized<S: AsRef<str>>(&self, document: &[S], min_confidence: f64)
+        -> Result<Option<String>, ClassifierError>
+    {
+        Ok(self.classify_top_k_tokenized(document, 1)?.into_iter()
+            .find(|&(_, probability)| probability >= min_confidence)
+            .map(|(label, _)| label))
+    }
+
+    /// Tokenizes the document with the classifier's tokenizer and computes a
+    /// classifying label for it, or returns `None` if the classifier is less
+    /// confident in the label than `min_confidence`
+    pub fn classify_with_threshold(&self, document: &str, min_confidence: f64)
+        -> Result<Option<String>, ClassifierError>
+    {
+        self.classify_with_threshold_tokenized(&self.tokenize(document), min_confidence)
+    }
+
+    /// Takes an unlabeled document that has been tokenized into a vector of strings
+    /// and returns the `k` most likely labels with their posterior probabilities,
+    /// most likely first. Labels with the same probability are ordered by name.
+    pub fn classify_top_k_tokenized<S: AsRef<str>>(&self, document: &[S], k: usize)
+        -> Result<Vec<(String, f64)>, ClassifierError>
+    {
+        let mut posteriors = self.get_document_posteriors_tokenized(document)?;
+        posteriors.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        posteriors.truncate(k);
+        Ok(posteriors)
+    }
+
+    /// Tokenizes the document with the classifier's tokenizer and returns the
+    /// `k` most likely labels with their posterior probabilities
+    pub fn classify_top_k(&self, document: &str, k: usize) -> Result<Vec<(String, f64)>, ClassifierError> {
+        self.classify_top_k_tokenized(&self.tokenize(document), k)
+    }
+
+    /// Similar to classify but instead of returning a single label, returns all
+    /// labels and the posterior probability of each one given the document.
+    /// The probabilities are between 0 and 1 and sum to 1, and the labels
+    /// are in the order of `get_labels`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", name = "posteriors", skip_all,
+                                                         fields(tokens = document.len())))]
+    pub fn get_document_posteriors_tokenized<S: AsRef<str>>(&self, document: &[S])
+        -> Result<Vec<(String, f64)>, ClassifierError>
+    {
+        let features = self.features(document);
+        self.check_ready(&features)?;
+        Ok(posteriors(self.scores(&features), self.calibration_scale()))
+    }
+
+    /// Same as `get_document_posteriors_tokenized`, but takes the tokens as
+    /// string slices from any iterator, see `classify_tokens`
+    pub fn get_document_posteriors_tokens<'a, I: IntoIterator<Item = &'a str>>(&self, tokens: I)
+        -> Result<Vec<(String, f64)>, ClassifierError>
+    {
+        let tokens: Vec<&str> = tokens.into_iter().collect();
+        self.get_document_posteriors_tokenized(&tokens)
+    }
+
+    /// Tokenizes the document with the classifier's tokenizer and then
+    /// returns all labels and the posterior probability of each one
+    pub fn get_document_posteriors(&self, document: &str) -> Result<Vec<(String, f64)>, ClassifierError> {
+        self.get_document_posteriors_tokenized(&self.tokenize(document))
+    }
+
+    // the calibrated log score of every label given the document, in the
+    // order of `get_labels`. They differ from the log posteriors by a
+    // constant, so their differences are the log odds of the labels.
+    pub(crate) fn log_scores_tokenized<S: AsRef<str>>(&self, document: &[S])
+        -> Result<Vec<(String, f64)>, ClassifierError>
+    {
+        let features = self.features(document);
+        self.check_ready(&features)?;
+        let scale = self.calibration_scale();
+        Ok(self.scores(&features).into_iter()
+            .map(|(classification, score)| (classification.label.clone(), scale * score))
+            .collect())
+    }
+
+    /// Similar to classify but instead of returning a single label, returns all
+    /// labels and the probabilities of each one given the document
+    #[deprecated(since = "0.0.4", note = "the values are not a probability distribution, \
+                                          use `get_document_posteriors_tokenized` instead")]
+    pub fn get_document_probabilities_tokenized<S: AsRef<str>>(&self, document: &[S]) -> Vec<(String, f64)> {
+        let features = self.features(document);
+        probabilities(self.scores(&features))
+    }
+
+    /// Similar to classify but instead of returning a single label, returns all
+    /// labels and the probabilities of each one given the document
+    #[deprecated(since = "0.0.4", note = "the values are not a probability distribution, \
+                                          use `get_document_posteriors` instead")]
+    pub fn get_document_probabilities(&self, document: &str) -> Vec<(String, f64)> {
+        let tokens = self.tokenize(document);
+        let features = self.features(&tokens);
+        probabilities(self.scores(&features))
+    }
+
+    /// Takes an unlabeled document that has been tokenized into a vector of strings
+    /// and breaks the score of every label down into the prior, the baseline,
+    /// and the contribution of each word, see `Explanation`
+    pub fn explain_tokenized<S: AsRef<str>>(&self, document: &[S]) -> Result<Explanation, ClassifierError> {
+        let features = self.features(document);
+        self.check_ready(&features)?;
+        let ids = self.word_ids(&features);
+        let unknown = self.unknown_features(&features);
+        let complement = if self.online { self.complement_counts() } else { None };
+        let parameters = self.parameters(complement.as_ref());
+
+        let mut labels: Vec<LabelExplanation> = self.classifications.values()
+            .map(|classification| classification.explain_document(&ids, &unknown, &parameters, self.online))
+            .collect();
+
+        // the same normalization as the posteriors
+        let scale = self.calibration_scale();
+        let max_score = labels.iter().fold(f64::NEG_INFINITY, |acc, l| acc.max(l.score));
+        let total: f64 = labels.iter().map(|l| (scale * (l.score - max_score)).exp()).sum();
+        for explanation in labels.iter_mut() {
+            explanation.posterior = (scale * (explanation.score - max_score)).exp() / total;
+        }
+        labels.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.label.cmp(&b.label)));
+        Ok(Explanation { labels })
+    }
+
+    /// Tokenizes the document with the classifier's tokenizer and breaks the
+    /// score of every label down, see `explain_tokenized`
+    pub fn explain(&self, document: &str) -> Result<Explanation, ClassifierError> {
+        self.explain_tokenized(&self.tokenize(document))
+    }
+
+    /// Gets the `n` most probable words given the label, with the probability
+    /// of each, most probable first. The probabilities are computed from the
+    /// current word counts with smoothing. For the complement variant these
+    /// are the probabilities under the label itself rather than its complement.
+    /// Returns nothing for a label the classifier has not seen.
+    pub fn top_words_for_label(&self, label: &str, n: usize) -> Vec<(String, f64)> {
+        let classification = match self.classifications.get(label) {
+            Some(classification) => classification,
+            None => return Vec::new(),
+        };
+        let mut parameters = self.parameters(None);
+        if parameters.variant == NaiveBayesVariant::Complement {
+            parameters.variant = NaiveBayesVariant::Multinomial;
+        }
+        let total_weight = classification.total_weight(&parameters);
+
+        let words = classification.words.keys()
+            .map(|&id| (self.vocab.word(id).to_string(), classification.word_probability(id, total_weight, &parameters)))
+            .collect();
+        ranked(words, n)
+    }
+
+    /// Gets the `n` words that most set the label apart from every other label,
+    /// ranked by the log odds `ln(P(word | label) / P(word | other labels))`,
+    /// with the log odds of each. Positive log odds mean the word is more
+    /// likely with the label than without it. The probabilities are computed
+    /// from the word counts, whatever the variant. Returns nothing for a label
+    /// the classifier has not seen.
+    pub fn discriminative_words_for_label(&self, label: &str, n: usize) -> Vec<(String, f64)> {
+        let classification = match self.classifications.get(label) {
+            Some(classification) => classification,
+            None => return Vec::new(),
+        };
+        let pooled = self.pooled_counts();
+        let mut own = self.parameters(None);
+        own.variant = NaiveBayesVariant::Multinomial;
+        let mut others = self.parameters(Some(&pooled));
+        others.variant = NaiveBayesVariant::Complement;
+        let total_weight = classification.total_weight(&own);
+
+        let words = classification.words.keys()
+            .map(|&id| {
+                let p_label = classification.word_probability(id, total_weight, &own);
+                let p_others = classification.word_probability(id, total_weight, &others);
+                (self.vocab.word(id).to_string(), p_label.ln() - p_others.ln())
+            })
+            .collect();
+        ranked(words, n)
+    }
+
+    /// Gets the prior probability of the label, as set by `set_priors`.
+    /// Returns None for a label the classifier has not seen.
+    pub fn label_prior(&self, label: &str) -> Option<f64> {
+        let classification = self.classifications.get(label)?;
+        Some(classification.prior(&self.parameters(None)))
+    }
+
+    /// Gets the smoothed probability of a word (a feature, as returned by
+    /// `top_words_for_label`) given the label, computed from the word counts.
+    /// For the complement variant this is the probability of the word given
+    /// every other label. Returns None for a label the classifier has not
+    /// seen or a word that is not in the vocabulary.
+    pub fn word_probability(&self, label: &str, word: &str) -> Option<f64> {
+        let classification = self.classifications.get(label)?;
+        let id = self.vocab.id(word)?;
+        let complement = self.complement_counts();
+        let parameters = self.parameters(complement.as_ref());
+        let total_weight = classification.total_weight(&parameters);
+        Some(classification.word_probability(id, total_weight, &parameters))
+    }
+
+    /// Encodes the classifier as a JSON string.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("encoding JSON failed")
+    }
+
+    /// Encodes the classifier as JSON and writes it to the given writer
+    pub fn to_writer<W: Write>(&self, writer: W) -> Result<(), ClassifierError> {
+        serde_json::to_writer(writer, self).map_err(|e| ClassifierError::Encode(e.to_string()))
+    }
+
+    /// Saves the classifier to a JSON file. The classifier is written to a
+    /// temporary file first and then moved into place, so the file at `path`
+    /// is never left partly written.
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ClassifierError> {
+        self.save_to_file_compressed(path, Compression::None)
+    }
+
+    /// Same as `save_to_file`, but compresses the file, see `Compression`
+    pub fn save_to_file_compressed<P: AsRef<Path>>(&self, path: P, compression: Compression)
+                                                   -> Result<(), ClassifierError> {
+        persistence::save(self, path.as_ref(), compression)
+    }
+
+    /// Encodes the classifier in a compact binary format, which is much
+    /// smaller and faster to decode than JSON. The bytes start with a header
+    /// holding the format version. Requires the `binary` feature.
+    #[cfg(feature = "binary")]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ClassifierError> {
+        binary::encode(self)
+    }
+
+    /// Makes an `InferenceModel` that classifies documents the same way as
+    /// the trained classifier, for running the model without the standard
+    /// library. The model splits documents on whitespace (see
+    /// `InferenceModel::from_bytes_with_tokenizer` for other tokenizers) and
+    /// can only lowercase them, so other preprocessing and sanitizers are
+    /// not supported. It has no skip-grams and can only limit word counts
+    /// to 1.
+    pub fn to_inference_model(&self) -> Result<InferenceModel, ClassifierError> {
+        self.check_trained()?;
+        let lowercasing = Preprocessor::lowercasing();
+        if self.preprocessor != Preprocessor::new() && self.preprocessor != lowercasing {
+            return Err(ClassifierError::UnsupportedSetting("preprocessing other than lowercasing".to_string()));
+        }
+        if self.skip_window > 0 {
+            return Err(ClassifierError::UnsupportedSetting("skip-grams".to_string()));
+        }
+        if self.sanitizer.is_some() {
+            return Err(ClassifierError::UnsupportedSetting("sanitizer".to_string()));
+        }
+        // a limit of 1 counts each word once, as the Bernoulli variant does
+        let distinct = self.variant == NaiveBayesVariant::Bernoulli || self.max_word_count == Some(1);
+        if self.max_word_count.is_some() && !distinct {
+            return Err(ClassifierError::UnsupportedSetting("word count limits above 1".to_string()));
+        }
+
+        let table = self.score_table();
+        Ok(InferenceModel {
+            labels: table.labels,
+            biases: table.biases,
+            unknown: table.unknown,
+            words: table.words.into_iter().collect(),
+            distinct,
+            lowercase: self.preprocessor == lowercasing,
+            stop_words: self.stop_words.iter().cloned().collect(),
+            ngram_range: self.ngram_range,
+            hash_buckets: self.hash_buckets,
+            tokenizer: WhitespaceTokenizer,
+        })
+    }
+
+    /// Shrinks the classifier's maps to fit what they hold, giving back the
+    /// memory left over from words, labels, and documents that were removed
+    /// or pruned. Nothing else changes, so the classifier can still be
+    /// trained; `freeze` saves far more for classifiers that won't be.
+    pub fn shrink_to_fit(&mut self) {
+        self.vocab.shrink_to_fit();
+        self.label_smoothing.shrink_to_fit();
+        self.label_metadata.shrink_to_fit();
+        for metadata in self.label_metadata.values_mut() {
+            metadata.shrink_to_fit();
+        }
+        self.stop_words.shrink_to_fit();
+        if let Some(ref mut fixed_vocabulary) = self.fixed_vocabulary {
+            fixed_vocabulary.shrink_to_fit();
+        }
+        if let PriorMode::Custom(ref mut priors) = self.priors {
+            priors.shrink_to_fit();
+        }
+        self.document_frequencies.shrink_to_fit();
+        for (_, features) in self.documents.values_mut() {
+            features.shrink_to_fit();
+        }
+        for classification in self.classifications.values_mut() {
+            classification.shrink_to_fit();
+        }
+    }
+
+    /// Writes the trained classifier to a file as an ONNX model, for
+    /// serving it with ONNX Runtime or another ONNX stack. Tokenizing
+    /// documents and counting their features is left to the caller, so the
+    /// graph takes a batch of rows of feature counts (float32), one column
+    /// per word of the vocabulary in sorted order and a last column for the
+    /// features that are not in it. For the Bernoulli variant each column is
+    /// 1 if the feature appears and 0 if not. The counts are multiplied by a
+    /// matrix of word scores and added to the biases, and the graph outputs
+    /// these `scores`, their softmax as `probabilities`, and the index of
+    /// the best label as `label`. The labels and the vocabulary are stored
+    /// as JSON arrays in the model's `labels` and `vocabulary` metadata.
+    ///
+    /// The scores are stored in single precision, so the probabilities
+    /// differ from the classifier's by about one part in ten million.
+    pub fn export_onnx<P: AsRef<Path>>(&self, path: P) -> Result<(), ClassifierError> {
+        self.check_trained()?;
+        let model = onnx::encode(&self.score_table(), self.variant == NaiveBayesVariant::Bernoulli);
+        fs::write(path, model).map_err(|e| ClassifierError::Io(e.to_string()))
+    }
+
+    /// Trains the classifier and freezes it into a `FrozenModel`, which
+    /// classifies documents the same way in far less memory but cannot be
+    /// trained any further. Everything only training needs is dropped: the
+    /// word counts, the documents added with an ID, the label settings, and
+    /// the fixed vocabulary. The model keeps one score per word and label,
+    /// packed into flat arrays, and its stop words, shrunk to fit.
+    pub fn freeze(mut self) -> FrozenModel<T> {
+        self.train();
+        self.stop_words.shrink_to_fit();
+        let table = self.score_table();
+        let mut text = String::new();
+        let mut ends = Vec::with_capacity(table.words.len());
+        let mut scores = Vec::with_capacity(table.words.len() * table.labels.len());
+        for (word, terms) in table.words {
+            text.push_str(&word);
+            ends.push(text.len() as u32);
+            scores.extend(terms);
+        }
+        text.shrink_to_fit();
+
+        FrozenModel {
+            labels: table.labels,
+            biases: table.biases,
+            unknown: table.unknown,
+            text,
+            ends,
+            scores,
+            quantized_scores: Vec::new(),
+            distinct: self.variant == NaiveBayesVariant::Bernoulli,
+            preprocessor: self.preprocessor,
+            sanitizer: self.sanitizer,
+            stop_words: self.stop_words,
+            ngram_range: self.ngram_range,
+            skip_window: self.skip_window,
+            max_word_count: self.max_word_count,
+            hash_buckets: self.hash_buckets,
+            tokenizer: self.tokenizer,
+        }
+    }
+
+    /// Same as `freeze`, but keeps the score of every word in single rather
+    /// than double precision, which halves the memory the scores take up and
+    /// roughly halves the serialized model. The posteriors differ from the
+    /// classifier's by about one part in ten million, too little to change
+    /// the label of all but the closest of ties.
+    pub fn freeze_quantized(self) -> FrozenModel<T> {
+        let mut model = self.freeze();
+        model.quantize();
+        model
+    }
+
+    // what every feature adds to the score of each label, for models that
+    // classify without the classifier's counts
+    pub(crate) fn score_table(&self) -> ScoreTable {
+        let complement = if self.online { self.complement_counts() } else { None };
+        let parameters = self.parameters(complement.as_ref());
+        let classifications: Vec<&Classification> = self.classifications.values().collect();
+        // every score is a sum of one term per feature, so a feature's terms
+        // are the difference it makes to the score of a document without it.
+        // Scaling every term by the calibration scales the whole score.
+        let scale = self.calibration_scale();
+        let biases: Vec<f64> = classifications.iter()
+            .map(|classification| self.score(classification, &[], 0.0, &parameters))
+            .collect();
+        let terms = |document: &[(WordId, f64)], unknown: f64| -> Vec<f64> {
+            classifications.iter().zip(biases.iter())
+                .map(|(classification, bias)| scale * (self.score(classification, document, unknown, &parameters) - bias))
+                .collect()
+        };
+        let unknown = terms(&[], 1.0);
+        let mut words: Vec<(String, Vec<f64>)> = self.vocab.iter()
+            .map(|(id, word)| (word.to_string(), terms(&[(id, parameters.weight(id))], 0.0)))
+            .collect();
+        words.sort_by(|a, b| a.0.cmp(&b.0));
+
+        ScoreTable {
+            labels: classifications.iter().map(|classification| classification.label.clone()).collect(),
+            biases: biases.iter().map(|bias| scale * bias).collect(),
+            unknown,
+            words,
+        }
+    }
+
+    // moves a decoded classifier's model over to the given tokenizer
+    pub(crate) fn retokenized(classifier: Classifier, tokenizer: T) -> Classifier<T> {
+        Classifier {
+            vocab: classifier.vocab,
+            num_examples: classifier.num_examples,
+            smoothing: classifier.smoothing,
+            label_smoothing: classifier.label_smoothing,
+            label_metadata: classifier.label_metadata,
+            ngram_range: classifier.ngram_range,
+            skip_window: classifier.skip_window,
+            max_word_count: classifier.max_word_count,
+            fixed_vocabulary: classifier.fixed_vocabulary,
+            online: classifier.online,
+            stop_words: classifier.stop_words,
+            variant: classifier.variant,
+            weighting: classifier.weighting,
+            priors: classifier.priors,
+            unknown_words: classifier.unknown_words,
+            document_frequencies: classifier.document_frequencies,
+            preprocessor: classifier.preprocessor,
+            sanitizer: classifier.sanitizer,
+            hash_buckets: classifier.hash_buckets,
+            calibration: classifier.calibration,
+            documents: classifier.documents,
+            classifications: classifier.classifications,
+            tokenizer,
+        }
+    }
+
+    // replaces the classifier's model with a decoded one, keeping the
+    // tokenizer and the sanitizer, which aren't serialized
+    pub(crate) fn restore(&mut self, classifier: Classifier) {
+        let Classifier {
+            vocab, num_examples, smoothing, label_smoothing, label_metadata, ngram_range, skip_window,
+            max_word_count, fixed_vocabulary, online, stop_words, variant, weighting, priors, unknown_words,
+            document_frequencies, preprocessor, sanitizer: _, hash_buckets, calibration, documents,
+            classifications, tokenizer: _,
+        } = classifier;
+        self.vocab = vocab;
+        self.num_examples = num_examples;
+        self.smoothing = smoothing;
+        self.label_smoothing = label_smoothing;
+        self.label_metadata = label_metadata;
+        self.ngram_range = ngram_range;
+        self.skip_window = skip_window;
+        self.max_word_count = max_word_count;
+        self.fixed_vocabulary = fixed_vocabulary;
+        self.online = online;
+        self.stop_words = stop_words;
+        self.variant = variant;
+        self.weighting = weighting;
+        self.priors = priors;
+        self.unknown_words = unknown_words;
+        self.document_frequencies = document_frequencies;
+        self.preprocessor = preprocessor;
+        self.hash_buckets = hash_buckets;
+        self.calibration = calibration;
+        self.documents = documents;
+        self.classifications = classifications;
+    }
+
+    // scores a document's features against every classification, an online
+    // classifier computes its probabilities from the current word counts
+    fn scores(&self, features: &[Cow<str>]) -> Vec<(&Classification, f64)> {
+        let complement = if self.online { self.complement_counts() } else { None };
+        self.scores_with(features, &self.parameters(complement.as_ref()))
+    }
+
+    fn scores_with(&self, features: &[Cow<str>], parameters: &Parameters) -> Vec<(&Classification, f64)> {
+        self.vector_scores(&self.document_vector(features, parameters), parameters)
+    }
+
+    // scores a vectorized document against every classification
+    fn vector_scores(&self, vector: &DocumentVector, parameters: &Parameters) -> Vec<(&Classification, f64)> {
+        self.classifications.values()
+            .map(|classification| {
+                (classification, self.score(classification, &vector.counts, vector.unknown, parameters))
+            })
+            .collect()
+    }
+
+    // aggregates a document's features into the weighted count of each
+    // distinct word, see `weighted_counts`
+    fn document_vector(&self, features: &[Cow<str>], parameters: &Parameters) -> DocumentVector {
+        DocumentVector {
+            counts: weighted_counts(self.word_ids(features), parameters),
+            unknown: self.unknown_features(features).len() as f64,
+        }
+    }
+
+    // vectorizes a tokenized document for a `Vectorizer`
+    pub(crate) fn vectorize_tokens<S: AsRef<str>>(&self, tokens: &[S]) -> DocumentVector {
+        let features = self.features(tokens);
+        self.document_vector(&features, &self.parameters(None))
+    }
+
+    // builds a vector from (word id, value) pairs for a `Vectorizer`
+    pub(crate) fn sparse_vector(&self, entries: &[(WordId, f64)]) -> DocumentVector {
+        let mut counts: BTreeMap<WordId, f64> = BTreeMap::new();
+        let mut unknown = 0.0;
+        for &(id, value) in entries.iter().filter(|&&(_, value)| value != 0.0) {
+            if self.vocab.contains(id) {
+                *counts.entry(id).or_insert(0.0) += value;
+            } else {
+                unknown += value;
+            }
+        }
+        DocumentVector { counts: counts.into_iter().collect(), unknown }
+    }
+
+    // every word of the vocabulary and its id, in order of id, for a
+    // `Vectorizer`
+    pub(crate) fn vocabulary_ids(&self) -> Vec<(WordId, &str)> {
+        let mut ids: Vec<(WordId, &str)> = self.vocab.iter().collect();
+        ids.sort_unstable_by_key(|&(id, _)| id);
+        ids
+    }
+
+    // classifies a vectorized document for a `Vectorizer`, an online
+    // complement classifier needs the pooled word counts
+    pub(crate) fn classify_vector(&self, vector: &DocumentVector, complement: Option<&ComplementCounts>)
+        -> Result<String, ClassifierError>
+    {
+        let parameters = self.parameters(complement);
+        Ok(best_label(self.checked_vector_scores(vector, &parameters)?))
+    }
+
+    // same as `classify_vector`, but gets the posterior of every label
+    pub(crate) fn vector_posteriors(&self, vector: &DocumentVector, complement: Option<&ComplementCounts>)
+        -> Result<Vec<(String, f64)>, ClassifierError>
+    {
+        let parameters = self.parameters(complement);
+        Ok(posteriors(self.checked_vector_scores(vector, &parameters)?, self.calibration_scale()))
+    }
+
+    // scores a vectorized document, failing as `check_ready` would
+    fn checked_vector_scores<'a>(&'a self, vector: &DocumentVector, parameters: &Parameters)
+        -> Result<Vec<(&'a Classification, f64)>, ClassifierError>
+    {
+        self.check_trained()?;
+        if vector.is_empty() {
+            return Err(ClassifierError::EmptyDocument);
+        }
+        Ok(self.vector_scores(vector, parameters))
+    }
+
+    // the score of a document, given as the weighted count of each of its
+    // words (see `weighted_counts`), against one classification
+    fn score(&self, classification: &Classification, document: &[(WordId, f64)], unknown: f64,
+             parameters: &Parameters) -> f64 {
+        if self.online {
+            classification.score_document_online(document, unknown, parameters)
+        } else {
+            classification.score_document(document, unknown, parameters)
+        }
+    }
+
+    fn parameters<'a>(&'a self, complement: Option<&'a ComplementCounts>) -> Parameters<'a> {
+        Parameters {
+            vocab: &self.vocab,
+            total_examples: self.num_examples,
+            smoothing: self.smoothing,
+            label_smoothing: &self.label_smoothing,
+            variant: self.variant,
+            weighting: self.weighting,
+            priors: &self.priors,
+            unknown_words: self.unknown_words,
+            num_labels: self.classifications.len(),
+            document_frequencies: &self.document_frequencies,
+            complement,
+        }
+    }
+
+    /// Makes a `Vectorizer`, which turns documents into vectors of word
+    /// counts once and scores every label from them
+    pub fn vectorizer(&self) -> Vectorizer<'_, T> {
+        Vectorizer {
+            classifier: self,
+            complement: if self.online { self.complement_counts() } else { None },
+        }
+    }
+
+    // sums the weighted word counts of every classification, None unless
+    // the complement variant is being used
+    fn complement_counts(&self) -> Option<ComplementCounts> {
+        if self.variant != NaiveBayesVariant::Complement {
+            return None;
+        }
+        Some(self.pooled_counts())
+    }
+
+    // sums the weighted word counts of every classification
+    fn pooled_counts(&self) -> ComplementCounts {
+        let parameters = self.parameters(None);
+        let mut complement = ComplementCounts {
+            word_weights: HashMap::new(),
+            total_weight: 0.0,
+        };
+        for classification in self.classifications.values() {
+            for (&id, &(count, _)) in classification.words.iter() {
+                let weight = count * parameters.weight(id);
+                *complement.word_weights.entry(id).or_insert(0.0) += weight;
+                complement.total_weight += weight;
+            }
+        }
+        complement
+    }
+
+    // adds the count of a word in the documents of a label, and the number
+    // of those documents it appeared in, from counts kept outside of the
+    // classifier (by `DiskTrainer`, or made up for an imported model)
+    pub(crate) fn add_word_counts(&mut self, label: &str, word: &str, count: f64, documents: f64) {
+        let id = self.vocab.intern(word);
+        let classification = self.classifications.entry(label.to_string())
+            .or_insert_with(|| Classification::new(label));
+        classification.words.entry(id).or_insert((0.0, 0.0)).0 += count;
+        classification.num_words += count;
+        *classification.document_counts.entry(id).or_insert(0.0) += documents;
+        *self.document_frequencies.entry(id).or_insert(0.0) += documents;
+    }
+
+    // adds the total weight of the documents of a label, from counts kept
+    // outside of the classifier
+    pub(crate) fn add_label_examples(&mut self, label: &str, examples: f64) {
+        self.classifications.entry(label.to_string())
+            .or_insert_with(|| Classification::new(label))
+            .num_examples += examples;
+        self.num_examples += examples;
+    }
+
+    // adds the counts of another classifier with the same settings to this one
+    fn absorb(&mut self, other: &Classifier<T>) -> Result<(), ClassifierError> {
+        self.check_compatible(other)?;
+
+        let ids: HashMap<WordId, WordId> = other.vocab.iter()
+            .map(|(id, word)| (id, self.vocab.intern(word)))
+            .collect();
+        for (id, &count) in other.document_frequencies.iter() {
+            *self.document_frequencies.entry(ids[id]).or_insert(0.0) += count;
+        }
+        for (label, classification) in other.classifications.iter() {
+            self.classifications.entry(label.clone())
+                .or_insert_with(|| Classification::new(label))
+                .absorb(classification, &ids);
+        }
+        for (id, document) in other.documents.iter() {
+            self.documents.entry(id.clone()).or_insert_with(|| document.clone());
+        }
+        for (label, metadata) in other.label_metadata.iter() {
+            let merged = self.label_metadata.entry(label.clone()).or_default();
+            for (key, value) in metadata.iter() {
+                merged.entry(key.clone()).or_insert_with(|| value.clone());
+            }
+        }
+        self.num_examples += other.num_examples;
+        Ok(())
+    }
+
+    // fails with the name of the first setting the classifiers differ in
+    fn check_compatible(&self, other: &Classifier<T>) -> Result<(), ClassifierError> {
+        let differences = [
+            ("smoothing", self.smoothing != other.smoothing),
+            ("label smoothing", self.label_smoothing != other.label_smoothing),
+            ("n-gram range", self.ngram_range != other.ngram_range),
+            ("skip-gram window", self.skip_window != other.skip_window),
+            ("word count limit", self.max_word_count != other.max_word_count),
+            ("fixed vocabulary", self.fixed_vocabulary != other.fixed_vocabulary),
+            ("online", self.online != other.online),
+            ("stop words", self.stop_words != other.stop_words),
+            ("variant", self.variant != other.variant),
+            ("feature weighting", self.weighting != other.weighting),
+            ("priors", self.priors != other.priors),
+            ("unknown word policy", self.unknown_words != other.unknown_words),
+            ("preprocessor", self.preprocessor != other.preprocessor),
+            ("hashed features", self.hash_buckets != other.hash_buckets),
+        ];
+        match differences.iter().find(|&&(_, differs)| differs) {
+            Some(&(setting, _)) => Err(ClassifierError::IncompatibleModels(format!("the {} differs", setting))),
+            None => Ok(()),
+        }
+    }
+
+    // removes the words from the vocabulary and every classification
+    fn drop_words(&mut self, ids: &[WordId]) {
+        for &id in ids.iter() {
+            self.vocab.remove(id);
+            self.document_frequencies.remove(&id);
+            for classification in self.classifications.values_mut() {
+                classification.forget_word(id);
+            }
+        }
+    }
+
+    // the number of documents with the word and the label, with the word
+    // but not the label, with the label but not the word, and with neither
+    fn contingency(&self, id: WordId, classification: &Classification) -> (f64, f64, f64, f64) {
+        let with_word = self.document_frequencies.get(&id).cloned().unwrap_or(0.0);
+        let n11 = classification.document_counts.get(&id).cloned().unwrap_or(0.0);
+        let n10 = (with_word - n11).max(0.0);
+        let n01 = (classification.num_examples - n11).max(0.0);
+        let n00 = (self.num_examples - n11 - n10 - n01).max(0.0);
+        (n11, n10, n01, n00)
+    }
+
+    pub(crate) fn tokenizer(&self) -> &T {
+        &self.tokenizer
+    }
+
+    // preprocesses and tokenizes a document, then filters its tokens
+    pub(crate) fn tokenize(&self, document: &str) -> Vec<String> {
+        match self.sanitizer {
+            Some(sanitizer) => self.preprocessor.tokenize(&self.tokenizer, &sanitizer(document)),
+            None => self.preprocessor.tokenize(&self.tokenizer, document),
+        }
+    }
+
+    // turns the tokens of a document into the features the classifier counts
+    pub(crate) fn features<'a, S: AsRef<str>>(&self, tokens: &'a [S]) -> Vec<Cow<'a, str>> {
+        features(tokens, &self.stop_words, self.ngram_range, self.skip_window, self.hash_buckets, self.max_word_count)
+    }
+
+    // the features of a document that are counted when training, those in
+    // the fixed vocabulary if there is one
+    pub(crate) fn training_features<'a, S: AsRef<str>>(&self, tokens: &'a [S]) -> Vec<Cow<'a, str>> {
+        let mut features = self.features(tokens);
+        if let Some(ref words) = self.fixed_vocabulary {
+            features.retain(|feature| words.contains(feature.as_ref()));
+        }
+        features
+    }
+
+    // checks that a document with these features can be classified
+    fn check_ready(&self, features: &[Cow<str>]) -> Result<(), ClassifierError> {
+        self.check_trained()?;
+        if features.is_empty() {
+            return Err(ClassifierError::EmptyDocument);
+        }
+        Ok(())
+    }
+
+    // checks that the classifier can classify documents. A classifier that
+    // is not online must have been trained on its documents.
+    fn check_trained(&self) -> Result<(), ClassifierError> {
+        if self.classifications.is_empty() {
+            return Err(ClassifierError::Untrained);
+        }
+        if self.vocab.len() == 0 {
+            return Err(ClassifierError::EmptyVocabulary);
+        }
+        if !self.online && self.classifications.values().all(|c| c.probability == 0.0) {
+            return Err(ClassifierError::Untrained);
+        }
+        Ok(())
+    }
+
+    // what the log scores are multiplied by before they are normalized
+    fn calibration_scale(&self) -> f64 {
+        self.calibration.unwrap_or(1.0)
+    }
+
+    // the ids of the features that are in the vocabulary
+    fn word_ids(&self, features: &[Cow<str>]) -> Vec<WordId> {
+        features.iter().filter_map(|word| self.vocab.id(word)).collect()
+    }
+
+    // the features that are not in the vocabulary, in the order of the
+    // document. The Bernoulli variant only counts each one once.
+    fn unknown_features<'a>(&self, features: &'a [Cow<str>]) -> Vec<&'a str> {
+        let mut seen = HashSet::new();
+        features.iter()
+            .map(|word| word.as_ref())
+            .filter(|word| self.vocab.id(word).is_none())
+            .filter(|word| self.variant != NaiveBayesVariant::Bernoulli || seen.insert(*word))
+            .collect()
+    }
+
+}
+
+impl<T: Tokenizer + Sync> Classifier<T> {
+
+    /// Computes a classifying label for each of the documents, tokenizing
+    /// and scoring them across all of the available threads. The labels are
+    /// returned in the same order as the documents, each one failing as
+    /// `classify` would.
+    pub fn classify_batch<D: AsRef<str> + Sync>(&self, documents: &[D]) -> Vec<Result<String, ClassifierError>> {
+        let complement = if self.online { self.complement_counts() } else { None };
+        let parameters = self.parameters(complement.as_ref());
+        parallel_map(documents, |document| {
+            let tokens = self.tokenize(document.as_ref());
+            let features = self.features(&tokens);
+            self.check_ready(&features)?;
+            Ok(best_label(self.scores_with(&features, &parameters)))
+        })
+    }
+
+    /// Same as `get_document_posteriors` for each of the documents,
+    /// computed across all of the available threads
+    pub fn get_document_posteriors_batch<D: AsRef<str> + Sync>(&self, documents: &[D])
+        -> Vec<Result<Vec<(String, f64)>, ClassifierError>>
+    {
+        let complement = if self.online { self.complement_counts() } else { None };
+        let parameters = self.parameters(complement.as_ref());
+        parallel_map(documents, |document| {
+            let tokens = self.tokenize(document.as_ref());
+            let features = self.features(&tokens);
+            self.check_ready(&features)?;
+            Ok(posteriors(self.scores_with(&features, &parameters), self.calibration_scale()))
+        })
+    }
+
+    /// Ranks the unlabeled documents by how uncertain the classifier is
+    /// about their labels, by the entropy of their posteriors, and returns
+    /// the `n` most uncertain as (index into `documents`, uncertainty), most
+    /// uncertain first. These are the documents most worth labeling by hand
+    /// next. Documents without features are left out. Fails if the
+    /// classifier can't classify documents yet.
+    pub fn most_uncertain<D: AsRef<str> + Sync>(&self, documents: &[D], n: usize)
+        -> Result<Vec<(usize, f64)>, ClassifierError>
+    {
+        self.most_uncertain_by(documents, n, Uncertainty::Entropy)
+    }
+
+    /// Same as `most_uncertain`, with the given measure of uncertainty. Of
+    /// documents that are equally uncertain, the first comes first.
+    pub fn most_uncertain_by<D: AsRef<str> + Sync>(&self, documents: &[D], n: usize, measure: Uncertainty)
+        -> Result<Vec<(usize, f64)>, ClassifierError>
+    {
+        self.check_trained()?;
+        let mut ranked: Vec<(usize, f64)> = self.get_document_posteriors_batch(documents).into_iter()
+            .enumerate()
+            .filter_map(|(i, posteriors)| {
+                let posteriors: Vec<f64> = posteriors.ok()?.into_iter().map(|(_, p)| p).collect();
+                Some((i, measure.score(&posteriors)))
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        ranked.truncate(n);
+        Ok(ranked)
+    }
+
+    /// Self-training: trains the classifier, labels the unlabeled documents
+    /// it is confident about, adds them as training documents, and trains
+    /// again, up to `iterations` times or until no more documents are
+    /// confident enough. A document is confident enough when the posterior
+    /// of its most likely label is at least `confidence_threshold`. This
+    /// makes use of a large unlabeled corpus when labeled documents are
+    /// scarce, though a confident mistake is learned as if it were true.
+    /// Returns the (index into `unlabeled`, label) of every document that
+    /// was added, in the order they were added. Fails if the classifier
+    /// can't classify documents after it is trained.
+    pub fn self_train<D: AsRef<str> + Sync>(&mut self, unlabeled: &[D], confidence_threshold: f64, iterations: usize)
+        -> Result<Vec<(usize, String)>, ClassifierError>
+    {
+        self.train();
+        self.check_trained()?;
+        let mut remaining: Vec<usize> = (0..unlabeled.len()).collect();
+        let mut added = Vec::new();
+        for _ in 0..iterations {
+            let documents: Vec<&str> = remaining.iter().map(|&i| unlabeled[i].as_ref()).collect();
+            let confident: Vec<(usize, String)> = self.get_document_posteriors_batch(&documents).into_iter()
+                .zip(remaining.iter())
+                .filter_map(|(posteriors, &i)| {
+                    let (label, posterior) = posteriors.ok()?.into_iter()
+                        .fold(None, |best: Option<(String, f64)>, (label, p)| match best {
+                            Some(best) if best.1 >= p => Some(best),
+                            _ => Some((label, p)),
+                        })?;
+                    if posterior >= confidence_threshold { Some((i, label)) } else { None }
+                })
+                .collect();
+            if confident.is_empty() {
+                break;
+            }
+
+            for (i, label) in confident.iter() {
+                self.add_document(unlabeled[*i].as_ref(), label);
+            }
+            remaining.retain(|i| !confident.iter().any(|&(j, _)| j == *i));
+            added.extend(confident);
+            self.train();
+        }
+        Ok(added)
+    }
+
+    /// Same as `get_document_probabilities` for each of the documents,
+    /// computed across all of the available threads
+    #[deprecated(since = "0.0.4", note = "the values are not a probability distribution, \
+                                          use `get_document_posteriors_batch` instead")]
+    pub fn get_document_probabilities_batch<D: AsRef<str> + Sync>(&self, documents: &[D]) -> Vec<Vec<(String, f64)>> {
+        let complement = if self.online { self.complement_counts() } else { None };
+        let parameters = self.parameters(complement.as_ref());
+        parallel_map(documents, |document| {
+            let tokens = self.tokenize(document.as_ref());
+            let features = self.features(&tokens);
+            probabilities(self.scores_with(&features, &parameters))
+        })
+    }
+}
+
+/// Adds (document, label) pairs to the classifier, see `add_documents_iter`
+impl<T: Tokenizer, D: AsRef<str>, L: AsRef<str>> Extend<(D, L)> for Classifier<T> {
+    fn extend<I: IntoIterator<Item = (D, L)>>(&mut self, examples: I) {
+        self.add_documents_iter(examples);
+    }
+}
+
+impl<T> Serialize for Classifier<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let vocab = &self.vocab;
+        EncodedClassifier {
+            model_version: MODEL_VERSION,
+            vocab: vocab.iter().map(|(_, word)| word).collect(),
+            num_examples: self.num_examples,
+            smoothing: self.smoothing,
+            label_smoothing: self.label_smoothing.iter()
+                .map(|(label, &smoothing)| (label.as_str(), smoothing))
+                .collect(),
+            label_metadata: self.label_metadata.iter()
+                .map(|(label, metadata)| {
+                    let metadata = metadata.iter().map(|(key, value)| (key.as_str(), value.as_str())).collect();
+                    (label.as_str(), metadata)
+                })
+                .collect(),
+            ngram_range: self.ngram_range,
+            skip_window: self.skip_window,
+            max_word_count: self.max_word_count,
+            fixed_vocabulary: self.fixed_vocabulary.as_ref()
+                .map(|words| words.iter().map(|word| word.as_str()).collect()),
+            online: self.online,
+            stop_words: self.stop_words.iter().map(|word| word.as_str()).collect(),
+            variant: self.variant,
+            weighting: self.weighting,
+            priors: self.priors.clone(),
+            unknown_words: self.unknown_words,
+            document_frequencies: self.document_frequencies.iter()
+                .map(|(&id, &count)| (vocab.word(id), count))
+                .collect(),
+            preprocessor: self.preprocessor.clone(),
+            hash_buckets: self.hash_buckets,
+            calibration: self.calibration,
+            documents: self.documents.iter()
+                .map(|(id, (label, tokens))| {
+                    (id.as_str(), (label.as_str(), tokens.iter().map(|token| token.as_str()).collect()))
+                })
+                .collect(),
+            classifications: self.classifications.iter()
+                .map(|(label, classification)| (label.as_str(), classification.encode(vocab)))
+                .collect(),
+        }.serialize(serializer)
+    }
+}
+
+impl<'de, T: Default> Deserialize<'de> for Classifier<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Classifier<T>, D::Error> {
+        Classifier::decode(EncodedClassifier::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl<T: Default> Classifier<T> {
+
+    // builds a classifier from its encoded form, migrating older models
+    fn decode(encoded: EncodedClassifier<String>) -> Result<Classifier<T>, ClassifierError> {
+        let encoded = migrate(encoded)?;
+        let mut vocab = Vocabulary::new();
+        for word in encoded.vocab.iter() {
+            vocab.intern(word);
+        }
+        let document_frequencies = encoded.document_frequencies.into_iter()
+            .filter_map(|(word, count)| vocab.id(&word).map(|id| (id, count)))
+            .collect();
+        let variant = encoded.variant;
+        let classifications = encoded.classifications.into_iter()
+            .map(|(label, classification)| (label, Classification::decode(classification, &vocab, variant)))
+            .collect();
+        Ok(Classifier {
+            vocab,
+            num_examples: encoded.num_examples,
+            smoothing: encoded.smoothing,
+            label_smoothing: encoded.label_smoothing,
+            label_metadata: encoded.label_metadata,
+            ngram_range: encoded.ngram_range,
+            skip_window: encoded.skip_window,
+            max_word_count: encoded.max_word_count,
+            fixed_vocabulary: encoded.fixed_vocabulary,
+            online: encoded.online,
+            stop_words: encoded.stop_words,
+            variant: encoded.variant,
+            weighting: encoded.weighting,
+            priors: encoded.priors,
+            unknown_words: encoded.unknown_words,
+            document_frequencies,
+            preprocessor: encoded.preprocessor,
+            sanitizer: None,
+            hash_buckets: encoded.hash_buckets,
+            calibration: encoded.calibration,
+            documents: encoded.documents.into_iter().collect(),
+            classifications,
+            tokenizer: T::default(),
+        })
+    }
+}
+
+fn default_ngram_range() -> (usize, usize) {
+    (1, 1)
+}
+
+// brings a model serialized by an older release up to the current model
+// version one version at a time, and refuses models from newer releases
+fn migrate(mut encoded: EncodedClassifier<String>) -> Result<EncodedClassifier<String>, ClassifierError> {
+    if encoded.model_version > MODEL_VERSION {
+        return Err(ClassifierError::UnsupportedModelVersion(encoded.model_version, MODEL_VERSION));
+    }
+    while encoded.model_version < MODEL_VERSION {
+        encoded = match encoded.model_version {
+            // the fields added before versioning are filled in with their
+            // defaults, which is all these models need
+            0 => EncodedClassifier { model_version: 1, ..encoded },
+            version => return Err(ClassifierError::UnsupportedModelVersion(version, MODEL_VERSION)),
+        };
+    }
+    Ok(encoded)
+}
+
+// the model-wide settings and statistics needed to train a classification
+// and to score documents against it
+struct Parameters<'a> {
+    vocab: &'a Vocabulary,
+    total_examples: f64,
+    smoothing: f64,
+    label_smoothing: &'a HashMap<String, f64>,
+    variant: NaiveBayesVariant,
+    weighting: FeatureWeighting,
+    priors: &'a PriorMode,
+    unknown_words: UnknownWordPolicy,
+    num_labels: usize,
+    document_frequencies: &'a HashMap<WordId, f64>,
+    complement: Option<&'a ComplementCounts>,
+}
+
+// the labels of a trained classifier, in order, and what a document with no
+// features scores for each of them, what every feature not in the
+// vocabulary adds, and what each occurrence of every word adds, in order of
+// the words, all multiplied by the calibration scale
+pub(crate) struct ScoreTable {
+    pub labels: Vec<String>,
+    pub biases: Vec<f64>,
+    pub unknown: Vec<f64>,
+    pub words: Vec<(String, Vec<f64>)>,
+}
+
+// the weighted count of every word summed over all of the classifications,
+// the complement variant subtracts a classification's own counts from these
+#[derive(Debug)]
+pub(crate) struct ComplementCounts {
+    word_weights: HashMap<WordId, f64>,
+    total_weight: f64,
+}
+
+impl<'a> Parameters<'a> {
+
+    // the weight given to each occurrence of a word
+    fn weight(&self, id: WordId) -> f64 {
+        match self.weighting {
+            FeatureWeighting::Counts => 1.0,
+            FeatureWeighting::TfIdf => {
+                // smoothed inverse document frequency, as if one extra
+                // document containing every word had been seen
+                let document_frequency = self.document_frequencies.get(&id).cloned().unwrap_or(0.0);
+                ((1.0 + self.total_examples) / (1.0 + document_frequency)).ln() + 1.0
+            }
+        }
+    }
+
+    // the weight given to each occurrence of a word that is not in the
+    // vocabulary, which has never appeared in a document
+    fn unknown_weight(&self) -> f64 {
+        match self.weighting {
+            FeatureWeighting::Counts => 1.0,
+            FeatureWeighting::TfIdf => (1.0 + self.total_examples).ln() + 1.0,
+        }
+    }
+
+    fn is_uniform(&self) -> bool {
+        self.weighting == FeatureWeighting::Counts
+    }
+}
+
+impl Classification {
+    
+    fn new(label: &str) -> Classification {
+        Classification {
+            label: label.to_string(),
+            num_examples: 0.0f64,
+            num_words: 0.0f64,
+            probability: 0.0f64,
+            default_word_probability: 0.0f64,
+            absent_log_probability: 0.0f64,
+            words: HashMap::new(),
+            document_counts: HashMap::new(),
+            complement_probabilities: HashMap::new(),
+            log_prior: 0.0f64,
+            word_scores: HashMap::new(),
+            default_word_score: 0.0f64,
+        }
+    }
+
+    // adds the counts of the same label from another classifier, whose
+    // word ids map to this classifier's through `ids`
+    fn absorb(&mut self, other: &Classification, ids: &HashMap<WordId, WordId>) {
+        self.num_examples += other.num_examples;
+        self.num_words += other.num_words;
+        for (id, &(count, _)) in other.words.iter() {
+            self.words.entry(ids[id]).or_insert((0.0, 0.0)).0 += count;
+        }
+        for (id, &count) in other.document_counts.iter() {
+            *self.document_counts.entry(ids[id]).or_insert(0.0) += count;
+        }
+    }
+
+    fn add_document(&mut self, ids: &[WordId], weight: f64) {
+        for &id in ids.iter() {
+            self.add_word(id, weight);
+        }
+        let unique_ids: HashSet<&WordId> = ids.iter().collect();
+        for &id in unique_ids {
+            *self.document_counts.entry(id).or_insert(0.0) += weight;
+        }
+        self.num_examples += weight;
+    }
+
+    // adds a document given as the count of each of its distinct words
+    fn add_counts(&mut self, counts: &[(WordId, f64)], weight: f64) {
+        for &(id, count) in counts.iter() {
+            self.add_word(id, count * weight);
+            *self.document_counts.entry(id).or_insert(0.0) += weight;
+        }
+        self.num_examples += weight;
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.words.shrink_to_fit();
+        self.document_counts.shrink_to_fit();
+        self.complement_probabilities.shrink_to_fit();
+        self.word_scores.shrink_to_fit();
+    }
+
+    // roughly how many bytes the classification takes up
+    fn heap_size(&self) -> usize {
+        mem::size_of::<Classification>() + self.label.len()
+            + self.words.capacity() * mem::size_of::<(WordId, (f64, f64))>()
+            + (self.document_counts.capacity() + self.complement_probabilities.capacity()
+               + self.word_scores.capacity())
+                * mem::size_of::<(WordId, f64)>()
+    }
+
+    fn add_word(&mut self, id: WordId, weight: f64) {
+        self.num_words += weight;
+        self.words.entry(id).or_insert((0.0, 0.0f64)).0 += weight;
+    }
+
+    // whether the features could have been added to this classification as
+    // a document with the weight, that is, whether it has at least that much
+    // of the document and of every word
+    fn contains_document(&self, ids: &[WordId], weight: f64) -> bool {
+        if self.num_examples < weight - REMOVAL_TOLERANCE * weight {
+            return false;
+        }
+        let mut counts: HashMap<WordId, f64> = HashMap::new();
+        for &id in ids.iter() {
+            *counts.entry(id).or_insert(0.0) += weight;
+        }
+        counts.iter().all(|(id, &count)| {
+            self.words.get(id).is_some_and(|&(total, _)| total >= count - REMOVAL_TOLERANCE * count)
+        })
+    }
+
+    // subtracts what `add_document` added for the document with the weight
+    fn remove_document(&mut self, ids: &[WordId], weight: f64) {
+        for &id in ids.iter() {
+            self.remove_word(id, weight);
+        }
+        let unique_ids: HashSet<&WordId> = ids.iter().collect();
+        for id in unique_ids {
+            // models serialized before document counts were kept have none
+            let forgotten = match self.document_counts.get_mut(id) {
+                Some(count) => {
+                    *count -= weight;
+                    is_removed(*count, weight)
+                },
+                None => false,
+            };
+            if forgotten {
+                self.document_counts.remove(id);
+            }
+        }
+        self.num_examples -= weight;
+        if self.words.is_empty() {
+            self.num_words = 0.0;
+        }
+    }
+
+    // drops every count of the word
+    fn forget_word(&mut self, id: WordId) {
+        if let Some((count, _)) = self.words.remove(&id) {
+            self.num_words -= count;
+        }
+        self.document_counts.remove(&id);
+        self.complement_probabilities.remove(&id);
+        self.word_scores.remove(&id);
+    }
+
+    fn remove_word(&mut self, id: WordId, weight: f64) {
+        self.num_words -= weight;
+        let forgotten = {
+            let entry = self.words.get_mut(&id).unwrap();
+            entry.0 -= weight;
+            is_removed(entry.0, weight)
+        };
+        if forgotten {
+            self.words.remove(&id);
+        }
+    }
+
+    // the total weight of all of the words seen with this classification
+    fn total_weight(&self, parameters: &Parameters) -> f64 {
+        if parameters.is_uniform() {
+            return self.num_words;
+        }
+        self.words.iter()
+            .map(|(&id, &(count, _))| count * parameters.weight(id))
+            .sum()
+    }
+
+    // the probability of a word given this classification, computed from
+    // the counts. `total_weight` is only needed by the multinomial variant.
+    fn word_probability(&self, id: WordId, total_weight: f64, parameters: &Parameters) -> f64 {
+        let own_count = match self.words.get(&id) {
+            Some( &(count, _) ) => count * parameters.weight(id),
+            None => 0.0,
+        };
+        let count = match parameters.variant {
+            NaiveBayesVariant::Multinomial => own_count,
+            NaiveBayesVariant::Bernoulli =>
+                self.document_counts.get(&id).cloned().unwrap_or(0.0),
+            NaiveBayesVariant::Complement => {
+                let complement = parameters.complement.expect("complement counts missing");
+                complement.word_weights.get(&id).cloned().unwrap_or(0.0) - own_count
+            },
+        };
+        self.smoothed_probability(count, total_weight, parameters)
+    }
+
+    // the probability of any word that has not been seen with this classification
+    fn default_word_probability(&self, total_weight: f64, parameters: &Parameters) -> f64 {
+        self.smoothed_probability(0.0, total_weight, parameters)
+    }
+
+    fn smoothed_probability(&self, count: f64, total_weight: f64, parameters: &Parameters) -> f64 {
+        let smoothing = parameters.label_smoothing.get(&self.label).cloned().unwrap_or(parameters.smoothing);
+        match parameters.variant {
+            NaiveBayesVariant::Multinomial =>
+                (count + smoothing) /
+                    (total_weight + smoothing * parameters.vocab.len() as f64),
+            NaiveBayesVariant::Bernoulli =>
+                (count + smoothing) /
+                    (self.num_examples + 2.0 * smoothing),
+            NaiveBayesVariant::Complement => {
+                let complement = parameters.complement.expect("complement counts missing");
+                (count + smoothing) /
+                    (complement.total_weight - total_weight +
+                     smoothing * parameters.vocab.len() as f64)
+            },
+        }
+    }
+
+    // the prior probability of this classification
+    fn prior(&self, parameters: &Parameters) -> f64 {
+        let learned = self.num_examples / parameters.total_examples;
+        match *parameters.priors {
+            PriorMode::Learned => learned,
+            PriorMode::Uniform => 1.0 / parameters.num_labels as f64,
+            PriorMode::Custom(ref custom) => custom.get(&self.label).cloned().unwrap_or(learned),
+        }
+    }
+
+    // the Bernoulli log probability of a document with none of the words in
+    // the vocabulary
+    fn absent_log_probability(&self, total_weight: f64, parameters: &Parameters) -> f64 {
+        let default_word_probability = self.default_word_probability(total_weight, parameters);
+        let mut total = 0.0f64;
+        let mut num_seen = 0;
+        for &id in self.document_counts.keys() {
+            if parameters.vocab.contains(id) {
+                total += (1.0 - self.word_probability(id, total_weight, parameters)).ln();
+                num_seen += 1;
+            }
+        }
+        let num_unseen = parameters.vocab.len() - num_seen;
+        total + num_unseen as f64 * (1.0 - default_word_probability).ln()
+    }
+
+    // trains this classification
+    fn train(&mut self, parameters: &Parameters) {
+        let total_weight = self.total_weight(parameters);
+        // the probability of this classification
+        self.probability = self.prior(parameters);
+        // the probability of any word that has not been seen in a document
+        // labeled with this classification's label
+        self.default_word_probability = self.default_word_probability(total_weight, parameters);
+        if parameters.variant == NaiveBayesVariant::Bernoulli {
+            self.absent_log_probability = self.absent_log_probability(total_weight, parameters);
+        }
+        self.complement_probabilities.clear();
+        if let Some(complement) = parameters.complement {
+            for &id in complement.word_weights.keys() {
+                if parameters.vocab.contains(id) {
+                    let p = self.word_probability(id, total_weight, parameters);
+                    self.complement_probabilities.insert(id, p);
+                }
+            }
+        }
+
+        let mut probabilities = HashMap::new();
+        for &id in self.words.keys() {
+            if parameters.vocab.contains(id) {
+                probabilities.insert(id, self.word_probability(id, total_weight, parameters));
+            }
+        }
+        for (id, p_word_given_label) in probabilities {
+            self.words.get_mut(&id).unwrap().1 = p_word_given_label;
+        }
+        self.compute_word_scores(parameters.variant);
+    }
+
+    // takes the logarithms of the trained probabilities, see `word_scores`
+    fn compute_word_scores(&mut self, variant: NaiveBayesVariant) {
+        let score = |p: f64| match variant {
+            NaiveBayesVariant::Multinomial => p.ln(),
+            NaiveBayesVariant::Bernoulli => p.ln() - (1.0 - p).ln(),
+            NaiveBayesVariant::Complement => -p.ln(),
+        };
+        self.log_prior = self.probability.ln();
+        self.default_word_score = score(self.default_word_probability);
+        self.word_scores = match variant {
+            NaiveBayesVariant::Complement => self.complement_probabilities.iter()
+                .map(|(&id, &p)| (id, score(p)))
+                .collect(),
+            _ => self.words.iter().map(|(&id, &(_, p))| (id, score(p))).collect(),
+        };
+    }
+
+    // retrieves the probability of the document given this classification
+    // times the probability of this classification
+    fn score_document(&self, document: &[(WordId, f64)], unknown: f64, parameters: &Parameters) -> f64 {
+        let word_score = |id: WordId| self.word_scores.get(&id).cloned().unwrap_or(self.default_word_score);
+        let total = match parameters.variant {
+            // start from every word being absent and then swap in the
+            // probability of each word that is present, however often
+            NaiveBayesVariant::Bernoulli => {
+                let mut total = self.absent_log_probability;
+                for &(id, _) in document.iter() {
+                    total += word_score(id);
+                }
+                total
+            },
+            // for the complement variant, the less likely the document is
+            // under every other classification, the better it fits this one
+            _ => {
+                let mut total = 0.0f64;
+                for &(id, count) in document.iter() {
+                    total += count * word_score(id);
+                }
+                total
+            },
+        } + self.unknown_score(unknown, self.default_word_probability, parameters);
+        match parameters.variant {
+            // the complement variant does not use priors
+            NaiveBayesVariant::Complement => total,
+            _ => self.log_prior + total,
+        }
+    }
+
+    // same as score_document but computes the probabilities from the word
+    // counts, so this classification does not need to be trained first
+    fn score_document_online(&self, document: &[(WordId, f64)], unknown: f64, parameters: &Parameters) -> f64 {
+        let total_weight = match parameters.variant {
+            NaiveBayesVariant::Bernoulli => 0.0,
+            _ => self.total_weight(parameters),
+        };
+        let total = match parameters.variant {
+            NaiveBayesVariant::Multinomial => {
+                let mut total = 0.0f64;
+                for &(id, count) in document.iter() {
+                    let p = self.word_probability(id, total_weight, parameters);
+                    total += count * p.ln();
+                }
+                total
+            },
+            NaiveBayesVariant::Bernoulli => {
+                let mut total = self.absent_log_probability(total_weight, parameters);
+                for &(id, _) in document.iter() {
+                    let p = self.word_probability(id, total_weight, parameters);
+                    total += p.ln() - (1.0 - p).ln();
+                }
+                total
+            },
+            NaiveBayesVariant::Complement => {
+                let mut total = 0.0f64;
+                for &(id, count) in document.iter() {
+                    let p = self.word_probability(id, total_weight, parameters);
+                    total -= count * p.ln();
+                }
+                total
+            },
+        } + self.unknown_score(unknown, self.default_word_probability(total_weight, parameters), parameters);
+        match parameters.variant {
+            NaiveBayesVariant::Complement => total,
+            _ => self.prior(parameters).ln() + total,
+        }
+    }
+
+    // the score of `unknown` words that are not in the vocabulary, given the
+    // probability of a word never seen with this classification
+    fn unknown_score(&self, unknown: f64, default_probability: f64, parameters: &Parameters) -> f64 {
+        match parameters.unknown_words {
+            UnknownWordPolicy::Ignore => 0.0,
+            UnknownWordPolicy::PenalizePerToken(penalty) => -penalty * unknown,
+            UnknownWordPolicy::DefaultProbability => match parameters.variant {
+                NaiveBayesVariant::Multinomial => unknown * parameters.unknown_weight() * default_probability.ln(),
+                // an unknown word is not part of the absent words' baseline
+                NaiveBayesVariant::Bernoulli => unknown * default_probability.ln(),
+                NaiveBayesVariant::Complement => -unknown * parameters.unknown_weight() * default_probability.ln(),
+            },
+        }
+    }
+
+    // the terms of score_document (or score_document_online) for a document,
+    // one per word
+    fn explain_document(&self, document: &[WordId], unknown: &[&str], parameters: &Parameters, online: bool)
+        -> LabelExplanation
+    {
+        let total_weight = if online && parameters.variant != NaiveBayesVariant::Bernoulli {
+            self.total_weight(parameters)
+        } else {
+            0.0
+        };
+        let word_probability = |id: WordId| {
+            if online {
+                return self.word_probability(id, total_weight, parameters);
+            }
+            let trained = match parameters.variant {
+                NaiveBayesVariant::Complement => self.complement_probabilities.get(&id).cloned(),
+                _ => self.words.get(&id).map(|&(_, p)| p),
+            };
+            trained.unwrap_or(self.default_word_probability)
+        };
+
+        let mut baseline = 0.0f64;
+        let mut contributions = Vec::new();
+        match parameters.variant {
+            NaiveBayesVariant::Multinomial => {
+                for &id in document.iter() {
+                    let contribution = parameters.weight(id) * word_probability(id).ln();
+                    contributions.push((parameters.vocab.word(id).to_string(), contribution));
+                }
+            },
+            NaiveBayesVariant::Bernoulli => {
+                baseline = if online {
+                    self.absent_log_probability(total_weight, parameters)
+                } else {
+                    self.absent_log_probability
+                };
+                let mut seen = HashSet::new();
+                for &id in document.iter() {
+                    if seen.insert(id) {
+                        let p = word_probability(id);
+                        contributions.push((parameters.vocab.word(id).to_string(), p.ln() - (1.0 - p).ln()));
+                    }
+                }
+            },
+            NaiveBayesVariant::Complement => {
+                for &id in document.iter() {
+                    let contribution = -parameters.weight(id) * word_probability(id).ln();
+                    contributions.push((parameters.vocab.word(id).to_string(), contribution));
+                }
+            },
+        }
+
+        if parameters.unknown_words != UnknownWordPolicy::Ignore {
+            let default_probability = if online {
+                self.default_word_probability(total_weight, parameters)
+            } else {
+                self.default_word_probability
+            };
+            for word in unknown.iter() {
+                let contribution = self.unknown_score(1.0, default_probability, parameters);
+                contributions.push((word.to_string(), contribution));
+            }
+        }
+
+        let prior = match parameters.variant {
+            NaiveBayesVariant::Complement => 0.0,
+            _ if online => self.prior(parameters).ln(),
+            _ => self.probability.ln(),
+        };
+        let score = prior + baseline + contributions.iter().map(|&(_, c)| c).sum::<f64>();
+        LabelExplanation {
+            label: self.label.clone(),
+            prior,
+            baseline,
+            contributions,
+            score,
+            posterior: 0.0,
+        }
+    }
+
+    fn encode<'a>(&'a self, vocab: &'a Vocabulary) -> EncodedClassification<&'a str> {
+        EncodedClassification {
+            label: &self.label,
+            num_examples: self.num_examples,
+            num_words: self.num_words,
+            probability: self.probability,
+            default_word_probability: self.default_word_probability,
+            absent_log_probability: self.absent_log_probability,
+            words: self.words.iter().map(|(&id, &entry)| (vocab.word(id), entry)).collect(),
+            document_counts: self.document_counts.iter()
+                .map(|(&id, &count)| (vocab.word(id), count))
+                .collect(),
+            complement_probabilities: self.complement_probabilities.iter()
+                .map(|(&id, &p)| (vocab.word(id), p))
+                .collect(),
+        }
+    }
+
+    // words missing from the vocabulary are dropped, the classifier never
+    // looks them up
+    fn decode(encoded: EncodedClassification<String>, vocab: &Vocabulary, variant: NaiveBayesVariant)
+        -> Classification
+    {
+        let mut classification = Classification {
+            label: encoded.label,
+            num_examples: encoded.num_examples,
+            num_words: encoded.num_words,
+            probability: encoded.probability,
+            default_word_probability: encoded.default_word_probability,
+            absent_log_probability: encoded.absent_log_probability,
+            words: encoded.words.into_iter()
+                .filter_map(|(word, entry)| vocab.id(&word).map(|id| (id, entry)))
+                .collect(),
+            document_counts: encoded.document_counts.into_iter()
+                .filter_map(|(word, count)| vocab.id(&word).map(|id| (id, count)))
+                .collect(),
+            complement_probabilities: encoded.complement_probabilities.into_iter()
+                .filter_map(|(word, p)| vocab.id(&word).map(|id| (id, p)))
+                .collect(),
+            log_prior: 0.0f64,
+            word_scores: HashMap::new(),
+            default_word_score: 0.0f64,
+        };
+        classification.compute_word_scores(variant);
+        classification
+    }
+}
+
+// turns the tokens of a document into features, the empty tokens left
+// between runs of whitespace are not features. Single words are borrowed
+// from the tokens rather than copied.
+pub(crate) fn features<'a, S: AsRef<str>>(tokens: &'a [S], stop_words: &HashSet<String>,
+                                          (min, max): (usize, usize), skip_window: usize,
+                                          hash_buckets: Option<usize>, max_count: Option<usize>)
+                                          -> Vec<Cow<'a, str>> {
+    let tokens: Vec<&str> = tokens.iter()
+        .map(AsRef::as_ref)
+        .filter(|token| !token.is_empty() && !stop_words.contains(*token))
+        .collect();
+    let skipping = if skip_window > 0 { skip_grams(&tokens, skip_window) } else { Vec::new() };
+    let mut features: Vec<Cow<str>> = if min == 1 && max == 1 {
+        tokens.into_iter().map(Cow::Borrowed).collect()
+    } else {
+        ngrams(&tokens, min, max).into_iter().map(Cow::Owned).collect()
+    };
+    features.extend(skipping.into_iter().map(Cow::Owned));
+    let mut features = match hash_buckets {
+        Some(num_buckets) => features.iter().map(|feature| Cow::Owned(bucket(feature, num_buckets))).collect(),
+        None => features,
+    };
+    if let Some(max_count) = max_count {
+        let mut counts: HashMap<Cow<str>, usize> = HashMap::new();
+        features.retain(|feature| {
+            let count = counts.entry(feature.clone()).or_insert(0);
+            *count += 1;
+            *count <= max_count
+        });
+    }
+    features
+}
+
+// aggregates the words of a document into the count of each distinct word
+// times its weight, in order of id, so a long document is scored with one
+// term per distinct word rather than one per occurrence
+fn weighted_counts(mut ids: Vec<WordId>, parameters: &Parameters) -> Vec<(WordId, f64)> {
+    ids.sort_unstable();
+    let mut counts: Vec<(WordId, f64)> = Vec::new();
+    for id in ids {
+        match counts.last_mut() {
+            Some(&mut (last, ref mut count)) if last == id => *count += 1.0,
+            _ => counts.push((id, 1.0)),
+        }
+    }
+    for &mut (id, ref mut count) in counts.iter_mut() {
+        *count *= parameters.weight(id);
+    }
+    counts
+}
+
+// whether a count a document's weight was subtracted from is left with
+// nothing but rounding error
+fn is_removed(count: f64, weight: f64) -> bool {
+    count <= REMOVAL_TOLERANCE * weight
+}
+
+// sorts the scored words from highest to lowest score (ties in order of
+// the words) and keeps the first n
+fn ranked(mut words: Vec<(String, f64)>, n: usize) -> Vec<(String, f64)> {
+    words.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    words.truncate(n);
+    words
+}
+
+// the label of the highest scoring classification
+fn best_label(scores: Vec<(&Classification, f64)>) -> String {
+    let mut max_score = f64::NEG_INFINITY;
+    let mut max_classification = None;
+
+    for (classification, score) in scores {
+        if score > max_score {
+            max_classification = Some(classification);
+            max_score = score;
+        }
+    }
+
+    max_classification.expect("no classification found").label.clone()
+}
+
+// normalizes the log scores, multiplied by the calibration scale, into
+// posterior probabilities, shifting by the largest score first so that
+// exponentiating can't underflow to zero
+fn posteriors(scores: Vec<(&Classification, f64)>, scale: f64) -> Vec<(String, f64)> {
+    let max_score = scores.iter().fold(f64::NEG_INFINITY, |acc, &(_, s)| acc.max(s));
+    let total: f64 = scores.iter().map(|&(_, s)| (scale * (s - max_score)).exp()).sum();
+    scores.into_iter()
+        .map(|(classification, s)| (classification.label.clone(), (scale * (s - max_score)).exp() / total))
+        .collect()
+}
+
+// the calibration scale that minimizes the log loss of the posteriors of
+// the true labels, given the log scores of every label for each example and
+// the index of its true label. The derivative of the loss with respect to
+// the scale is the expected score under the posteriors minus the true
+// label's score, summed over the examples, and it only grows with the scale,
+// so its root is found by bisection.
+fn fit_calibration(examples: &[(Vec<f64>, usize)]) -> f64 {
+    let slope = |scale: f64| -> f64 {
+        examples.iter().map(|(scores, truth)| {
+            let max_score = scores.iter().fold(f64::NEG_INFINITY, |acc, &s| acc.max(s));
+            let weights: Vec<f64> = scores.iter().map(|&s| (scale * (s - max_score)).exp()).collect();
+            let total: f64 = weights.iter().sum();
+            // labels that can't be scored have no weight and add nothing
+            let expected: f64 = weights.iter().zip(scores.iter())
+                .filter(|&(&weight, _)| weight > 0.0)
+                .map(|(&weight, &s)| weight / total * (s - max_score))
+                .sum();
+            expected - (scores[*truth] - max_score)
+        }).sum()
+    };
+
+    // widen the range until it holds the root, the loss keeps falling as the
+    // scale grows when the examples are perfectly separated
+    let (mut low, mut high) = (0.0, 1.0);
+    while slope(high) < 0.0 && high < MAX_CALIBRATION {
+        low = high;
+        high *= 2.0;
+    }
+    for _ in 0..100 {
+        let middle = (low + high) / 2.0;
+        if slope(middle) < 0.0 {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    (low + high) / 2.0
+}
+
+// the original, deprecated normalization of the log scores
+fn probabilities(scores: Vec<(&Classification, f64)>) -> Vec<(String, f64)> {
+    let all_probs: Vec<(String, f64)> = scores.into_iter()
+        .map(|(classification, score)| (classification.label.clone(), score))
+        .collect();
+
+    let total_prob = all_probs.iter()
+        .map(|&(_, s)| s)
+        .fold(0.0, |acc, s| acc + s);
+
+    all_probs.into_iter().map(|(c, s)| (c, 1.0 - s/total_prob) ).collect()
+}
+
+// roughly how many bytes a map keyed by strings takes up, given the heap
+// size of each value
+fn map_size<V, F: Fn(&V) -> usize>(map: &HashMap<String, V>, value_size: F) -> usize {
+    map.capacity() * mem::size_of::<(String, V)>()
+        + map.iter().map(|(key, value)| key.len() + value_size(value)).sum::<usize>()
+}
+
+// roughly how many bytes a set of strings takes up
+fn set_size(set: &HashSet<String>) -> usize {
+    set.capacity() * mem::size_of::<String>() + set.iter().map(|word| word.len()).sum::<usize>()
+}
+
+// maps every item on a pool of scoped threads, one contiguous chunk of
+// items per thread, keeping the results in the order of the items
+pub(crate) fn parallel_map<I, O, F>(items: &[I], f: F) -> Vec<O>
+    where I: Sync, O: Send, F: Fn(&I) -> O + Sync
+{
+    let num_threads = thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    if num_threads < 2 || items.len() < 2 {
+        return items.iter().map(f).collect();
+    }
+
+    let chunk_size = items.len().div_ceil(num_threads);
+    let f = &f;
+    thread::scope(|scope| {
+        let handles: Vec<_> = items.chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<O>>()))
+            .collect();
+        handles.into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect()
+    })
+}
@@ -0,0 +1,41 @@
+/// Watches a classifier while it is being trained, for showing progress
+/// bars or logging throughput. Every method does nothing unless it is
+/// overridden.
+///
+/// Observers are passed to the `_observed` methods of the classifiers,
+/// e.g. `NaiveBayes::add_documents_observed` and `NaiveBayes::train_observed`.
+///
+/// ```rust
+/// use classifier::{NaiveBayes, TrainingObserver};
+///
+/// struct Progress {
+///     documents: usize,
+/// }
+///
+/// impl TrainingObserver for Progress {
+///     fn on_document_added(&mut self, _label: &str) {
+///         self.documents += 1;
+///     }
+///
+///     fn on_train_progress(&mut self, fraction: f64) {
+///         println!("{} documents, {:.0}% trained", self.documents, fraction * 100.0);
+///     }
+/// }
+///
+/// let mut progress = Progress { documents: 0 };
+/// let mut nb = NaiveBayes::new();
+/// nb.add_documents_observed(vec![("pork belly", "meat"), ("kale okra", "veggie")], &mut progress);
+/// nb.train_observed(&mut progress);
+/// assert_eq!(progress.documents, 2);
+/// ```
+pub trait TrainingObserver {
+    /// Called after each document is added, with the document's label
+    fn on_document_added(&mut self, _label: &str) {}
+
+    /// Called as training goes on with the fraction of it done so far,
+    /// ending with 1.0
+    fn on_train_progress(&mut self, _fraction: f64) {}
+}
+
+/// Observes nothing
+impl TrainingObserver for () {}
@@ -0,0 +1,156 @@
+// encodes a trained naive Bayes model as an ONNX graph, see
+// `NaiveBayes::export_onnx`. ONNX models are protocol buffers, and the few
+// messages needed here are written by hand rather than pulling in a protobuf
+// code generator. The field numbers are those of onnx.proto.
+
+use serde_json;
+use naive_bayes::ScoreTable;
+
+// the ONNX IR version and the default operator set version the graph uses
+const IR_VERSION: u64 = 8;
+const OPSET_VERSION: u64 = 13;
+
+// TensorProto.DataType
+const FLOAT: u64 = 1;
+const INT64: u64 = 7;
+// AttributeProto.AttributeType
+const ATTRIBUTE_INT: u64 = 2;
+
+// the protobuf wire types
+const VARINT: u32 = 0;
+const LENGTH_DELIMITED: u32 = 2;
+
+// a protobuf message being written
+#[derive(Default)]
+struct Message {
+    bytes: Vec<u8>,
+}
+
+impl Message {
+
+    fn varint(&mut self, field: u32, value: u64) -> &mut Message {
+        self.key(field, VARINT);
+        write_varint(&mut self.bytes, value);
+        self
+    }
+
+    fn bytes(&mut self, field: u32, value: &[u8]) -> &mut Message {
+        self.key(field, LENGTH_DELIMITED);
+        write_varint(&mut self.bytes, value.len() as u64);
+        self.bytes.extend_from_slice(value);
+        self
+    }
+
+    fn string(&mut self, field: u32, value: &str) -> &mut Message {
+        self.bytes(field, value.as_bytes())
+    }
+
+    fn message(&mut self, field: u32, value: &Message) -> &mut Message {
+        self.bytes(field, &value.bytes)
+    }
+
+    fn key(&mut self, field: u32, wire_type: u32) {
+        write_varint(&mut self.bytes, u64::from(field << 3 | wire_type));
+    }
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+// the model as a serialized ModelProto: a row of feature counts, one column
+// per word and a last one for the features that are not in the vocabulary,
+// is multiplied by the score matrix and added to the biases, giving the log
+// scores, whose softmax is the posteriors and whose argmax is the label
+pub(crate) fn encode(table: &ScoreTable, distinct: bool) -> Vec<u8> {
+    let num_labels = table.labels.len() as u64;
+    let num_features = table.words.len() as u64 + 1;
+
+    let scores: Vec<f64> = table.words.iter()
+        .flat_map(|(_, terms)| terms.iter().cloned())
+        .chain(table.unknown.iter().cloned())
+        .collect();
+    let mut graph = Message::default();
+    graph.string(2, "naive_bayes")
+        .message(1, &node("MatMul", &["features", "weights"], "word_scores", &[]))
+        .message(1, &node("Add", &["word_scores", "biases"], "scores", &[]))
+        .message(1, &node("Softmax", &["scores"], "probabilities", &[("axis", 1)]))
+        .message(1, &node("ArgMax", &["scores"], "label", &[("axis", 1), ("keepdims", 0)]))
+        .message(5, &tensor("weights", &[num_features, num_labels], &scores))
+        .message(5, &tensor("biases", &[num_labels], &table.biases))
+        .message(11, &value_info("features", FLOAT, &[None, Some(num_features)]))
+        .message(12, &value_info("label", INT64, &[None]))
+        .message(12, &value_info("probabilities", FLOAT, &[None, Some(num_labels)]))
+        .message(12, &value_info("scores", FLOAT, &[None, Some(num_labels)]));
+
+    let words: Vec<&str> = table.words.iter().map(|(word, _)| word.as_str()).collect();
+    let mut opset = Message::default();
+    opset.string(1, "").varint(2, OPSET_VERSION);
+    let mut model = Message::default();
+    model.varint(1, IR_VERSION)
+        .string(2, env!("CARGO_PKG_NAME"))
+        .string(3, env!("CARGO_PKG_VERSION"))
+        .message(8, &opset)
+        .message(7, &graph)
+        .message(14, &property("labels", &serde_json::to_string(&table.labels).unwrap()))
+        .message(14, &property("vocabulary", &serde_json::to_string(&words).unwrap()))
+        .message(14, &property("binary_features", if distinct { "true" } else { "false" }));
+    model.bytes
+}
+
+// a NodeProto with integer attributes
+fn node(op_type: &str, inputs: &[&str], output: &str, attributes: &[(&str, i64)]) -> Message {
+    let mut node = Message::default();
+    for input in inputs.iter() {
+        node.string(1, input);
+    }
+    node.string(2, output).string(3, output).string(4, op_type);
+    for &(name, value) in attributes.iter() {
+        let mut attribute = Message::default();
+        attribute.string(1, name).varint(3, value as u64).varint(20, ATTRIBUTE_INT);
+        node.message(5, &attribute);
+    }
+    node
+}
+
+// a TensorProto of floats, rounded to single precision
+fn tensor(name: &str, dims: &[u64], values: &[f64]) -> Message {
+    let mut tensor = Message::default();
+    for &dim in dims.iter() {
+        tensor.varint(1, dim);
+    }
+    let raw: Vec<u8> = values.iter().flat_map(|&value| (value as f32).to_le_bytes()).collect();
+    tensor.varint(2, FLOAT).string(8, name).bytes(9, &raw);
+    tensor
+}
+
+// a ValueInfoProto of a tensor, None being a dimension of any size
+fn value_info(name: &str, elem_type: u64, dims: &[Option<u64>]) -> Message {
+    let mut shape = Message::default();
+    for dim in dims.iter() {
+        let mut dimension = Message::default();
+        match *dim {
+            Some(size) => dimension.varint(1, size),
+            None => dimension.string(2, "batch"),
+        };
+        shape.message(1, &dimension);
+    }
+    let mut tensor_type = Message::default();
+    tensor_type.varint(1, elem_type).message(2, &shape);
+    let mut type_proto = Message::default();
+    type_proto.message(1, &tensor_type);
+    let mut value_info = Message::default();
+    value_info.string(1, name).message(2, &type_proto);
+    value_info
+}
+
+// a StringStringEntryProto of the model's metadata
+fn property(key: &str, value: &str) -> Message {
+    let mut property = Message::default();
+    property.string(1, key).string(2, value);
+    property
+}
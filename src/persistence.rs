@@ -0,0 +1,135 @@
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read};
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json;
+#[cfg(feature = "gzip")]
+use flate2;
+#[cfg(feature = "zstandard")]
+use zstd;
+use error::ClassifierError;
+
+// the first bytes of compressed files, so loading can tell them apart
+static GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+static ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
+/// How a model file is compressed
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Compression {
+    /// Plain JSON (the default)
+    #[default]
+    None,
+    /// Gzip compressed JSON. Requires the `gzip` feature.
+    #[cfg(feature = "gzip")]
+    Gzip,
+    /// Zstandard compressed JSON. Requires the `zstandard` feature.
+    #[cfg(feature = "zstandard")]
+    Zstd,
+}
+
+// writes the model as JSON to a temporary file next to `path` and then
+// renames it over `path`, so a failed save never leaves a partial model
+pub fn save<S: Serialize>(model: &S, path: &Path, compression: Compression) -> Result<(), ClassifierError> {
+    let temp_path = temp_path(path);
+    let result = write_file(model, &temp_path, compression)
+        .and_then(|_| fs::rename(&temp_path, path).map_err(io_error));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
+
+// reads a JSON model, decompressing it first if it is compressed
+pub fn load<D: DeserializeOwned>(path: &Path) -> Result<D, ClassifierError> {
+    let mut file = BufReader::new(File::open(path).map_err(io_error)?);
+    let mut magic = Vec::with_capacity(4);
+    (&mut file).take(4).read_to_end(&mut magic).map_err(io_error)?;
+    let reader = io::Cursor::new(magic.clone()).chain(file);
+
+    if magic.starts_with(GZIP_MAGIC) {
+        return load_gzip(reader);
+    }
+    if magic.starts_with(ZSTD_MAGIC) {
+        return load_zstd(reader);
+    }
+    serde_json::from_reader(reader).map_err(decode_error)
+}
+
+fn write_file<S: Serialize>(model: &S, path: &Path, compression: Compression) -> Result<(), ClassifierError> {
+    let file = File::create(path).map_err(io_error)?;
+    let mut writer = BufWriter::new(file);
+    match compression {
+        Compression::None => serde_json::to_writer(&mut writer, model).map_err(encode_error)?,
+        #[cfg(feature = "gzip")]
+        Compression::Gzip => {
+            let mut encoder = flate2::write::GzEncoder::new(&mut writer, flate2::Compression::default());
+            serde_json::to_writer(&mut encoder, model).map_err(encode_error)?;
+            encoder.finish().map_err(io_error)?;
+        },
+        #[cfg(feature = "zstandard")]
+        Compression::Zstd => {
+            let mut encoder = zstd::Encoder::new(&mut writer, 0).map_err(io_error)?;
+            serde_json::to_writer(&mut encoder, model).map_err(encode_error)?;
+            encoder.finish().map_err(io_error)?;
+        },
+    }
+    let file = writer.into_inner().map_err(|e| io_error(e.into_error()))?;
+    file.sync_all().map_err(io_error)
+}
+
+#[cfg(feature = "gzip")]
+fn load_gzip<R: Read, D: DeserializeOwned>(reader: R) -> Result<D, ClassifierError> {
+    serde_json::from_reader(flate2::read::GzDecoder::new(reader)).map_err(decode_error)
+}
+
+#[cfg(not(feature = "gzip"))]
+fn load_gzip<R: Read, D: DeserializeOwned>(_reader: R) -> Result<D, ClassifierError> {
+    Err(ClassifierError::Decode("loading a gzip compressed model requires the gzip feature".to_string()))
+}
+
+#[cfg(feature = "zstandard")]
+fn load_zstd<R: Read, D: DeserializeOwned>(reader: R) -> Result<D, ClassifierError> {
+    let decoder = zstd::Decoder::new(reader).map_err(io_error)?;
+    serde_json::from_reader(decoder).map_err(decode_error)
+}
+
+#[cfg(not(feature = "zstandard"))]
+fn load_zstd<R: Read, D: DeserializeOwned>(_reader: R) -> Result<D, ClassifierError> {
+    Err(ClassifierError::Decode("loading a zstd compressed model requires the zstandard feature".to_string()))
+}
+
+// a hidden file in the same directory, so the rename stays on one file system
+fn temp_path(path: &Path) -> PathBuf {
+    let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+    path.with_file_name(format!(".{}.{}.tmp", name, process_id()))
+}
+
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+fn process_id() -> u32 {
+    std::process::id()
+}
+
+// the browser has no processes (nor files), and `process::id` panics there
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+fn process_id() -> u32 {
+    0
+}
+
+fn io_error(error: io::Error) -> ClassifierError {
+    ClassifierError::Io(error.to_string())
+}
+
+fn encode_error(error: serde_json::Error) -> ClassifierError {
+    if error.is_io() {
+        return ClassifierError::Io(error.to_string());
+    }
+    ClassifierError::Encode(error.to_string())
+}
+
+fn decode_error(error: serde_json::Error) -> ClassifierError {
+    if error.is_io() {
+        return ClassifierError::Io(error.to_string());
+    }
+    ClassifierError::Decode(error.to_string())
+}
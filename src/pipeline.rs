@@ -0,0 +1,315 @@
+use std::path::Path;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::Error;
+use serde_json;
+use collocations::Collocations;
+use error::ClassifierError;
+use naive_bayes::{Classifier, FeatureWeighting, NaiveBayesVariant};
+use persistence::{self, Compression};
+use preprocessing::Preprocessor;
+use text_classifier::TextClassifier;
+use tokenizer::{CharNgramTokenizer, Tokenizer, WhitespaceTokenizer};
+
+/// How a `Pipeline` breaks documents into tokens. Unlike a tokenizer of
+/// the classifier's own, it is serialized along with the pipeline.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum TokenizerStage {
+    /// Splits documents on whitespace, see `WhitespaceTokenizer` (the
+    /// default)
+    #[default]
+    Whitespace,
+    /// The character n-grams of every length from the first number to the
+    /// second of each word, see `CharNgramTokenizer`
+    CharNgrams(usize, usize),
+    /// Splits documents on whitespace and merges the collocations, see
+    /// `CollocationTokenizer`
+    Collocations(Collocations),
+}
+
+impl TokenizerStage {
+
+    // checks that the stage can tokenize
+    fn check(&self) -> Result<(), ClassifierError> {
+        if let TokenizerStage::CharNgrams(min, max) = *self {
+            CharNgramTokenizer::new(min, max)?;
+        }
+        Ok(())
+    }
+}
+
+impl Tokenizer for TokenizerStage {
+    fn tokenize(&self, document: &str) -> Vec<String> {
+        match *self {
+            TokenizerStage::Whitespace => WhitespaceTokenizer.tokenize(document),
+            // pipelines only hold valid ranges
+            TokenizerStage::CharNgrams(min, max) =>
+                CharNgramTokenizer::new(min, max).map(|tokenizer| tokenizer.tokenize(document)).unwrap_or_default(),
+            TokenizerStage::Collocations(ref collocations) => collocations.merge(&WhitespaceTokenizer.tokenize(document)),
+        }
+    }
+}
+
+/// A naive Bayes classifier together with every stage that turns text into
+/// its features, saved and loaded as a unit, so documents are always
+/// classified exactly the way the training documents were read
+///
+/// A document goes through the stages in order: the preprocessor, the
+/// tokenizer, the token filters of the preprocessor and the stop words,
+/// and the feature extractor (n-grams, skip-grams, feature hashing, and
+/// word count limits), before the classifier counts or scores it. A plain
+/// `NaiveBayes` does the same, but its tokenizer is not serialized, so a
+/// loaded classifier has to be given the right one by hand. The sanitizer
+/// is not serialized either, and has to be set again on `classifier_mut`
+/// after loading.
+///
+/// ```rust
+/// use classifier::{Pipeline, Preprocessor, TokenizerStage};
+///
+/// let mut pipeline = Pipeline::builder()
+///     .preprocessor(Preprocessor::builder().lowercase(true).build().unwrap())
+///     .tokenizer(TokenizerStage::CharNgrams(3, 4))
+///     .stop_words(vec!["the"])
+///     .build()
+///     .unwrap();
+/// pipeline.add_document("Pork Belly Ribs", "meat");
+/// pipeline.add_document("Kale Okra Spinach", "veggie");
+/// pipeline.train();
+///
+/// let loaded = Pipeline::from_json(&pipeline.to_json()).unwrap();
+/// assert_eq!(loaded.classify("the porky ribs").unwrap(), "meat");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Pipeline {
+    classifier: Classifier<TokenizerStage>,
+}
+
+/// Builds a `Pipeline` stage by stage, every stage left out does nothing
+/// (or, for the tokenizer, splits on whitespace)
+#[derive(Debug, Clone, Default)]
+pub struct PipelineBuilder {
+    preprocessor: Preprocessor,
+    tokenizer: TokenizerStage,
+    stop_words: Vec<String>,
+    ngram_range: Option<(usize, usize)>,
+    skip_window: usize,
+    hash_buckets: Option<usize>,
+    max_word_count: Option<usize>,
+    weighting: FeatureWeighting,
+    variant: NaiveBayesVariant,
+}
+
+impl PipelineBuilder {
+
+    /// Preprocesses documents before they are tokenized and filters their
+    /// tokens afterwards, see `NaiveBayes::set_preprocessor`
+    pub fn preprocessor(mut self, preprocessor: Preprocessor) -> PipelineBuilder {
+        self.preprocessor = preprocessor;
+        self
+    }
+
+    /// Breaks documents into tokens with the given stage
+    pub fn tokenizer(mut self, tokenizer: TokenizerStage) -> PipelineBuilder {
+        self.tokenizer = tokenizer;
+        self
+    }
+
+    /// Drops the given tokens, see `NaiveBayes::set_stop_words`
+    pub fn stop_words<I, S>(mut self, stop_words: I) -> PipelineBuilder
+        where I: IntoIterator<Item = S>, S: AsRef<str>
+    {
+        self.stop_words = stop_words.into_iter().map(|word| word.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Uses the n-grams of tokens from `min` to `max` as features, see
+    /// `NaiveBayes::set_ngram_range`
+    pub fn ngram_range(mut self, min: usize, max: usize) -> PipelineBuilder {
+        self.ngram_range = Some((min, max));
+        self
+    }
+
+    /// Adds skip-grams skipping up to `window` tokens as features, see
+    /// `NaiveBayes::set_skip_grams`
+    pub fn skip_grams(mut self, window: usize) -> PipelineBuilder {
+        self.skip_window = window;
+        self
+    }
+
+    /// Hashes the features into `num_buckets` buckets, see
+    /// `NaiveBayes::set_hashed_features`
+    pub fn hashed_features(mut self, num_buckets: usize) -> PipelineBuilder {
+        self.hash_buckets = Some(num_buckets);
+        self
+    }
+
+    /// Counts a feature at most `max_count` times per document, see
+    /// `NaiveBayes::set_max_word_count`
+    pub fn max_word_count(mut self, max_count: usize) -> PipelineBuilder {
+        self.max_word_count = Some(max_count);
+        self
+    }
+
+    /// Weights the features, see `NaiveBayes::set_feature_weighting`
+    pub fn feature_weighting(mut self, weighting: FeatureWeighting) -> PipelineBuilder {
+        self.weighting = weighting;
+        self
+    }
+
+    /// Uses the given variant of naive Bayes, see `NaiveBayes::set_variant`
+    pub fn variant(mut self, variant: NaiveBayesVariant) -> PipelineBuilder {
+        self.variant = variant;
+        self
+    }
+
+    /// Builds the pipeline, failing as the classifier's setters would for
+    /// an invalid n-gram range, number of buckets, or word count limit, or
+    /// with `InvalidNgramRange` for an invalid character n-gram range
+    pub fn build(self) -> Result<Pipeline, ClassifierError> {
+        self.tokenizer.check()?;
+        let mut classifier = Classifier::with_tokenizer(self.tokenizer);
+        classifier.set_preprocessor(self.preprocessor);
+        classifier.set_stop_words(self.stop_words);
+        if let Some((min, max)) = self.ngram_range {
+            classifier.set_ngram_range(min, max)?;
+        }
+        classifier.set_skip_grams(self.skip_window);
+        if let Some(num_buckets) = self.hash_buckets {
+            classifier.set_hashed_features(num_buckets)?;
+        }
+        if let Some(max_count) = self.max_word_count {
+            classifier.set_max_word_count(max_count)?;
+        }
+        classifier.set_feature_weighting(self.weighting);
+        classifier.set_variant(self.variant);
+        Ok(Pipeline { classifier })
+    }
+}
+
+impl Pipeline {
+
+    /// Creates a pipeline that splits documents on whitespace and does
+    /// nothing else to them
+    pub fn new() -> Pipeline {
+        Pipeline { classifier: Classifier::with_tokenizer(TokenizerStage::Whitespace) }
+    }
+
+    /// Starts building a pipeline
+    pub fn builder() -> PipelineBuilder {
+        PipelineBuilder::default()
+    }
+
+    /// Gets the tokenizing stage
+    pub fn tokenizer(&self) -> &TokenizerStage {
+        self.classifier.tokenizer()
+    }
+
+    /// Gets the classifier at the end of the pipeline, which holds the
+    /// other stages
+    pub fn classifier(&self) -> &Classifier<TokenizerStage> {
+        &self.classifier
+    }
+
+    /// Gets the classifier at the end of the pipeline, e.g. to add
+    /// documents in other ways or change its smoothing
+    pub fn classifier_mut(&mut self) -> &mut Classifier<TokenizerStage> {
+        &mut self.classifier
+    }
+
+    /// Runs the document through the stages and adds it with the label
+    pub fn add_document(&mut self, document: &str, label: &str) {
+        self.classifier.add_document(document, label);
+    }
+
+    /// Trains the classifier on the documents that have been added
+    pub fn train(&mut self) {
+        self.classifier.train();
+    }
+
+    /// Runs the document through the stages and computes a classifying
+    /// label for it, failing as `NaiveBayes::classify` would
+    pub fn classify(&self, document: &str) -> Result<String, ClassifierError> {
+        self.classifier.classify(document)
+    }
+
+    /// Runs the document through the stages and gets every label and its
+    /// posterior probability, see `NaiveBayes::get_document_posteriors`
+    pub fn get_document_posteriors(&self, document: &str) -> Result<Vec<(String, f64)>, ClassifierError> {
+        self.classifier.get_document_posteriors(document)
+    }
+
+    /// Encodes the pipeline as a JSON string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("encoding JSON failed")
+    }
+
+    /// Builds a pipeline from a JSON string written by `to_json`
+    pub fn from_json(encoded: &str) -> Result<Pipeline, ClassifierError> {
+        serde_json::from_str(encoded).map_err(|e| ClassifierError::Decode(e.to_string()))
+    }
+
+    /// Saves the pipeline to a JSON file, see `NaiveBayes::save_to_file`
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ClassifierError> {
+        persistence::save(self, path.as_ref(), Compression::None)
+    }
+
+    /// Loads a pipeline from a file written by `save_to_file`
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Pipeline, ClassifierError> {
+        persistence::load(path.as_ref())
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Pipeline {
+        Pipeline::new()
+    }
+}
+
+impl TextClassifier for Pipeline {
+    fn add_document(&mut self, document: &str, label: &str) {
+        Pipeline::add_document(self, document, label);
+    }
+
+    fn train(&mut self) {
+        Pipeline::train(self);
+    }
+
+    fn classify(&self, document: &str) -> Result<String, ClassifierError> {
+        Pipeline::classify(self, document)
+    }
+
+    fn probabilities(&self, document: &str) -> Result<Vec<(String, f64)>, ClassifierError> {
+        self.get_document_posteriors(document)
+    }
+
+    fn labels(&self) -> Vec<String> {
+        self.classifier.get_labels()
+    }
+}
+
+// the encoded form of a pipeline: the tokenizing stage alongside the
+// classifier, which serializes the other stages itself
+#[derive(Serialize)]
+struct EncodedPipeline<'a> {
+    tokenizer: &'a TokenizerStage,
+    classifier: &'a Classifier<TokenizerStage>,
+}
+
+#[derive(Deserialize)]
+struct DecodedPipeline {
+    tokenizer: TokenizerStage,
+    classifier: Classifier,
+}
+
+impl Serialize for Pipeline {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        EncodedPipeline { tokenizer: self.tokenizer(), classifier: &self.classifier }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Pipeline {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Pipeline, D::Error> {
+        let decoded = DecodedPipeline::deserialize(deserializer)?;
+        decoded.tokenizer.check().map_err(D::Error::custom)?;
+        Ok(Pipeline { classifier: Classifier::retokenized(decoded.classifier, decoded.tokenizer) })
+    }
+}
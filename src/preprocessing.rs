@@ -0,0 +1,323 @@
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::Error;
+use unicode_normalization::UnicodeNormalization as Normalize;
+use unicode_normalization::char::is_combining_mark;
+use error::ClassifierError;
+use tokenizer::Tokenizer;
+
+/// A Unicode normalization form, see the Unicode standard annex #15
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum UnicodeNormalization {
+    /// Canonical composition, characters that are written differently but
+    /// mean the same thing (like "é" and "e" followed by a combining accent)
+    /// become the same
+    Nfc,
+    /// Compatibility composition, like `Nfc` but also folds compatibility
+    /// characters such as ligatures and full width forms ("ﬁ" becomes "fi")
+    Nfkc,
+}
+
+/// Keeps or drops the tokens of a document that match a regular expression,
+/// see `PreprocessorBuilder::token_filter`. The expression matches anywhere
+/// in a token unless it is anchored with `^` and `$`.
+#[derive(Debug, Clone)]
+pub struct TokenFilter {
+    include: bool,
+    regex: Regex,
+}
+
+#[derive(Serialize, Deserialize)]
+struct EncodedTokenFilter {
+    include: bool,
+    pattern: String,
+}
+
+impl TokenFilter {
+
+    /// Creates a filter that keeps only the tokens matching `pattern`
+    pub fn include(pattern: &str) -> Result<TokenFilter, ClassifierError> {
+        TokenFilter::new(true, pattern)
+    }
+
+    /// Creates a filter that drops the tokens matching `pattern`, e.g.
+    /// `^[0-9]+$` for pure numbers or `^@` for mentions
+    pub fn exclude(pattern: &str) -> Result<TokenFilter, ClassifierError> {
+        TokenFilter::new(false, pattern)
+    }
+
+    fn new(include: bool, pattern: &str) -> Result<TokenFilter, ClassifierError> {
+        let regex = Regex::new(pattern).map_err(|e| ClassifierError::InvalidPattern(e.to_string()))?;
+        Ok(TokenFilter { include, regex })
+    }
+
+    /// Whether the filter lets the token through
+    pub fn keeps(&self, token: &str) -> bool {
+        self.regex.is_match(token) == self.include
+    }
+}
+
+impl PartialEq for TokenFilter {
+    fn eq(&self, other: &TokenFilter) -> bool {
+        self.include == other.include && self.regex.as_str() == other.regex.as_str()
+    }
+}
+
+impl Serialize for TokenFilter {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        EncodedTokenFilter {
+            include: self.include,
+            pattern: self.regex.as_str().to_string(),
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TokenFilter {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<TokenFilter, D::Error> {
+        let encoded = EncodedTokenFilter::deserialize(deserializer)?;
+        TokenFilter::new(encoded.include, &encoded.pattern).map_err(D::Error::custom)
+    }
+}
+
+/// Text transformations applied to a document before it is tokenized, and
+/// filters applied to the tokens afterwards
+///
+/// The default preprocessor leaves documents unchanged. Build one that does
+/// something with `Preprocessor::builder()`:
+///
+/// ```rust
+/// use classifier::{Preprocessor, TokenFilter, UnicodeNormalization};
+///
+/// let preprocessor = Preprocessor::builder()
+///     .lowercase(true)
+///     .normalization(UnicodeNormalization::Nfkc)
+///     .strip_accents(true)
+///     .strip_punctuation(true)
+///     .token_filter(TokenFilter::exclude("^[0-9]+$").unwrap())
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(preprocessor.process("Crème Brûlée"), "creme brulee");
+/// let tokens = vec!["2".to_string(), "(fresh)".to_string(), "--".to_string(), "eggs!".to_string()];
+/// assert_eq!(preprocessor.filter_tokens(tokens), vec!["fresh", "eggs"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Preprocessor {
+    lowercase: bool,
+    normalization: Option<UnicodeNormalization>,
+    strip_accents: bool,
+    #[serde(default)]
+    strip_punctuation: bool,
+    #[serde(default)]
+    min_token_len: Option<usize>,
+    #[serde(default)]
+    max_token_len: Option<usize>,
+    #[serde(default)]
+    token_filters: Vec<TokenFilter>,
+}
+
+// the layout of a preprocessor in versions 1 and 2 of the binary format,
+// from before token filters
+#[cfg(feature = "binary")]
+#[derive(Deserialize)]
+pub(crate) struct PreprocessorV2 {
+    lowercase: bool,
+    normalization: Option<UnicodeNormalization>,
+    strip_accents: bool,
+}
+
+#[cfg(feature = "binary")]
+impl PreprocessorV2 {
+
+    // the preprocessor in the layout of the next versions, without filters
+    pub(crate) fn upgrade(self) -> PreprocessorV4 {
+        PreprocessorV4 {
+            lowercase: self.lowercase,
+            normalization: self.normalization,
+            strip_accents: self.strip_accents,
+            token_filters: Vec::new(),
+        }
+    }
+}
+
+// the layout of a preprocessor in versions 3 and 4 of the binary format,
+// from before punctuation stripping and token length limits
+#[cfg(feature = "binary")]
+#[derive(Deserialize)]
+pub(crate) struct PreprocessorV4 {
+    lowercase: bool,
+    normalization: Option<UnicodeNormalization>,
+    strip_accents: bool,
+    token_filters: Vec<TokenFilter>,
+}
+
+#[cfg(feature = "binary")]
+impl PreprocessorV4 {
+
+    // the preprocessor, with the steps it predates off
+    pub(crate) fn upgrade(self) -> Preprocessor {
+        Preprocessor {
+            lowercase: self.lowercase,
+            normalization: self.normalization,
+            strip_accents: self.strip_accents,
+            strip_punctuation: false,
+            min_token_len: None,
+            max_token_len: None,
+            token_filters: self.token_filters,
+        }
+    }
+}
+
+// punctuation outside of ASCII that is stripped along with ASCII punctuation
+static PUNCTUATION: &[char] = &['“', '”', '‘', '’', '«', '»', '…', '–', '—', '¿', '¡'];
+
+/// Builds a `Preprocessor`, every step is off until it is turned on
+#[derive(Debug, Clone, Default)]
+pub struct PreprocessorBuilder {
+    preprocessor: Preprocessor,
+}
+
+impl Preprocessor {
+
+    /// Creates a preprocessor that leaves documents unchanged
+    pub fn new() -> Preprocessor {
+        Preprocessor::default()
+    }
+
+    /// Starts building a preprocessor
+    pub fn builder() -> PreprocessorBuilder {
+        PreprocessorBuilder::default()
+    }
+
+    // a preprocessor that only lowercases documents
+    pub(crate) fn lowercasing() -> Preprocessor {
+        Preprocessor { lowercase: true, ..Preprocessor::default() }
+    }
+
+    /// Whether the preprocessor leaves documents unchanged before they are
+    /// tokenized (token filters are not considered)
+    pub fn is_identity(&self) -> bool {
+        !self.lowercase && self.normalization.is_none() && !self.strip_accents
+    }
+
+    /// Applies the preprocessing steps to a document, in the order
+    /// normalization, accent stripping, lowercasing
+    pub fn process(&self, document: &str) -> String {
+        let mut text = match self.normalization {
+            Some(UnicodeNormalization::Nfc) => document.nfc().collect(),
+            Some(UnicodeNormalization::Nfkc) => document.nfkc().collect(),
+            None => document.to_string(),
+        };
+        if self.strip_accents {
+            // decompose so accents become separate combining marks, drop
+            // the marks, and put whatever is left back together
+            text = text.nfd().filter(|&c| !is_combining_mark(c)).nfc().collect();
+        }
+        if self.lowercase {
+            text = text.to_lowercase();
+        }
+        text
+    }
+
+    // preprocesses and tokenizes a document, then filters its tokens
+    pub(crate) fn tokenize<T: Tokenizer>(&self, tokenizer: &T, document: &str) -> Vec<String> {
+        let tokens = if self.is_identity() {
+            tokenizer.tokenize(document)
+        } else {
+            tokenizer.tokenize(&self.process(document))
+        };
+        self.filter_tokens(tokens)
+    }
+
+    /// Strips the punctuation from the ends of the tokens if punctuation
+    /// stripping is on, then drops empty tokens, tokens outside of the
+    /// token length limits, and the tokens that any of the token filters
+    /// does not let through
+    pub fn filter_tokens(&self, mut tokens: Vec<String>) -> Vec<String> {
+        if self.strip_punctuation {
+            for token in tokens.iter_mut() {
+                let stripped = token.trim_matches(|c: char| c.is_ascii_punctuation() || PUNCTUATION.contains(&c));
+                if stripped.len() != token.len() {
+                    *token = stripped.to_string();
+                }
+            }
+        }
+        tokens.retain(|token| {
+            !token.is_empty() && self.fits_length(token)
+                && self.token_filters.iter().all(|filter| filter.keeps(token))
+        });
+        tokens
+    }
+
+    // whether the token is within the token length limits, if there are any
+    fn fits_length(&self, token: &str) -> bool {
+        if self.min_token_len.is_none() && self.max_token_len.is_none() {
+            return true;
+        }
+        let len = token.chars().count();
+        self.min_token_len.is_none_or(|min| len >= min) && self.max_token_len.is_none_or(|max| len <= max)
+    }
+}
+
+impl PreprocessorBuilder {
+
+    /// Turns lowercasing on or off, so "Beef" and "beef" are the same word
+    pub fn lowercase(mut self, lowercase: bool) -> PreprocessorBuilder {
+        self.preprocessor.lowercase = lowercase;
+        self
+    }
+
+    /// Sets the Unicode normalization form documents are put into
+    pub fn normalization(mut self, normalization: UnicodeNormalization) -> PreprocessorBuilder {
+        self.preprocessor.normalization = Some(normalization);
+        self
+    }
+
+    /// Turns accent stripping on or off, so "café" and "cafe" are the same word
+    pub fn strip_accents(mut self, strip_accents: bool) -> PreprocessorBuilder {
+        self.preprocessor.strip_accents = strip_accents;
+        self
+    }
+
+    /// Turns punctuation stripping on or off, so "ribs," and "(ribs)" are
+    /// the same word as "ribs". Punctuation is only stripped from the start
+    /// and end of each token, so "t-bone" and "o'clock" are kept whole, and
+    /// tokens that are nothing but punctuation are dropped.
+    pub fn strip_punctuation(mut self, strip_punctuation: bool) -> PreprocessorBuilder {
+        self.preprocessor.strip_punctuation = strip_punctuation;
+        self
+    }
+
+    /// Drops tokens with fewer than `len` characters, e.g. 2 to leave out
+    /// single letters
+    pub fn min_token_len(mut self, len: usize) -> PreprocessorBuilder {
+        self.preprocessor.min_token_len = Some(len);
+        self
+    }
+
+    /// Drops tokens with more than `len` characters, e.g. to leave out
+    /// URLs and encoded blobs that are almost never seen twice
+    pub fn max_token_len(mut self, len: usize) -> PreprocessorBuilder {
+        self.preprocessor.max_token_len = Some(len);
+        self
+    }
+
+    /// Adds a filter applied to the tokens of every document, a token is
+    /// only kept if every filter keeps it
+    pub fn token_filter(mut self, filter: TokenFilter) -> PreprocessorBuilder {
+        self.preprocessor.token_filters.push(filter);
+        self
+    }
+
+    /// Finishes building the preprocessor. Fails with `InvalidTokenLengths`
+    /// if the minimum token length is above the maximum, which would drop
+    /// every token.
+    pub fn build(self) -> Result<Preprocessor, ClassifierError> {
+        if let (Some(min), Some(max)) = (self.preprocessor.min_token_len, self.preprocessor.max_token_len) {
+            if min > max {
+                return Err(ClassifierError::InvalidTokenLengths(min, max));
+            }
+        }
+        Ok(self.preprocessor)
+    }
+}
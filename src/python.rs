@@ -0,0 +1,107 @@
+// the code PyO3 generates for methods returning `PyResult` trips this lint
+#![allow(clippy::useless_conversion)]
+
+use std::collections::HashMap;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use error::ClassifierError;
+use naive_bayes::Classifier;
+
+/// A naive Bayes classifier for Python, exported to it as
+/// `classifier.NaiveBayes` with scikit-learn's method names. Requires the
+/// `python` feature.
+///
+/// ```python
+/// from classifier import NaiveBayes
+///
+/// nb = NaiveBayes(alpha=1.0)
+/// nb.fit(["pork belly ribs", "kale okra spinach"], ["meat", "veggie"])
+/// nb.predict(["pork ribs"])        # ["meat"]
+/// nb.predict_proba(["pork ribs"])  # one column per label in nb.classes_
+/// ```
+///
+/// Errors are raised as `ValueError`s holding the `ClassifierError`'s
+/// message.
+#[pyclass(name = "NaiveBayes", module = "classifier")]
+#[derive(Debug, Clone)]
+pub struct PyClassifier {
+    alpha: f64,
+    classifier: Classifier,
+}
+
+#[pymethods]
+impl PyClassifier {
+
+    /// Creates a classifier with the given additive smoothing, which must
+    /// be greater than 0.0
+    #[new]
+    #[pyo3(signature = (alpha = 1.0))]
+    pub fn new(alpha: f64) -> PyResult<PyClassifier> {
+        let mut classifier = Classifier::new();
+        classifier.set_smoothing(alpha)?;
+        Ok(PyClassifier { alpha, classifier })
+    }
+
+    /// Trains the classifier on the documents `x` and their labels `y`,
+    /// forgetting what it learned from earlier calls, and returns it
+    pub fn fit(mut slf: PyRefMut<'_, Self>, x: Vec<String>, y: Vec<String>) -> PyResult<PyRefMut<'_, Self>> {
+        if x.len() != y.len() {
+            return Err(PyValueError::new_err(format!("got {} documents but {} labels", x.len(), y.len())));
+        }
+        let mut classifier = Classifier::new();
+        classifier.set_smoothing(slf.alpha)?;
+        for (document, label) in x.iter().zip(y.iter()) {
+            classifier.add_document(document, label);
+        }
+        classifier.train();
+        slf.classifier = classifier;
+        Ok(slf)
+    }
+
+    /// Computes a label for each of the documents `x`
+    pub fn predict(&self, x: Vec<String>) -> PyResult<Vec<String>> {
+        x.iter()
+            .map(|document| self.classifier.classify(document).map_err(PyErr::from))
+            .collect()
+    }
+
+    /// Computes the posterior probability of every label for each of the
+    /// documents `x`, in the order of `classes_`
+    pub fn predict_proba(&self, x: Vec<String>) -> PyResult<Vec<Vec<f64>>> {
+        let classes = self.classes();
+        x.iter().map(|document| {
+            let posteriors: HashMap<String, f64> = self.classifier.get_document_posteriors(document)?
+                .into_iter()
+                .collect();
+            Ok(classes.iter().map(|label| posteriors[label]).collect())
+        }).collect()
+    }
+
+    /// The labels the classifier has learned, sorted
+    #[getter(classes_)]
+    pub fn classes(&self) -> Vec<String> {
+        let mut labels = self.classifier.get_labels();
+        labels.sort();
+        labels
+    }
+}
+
+impl PyClassifier {
+
+    /// Gets the classifier, for everything the Python bindings leave out
+    pub fn classifier(&self) -> &Classifier {
+        &self.classifier
+    }
+}
+
+impl From<ClassifierError> for PyErr {
+    fn from(error: ClassifierError) -> PyErr {
+        PyValueError::new_err(error.to_string())
+    }
+}
+
+// the `classifier` Python module
+#[pymodule]
+fn classifier(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyClassifier>()
+}
@@ -0,0 +1,51 @@
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+// a small seeded generator, so that a seed gives the same results on every
+// platform and every release of this crate
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+
+    pub fn new(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+
+    pub fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+// the randomness the crate needs, from either the built-in generator or one
+// of `rand`'s
+pub trait Shuffle {
+    fn shuffle<T>(&mut self, items: &mut [T]);
+}
+
+impl Shuffle for SplitMix64 {
+
+    // Fisher-Yates shuffle
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = (self.next() % (i as u64 + 1)) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+#[cfg(feature = "rand")]
+impl<R: Rng + ?Sized> Shuffle for R {
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.gen_range(0..=i);
+            items.swap(i, j);
+        }
+    }
+}
@@ -0,0 +1,83 @@
+//! Scrubbers that mask personal information in documents, for use with
+//! `NaiveBayes::set_sanitizer`, so that email addresses, phone numbers, and
+//! credit card numbers never become words of the model
+//!
+//! Each scrubber replaces what it finds with a placeholder word, so the
+//! classifier still learns that a document had, say, an email address in
+//! it. The patterns are deliberately simple and will miss unusual formats.
+//!
+//! ```rust
+//! use classifier::NaiveBayes;
+//! use classifier::sanitize;
+//!
+//! assert_eq!(sanitize::scrub_pii("mail jane.doe@example.com or call +1 (555) 010-0199"),
+//!            "mail <email> or call <phone>");
+//!
+//! let mut nb = NaiveBayes::new();
+//! nb.set_sanitizer(sanitize::scrub_pii);
+//! nb.add_document("refund to card 4111 1111 1111 1111 please", "billing");
+//! assert!(!nb.to_json().contains("4111"));
+//! ```
+
+use std::sync::OnceLock;
+use regex::{Captures, Regex};
+
+/// The word email addresses are replaced with
+pub static EMAIL: &str = "<email>";
+/// The word phone numbers are replaced with
+pub static PHONE: &str = "<phone>";
+/// The word credit card numbers are replaced with
+pub static CREDIT_CARD: &str = "<credit_card>";
+
+/// Replaces every email address in the text with `EMAIL`
+pub fn scrub_emails(text: &str) -> String {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| {
+        Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}").unwrap()
+    });
+    pattern.replace_all(text, EMAIL)
+}
+
+/// Replaces every phone number in the text with `PHONE`. A phone number is
+/// a run of 7 to 15 digits, which may start with a `+` and be broken up by
+/// spaces, dots, dashes, and parentheses.
+pub fn scrub_phone_numbers(text: &str) -> String {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| Regex::new(r"(?:\+\(?|\(|\b)\d[\d .()-]*\d\b").unwrap());
+    pattern.replace_all(text, |captures: &Captures| {
+        let number = &captures[0];
+        let digits = number.chars().filter(char::is_ascii_digit).count();
+        if (7..=15).contains(&digits) { PHONE.to_string() } else { number.to_string() }
+    })
+}
+
+/// Replaces every credit card number in the text with `CREDIT_CARD`. A
+/// credit card number is a run of 13 to 19 digits, which may be broken up
+/// by spaces or dashes, with a valid Luhn check digit.
+pub fn scrub_credit_cards(text: &str) -> String {
+    static PATTERN: OnceLock<Regex> = OnceLock::new();
+    let pattern = PATTERN.get_or_init(|| Regex::new(r"\b\d(?:[ -]?\d){12,18}\b").unwrap());
+    pattern.replace_all(text, |captures: &Captures| {
+        let number = &captures[0];
+        if luhn(number) { CREDIT_CARD.to_string() } else { number.to_string() }
+    })
+}
+
+/// Scrubs credit card numbers, then email addresses, then phone numbers
+pub fn scrub_pii(text: &str) -> String {
+    scrub_phone_numbers(&scrub_emails(&scrub_credit_cards(text)))
+}
+
+// whether the digits of the number pass the Luhn checksum
+fn luhn(number: &str) -> bool {
+    let mut sum = 0;
+    for (i, digit) in number.chars().rev().filter_map(|c| c.to_digit(10)).enumerate() {
+        sum += if i % 2 == 1 {
+            let doubled = digit * 2;
+            if doubled > 9 { doubled - 9 } else { doubled }
+        } else {
+            digit
+        };
+    }
+    sum % 10 == 0
+}
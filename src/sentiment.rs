@@ -0,0 +1,211 @@
+//! Telling positive from negative text, put together from the crate's
+//! parts: a lowercasing `Preprocessor`, a `NegationTokenizer` marking the
+//! words a negation applies to, and a naive Bayes classifier
+//!
+//! `SentimentAnalyzer::pretrained` comes trained on a small bundled sample of
+//! short English reviews. A model trained on text like your own will do much
+//! better, and can be trained, saved, and loaded the same way:
+//!
+//! ```rust
+//! use classifier::sentiment::SentimentAnalyzer;
+//!
+//! let analyzer = SentimentAnalyzer::pretrained();
+//! assert_eq!(analyzer.classify("what a wonderful film").unwrap(), "positive");
+//! assert_eq!(analyzer.classify("the food was not good").unwrap(), "negative");
+//! assert!(analyzer.score("i loved it, great service").unwrap() > 0.0);
+//!
+//! let mut custom = SentimentAnalyzer::new();
+//! custom.add_example("fast shipping and works perfectly", "positive");
+//! custom.add_example("arrived broken and support never answered", "negative");
+//! custom.train();
+//! let loaded = SentimentAnalyzer::from_json(&custom.to_json()).unwrap();
+//! assert_eq!(loaded.classify("works perfectly").unwrap(), "positive");
+//! ```
+
+use std::path::Path;
+use error::ClassifierError;
+use naive_bayes::Classifier;
+use preprocessing::Preprocessor;
+use tokenizer::{Tokenizer, WhitespaceTokenizer};
+
+/// The words that negate the words after them, up to the end of the clause.
+/// Words ending in "n't" are negations as well.
+pub static NEGATIONS: &[&str] = &[
+    "not", "no", "never", "neither", "nor", "none", "nobody", "nothing", "nowhere", "cannot", "without",
+];
+
+/// The (text, label) examples `SentimentAnalyzer::pretrained` is trained on,
+/// labeled "positive" or "negative"
+pub static SAMPLES: &[(&str, &str)] = &[
+    ("what a wonderful film, i loved every minute of it", "positive"),
+    ("great food and friendly staff, we will definitely come back", "positive"),
+    ("the best purchase i have made all year", "positive"),
+    ("excellent quality and it works perfectly", "positive"),
+    ("i am very happy with this, highly recommended", "positive"),
+    ("the service was fast and the room was clean and comfortable", "positive"),
+    ("a beautiful story with brilliant acting", "positive"),
+    ("delicious meal, fair prices and a lovely view", "positive"),
+    ("easy to use and the battery lasts for days", "positive"),
+    ("not bad at all, better than i expected", "positive"),
+    ("i never had a problem with it, solid and reliable", "positive"),
+    ("fantastic experience, thank you so much", "positive"),
+    ("the staff were helpful and kind", "positive"),
+    ("an amazing concert, the band was great", "positive"),
+    ("good value for money, i would buy it again", "positive"),
+    ("i enjoyed the book and could not put it down", "positive"),
+    ("nice design and it feels sturdy", "positive"),
+    ("perfect fit, comfortable and well made", "positive"),
+    ("terrible film, a complete waste of time", "negative"),
+    ("the food was cold and the staff were rude", "negative"),
+    ("the worst purchase i have ever made", "negative"),
+    ("poor quality, it broke after two days", "negative"),
+    ("i am very disappointed and want a refund", "negative"),
+    ("the room was dirty and the service was slow", "negative"),
+    ("a boring story with awful acting", "negative"),
+    ("overpriced and tasteless, not worth it", "negative"),
+    ("hard to use and the battery dies in an hour", "negative"),
+    ("not good, much worse than i expected", "negative"),
+    ("it never worked properly, avoid it", "negative"),
+    ("horrible experience, i will not come back", "negative"),
+    ("the staff were unhelpful and did not care", "negative"),
+    ("the sound was bad and the band was late", "negative"),
+    ("i would not recommend it to anyone", "negative"),
+    ("i did not enjoy the book and gave up halfway", "negative"),
+    ("ugly design and it feels cheap", "negative"),
+    ("it doesn't fit and the seams are coming apart", "negative"),
+];
+
+/// A tokenizer that marks the tokens following a negation, up to the next
+/// punctuation mark ending a clause, by prefixing them with `not_`, so that
+/// "not good" is counted as a different word than "good". Punctuation at the
+/// start and end of every token is stripped. See `NEGATIONS`.
+///
+/// ```rust
+/// use classifier::{Tokenizer, WhitespaceTokenizer};
+/// use classifier::sentiment::NegationTokenizer;
+///
+/// let tokenizer = NegationTokenizer::new(WhitespaceTokenizer);
+/// assert_eq!(tokenizer.tokenize("not very good, but cheap"), vec!["not", "not_very", "not_good", "but", "cheap"]);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NegationTokenizer<T = WhitespaceTokenizer> {
+    tokenizer: T,
+}
+
+impl<T: Tokenizer> NegationTokenizer<T> {
+
+    /// Creates a tokenizer that marks the negated tokens of `tokenizer`
+    pub fn new(tokenizer: T) -> NegationTokenizer<T> {
+        NegationTokenizer { tokenizer }
+    }
+}
+
+impl<T: Tokenizer> Tokenizer for NegationTokenizer<T> {
+    fn tokenize(&self, document: &str) -> Vec<String> {
+        let mut tokens = Vec::new();
+        let mut negated = false;
+        for token in self.tokenizer.tokenize(document) {
+            let word = token.trim_matches(|c: char| c.is_ascii_punctuation() && c != '\'');
+            if !word.is_empty() {
+                if negated {
+                    tokens.push(format!("not_{}", word));
+                } else {
+                    tokens.push(word.to_string());
+                }
+                if NEGATIONS.contains(&word) || word.ends_with("n't") {
+                    negated = true;
+                }
+            }
+            if token.ends_with(['.', ',', ';', ':', '!', '?']) {
+                negated = false;
+            }
+        }
+        tokens
+    }
+}
+
+/// Classifies text as "positive" or "negative" (or whatever labels it was
+/// trained with), see the `sentiment` module
+#[derive(Debug, Clone)]
+pub struct SentimentAnalyzer {
+    classifier: Classifier<NegationTokenizer>,
+}
+
+impl SentimentAnalyzer {
+
+    /// Creates an untrained analyzer that lowercases text and marks negated
+    /// words before counting them
+    pub fn new() -> SentimentAnalyzer {
+        let mut classifier = Classifier::with_tokenizer(NegationTokenizer::default());
+        classifier.set_preprocessor(Preprocessor::lowercasing());
+        SentimentAnalyzer { classifier }
+    }
+
+    /// Creates an analyzer trained on the bundled `SAMPLES`
+    pub fn pretrained() -> SentimentAnalyzer {
+        let mut analyzer = SentimentAnalyzer::new();
+        for &(text, label) in SAMPLES.iter() {
+            analyzer.add_example(text, label);
+        }
+        analyzer.train();
+        analyzer
+    }
+
+    /// Builds an analyzer from a JSON string written by `to_json`
+    pub fn from_json(encoded: &str) -> Result<SentimentAnalyzer, ClassifierError> {
+        Ok(SentimentAnalyzer { classifier: Classifier::from_json_with_tokenizer(encoded, NegationTokenizer::default())? })
+    }
+
+    /// Loads an analyzer from a file written by `save_to_file`
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<SentimentAnalyzer, ClassifierError> {
+        Ok(SentimentAnalyzer { classifier: Classifier::load_from_file_with_tokenizer(path, NegationTokenizer::default())? })
+    }
+
+    /// Adds a labeled example for the next call to `train()`
+    pub fn add_example(&mut self, text: &str, label: &str) {
+        self.classifier.add_document(text, label);
+    }
+
+    /// Trains the analyzer on the examples that have been added
+    pub fn train(&mut self) {
+        self.classifier.train();
+    }
+
+    /// Computes the sentiment label of the text. Fails if the analyzer has
+    /// not been trained or the text has no words.
+    pub fn classify(&self, text: &str) -> Result<String, ClassifierError> {
+        self.classifier.classify(text)
+    }
+
+    /// Scores the text from -1 (certainly negative) to 1 (certainly
+    /// positive), the posterior probability of "positive" minus that of
+    /// "negative"
+    pub fn score(&self, text: &str) -> Result<f64, ClassifierError> {
+        let posteriors = self.classifier.get_document_posteriors(text)?;
+        let posterior = |label: &str| posteriors.iter()
+            .find(|&(l, _)| l == label)
+            .map_or(0.0, |&(_, p)| p);
+        Ok(posterior("positive") - posterior("negative"))
+    }
+
+    /// Gets the classifier behind the analyzer
+    pub fn classifier(&self) -> &Classifier<NegationTokenizer> {
+        &self.classifier
+    }
+
+    /// Encodes the analyzer as a JSON string
+    pub fn to_json(&self) -> String {
+        self.classifier.to_json()
+    }
+
+    /// Saves the analyzer to a JSON file, see `NaiveBayes::save_to_file`
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ClassifierError> {
+        self.classifier.save_to_file(path)
+    }
+}
+
+impl Default for SentimentAnalyzer {
+    fn default() -> SentimentAnalyzer {
+        SentimentAnalyzer::new()
+    }
+}
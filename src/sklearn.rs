@@ -0,0 +1,170 @@
+use std::collections::HashSet;
+use std::fmt;
+use serde::de::{Deserializer, MapAccess, Visitor};
+use serde_json::{self, Value};
+use error::ClassifierError;
+use naive_bayes::Classifier;
+
+// the smoothing of an imported classifier, which is the default
+const SMOOTHING: f64 = 1.0;
+
+// the fitted attributes of a scikit-learn `MultinomialNB` and the vocabulary
+// of the vectorizer it was trained with
+#[derive(Deserialize)]
+struct SklearnModel {
+    #[serde(rename = "classes_", default)]
+    classes: Option<Vec<Value>>,
+    #[serde(rename = "class_log_prior_")]
+    class_log_prior: Vec<f64>,
+    #[serde(rename = "feature_log_prob_")]
+    feature_log_prob: Vec<Vec<f64>>,
+    vocabulary: SklearnVocabulary,
+}
+
+// a `CountVectorizer`'s `vocabulary_`, which maps each word to its column,
+// or the output of its `get_feature_names_out`, the words in column order
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SklearnVocabulary {
+    Columns(#[serde(deserialize_with = "column_entries")] Vec<(String, usize)>),
+    Words(Vec<String>),
+}
+
+// the entries of a `vocabulary_` in order, keeping a word given twice so
+// that it can be rejected rather than overwritten
+fn column_entries<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<(String, usize)>, D::Error> {
+    struct Entries;
+
+    impl<'de> Visitor<'de> for Entries {
+        type Value = Vec<(String, usize)>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a map of words to columns")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let mut entries = Vec::new();
+            while let Some(entry) = map.next_entry()? {
+                entries.push(entry);
+            }
+            Ok(entries)
+        }
+    }
+
+    deserializer.deserialize_map(Entries)
+}
+
+impl SklearnVocabulary {
+
+    // the words in column order, failing if a word appears twice
+    fn into_words(self) -> Result<Vec<String>, ClassifierError> {
+        let words = self.into_columns()?;
+        let mut seen = HashSet::new();
+        if let Some(word) = words.iter().find(|word| !seen.insert(word.as_str())) {
+            return Err(decode_error(format!("the word {} appears twice in the vocabulary", word)));
+        }
+        Ok(words)
+    }
+
+    fn into_columns(self) -> Result<Vec<String>, ClassifierError> {
+        match self {
+            SklearnVocabulary::Words(words) => Ok(words),
+            SklearnVocabulary::Columns(columns) => {
+                let mut words = vec![None; columns.len()];
+                for (word, column) in columns {
+                    match words.get_mut(column) {
+                        Some(slot @ &mut None) => *slot = Some(word),
+                        _ => return Err(decode_error(format!("vocabulary column {} is out of range or taken twice",
+                                                             column))),
+                    }
+                }
+                Ok(words.into_iter().map(Option::unwrap).collect())
+            },
+        }
+    }
+}
+
+impl Classifier {
+
+    /// Builds a classifier from a scikit-learn `MultinomialNB` exported as a
+    /// JSON object, so models trained in Python can be served from Rust. The
+    /// object holds the model's `class_log_prior_` and `feature_log_prob_`,
+    /// optionally its `classes_` (the labels, which are numbered from 0
+    /// otherwise), and a `vocabulary` giving the word of each feature: either
+    /// a `CountVectorizer`'s `vocabulary_`, mapping each word to its column,
+    /// or a list of the words in column order. Fails with `Decode` if a word
+    /// appears twice in the vocabulary, and with `DimensionMismatch` if a row
+    /// of `feature_log_prob_` doesn't have a probability per word.
+    ///
+    /// The classifier is given made-up word counts that its default
+    /// smoothing turns back into the model's probabilities, so it classifies
+    /// documents as the scikit-learn model does, as long as it tokenizes them
+    /// the way the vectorizer did (e.g. lowercasing them, see
+    /// `set_preprocessor`). Changing its smoothing or adding documents to it
+    /// works from these made-up counts.
+    ///
+    /// ```rust
+    /// use classifier::NaiveBayes;
+    ///
+    /// let nb = NaiveBayes::from_sklearn_json(r#"{
+    ///     "classes_": ["meat", "veggie"],
+    ///     "class_log_prior_": [-0.6931, -0.6931],
+    ///     "feature_log_prob_": [[-0.4055, -1.0986], [-1.0986, -0.4055]],
+    ///     "vocabulary": {"pork": 0, "kale": 1}
+    /// }"#).unwrap();
+    /// assert_eq!(nb.classify("pork").unwrap(), "meat");
+    /// ```
+    pub fn from_sklearn_json(encoded: &str) -> Result<Classifier, ClassifierError> {
+        let model: SklearnModel = serde_json::from_str(encoded).map_err(|e| decode_error(e.to_string()))?;
+        let words = model.vocabulary.into_words()?;
+        let num_labels = model.class_log_prior.len();
+        if num_labels == 0 {
+            return Err(decode_error("the model has no classes".to_string()));
+        }
+        if model.feature_log_prob.len() != num_labels {
+            return Err(decode_error(format!("the model has {} classes but {} rows of feature_log_prob_",
+                                            num_labels, model.feature_log_prob.len())));
+        }
+        let labels: Vec<String> = match model.classes {
+            Some(ref classes) if classes.len() != num_labels =>
+                return Err(decode_error(format!("the model has {} classes but {} class priors",
+                                                classes.len(), num_labels))),
+            Some(classes) => classes.into_iter().map(|class| match class {
+                Value::String(label) => label,
+                other => other.to_string(),
+            }).collect(),
+            None => (0..num_labels).map(|i| i.to_string()).collect(),
+        };
+
+        let mut nb = Classifier::new();
+        for ((label, log_prior), log_probabilities) in labels.iter().zip(model.class_log_prior).zip(model.feature_log_prob) {
+            if nb.get_labels().contains(label) {
+                return Err(decode_error(format!("the class {} appears twice", label)));
+            }
+            if log_probabilities.len() != words.len() {
+                return Err(ClassifierError::DimensionMismatch(words.len(), log_probabilities.len()));
+            }
+            if !log_prior.is_finite() || log_probabilities.iter().any(|p| !p.is_finite()) {
+                return Err(decode_error("the model has probabilities of 0, it was fitted without smoothing"
+                                            .to_string()));
+            }
+            // counts for which (count + smoothing) / (total + smoothing * words)
+            // is the probability of each word, the least likely word having
+            // a count of 0
+            let probabilities: Vec<f64> = log_probabilities.iter().map(|p| p.exp()).collect();
+            let total: f64 = probabilities.iter().sum();
+            let least = probabilities.iter().cloned().fold(f64::INFINITY, f64::min);
+            let scale = SMOOTHING * total / least;
+            for (word, p) in words.iter().zip(probabilities) {
+                nb.add_word_counts(label, word, (p / total * scale - SMOOTHING).max(0.0), 0.0);
+            }
+            nb.add_label_examples(label, log_prior.exp());
+        }
+        nb.train();
+        Ok(nb)
+    }
+}
+
+fn decode_error(reason: String) -> ClassifierError {
+    ClassifierError::Decode(format!("invalid scikit-learn model: {}", reason))
+}
@@ -0,0 +1,43 @@
+/// A summary of a naive Bayes classifier, for keeping an eye on deployed
+/// models, see `NaiveBayes::stats`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelStats {
+    /// The number of distinct words (features) the classifier knows
+    pub vocab_size: usize,
+    /// The number of labels the classifier knows
+    pub num_labels: usize,
+    /// The total weight of the documents the classifier was trained on,
+    /// which is the number of documents unless they were weighted
+    pub num_examples: f64,
+    /// The statistics of every label, in the order of `get_labels`
+    pub labels: Vec<LabelStats>,
+    /// The smoothing value of labels without one of their own
+    pub smoothing: f64,
+    /// A rough estimate of the memory the classifier takes up, in bytes,
+    /// see `NaiveBayes::estimated_memory_bytes`
+    pub memory_bytes: usize,
+}
+
+/// The statistics of one label of a classifier
+#[derive(Debug, Clone, PartialEq)]
+pub struct LabelStats {
+    /// The label
+    pub label: String,
+    /// The total weight of the documents with the label
+    pub examples: f64,
+    /// The total weight of the words (features) in the documents with the
+    /// label, each occurrence counting once
+    pub tokens: f64,
+    /// The number of distinct words seen in documents with the label
+    pub distinct_words: usize,
+    /// The smoothing value used for the label
+    pub smoothing: f64,
+}
+
+impl ModelStats {
+
+    /// Gets the statistics of the given label
+    pub fn label(&self, label: &str) -> Option<&LabelStats> {
+        self.labels.iter().find(|stats| stats.label == label)
+    }
+}
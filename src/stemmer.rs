@@ -0,0 +1,80 @@
+#[cfg(feature = "stemming")]
+use rust_stemmers::{self, Algorithm};
+use tokenizer::Tokenizer;
+
+/// Reduces a word to its stem, so that different forms of a word ("runs",
+/// "running") are counted as the same word
+///
+/// Any closure taking a `&str` and returning a `String` can be used as a
+/// stemmer.
+pub trait Stemmer {
+    /// Gets the stem of a word
+    fn stem(&self, word: &str) -> String;
+}
+
+impl<F> Stemmer for F where F: Fn(&str) -> String {
+    fn stem(&self, word: &str) -> String {
+        self(word)
+    }
+}
+
+/// A tokenizer that stems every token produced by another tokenizer
+///
+/// ```rust
+/// use classifier::{NaiveBayes, StemmingTokenizer, Tokenizer, WhitespaceTokenizer};
+///
+/// // strip a trailing "s" from every word
+/// let stemmer = |word: &str| word.trim_end_matches('s').to_string();
+/// let tokenizer = StemmingTokenizer::new(WhitespaceTokenizer, stemmer);
+/// assert_eq!(tokenizer.tokenize("pork ribs"), vec!["pork", "rib"]);
+///
+/// let mut nb = NaiveBayes::with_tokenizer(tokenizer);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StemmingTokenizer<T, S> {
+    tokenizer: T,
+    stemmer: S,
+}
+
+impl<T: Tokenizer, S: Stemmer> StemmingTokenizer<T, S> {
+
+    /// Creates a tokenizer that stems the tokens of `tokenizer` with `stemmer`
+    pub fn new(tokenizer: T, stemmer: S) -> StemmingTokenizer<T, S> {
+        StemmingTokenizer { tokenizer, stemmer }
+    }
+}
+
+impl<T: Tokenizer, S: Stemmer> Tokenizer for StemmingTokenizer<T, S> {
+    fn tokenize(&self, document: &str) -> Vec<String> {
+        self.tokenizer.tokenize(document).iter().map(|token| self.stemmer.stem(token)).collect()
+    }
+}
+
+/// A stemmer using the Snowball stemming algorithms (for English, this is
+/// the Porter2 algorithm). Requires the `stemming` feature.
+#[cfg(feature = "stemming")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnowballStemmer {
+    algorithm: Algorithm,
+}
+
+#[cfg(feature = "stemming")]
+impl SnowballStemmer {
+
+    /// Creates a stemmer for the language of the given algorithm
+    pub fn new(algorithm: Algorithm) -> SnowballStemmer {
+        SnowballStemmer { algorithm }
+    }
+
+    /// Creates an English stemmer
+    pub fn english() -> SnowballStemmer {
+        SnowballStemmer::new(Algorithm::English)
+    }
+}
+
+#[cfg(feature = "stemming")]
+impl Stemmer for SnowballStemmer {
+    fn stem(&self, word: &str) -> String {
+        rust_stemmers::Stemmer::create(self.algorithm).stem(word).into_owned()
+    }
+}
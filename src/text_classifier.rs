@@ -0,0 +1,83 @@
+use error::ClassifierError;
+use logistic::LogisticRegression;
+use naive_bayes::Classifier;
+use tokenizer::Tokenizer;
+
+/// The interface shared by the text classifiers, so that code can be
+/// written once and used with any of them
+///
+/// ```rust
+/// use classifier::{LogisticRegression, NaiveBayes, TextClassifier};
+///
+/// let mut models: Vec<Box<dyn TextClassifier>> = vec![
+///     Box::new(NaiveBayes::new()),
+///     Box::new(LogisticRegression::new()),
+/// ];
+/// for model in models.iter_mut() {
+///     model.add_document("pork belly", "meat");
+///     model.add_document("kale okra", "veggie");
+///     model.train();
+///     assert_eq!(model.classify("pork").unwrap(), "meat");
+/// }
+/// ```
+pub trait TextClassifier {
+    /// Adds a labeled document for the next call to `train()`
+    fn add_document(&mut self, document: &str, label: &str);
+
+    /// Trains the classifier on the documents that have been added
+    fn train(&mut self);
+
+    /// Computes a classifying label for a document. Fails if the classifier
+    /// has not been trained or the document has no features.
+    fn classify(&self, document: &str) -> Result<String, ClassifierError>;
+
+    /// Gets every label and the probability of each one given the document
+    fn probabilities(&self, document: &str) -> Result<Vec<(String, f64)>, ClassifierError>;
+
+    /// Gets every label the classifier has seen
+    fn labels(&self) -> Vec<String>;
+}
+
+impl<T: Tokenizer> TextClassifier for Classifier<T> {
+    fn add_document(&mut self, document: &str, label: &str) {
+        Classifier::add_document(self, document, label);
+    }
+
+    fn train(&mut self) {
+        Classifier::train(self);
+    }
+
+    fn classify(&self, document: &str) -> Result<String, ClassifierError> {
+        Classifier::classify(self, document)
+    }
+
+    fn probabilities(&self, document: &str) -> Result<Vec<(String, f64)>, ClassifierError> {
+        self.get_document_posteriors(document)
+    }
+
+    fn labels(&self) -> Vec<String> {
+        self.get_labels()
+    }
+}
+
+impl<T: Tokenizer> TextClassifier for LogisticRegression<T> {
+    fn add_document(&mut self, document: &str, label: &str) {
+        LogisticRegression::add_document(self, document, label);
+    }
+
+    fn train(&mut self) {
+        LogisticRegression::train(self);
+    }
+
+    fn classify(&self, document: &str) -> Result<String, ClassifierError> {
+        LogisticRegression::classify(self, document)
+    }
+
+    fn probabilities(&self, document: &str) -> Result<Vec<(String, f64)>, ClassifierError> {
+        self.get_document_posteriors(document)
+    }
+
+    fn labels(&self) -> Vec<String> {
+        self.get_labels()
+    }
+}
@@ -0,0 +1,155 @@
+#[cfg(feature = "std")]
+use std::sync::OnceLock;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use regex::Regex;
+use error::ClassifierError;
+
+/// Breaks a document up into the words (tokens) that a classifier counts
+/// during training and looks up during classification
+///
+/// Any closure taking a `&str` and returning a `Vec<String>` can be used
+/// as a tokenizer.
+pub trait Tokenizer {
+    /// Splits a document into a vector of tokens
+    fn tokenize(&self, document: &str) -> Vec<String>;
+}
+
+impl<F> Tokenizer for F where F: Fn(&str) -> Vec<String> {
+    fn tokenize(&self, document: &str) -> Vec<String> {
+        self(document)
+    }
+}
+
+/// The default tokenizer, breaks documents on whitespace characters. Runs of
+/// whitespace and whitespace at either end of a document give no empty
+/// tokens. Without the `std` feature it splits on `char::is_whitespace`
+/// instead of a regular expression, which gives the same tokens.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WhitespaceTokenizer;
+
+impl Tokenizer for WhitespaceTokenizer {
+    #[cfg(feature = "std")]
+    fn tokenize(&self, document: &str) -> Vec<String> {
+        whitespace().split(document).filter(|s| !s.is_empty()).map(|s| s.to_string()).collect()
+    }
+
+    #[cfg(not(feature = "std"))]
+    fn tokenize(&self, document: &str) -> Vec<String> {
+        document.split(char::is_whitespace).filter(|s| !s.is_empty()).map(|s| s.to_string()).collect()
+    }
+}
+
+// the expression documents are split on, compiled the first time it is used
+#[cfg(feature = "std")]
+fn whitespace() -> &'static Regex {
+    static WHITESPACE: OnceLock<Regex> = OnceLock::new();
+    WHITESPACE.get_or_init(|| Regex::new(r"(\s)").unwrap())
+}
+
+/// Breaks documents into the character n-grams of each whitespace separated
+/// word, so that misspellings and different forms of a word still share most
+/// of their tokens. Each word is wrapped in `<` and `>` first, so that the
+/// n-grams at the start and end of a word differ from those in the middle.
+/// A wrapped word shorter than the smallest n-gram is kept whole.
+///
+/// ```rust
+/// use classifier::{CharNgramTokenizer, Tokenizer};
+///
+/// let tokenizer = CharNgramTokenizer::new(3, 4).unwrap();
+/// assert_eq!(tokenizer.tokenize("ham"), vec!["<ha", "ham", "am>", "<ham", "ham>"]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CharNgramTokenizer {
+    min: usize,
+    max: usize,
+}
+
+impl CharNgramTokenizer {
+
+    /// Creates a tokenizer producing the character n-grams of every length
+    /// from `min` to `max` (inclusive), which must satisfy `0 < min <= max`
+    pub fn new(min: usize, max: usize) -> Result<CharNgramTokenizer, ClassifierError> {
+        if min == 0 || min > max {
+            return Err(ClassifierError::InvalidNgramRange(min, max));
+        }
+        Ok(CharNgramTokenizer { min, max })
+    }
+}
+
+/// Produces the character 3, 4, and 5-grams of every word
+impl Default for CharNgramTokenizer {
+    fn default() -> CharNgramTokenizer {
+        CharNgramTokenizer { min: 3, max: 5 }
+    }
+}
+
+impl Tokenizer for CharNgramTokenizer {
+    fn tokenize(&self, document: &str) -> Vec<String> {
+        let mut tokens = Vec::new();
+        for word in document.split_whitespace() {
+            let chars: Vec<char> = alloc::format!("<{}>", word).chars().collect();
+            if chars.len() < self.min {
+                tokens.push(chars.iter().collect());
+                continue;
+            }
+            for n in self.min..self.max + 1 {
+                for window in chars.windows(n) {
+                    tokens.push(window.iter().collect());
+                }
+            }
+        }
+        tokens
+    }
+}
+
+// generates every n-gram of the tokens for each n from min to max (inclusive),
+// joining the words of each n-gram with a space
+pub fn ngrams<S: AsRef<str>>(tokens: &[S], min: usize, max: usize) -> Vec<String> {
+    let mut features = Vec::new();
+    for n in min..max + 1 {
+        for window in tokens.windows(n) {
+            let mut feature = String::new();
+            for (i, token) in window.iter().enumerate() {
+                if i > 0 {
+                    feature.push(' ');
+                }
+                feature.push_str(token.as_ref());
+            }
+            features.push(feature);
+        }
+    }
+    features
+}
+
+// generates the skip-grams of the tokens, every pair of tokens with 1 to
+// `window` (inclusive) tokens between them, written as the pair's words with
+// an underscore in place of each token skipped
+pub fn skip_grams<S: AsRef<str>>(tokens: &[S], window: usize) -> Vec<String> {
+    let mut features = Vec::new();
+    for skipped in 1..window + 1 {
+        for pair in tokens.windows(skipped + 2) {
+            let mut feature = String::from(pair[0].as_ref());
+            for _ in 0..skipped {
+                feature.push_str(" _");
+            }
+            feature.push(' ');
+            feature.push_str(pair[skipped + 1].as_ref());
+            features.push(feature);
+        }
+    }
+    features
+}
+
+// the name of the bucket a feature hashes into. FNV-1a is used because,
+// unlike the standard library's hasher, it is the same on every platform
+// and release, so a saved model hashes features the way it was trained.
+pub fn bucket(feature: &str, num_buckets: usize) -> String {
+    let mut hash = 0xcbf29ce484222325u64;
+    for &byte in feature.as_bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    alloc::format!("#{}", hash % num_buckets as u64)
+}
@@ -0,0 +1,214 @@
+//! Searching a grid of classifier settings for the ones that cross-validate
+//! best on a set of examples
+//!
+//! ```rust
+//! use classifier::FeatureWeighting;
+//! use classifier::tuning::{self, ParameterGrid};
+//!
+//! let examples = vec![
+//!     ("ham hock pork belly", "meat"),
+//!     ("kale okra spinach", "veggie"),
+//!     ("ball tip short ribs", "meat"),
+//!     ("water chestnut kale", "veggie"),
+//!     ("pork ribs and the kale", "meat"),
+//!     ("the okra and spinach", "veggie"),
+//! ];
+//!
+//! let grid = ParameterGrid::new()
+//!     .smoothing(&[0.1, 1.0])
+//!     .ngram_ranges(&[(1, 1), (1, 2)])
+//!     .stop_words(&["the", "and"])
+//!     .weightings(&[FeatureWeighting::Counts, FeatureWeighting::TfIdf]);
+//! assert_eq!(grid.configurations().len(), 16);
+//!
+//! let report = tuning::grid_search(&examples, &grid, 2).unwrap();
+//! let best = report.best().unwrap();
+//! println!("{:?} has accuracy {}", best.configuration, best.report.average.accuracy);
+//! ```
+
+use error::ClassifierError;
+use naive_bayes::{parallel_map, Classifier, FeatureWeighting, DEFAULT_SMOOTHING};
+use tokenizer::Tokenizer;
+use validation::{cross_validate_with, CrossValidationReport};
+
+/// The values of each setting to try, every combination of which is a
+/// `Configuration`. By default each setting has one value, the default of
+/// a new classifier, and a setting with no values has no combinations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterGrid {
+    /// The smoothing values to try
+    pub smoothing: Vec<f64>,
+    /// The n-gram ranges to try
+    pub ngram_ranges: Vec<(usize, usize)>,
+    /// The stop word lists to try, an empty list uses none
+    pub stop_words: Vec<Vec<String>>,
+    /// The feature weightings to try
+    pub weightings: Vec<FeatureWeighting>,
+}
+
+/// One combination of the settings of a `ParameterGrid`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Configuration {
+    /// The smoothing value
+    pub smoothing: f64,
+    /// The range of n-gram sizes
+    pub ngram_range: (usize, usize),
+    /// The stop words, none if empty
+    pub stop_words: Vec<String>,
+    /// How the occurrences of words are weighted
+    pub weighting: FeatureWeighting,
+}
+
+/// How well one configuration cross-validated
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridSearchResult {
+    /// The settings the classifiers were given
+    pub configuration: Configuration,
+    /// The scores of the classifiers with those settings
+    pub report: CrossValidationReport,
+}
+
+/// The results of a grid search, one for each configuration in the order
+/// of `ParameterGrid::configurations`
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridSearchReport {
+    /// The result of every configuration
+    pub results: Vec<GridSearchResult>,
+}
+
+impl ParameterGrid {
+
+    /// Creates a grid with the default value of every setting
+    pub fn new() -> ParameterGrid {
+        ParameterGrid {
+            smoothing: vec![DEFAULT_SMOOTHING],
+            ngram_ranges: vec![(1, 1)],
+            stop_words: vec![Vec::new()],
+            weightings: vec![FeatureWeighting::Counts],
+        }
+    }
+
+    /// Sets the smoothing values to try
+    pub fn smoothing(mut self, values: &[f64]) -> ParameterGrid {
+        self.smoothing = values.to_vec();
+        self
+    }
+
+    /// Sets the n-gram ranges to try
+    pub fn ngram_ranges(mut self, ranges: &[(usize, usize)]) -> ParameterGrid {
+        self.ngram_ranges = ranges.to_vec();
+        self
+    }
+
+    /// Tries both without stop words and with the given ones
+    pub fn stop_words<S: AsRef<str>>(mut self, stop_words: &[S]) -> ParameterGrid {
+        let stop_words = stop_words.iter().map(|word| word.as_ref().to_string()).collect();
+        self.stop_words = vec![Vec::new(), stop_words];
+        self
+    }
+
+    /// Sets the feature weightings to try
+    pub fn weightings(mut self, weightings: &[FeatureWeighting]) -> ParameterGrid {
+        self.weightings = weightings.to_vec();
+        self
+    }
+
+    /// Gets every combination of the settings, varying the weighting
+    /// fastest and the smoothing slowest
+    pub fn configurations(&self) -> Vec<Configuration> {
+        let mut configurations = Vec::new();
+        for &smoothing in self.smoothing.iter() {
+            for &ngram_range in self.ngram_ranges.iter() {
+                for stop_words in self.stop_words.iter() {
+                    for &weighting in self.weightings.iter() {
+                        configurations.push(Configuration {
+                            smoothing,
+                            ngram_range,
+                            stop_words: stop_words.clone(),
+                            weighting,
+                        });
+                    }
+                }
+            }
+        }
+        configurations
+    }
+}
+
+impl Default for ParameterGrid {
+    fn default() -> ParameterGrid {
+        ParameterGrid::new()
+    }
+}
+
+impl Configuration {
+
+    /// Gives a classifier these settings. It should not have any documents
+    /// yet. Fails if the smoothing value or n-gram range is invalid.
+    pub fn apply<T: Tokenizer>(&self, classifier: &mut Classifier<T>) -> Result<(), ClassifierError> {
+        classifier.set_smoothing(self.smoothing)?;
+        classifier.set_ngram_range(self.ngram_range.0, self.ngram_range.1)?;
+        classifier.set_stop_words(self.stop_words.iter());
+        classifier.set_feature_weighting(self.weighting);
+        Ok(())
+    }
+}
+
+impl GridSearchReport {
+
+    /// The result with the highest average accuracy, the first of any that
+    /// tie, or `None` if no configurations were searched
+    pub fn best(&self) -> Option<&GridSearchResult> {
+        self.results.iter().fold(None, |best: Option<&GridSearchResult>, result| match best {
+            Some(best) if best.report.average.accuracy >= result.report.average.accuracy => Some(best),
+            _ => Some(result),
+        })
+    }
+}
+
+/// Stratified k-fold cross-validates a new classifier with each
+/// configuration of the grid on the (document, label) examples, see
+/// `validation::cross_validate_with`. Fails if any configuration is
+/// invalid or fails to cross-validate.
+pub fn grid_search<D, L>(examples: &[(D, L)], grid: &ParameterGrid, k: usize)
+                         -> Result<GridSearchReport, ClassifierError>
+    where D: AsRef<str> + Sync, L: AsRef<str> + Sync
+{
+    grid_search_with(examples, grid, k, false, Classifier::new)
+}
+
+/// Same as `grid_search`, but cross-validates the configurations across all
+/// of the available threads
+pub fn grid_search_parallel<D, L>(examples: &[(D, L)], grid: &ParameterGrid, k: usize)
+                                  -> Result<GridSearchReport, ClassifierError>
+    where D: AsRef<str> + Sync, L: AsRef<str> + Sync
+{
+    grid_search_with(examples, grid, k, true, Classifier::new)
+}
+
+/// Grid searches classifiers built by `new_classifier`, which allows them
+/// to be configured (with a tokenizer, variant, etc.) before each
+/// configuration is applied, optionally across all of the available threads
+pub fn grid_search_with<D, L, T, F>(examples: &[(D, L)], grid: &ParameterGrid, k: usize, parallel: bool,
+                                    new_classifier: F) -> Result<GridSearchReport, ClassifierError>
+    where D: AsRef<str> + Sync, L: AsRef<str> + Sync, T: Tokenizer, F: Fn() -> Classifier<T> + Sync
+{
+    let configurations = grid.configurations();
+    for configuration in configurations.iter() {
+        configuration.apply(&mut new_classifier())?;
+    }
+
+    let evaluate = |configuration: &Configuration| {
+        cross_validate_with(examples, k, true, || {
+            let mut classifier = new_classifier();
+            configuration.apply(&mut classifier).expect("the configuration was checked");
+            classifier
+        }).map(|report| GridSearchResult { configuration: configuration.clone(), report })
+    };
+    let results = if parallel {
+        parallel_map(&configurations, evaluate)
+    } else {
+        configurations.iter().map(evaluate).collect()
+    };
+    Ok(GridSearchReport { results: results.into_iter().collect::<Result<_, _>>()? })
+}
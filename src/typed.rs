@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::slice;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde::de::{DeserializeOwned, Error};
+use serde_json;
+use error::ClassifierError;
+use naive_bayes::Classifier;
+use tokenizer::{Tokenizer, WhitespaceTokenizer};
+
+/// A naive Bayes classifier with labels of any type that can be compared,
+/// hashed, and cloned, such as an enum, so documents are classified as that
+/// type directly. Labels are `String`s by default.
+///
+/// Each label is kept in the `NaiveBayes` inside under a key made from its
+/// position in the order the labels were first added, so labels come out in
+/// that order and ties go to the label added first. Serializing a typed
+/// classifier also requires its labels to be serializable.
+///
+/// ```rust
+/// use classifier::TypedNaiveBayes;
+///
+/// #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+/// enum Food { Meat, Veggie }
+///
+/// let mut nb = TypedNaiveBayes::new();
+/// nb.add_document("pork belly ribs", &Food::Meat);
+/// nb.add_document("kale okra spinach", &Food::Veggie);
+/// nb.train();
+/// assert_eq!(nb.classify("pork ribs").unwrap(), Food::Meat);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TypedNaiveBayes<L = String, T = WhitespaceTokenizer> {
+    classifier: Classifier<T>,
+    // every label ever added, in the order they were first added, each known
+    // to the classifier by the key of its position. A label the classifier
+    // no longer has keeps its position, in case it is added again.
+    labels: Vec<L>,
+    positions: HashMap<L, usize>,
+}
+
+// the number of digits the positions in keys are padded to, enough for any
+// position, so that sorting the keys sorts the positions
+static KEY_WIDTH: usize = 20;
+
+impl<L: Eq + Hash + Clone> TypedNaiveBayes<L> {
+
+    /// Creates a new classifier that tokenizes documents by breaking on
+    /// whitespace characters
+    pub fn new() -> TypedNaiveBayes<L> {
+        TypedNaiveBayes::with_tokenizer(WhitespaceTokenizer)
+    }
+}
+
+impl<L: Eq + Hash + Clone + DeserializeOwned> TypedNaiveBayes<L> {
+
+    /// Builds a classifier from a JSON string made by `to_json`
+    pub fn from_json(encoded: &str) -> Result<TypedNaiveBayes<L>, ClassifierError> {
+        serde_json::from_str(encoded).map_err(|e| ClassifierError::Decode(e.to_string()))
+    }
+}
+
+impl<L: Eq + Hash + Clone> Default for TypedNaiveBayes<L> {
+    fn default() -> TypedNaiveBayes<L> {
+        TypedNaiveBayes::new()
+    }
+}
+
+impl<L: Eq + Hash + Clone, T: Tokenizer> TypedNaiveBayes<L, T> {
+
+    /// Creates a new classifier that uses the given tokenizer to break
+    /// documents into words
+    pub fn with_tokenizer(tokenizer: T) -> TypedNaiveBayes<L, T> {
+        TypedNaiveBayes { classifier: Classifier::with_tokenizer(tokenizer), labels: Vec::new(), positions: HashMap::new() }
+    }
+
+    /// Wraps a classifier that has been configured (with a tokenizer,
+    /// smoothing value, etc.) but not given any documents. Fails with
+    /// `InvalidLabel` if it already has a label, as no `L` stands for it.
+    pub fn from_classifier(classifier: Classifier<T>) -> Result<TypedNaiveBayes<L, T>, ClassifierError> {
+        if let Some(label) = classifier.get_labels().into_iter().next() {
+            return Err(ClassifierError::InvalidLabel(label));
+        }
+        Ok(TypedNaiveBayes { classifier, labels: Vec::new(), positions: HashMap::new() })
+    }
+
+    /// Gets the classifier, for everything the typed classifier leaves out.
+    /// It knows each label by the key of its position, see `TypedNaiveBayes`.
+    pub fn classifier(&self) -> &Classifier<T> {
+        &self.classifier
+    }
+
+    /// Unwraps the classifier, which knows each label by the key of its
+    /// position
+    pub fn into_classifier(self) -> Classifier<T> {
+        self.classifier
+    }
+
+    /// Adds a labeled document, see `NaiveBayes::add_document`
+    pub fn add_document(&mut self, document: &str, label: &L) {
+        let key = self.key(label);
+        self.classifier.add_document(document, &key);
+    }
+
+    /// Adds a labeled, tokenized document, see `NaiveBayes::add_document_tokenized`
+    pub fn add_document_tokenized<S: AsRef<str>>(&mut self, document: &[S], label: &L) {
+        let key = self.key(label);
+        self.classifier.add_document_tokenized(document, &key);
+    }
+
+    /// Removes a labeled document, see `NaiveBayes::remove_document`. The
+    /// errors hold the key of the label.
+    pub fn remove_document(&mut self, document: &str, label: &L) -> Result<(), ClassifierError> {
+        let key = self.key(label);
+        self.classifier.remove_document(document, &key)
+    }
+
+    /// Removes a labeled, tokenized document, see
+    /// `NaiveBayes::remove_document_tokenized`. The errors hold the key of
+    /// the label.
+    pub fn remove_document_tokenized<S: AsRef<str>>(&mut self, document: &[S], label: &L) -> Result<(), ClassifierError> {
+        let key = self.key(label);
+        self.classifier.remove_document_tokenized(document, &key)
+    }
+
+    /// Removes a label and all of its documents, see
+    /// `NaiveBayes::remove_label`. The errors hold the key of the label.
+    pub fn remove_label(&mut self, label: &L) -> Result<(), ClassifierError> {
+        let key = self.key(label);
+        self.classifier.remove_label(&key)
+    }
+
+    /// Renames a label, see `NaiveBayes::rename_label`. The errors hold the
+    /// key of the label.
+    pub fn rename_label(&mut self, old: &L, new: &L) -> Result<(), ClassifierError> {
+        self.merge_labels(slice::from_ref(old), new)
+    }
+
+    /// Combines the labels into `new_label`, see `NaiveBayes::merge_labels`.
+    /// The combined label keeps the position of `new_label`, which comes
+    /// after every other label if it is new. The errors hold the key of the
+    /// label.
+    pub fn merge_labels(&mut self, labels: &[L], new_label: &L) -> Result<(), ClassifierError> {
+        let keys: Vec<String> = labels.iter().map(|label| self.key(label)).collect();
+        let new_key = self.key(new_label);
+        self.classifier.merge_labels(&keys, &new_key)
+    }
+
+    /// Trains the classifier on the documents that have been added so far
+    pub fn train(&mut self) {
+        self.classifier.train();
+    }
+
+    /// Gets all of the labels the classifier has documents for, in the
+    /// order they were first added. Fails with `InvalidLabel` if the
+    /// classifier has a label no `L` stands for, which only a corrupt
+    /// serialized classifier can have.
+    pub fn get_labels(&self) -> Result<Vec<L>, ClassifierError> {
+        self.classifier.get_labels().iter().map(|key| self.label(key).cloned()).collect()
+    }
+
+    /// Computes a classifying label for the document, see `NaiveBayes::classify`
+    pub fn classify(&self, document: &str) -> Result<L, ClassifierError> {
+        self.label(&self.classifier.classify(document)?).cloned()
+    }
+
+    /// Computes a classifying label for the tokenized document, see
+    /// `NaiveBayes::classify_tokenized`
+    pub fn classify_tokenized<S: AsRef<str>>(&self, document: &[S]) -> Result<L, ClassifierError> {
+        self.label(&self.classifier.classify_tokenized(document)?).cloned()
+    }
+
+    /// Gets every label and its posterior probability given the document,
+    /// in the order the labels were first added, see
+    /// `NaiveBayes::get_document_posteriors`
+    pub fn get_document_posteriors(&self, document: &str) -> Result<Vec<(L, f64)>, ClassifierError> {
+        self.classifier.get_document_posteriors(document)?
+            .into_iter()
+            .map(|(key, p)| self.label(&key).map(|label| (label.clone(), p)))
+            .collect()
+    }
+
+    // the label's key in the classifier, giving the label the next position
+    // if it is new
+    fn key(&mut self, label: &L) -> String {
+        let position = match self.positions.get(label) {
+            Some(&position) => position,
+            None => {
+                self.labels.push(label.clone());
+                self.positions.insert(label.clone(), self.labels.len() - 1);
+                self.labels.len() - 1
+            },
+        };
+        format!("{:0width$}", position, width = KEY_WIDTH)
+    }
+
+    // the label the classifier knows by the key
+    fn label(&self, key: &str) -> Result<&L, ClassifierError> {
+        key.parse::<usize>().ok()
+            .and_then(|position| self.labels.get(position))
+            .ok_or_else(|| ClassifierError::InvalidLabel(key.to_string()))
+    }
+}
+
+impl<L: Serialize, T> TypedNaiveBayes<L, T> {
+
+    /// Encodes the classifier and its labels as a JSON string
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("encoding JSON failed")
+    }
+}
+
+// a typed classifier as it is serialized, its labels in order beside the
+// classifier that knows them by their positions
+#[derive(Serialize, Deserialize)]
+struct EncodedTypedClassifier<Labels, C> {
+    labels: Labels,
+    classifier: C,
+}
+
+impl<L: Serialize, T> Serialize for TypedNaiveBayes<L, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        EncodedTypedClassifier { labels: &self.labels, classifier: &self.classifier }.serialize(serializer)
+    }
+}
+
+impl<'de, L, T> Deserialize<'de> for TypedNaiveBayes<L, T>
+    where L: Eq + Hash + Clone + Deserialize<'de>, T: Default
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<TypedNaiveBayes<L, T>, D::Error> {
+        let encoded: EncodedTypedClassifier<Vec<L>, Classifier<T>> = Deserialize::deserialize(deserializer)?;
+        let mut positions = HashMap::new();
+        for (position, label) in encoded.labels.iter().enumerate() {
+            if positions.insert(label.clone(), position).is_some() {
+                return Err(D::Error::custom(format!("the label at position {} was given before", position)));
+            }
+        }
+        Ok(TypedNaiveBayes { classifier: encoded.classifier, labels: encoded.labels, positions })
+    }
+}
@@ -0,0 +1,144 @@
+//! K-fold cross-validation of naive Bayes classifiers
+//!
+//! ```rust
+//! use classifier::validation;
+//!
+//! let examples: Vec<(String, String)> = vec![
+//!     ("ham hock pork belly".to_string(), "meat".to_string()),
+//!     ("kale okra spinach".to_string(), "veggie".to_string()),
+//!     ("ball tip short ribs".to_string(), "meat".to_string()),
+//!     ("water chestnut kale".to_string(), "veggie".to_string()),
+//! ];
+//!
+//! let report = validation::stratified_k_fold_cross_validate(&examples, 2).unwrap();
+//! assert_eq!(report.folds.len(), 2);
+//! println!("accuracy: {}", report.average.accuracy);
+//! ```
+
+use std::collections::BTreeMap;
+use error::ClassifierError;
+use evaluation::ConfusionMatrix;
+use naive_bayes::Classifier;
+use tokenizer::Tokenizer;
+
+/// Scores measuring how well a classifier labeled a set of documents.
+/// Precision, recall, and F1 are macro averages over every label that
+/// was either expected or predicted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Metrics {
+    /// The fraction of documents that were labeled correctly
+    pub accuracy: f64,
+    /// The fraction of the documents given a label that really have it
+    pub precision: f64,
+    /// The fraction of the documents with a label that were given it
+    pub recall: f64,
+    /// The harmonic mean of precision and recall
+    pub f1: f64,
+}
+
+/// The results of cross-validating a classifier
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrossValidationReport {
+    /// The scores of each fold, measured on that fold's held out documents
+    pub folds: Vec<Metrics>,
+    /// The mean of the scores of every fold
+    pub average: Metrics,
+}
+
+/// Splits the (document, label) examples into `k` folds, then for each fold
+/// trains a new classifier on the other folds and measures how well it
+/// labels the documents in that fold. Fails if a held out document cannot
+/// be classified, e.g. because it has no features.
+pub fn k_fold_cross_validate<D, L>(examples: &[(D, L)], k: usize) -> Result<CrossValidationReport, ClassifierError>
+    where D: AsRef<str>, L: AsRef<str>
+{
+    cross_validate_with(examples, k, false, Classifier::new)
+}
+
+/// Same as `k_fold_cross_validate`, but every fold gets (as close as
+/// possible to) the same proportion of each label as the whole set of examples
+pub fn stratified_k_fold_cross_validate<D, L>(examples: &[(D, L)], k: usize)
+                                              -> Result<CrossValidationReport, ClassifierError>
+    where D: AsRef<str>, L: AsRef<str>
+{
+    cross_validate_with(examples, k, true, Classifier::new)
+}
+
+/// Cross-validates classifiers built by `new_classifier`, which allows
+/// the classifiers to be configured (with a tokenizer, smoothing value, etc.)
+/// before they are trained on each fold
+pub fn cross_validate_with<D, L, T, F>(examples: &[(D, L)], k: usize, stratified: bool, new_classifier: F)
+                                       -> Result<CrossValidationReport, ClassifierError>
+    where D: AsRef<str>, L: AsRef<str>, T: Tokenizer, F: Fn() -> Classifier<T>
+{
+    if k < 2 || k > examples.len() {
+        return Err(ClassifierError::InvalidFoldCount(k));
+    }
+
+    let folds = assign_folds(examples, k, stratified);
+    let mut fold_metrics = Vec::with_capacity(k);
+
+    for fold in 0..k {
+        let mut classifier = new_classifier();
+        for (i, (document, label)) in examples.iter().enumerate() {
+            if folds[i] != fold {
+                classifier.add_document(document.as_ref(), label.as_ref());
+            }
+        }
+        classifier.train();
+
+        let mut results = Vec::new();
+        for (i, (document, label)) in examples.iter().enumerate() {
+            if folds[i] == fold {
+                results.push((classifier.classify(document.as_ref())?, label.as_ref()));
+            }
+        }
+        fold_metrics.push(metrics(&results));
+    }
+
+    let n = k as f64;
+    let average = Metrics {
+        accuracy: fold_metrics.iter().map(|m| m.accuracy).sum::<f64>() / n,
+        precision: fold_metrics.iter().map(|m| m.precision).sum::<f64>() / n,
+        recall: fold_metrics.iter().map(|m| m.recall).sum::<f64>() / n,
+        f1: fold_metrics.iter().map(|m| m.f1).sum::<f64>() / n,
+    };
+
+    Ok(CrossValidationReport { folds: fold_metrics, average })
+}
+
+// assigns each example to a fold, round robin in the order the examples were
+// given, or round robin within each label when stratified
+fn assign_folds<D, L: AsRef<str>>(examples: &[(D, L)], k: usize, stratified: bool) -> Vec<usize> {
+    if !stratified {
+        return (0..examples.len()).map(|i| i % k).collect();
+    }
+
+    let mut by_label: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (i, (_, label)) in examples.iter().enumerate() {
+        by_label.entry(label.as_ref()).or_default().push(i);
+    }
+
+    // keep counting across labels so the small labels don't all land in
+    // the first folds
+    let mut folds = vec![0; examples.len()];
+    let mut next = 0;
+    for indices in by_label.values() {
+        for &i in indices.iter() {
+            folds[i] = next % k;
+            next += 1;
+        }
+    }
+    folds
+}
+
+// computes the metrics of a set of (predicted, actual) labels
+fn metrics(results: &[(String, &str)]) -> Metrics {
+    let matrix = ConfusionMatrix::from_pairs(results);
+    Metrics {
+        accuracy: matrix.accuracy(),
+        precision: matrix.macro_precision(),
+        recall: matrix.macro_recall(),
+        f1: matrix.macro_f1(),
+    }
+}
@@ -0,0 +1,120 @@
+use error::ClassifierError;
+use naive_bayes::{Classifier, ComplementCounts};
+use tokenizer::Tokenizer;
+use vocabulary::WordId;
+
+/// Turns documents into `DocumentVector`s and scores every label from them,
+/// made with `NaiveBayes::vectorizer`
+///
+/// A document is tokenized and its words are looked up in the vocabulary
+/// once, when it is vectorized, and a word that appears many times is
+/// scored once per label times its count. A vector can be classified as
+/// often as needed without going back to the text, and an online complement
+/// classifier's pooled word counts are only summed once for every document.
+///
+/// ```rust
+/// use classifier::NaiveBayes;
+///
+/// let mut nb = NaiveBayes::new();
+/// nb.add_document("pork belly ribs", "meat");
+/// nb.add_document("kale okra spinach", "veggie");
+/// nb.train();
+///
+/// let vectorizer = nb.vectorizer();
+/// let vector = vectorizer.vectorize("pork pork pork kale");
+/// assert_eq!(vector.distinct_words(), 2);
+/// assert_eq!(vectorizer.classify(&vector).unwrap(), "meat");
+/// ```
+#[derive(Debug)]
+pub struct Vectorizer<'a, T: 'a> {
+    pub(crate) classifier: &'a Classifier<T>,
+    // the pooled word counts of an online complement classifier
+    pub(crate) complement: Option<ComplementCounts>,
+}
+
+/// A document as the weighted count of each distinct word of a classifier's
+/// vocabulary, made by a `Vectorizer`. It is only meaningful to the
+/// classifier it was made with, and only until the classifier changes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentVector {
+    // (word id, count times the word's weight) in order of id
+    pub(crate) counts: Vec<(WordId, f64)>,
+    // the number of features that are not in the vocabulary
+    pub(crate) unknown: f64,
+}
+
+impl DocumentVector {
+
+    /// The number of distinct words of the document that are in the vocabulary
+    pub fn distinct_words(&self) -> usize {
+        self.counts.len()
+    }
+
+    /// The number of features of the document that are not in the
+    /// vocabulary (each distinct one once for the Bernoulli variant)
+    pub fn unknown_features(&self) -> usize {
+        self.unknown as usize
+    }
+
+    /// Whether the document has no features at all
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty() && self.unknown == 0.0
+    }
+
+    /// Gets the document as a sparse vector of (word id, value) pairs in
+    /// order of id, for handing to linear algebra crates such as ndarray or
+    /// sprs. A word's value is its count times its weight, and
+    /// `Vectorizer::vocabulary_mapping` gives the word of each id. Features
+    /// that are not in the vocabulary are left out.
+    pub fn to_sparse(&self) -> Vec<(u32, f64)> {
+        self.counts.clone()
+    }
+}
+
+impl<'a, T: Tokenizer> Vectorizer<'a, T> {
+
+    /// Tokenizes the document with the classifier's tokenizer and vectorizes it
+    pub fn vectorize(&self, document: &str) -> DocumentVector {
+        self.vectorize_tokenized(&self.classifier.tokenize(document))
+    }
+
+    /// Vectorizes a document that has been tokenized into a vector of strings
+    pub fn vectorize_tokenized<S: AsRef<str>>(&self, document: &[S]) -> DocumentVector {
+        self.classifier.vectorize_tokens(document)
+    }
+
+    /// Builds a vector from (word id, value) pairs, the inverse of
+    /// `DocumentVector::to_sparse`, e.g. to classify features that were
+    /// post-processed elsewhere. The pairs need not be in order, the values
+    /// of pairs with the same id are summed, and ids that aren't in the
+    /// vocabulary count as that many unknown features.
+    pub fn from_sparse(&self, entries: &[(u32, f64)]) -> DocumentVector {
+        self.classifier.sparse_vector(entries)
+    }
+
+    /// Gets every word of the classifier's vocabulary and its id, the
+    /// index it has in sparse vectors, in order of id. Ids are not reused
+    /// and words that were removed leave gaps, so they are all less than
+    /// `dimension` but not every id below it has a word.
+    pub fn vocabulary_mapping(&self) -> Vec<(u32, &'a str)> {
+        self.classifier.vocabulary_ids()
+    }
+
+    /// The number of columns a matrix of sparse vectors of the classifier
+    /// needs, one more than the largest word id
+    pub fn dimension(&self) -> usize {
+        self.classifier.vocabulary_ids().last().map_or(0, |&(id, _)| id as usize + 1)
+    }
+
+    /// Computes a classifying label for the vectorized document, failing as
+    /// `NaiveBayes::classify` would
+    pub fn classify(&self, vector: &DocumentVector) -> Result<String, ClassifierError> {
+        self.classifier.classify_vector(vector, self.complement.as_ref())
+    }
+
+    /// Gets every label and its posterior probability given the vectorized
+    /// document, see `NaiveBayes::get_document_posteriors`
+    pub fn get_posteriors(&self, vector: &DocumentVector) -> Result<Vec<(String, f64)>, ClassifierError> {
+        self.classifier.vector_posteriors(vector, self.complement.as_ref())
+    }
+}
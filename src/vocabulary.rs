@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+use std::mem;
+
+/// The id of a word in a `Vocabulary`
+pub type WordId = u32;
+
+// every word the classifier knows, each stored once and referred to by its
+// id everywhere else. Ids are never reused, a removed word's slot is emptied
+// and the word gets a new id if it is ever added again.
+#[derive(Debug, Clone, Default)]
+pub struct Vocabulary {
+    words: Vec<String>,
+    ids: HashMap<String, WordId>,
+}
+
+impl Vocabulary {
+
+    pub fn new() -> Vocabulary {
+        Vocabulary::default()
+    }
+
+    // gets the id of the word, adding it if it is new
+    pub fn intern(&mut self, word: &str) -> WordId {
+        if let Some(&id) = self.ids.get(word) {
+            return id;
+        }
+        let id = self.words.len() as WordId;
+        self.words.push(word.to_string());
+        self.ids.insert(word.to_string(), id);
+        id
+    }
+
+    pub fn id(&self, word: &str) -> Option<WordId> {
+        self.ids.get(word).cloned()
+    }
+
+    pub fn word(&self, id: WordId) -> &str {
+        &self.words[id as usize]
+    }
+
+    pub fn contains(&self, id: WordId) -> bool {
+        self.words.get(id as usize).is_some_and(|word| self.ids.get(word) == Some(&id))
+    }
+
+    pub fn remove(&mut self, id: WordId) {
+        if self.contains(id) {
+            let word = mem::take(&mut self.words[id as usize]);
+            self.ids.remove(&word);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.words.shrink_to_fit();
+        self.ids.shrink_to_fit();
+    }
+
+    // roughly how many bytes the vocabulary takes up
+    pub fn heap_size(&self) -> usize {
+        let words: usize = self.ids.keys().map(|word| word.len()).sum();
+        // each word is held twice, once in `words` and once in `ids`
+        2 * words + self.words.capacity() * mem::size_of::<String>()
+            + self.ids.capacity() * (mem::size_of::<String>() + mem::size_of::<WordId>())
+    }
+
+    // the id and word of every word, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (WordId, &str)> {
+        self.ids.iter().map(|(word, &id)| (id, word.as_str()))
+    }
+}
@@ -0,0 +1,69 @@
+use wasm_bindgen::prelude::*;
+use naive_bayes::Classifier;
+
+/// A naive Bayes classifier for JavaScript, exported to it as `NaiveBayes`
+/// when building for WebAssembly. Requires the `wasm` feature.
+///
+/// ```js
+/// import { NaiveBayes } from "classifier";
+///
+/// const nb = new NaiveBayes();
+/// nb.add_document("pork belly ribs", "meat");
+/// nb.add_document("kale okra spinach", "veggie");
+/// nb.train();
+/// nb.classify("pork");  // "meat"
+///
+/// const copy = NaiveBayes.from_json(nb.to_json());
+/// ```
+///
+/// The methods that can fail throw a JavaScript `Error` holding the
+/// `ClassifierError`'s message.
+#[wasm_bindgen(js_name = NaiveBayes)]
+#[derive(Debug, Clone, Default)]
+pub struct WasmClassifier {
+    classifier: Classifier,
+}
+
+#[wasm_bindgen(js_class = NaiveBayes)]
+impl WasmClassifier {
+
+    /// Creates a new classifier that tokenizes documents by breaking on
+    /// whitespace characters
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> WasmClassifier {
+        WasmClassifier::default()
+    }
+
+    /// Builds a classifier from a JSON string, see `NaiveBayes::from_json`
+    pub fn from_json(encoded: &str) -> Result<WasmClassifier, JsError> {
+        Ok(WasmClassifier { classifier: Classifier::from_json(encoded)? })
+    }
+
+    /// Adds a labeled document to the classifier
+    pub fn add_document(&mut self, document: &str, label: &str) {
+        self.classifier.add_document(document, label);
+    }
+
+    /// Trains the classifier on the documents added so far
+    pub fn train(&mut self) {
+        self.classifier.train();
+    }
+
+    /// Computes a classifying label for the document
+    pub fn classify(&self, document: &str) -> Result<String, JsError> {
+        Ok(self.classifier.classify(document)?)
+    }
+
+    /// Encodes the classifier as a JSON string
+    pub fn to_json(&self) -> String {
+        self.classifier.to_json()
+    }
+}
+
+impl WasmClassifier {
+
+    /// Gets the classifier, for everything the JavaScript bindings leave out
+    pub fn classifier(&self) -> &Classifier {
+        &self.classifier
+    }
+}
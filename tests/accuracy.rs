@@ -0,0 +1,40 @@
+extern crate classifier;
+use classifier::{LogisticRegression, NaiveBayes, NaiveBayesVariant, TextClassifier};
+use classifier::dataset;
+use classifier::evaluation::ConfusionMatrix;
+
+// a small bundled corpus of food descriptions, kept fixed so that changes
+// to training and scoring can be checked for accuracy regressions
+static CORPUS: &str = include_str!("data/foods.jsonl");
+
+fn accuracy(model: &mut dyn TextClassifier) -> f64 {
+    let examples: Vec<(String, String)> = dataset::from_jsonl(CORPUS.as_bytes())
+        .map(|record| record.unwrap())
+        .collect();
+    let (train, test) = dataset::stratified_train_test_split(&examples, 0.25, 42).unwrap();
+    for (document, label) in train.iter() {
+        model.add_document(document, label);
+    }
+    model.train();
+
+    let results: Vec<(String, String)> = test.iter()
+        .map(|(document, label)| (model.classify(document).unwrap(), label.clone()))
+        .collect();
+    ConfusionMatrix::from_pairs(&results).accuracy()
+}
+
+#[test]
+fn naive_bayes_accuracy_test() {
+    for &variant in [NaiveBayesVariant::Multinomial, NaiveBayesVariant::Bernoulli, NaiveBayesVariant::Complement].iter() {
+        let mut nb = NaiveBayes::new();
+        nb.set_variant(variant);
+        let accuracy = accuracy(&mut nb);
+        assert!( accuracy >= 0.9, "{:?} accuracy fell to {}", variant, accuracy );
+    }
+}
+
+#[test]
+fn logistic_regression_accuracy_test() {
+    let accuracy = accuracy(&mut LogisticRegression::new());
+    assert!( accuracy >= 0.9, "accuracy fell to {}", accuracy );
+}
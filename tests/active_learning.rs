@@ -0,0 +1,47 @@
+extern crate classifier;
+use classifier::{ClassifierError, NaiveBayes};
+use classifier::active_learning::Uncertainty;
+
+fn trained() -> NaiveBayes {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("pork belly ribs bacon", "meat");
+    nb.add_document("kale okra spinach chard", "veggie");
+    nb.add_document("apple pear plum fig", "fruit");
+    nb.train();
+    nb
+}
+
+#[test]
+fn uncertainty_score_test() {
+    assert_eq!(Uncertainty::Entropy.score(&[1.0, 0.0]), 0.0);
+    assert_eq!(Uncertainty::Entropy.score(&[0.5, 0.5]), 1.0);
+    assert_eq!(Uncertainty::Entropy.score(&[0.25; 4]), 2.0);
+    assert!((Uncertainty::Margin.score(&[0.2, 0.5, 0.3]) - 0.8).abs() < 1e-12);
+    assert_eq!(Uncertainty::Margin.score(&[1.0]), 0.0);
+    assert_eq!(Uncertainty::LeastConfidence.score(&[0.2, 0.7, 0.1]), 1.0 - 0.7);
+}
+
+#[test]
+fn most_uncertain_test() {
+    let nb = trained();
+    let documents = ["pork ribs bacon", "pork kale", "pork kale apple", "", "plum fig"];
+    let ranked = nb.most_uncertain(&documents, 10).unwrap();
+    // the empty document is left out
+    let order: Vec<usize> = ranked.iter().map(|&(i, _)| i).collect();
+    assert_eq!(order.len(), 4);
+    assert_eq!(order[0], 2);
+    assert_eq!(order[1], 1);
+    assert!(ranked.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+
+    // two labels tied is the least certain by margin
+    let ranked = nb.most_uncertain_by(&documents, 1, Uncertainty::Margin).unwrap();
+    assert_eq!(ranked[0].0, 1);
+    assert!(ranked[0].1 > 0.99);
+    assert_eq!(nb.most_uncertain(&documents, 0).unwrap(), vec![]);
+}
+
+#[test]
+fn most_uncertain_untrained_test() {
+    let nb = NaiveBayes::new();
+    assert_eq!(nb.most_uncertain(&["pork"], 1), Err(ClassifierError::Untrained));
+}
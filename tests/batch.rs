@@ -0,0 +1,48 @@
+extern crate classifier;
+use classifier::NaiveBayes;
+
+fn trained_classifier() -> NaiveBayes {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+    nb.add_document("ball tip pork chop short ribs", "meat");
+    nb.add_document("okra kale spinach water chestnut", "veggie");
+    nb.add_document("green bean okra kale arugula", "veggie");
+    nb.train();
+    nb
+}
+
+#[test]
+fn classify_batch_test() {
+    let nb = trained_classifier();
+
+    let documents: Vec<String> = (0..1000)
+        .map(|i| if i % 3 == 0 { "pork belly ribs" } else { "kale and okra" }.to_string())
+        .collect();
+
+    // the batch gives the same labels, in the same order, as one at a time
+    let labels = nb.classify_batch(&documents);
+    assert_eq!( labels.len(), documents.len() );
+    for (document, label) in documents.iter().zip(labels.iter()) {
+        assert_eq!( *label, nb.classify(document) );
+    }
+    assert_eq!( labels[0], Ok("meat".to_string()) );
+    assert_eq!( labels[1], Ok("veggie".to_string()) );
+}
+
+#[test]
+fn probabilities_batch_test() {
+    let nb = trained_classifier();
+    let documents = vec!["ham hock".to_string(), "spinach".to_string()];
+
+    let batch = nb.get_document_posteriors_batch(&documents);
+    assert_eq!( batch.len(), 2 );
+    for (document, probabilities) in documents.iter().zip(batch.iter()) {
+        let mut expected = nb.get_document_posteriors(document).unwrap();
+        let mut probabilities = probabilities.clone().unwrap();
+        expected.sort_by(|a, b| a.0.cmp(&b.0));
+        probabilities.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!( probabilities, expected );
+    }
+
+    assert!( nb.classify_batch(&[] as &[&str]).is_empty() );
+}
@@ -0,0 +1,57 @@
+extern crate classifier;
+use classifier::{NaiveBayes, NaiveBayesVariant};
+
+fn add_examples(nb: &mut NaiveBayes) {
+    // every spam message mentions "free", the word repeats a lot in one ham message
+    nb.add_document("free money now", "spam");
+    nb.add_document("free prize claim now", "spam");
+    nb.add_document("free gift card", "spam");
+    nb.add_document("lunch free free free free free free free free tomorrow", "ham");
+    nb.add_document("meeting moved to tomorrow", "ham");
+    nb.add_document("see you at lunch", "ham");
+}
+
+#[test]
+fn bernoulli_test() {
+
+    let mut multinomial = NaiveBayes::new();
+    add_examples(&mut multinomial);
+    multinomial.train();
+
+    let mut bernoulli = NaiveBayes::with_variant(NaiveBayesVariant::Bernoulli);
+    add_examples(&mut bernoulli);
+    bernoulli.train();
+
+    // the repeated word sways the multinomial model, the Bernoulli model
+    // only cares that "free" shows up in every spam document
+    assert_eq!( multinomial.classify("free").unwrap(), "ham" );
+    assert_eq!( bernoulli.classify("free").unwrap(), "spam" );
+    assert_eq!( bernoulli.classify("lunch tomorrow").unwrap(), "ham" );
+
+    // the variant survives exporting and reimporting the classifier
+    let bernoulli2 = NaiveBayes::from_json( &bernoulli.to_json() ).unwrap();
+    assert_eq!( bernoulli2.classify("free").unwrap(), "spam" );
+}
+
+#[test]
+fn bernoulli_online_test() {
+
+    let mut trained = NaiveBayes::with_variant(NaiveBayesVariant::Bernoulli);
+    add_examples(&mut trained);
+    trained.train();
+
+    let mut online = NaiveBayes::with_variant(NaiveBayesVariant::Bernoulli);
+    online.set_online(true);
+    add_examples(&mut online);
+
+    let document = "free lunch now";
+    let mut trained_probs = trained.get_document_posteriors(document).unwrap();
+    let mut online_probs = online.get_document_posteriors(document).unwrap();
+    trained_probs.sort_by(|a, b| a.0.cmp(&b.0));
+    online_probs.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (t, o) in trained_probs.iter().zip(online_probs.iter()) {
+        assert_eq!(t.0, o.0);
+        assert!((t.1 - o.1).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,127 @@
+#![cfg(feature = "binary")]
+extern crate classifier;
+use classifier::{NaiveBayes, GaussianNaiveBayes, NaiveBayesVariant, ClassifierError, Preprocessor};
+
+fn trained_classifier() -> NaiveBayes {
+    let mut nb = NaiveBayes::with_variant(NaiveBayesVariant::Complement);
+    nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+    nb.add_document("okra kale spinach water chestnut", "veggie");
+    nb.train();
+    nb
+}
+
+#[test]
+fn bytes_round_trip_test() {
+    let nb = trained_classifier();
+    let bytes = nb.to_bytes().unwrap();
+    assert_eq!( &bytes[..4], b"NBCL" );
+
+    let nb2 = NaiveBayes::from_bytes(&bytes).unwrap();
+    assert_eq!( nb2.classify("pork belly").unwrap(), "meat" );
+    assert_eq!( nb2.classify("kale").unwrap(), "veggie" );
+
+    let tokenizer = |document: &str| document.split(',').map(|s| s.to_string()).collect();
+    let nb3 = NaiveBayes::from_bytes_with_tokenizer(&bytes, tokenizer).unwrap();
+    assert_eq!( nb3.classify("kale,okra").unwrap(), "veggie" );
+
+    let mut gnb = GaussianNaiveBayes::new();
+    gnb.add_sample(&[1.0, 2.0], "a").unwrap();
+    gnb.add_sample(&[9.0, 8.0], "b").unwrap();
+    gnb.train();
+    let gnb2 = GaussianNaiveBayes::from_bytes(&gnb.to_bytes().unwrap()).unwrap();
+    assert_eq!( gnb2.classify(&[1.5, 2.5]).unwrap(), "a" );
+}
+
+#[test]
+fn bad_bytes_test() {
+    let mut bytes = trained_classifier().to_bytes().unwrap();
+
+    for bad in [&b"{}"[..], &b"NOPE\x01\x00"[..], &bytes[..10]].iter() {
+        match NaiveBayes::from_bytes(bad) {
+            Err(ClassifierError::Decode(_)) => {},
+            other => panic!("expected a decode error, got {:?}", other),
+        }
+    }
+
+    // a model from a newer version of the format is refused
+    bytes[4] = 6;
+    assert_eq!( NaiveBayes::from_bytes(&bytes).unwrap_err(), ClassifierError::UnsupportedModelVersion(6, 5) );
+}
+
+// the classifier the fixture of a version of the format in tests/data was
+// encoded from, trained with the current release, without the documents
+// the release that wrote it couldn't add
+fn fixture_classifier(version: u8) -> NaiveBayes {
+    let mut nb = NaiveBayes::new();
+    nb.set_preprocessor(Preprocessor::builder().lowercase(true).build().unwrap());
+    nb.set_ngram_range(1, 2).unwrap();
+    nb.set_stop_words(&["the".to_string()]);
+    nb.add_document("The pork belly and the ham hock", "meat");
+    nb.add_document("Sirloin pastrami pork chop", "meat");
+    nb.add_document("Okra kale and spinach", "veggie");
+    if version >= 2 {
+        nb.add_document_weighted("beef brisket and ribeye", "meat", 2.5).unwrap();
+    }
+    if version >= 4 {
+        nb.add_document_with_id("greens", "collard greens and okra", "veggie").unwrap();
+    }
+    nb.train();
+    nb
+}
+
+fn assert_same_posteriors(decoded: &NaiveBayes, expected: &NaiveBayes) {
+    for document in ["pork belly okra", "The Kale", "brisket and collard greens", "pork, pork pork belly ok"].iter() {
+        let posteriors = decoded.get_document_posteriors(document).unwrap();
+        for ((label, p), (expected_label, e)) in posteriors.into_iter().zip(expected.get_document_posteriors(document).unwrap()) {
+            assert_eq!( label, expected_label );
+            assert!( (p - e).abs() < 1e-12, "{}: {} != {}", document, p, e );
+        }
+    }
+}
+
+#[test]
+fn older_versions_test() {
+    // written by the releases that introduced each older version of the
+    // format, the models are read with the settings added since turned off
+    let fixtures: [&[u8]; 4] = [
+        include_bytes!("data/model_v1.bin"),
+        include_bytes!("data/model_v2.bin"),
+        include_bytes!("data/model_v3.bin"),
+        include_bytes!("data/model_v4.bin"),
+    ];
+    for (version, bytes) in (1..).zip(fixtures.iter()) {
+        assert_eq!( &bytes[4..6], &[version, 0] );
+        let nb = NaiveBayes::from_bytes(bytes).unwrap();
+        assert_same_posteriors(&nb, &fixture_classifier(version));
+
+        // and re-encoded in the current version
+        let bytes = nb.to_bytes().unwrap();
+        assert_eq!( &bytes[4..6], &[5, 0] );
+        assert_same_posteriors(&NaiveBayes::from_bytes(&bytes).unwrap(), &fixture_classifier(version));
+    }
+    let nb = NaiveBayes::from_bytes(fixtures[3]).unwrap();
+    assert_eq!( nb.documents_for_label("veggie"), vec!["greens"] );
+}
+
+#[test]
+fn version_5_test() {
+    // written by this release with every setting of the current layout, so
+    // that a change to the layout without a new version fails
+    let mut expected = NaiveBayes::new();
+    expected.set_preprocessor(Preprocessor::builder().lowercase(true).strip_punctuation(true).min_token_len(3).build().unwrap());
+    expected.set_skip_grams(1);
+    expected.set_max_word_count(1).unwrap();
+    expected.set_fixed_vocabulary(vec!["pork", "belly", "pork belly", "kale", "okra", "ham", "greens", "spinach",
+                                       "pork_belly"]);
+    expected.add_document("The pork, pork belly and the ham hock!", "meat");
+    expected.add_document("Okra kale and spinach", "veggie");
+    expected.add_document_with_id("greens", "collard greens and okra", "veggie").unwrap();
+    expected.train();
+
+    let bytes = include_bytes!("data/model_v5.bin");
+    assert_eq!( &bytes[4..6], &[5, 0] );
+    let nb = NaiveBayes::from_bytes(bytes).unwrap();
+    assert_same_posteriors(&nb, &expected);
+    assert_eq!( nb.vocabulary().count(), expected.vocabulary().count() );
+    assert_eq!( nb.documents_for_label("veggie"), vec!["greens"] );
+}
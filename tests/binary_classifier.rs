@@ -0,0 +1,68 @@
+extern crate classifier;
+use classifier::{BinaryClassifier, ClassifierError, NaiveBayes};
+
+fn trained() -> BinaryClassifier {
+    let mut spam = BinaryClassifier::new("spam", "ham");
+    spam.add_document("win free money now", true);
+    spam.add_document("free prize click now", true);
+    spam.add_document("lunch at noon tomorrow", false);
+    spam.add_document("meeting notes from tomorrow", false);
+    spam.train();
+    spam
+}
+
+#[test]
+fn score_test() {
+    let spam = trained();
+    let posteriors = spam.classifier().get_document_posteriors("free lunch now").unwrap();
+    let score = spam.score("free lunch now").unwrap();
+    // the log of the ratio of the posteriors, which are in label order
+    assert!((score - (posteriors[1].1 / posteriors[0].1).ln()).abs() < 1e-9);
+
+    assert!(spam.score("free prize").unwrap() > 0.0);
+    assert!(spam.score("meeting at noon").unwrap() < 0.0);
+    assert_eq!(spam.classify("free prize").unwrap(), "spam");
+    assert_eq!(spam.classify("meeting at noon").unwrap(), "ham");
+
+    // a long document saturates the posteriors but not the score
+    let long = vec!["free"; 2000].join(" ");
+    assert!(spam.score(&long).unwrap() > 2.0 * spam.score(&vec!["free"; 500].join(" ")).unwrap());
+}
+
+#[test]
+fn classify_bool_test() {
+    let spam = trained();
+    let score = spam.score("free lunch").unwrap();
+    assert!(spam.classify_bool("free lunch", score).unwrap());
+    assert!(!spam.classify_bool("free lunch", score + 0.1).unwrap());
+    assert!(matches!(spam.classify_bool("", 0.0), Err(ClassifierError::EmptyDocument)));
+}
+
+#[test]
+fn roc_auc_test() {
+    let spam = trained();
+    let perfect = [("free money", true), ("click now", true), ("lunch notes", false), ("meeting at noon", false)];
+    assert_eq!(spam.roc_auc(&perfect).unwrap(), 1.0);
+    let reversed: Vec<_> = perfect.iter().map(|&(document, positive)| (document, !positive)).collect();
+    assert_eq!(spam.roc_auc(&reversed).unwrap(), 0.0);
+    // tied scores count half
+    assert_eq!(spam.roc_auc(&[("free", true), ("free", false)]).unwrap(), 0.5);
+
+    assert_eq!(spam.roc_auc(&[("free", true)]), Err(ClassifierError::MissingLabel("ham".to_string())));
+
+    let curve = spam.roc_curve(&perfect).unwrap();
+    let threshold = curve.best_f1().unwrap().threshold;
+    for &(document, positive) in perfect.iter() {
+        assert_eq!(spam.classify_bool(document, threshold).unwrap(), positive);
+    }
+}
+
+#[test]
+fn missing_label_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("win free money", "spam");
+    nb.train();
+    let spam = BinaryClassifier::from_classifier(nb, "spam", "ham");
+    assert_eq!(spam.score("free"), Err(ClassifierError::MissingLabel("ham".to_string())));
+    assert!(matches!(BinaryClassifier::new("spam", "ham").score("free"), Err(ClassifierError::Untrained)));
+}
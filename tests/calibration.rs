@@ -0,0 +1,102 @@
+extern crate classifier;
+extern crate serde_json;
+use classifier::{ClassifierError, NaiveBayes};
+
+fn trained() -> NaiveBayes {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("pork belly ribs bacon ham sausage", "meat");
+    nb.add_document("beef ribs brisket sausage steak", "meat");
+    nb.add_document("kale okra spinach chard leek", "veggie");
+    nb.add_document("okra beans chard kale squash", "veggie");
+    nb.train();
+    nb
+}
+
+// documents the classifier is sure about but often wrong on
+fn validation() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("pork ribs sausage ham", "meat"),
+        ("kale okra chard beans", "veggie"),
+        ("sausage ribs beef bacon", "veggie"),
+        ("spinach squash leek kale", "meat"),
+        ("steak brisket ham", "meat"),
+        ("chard okra spinach", "veggie"),
+    ]
+}
+
+fn log_loss(nb: &NaiveBayes, examples: &[(&str, &str)]) -> f64 {
+    examples.iter().map(|&(document, label)| {
+        let posteriors = nb.get_document_posteriors(document).unwrap();
+        -posteriors.iter().find(|&(l, _)| l == label).unwrap().1.ln()
+    }).sum()
+}
+
+#[test]
+fn calibrate_test() {
+    let mut nb = trained();
+    assert_eq!(nb.calibration(), None);
+    let before = log_loss(&nb, &validation());
+    let confident = nb.get_document_posteriors("pork ribs").unwrap()[0].1;
+
+    let scale = nb.calibrate(&validation()).unwrap();
+    assert!(scale > 0.0 && scale < 1.0);
+    assert_eq!(nb.calibration(), Some(scale));
+    assert!(log_loss(&nb, &validation()) < before);
+
+    // softer posteriors for the same label
+    let posteriors = nb.get_document_posteriors("pork ribs").unwrap();
+    assert!(posteriors[0].1 < confident && posteriors[0].1 > 0.5);
+    assert_eq!(nb.classify("pork ribs").unwrap(), "meat");
+    assert!((posteriors.iter().map(|&(_, p)| p).sum::<f64>() - 1.0).abs() < 1e-9);
+    let explained = nb.explain("pork ribs").unwrap();
+    assert!((explained.labels[0].posterior - posteriors[0].1).abs() < 1e-12);
+
+    // the calibration is the best scale, so nudging it makes the loss worse
+    let loss = log_loss(&nb, &validation());
+    let nudged = |scale: f64| {
+        let mut encoded: serde_json::Value = serde_json::from_str(&trained().to_json()).unwrap();
+        encoded["calibration"] = scale.into();
+        log_loss(&NaiveBayes::from_json(&encoded.to_string()).unwrap(), &validation())
+    };
+    assert!(nudged(scale * 1.1) > loss && nudged(scale * 0.9) > loss);
+    nb.clear_calibration();
+    assert_eq!(log_loss(&nb, &validation()), before);
+}
+
+#[test]
+fn calibration_kept_test() {
+    let mut nb = trained();
+    nb.calibrate(&validation()).unwrap();
+    let posteriors = nb.get_document_posteriors("pork kale ribs").unwrap();
+
+    let decoded = NaiveBayes::from_json(&nb.to_json()).unwrap();
+    assert_eq!(decoded.calibration(), nb.calibration());
+    assert_eq!(decoded.get_document_posteriors("pork kale ribs").unwrap(), posteriors);
+
+    let close = |other: Vec<(String, f64)>| {
+        other.iter().zip(posteriors.iter()).all(|(a, b)| a.0 == b.0 && (a.1 - b.1).abs() < 1e-9)
+    };
+    let inference = nb.to_inference_model().unwrap();
+    assert!(close(inference.get_document_posteriors("pork kale ribs").unwrap()));
+    let frozen = nb.clone().freeze();
+    assert!(close(frozen.get_document_posteriors("pork kale ribs").unwrap()));
+    let vectorizer = nb.vectorizer();
+    assert!(close(vectorizer.get_posteriors(&vectorizer.vectorize("pork kale ribs")).unwrap()));
+    assert!(close(nb.get_document_posteriors_batch(&["pork kale ribs"]).remove(0).unwrap()));
+
+    nb.train();
+    assert!(nb.calibration().is_some());
+    assert_eq!(NaiveBayes::merge(&[nb.clone(), trained()]).unwrap().calibration(), None);
+}
+
+#[test]
+fn calibrate_errors_test() {
+    let mut nb = NaiveBayes::new();
+    assert_eq!(nb.calibrate(&validation()), Err(ClassifierError::Untrained));
+
+    let mut nb = trained();
+    let nothing: [(&str, &str); 0] = [];
+    assert_eq!(nb.calibrate(&nothing), Ok(1.0));
+    assert_eq!(nb.calibrate(&[("pork", "fruit"), ("", "meat")]), Ok(1.0));
+    assert_eq!(nb.calibration(), None);
+}
@@ -0,0 +1,56 @@
+extern crate classifier;
+use std::sync::atomic::{AtomicBool, Ordering};
+use classifier::{ClassifierError, LogisticRegression, NaiveBayes, TrainingObserver};
+
+// cancels training once it is half done
+struct CancelHalfway<'a> {
+    cancel: &'a AtomicBool,
+}
+
+impl<'a> TrainingObserver for CancelHalfway<'a> {
+    fn on_train_progress(&mut self, fraction: f64) {
+        if fraction >= 0.5 {
+            self.cancel.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+#[test]
+fn naive_bayes_cancel_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("pork belly", "meat");
+    nb.add_document("kale okra", "veggie");
+
+    let cancel = AtomicBool::new(false);
+    assert_eq!( nb.train_with_cancel(&cancel), Ok(()) );
+    assert_eq!( nb.classify("pork").unwrap(), "meat" );
+
+    cancel.store(true, Ordering::Relaxed);
+    assert_eq!( nb.train_with_cancel(&cancel), Err(ClassifierError::Cancelled) );
+
+    // cancelled part way through
+    nb.add_document("apple pear", "fruit");
+    nb.add_document("plum peach", "fruit");
+    let cancel = AtomicBool::new(false);
+    let result = nb.train_observed_with_cancel(&mut CancelHalfway { cancel: &cancel }, &cancel);
+    assert_eq!( result, Err(ClassifierError::Cancelled) );
+
+    // and trained again afterwards
+    nb.train();
+    assert_eq!( nb.classify("apple").unwrap(), "fruit" );
+}
+
+#[test]
+fn logistic_cancel_test() {
+    let mut lr = LogisticRegression::new();
+    lr.add_document("pork belly", "meat");
+    lr.add_document("kale okra", "veggie");
+
+    let cancel = AtomicBool::new(false);
+    let result = lr.train_observed_with_cancel(&mut CancelHalfway { cancel: &cancel }, &cancel);
+    assert_eq!( result, Err(ClassifierError::Cancelled) );
+
+    cancel.store(false, Ordering::Relaxed);
+    assert_eq!( lr.train_with_cancel(&cancel), Ok(()) );
+    assert_eq!( lr.classify("pork").unwrap(), "meat" );
+}
@@ -0,0 +1,95 @@
+extern crate classifier;
+extern crate serde_json;
+use std::env;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::PathBuf;
+use classifier::{NaiveBayes, ClassifierError};
+
+static EXAMPLES: &[(&str, &str)] = &[
+    ("pork belly ribs", "meat"),
+    ("okra kale spinach", "veggie"),
+    ("sirloin pastrami ham hock", "meat"),
+    ("kale carrots beets", "veggie"),
+    ("bacon sausage pork", "meat"),
+    ("spinach chard okra", "veggie"),
+    ("brisket ribs", "meat"),
+];
+
+// a fresh directory for each test
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("classifier-checkpoint-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+// the classifier's JSON, leaving out the vocabulary, which is written in
+// no particular order
+fn encoded(nb: &NaiveBayes) -> serde_json::Value {
+    let mut encoded: serde_json::Value = serde_json::from_str(&nb.to_json()).unwrap();
+    encoded.as_object_mut().unwrap().remove("vocab");
+    encoded
+}
+
+#[test]
+fn train_with_checkpoints_test() {
+    let dir = temp_dir("train");
+    let mut nb = NaiveBayes::new();
+    nb.train_with_checkpoints(EXAMPLES.iter().cloned(), &dir, 3).unwrap();
+
+    let mut expected = NaiveBayes::new();
+    expected.add_documents(EXAMPLES);
+    expected.train();
+    assert_eq!( encoded(&nb), encoded(&expected) );
+    assert!( dir.join("checkpoint.json").exists() );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn resume_from_checkpoint_test() {
+    let dir = temp_dir("resume");
+    let path = dir.join("checkpoint.json");
+
+    // the run crashes while reading the 6th document, after the checkpoint
+    // of the first 4
+    let mut crashed = NaiveBayes::new();
+    crashed.set_ngram_range(1, 2).unwrap();
+    let crashing = EXAMPLES.iter().cloned().enumerate().map(|(i, example)| {
+        if i == 5 { panic!("crashed"); }
+        example
+    });
+    let result = panic::catch_unwind(AssertUnwindSafe(|| crashed.train_with_checkpoints(crashing, &dir, 2)));
+    assert!( result.is_err() );
+
+    // the resumed classifier takes its settings and counts from the checkpoint
+    let mut resumed = NaiveBayes::new();
+    resumed.resume_from_checkpoint(&path, EXAMPLES.iter().cloned(), 2).unwrap();
+    let mut expected = NaiveBayes::new();
+    expected.set_ngram_range(1, 2).unwrap();
+    expected.add_documents(EXAMPLES);
+    expected.train();
+    assert_eq!( encoded(&resumed), encoded(&expected) );
+    assert_eq!( resumed.classify("pork okra ribs").unwrap(), "meat" );
+
+    // resuming a finished run adds nothing more
+    let mut finished = NaiveBayes::new();
+    finished.resume_from_checkpoint(&path, EXAMPLES.iter().cloned(), 2).unwrap();
+    assert_eq!( encoded(&finished), encoded(&expected) );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn checkpoint_errors_test() {
+    let dir = temp_dir("errors");
+    let mut nb = NaiveBayes::new();
+    assert_eq!( nb.train_with_checkpoints(EXAMPLES.iter().cloned(), &dir, 0),
+                Err(ClassifierError::InvalidCheckpointInterval(0)) );
+
+    nb.add_document("pork belly ribs", "meat");
+    match nb.resume_from_checkpoint(dir.join("checkpoint.json"), EXAMPLES.iter().cloned(), 2) {
+        Err(ClassifierError::Io(_)) => {},
+        other => panic!("expected an I/O error, got {:?}", other),
+    }
+    nb.train();
+    assert_eq!( nb.get_labels(), vec!["meat"] );
+}
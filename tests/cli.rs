@@ -0,0 +1,62 @@
+#![cfg(feature = "cli")]
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+static CLI: &str = env!("CARGO_BIN_EXE_classifier-cli");
+
+// a fresh directory for each test
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("classifier-cli-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn run(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(CLI).args(args)
+        .stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped())
+        .spawn().unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert!( output.status.success(), "{}", String::from_utf8_lossy(&output.stderr) );
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn train_classify_evaluate_inspect_test() {
+    let dir = temp_dir("commands");
+    let csv = dir.join("train.csv");
+    let jsonl = dir.join("test.jsonl");
+    let model = dir.join("model.json");
+    fs::write(&csv, "text,label\n\
+                     sirloin pastrami ham hock pork belly,meat\n\
+                     \"okra kale spinach, water chestnut\",veggie\n").unwrap();
+    fs::write(&jsonl, "{\"text\": \"pork belly\", \"label\": \"meat\"}\n\
+                       {\"text\": \"kale\", \"label\": \"veggie\"}\n").unwrap();
+    let (csv, jsonl, model) = (csv.to_str().unwrap(), jsonl.to_str().unwrap(), model.to_str().unwrap());
+
+    assert!( stdout(&run(&["train", csv, model, "--variant", "complement"], "")).contains("2 documents") );
+    assert_eq!( stdout(&run(&["classify", model, "ham", "hock"], "")), "meat\n" );
+    assert_eq!( stdout(&run(&["classify", model], "pork\nkale\n")), "meat\nveggie\n" );
+    assert!( stdout(&run(&["classify", model, "--posteriors", "pork"], "")).starts_with("meat\t") );
+    assert!( stdout(&run(&["evaluate", model, jsonl], "")).starts_with("accuracy\t1.0000\n") );
+    assert!( stdout(&run(&["inspect", model, "--top", "1"], "")).contains("veggie\t") );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn errors_test() {
+    let output = run(&["classify", "no-such-model.json", "pork"], "");
+    assert!( !output.status.success() );
+    assert!( String::from_utf8_lossy(&output.stderr).contains("no-such-model.json") );
+
+    assert!( !run(&["frobnicate"], "").status.success() );
+    assert!( !run(&["train", "data.txt"], "").status.success() );
+}
@@ -0,0 +1,63 @@
+extern crate classifier;
+extern crate serde_json;
+use classifier::{ClassifierError, Collocations, CollocationTokenizer, NaiveBayes, Tokenizer, WhitespaceTokenizer};
+
+fn tokenized(corpus: &[&str]) -> Vec<Vec<String>> {
+    corpus.iter().map(|document| WhitespaceTokenizer.tokenize(document)).collect()
+}
+
+#[test]
+fn learn_test() {
+    let documents = tokenized(&[
+        "the hot dog was good",
+        "a green bean and a hot dog",
+        "the green bean salad",
+        "the soup was hot and the bean was green",
+        "hot dog and green bean",
+    ]);
+    let collocations = Collocations::learn(&documents, 3, 0.3).unwrap();
+    assert_eq!( collocations.pairs(), vec![("green", "bean"), ("hot", "dog")] );
+    assert_eq!( collocations.len(), 2 );
+    assert!( collocations.contains("hot", "dog") );
+    assert!( !collocations.contains("dog", "hot") );
+
+    // pairs seen fewer times than the minimum are left out
+    assert!( Collocations::learn(&documents, 4, 0.3).unwrap().is_empty() );
+
+    for &threshold in [0.0, -0.5, 1.5, f64::NAN].iter() {
+        match Collocations::learn(&documents, 3, threshold) {
+            Err(ClassifierError::InvalidThreshold(_)) => {},
+            other => panic!("expected an invalid threshold error, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn merge_test() {
+    let mut collocations = Collocations::new();
+    collocations.insert("hot", "dog");
+    collocations.insert("dog", "food");
+    let tokens = ["a", "hot", "dog", "food", "hot"];
+    // merging goes from the start, so "dog food" is not merged
+    assert_eq!( collocations.merge(&tokens), vec!["a", "hot_dog", "food", "hot"] );
+    assert_eq!( Collocations::new().merge(&tokens), vec!["a", "hot", "dog", "food", "hot"] );
+
+    let encoded = serde_json::to_string(&collocations).unwrap();
+    assert_eq!( serde_json::from_str::<Collocations>(&encoded).unwrap(), collocations );
+}
+
+#[test]
+fn collocation_tokenizer_test() {
+    let mut collocations = Collocations::new();
+    collocations.insert("hot", "dog");
+    let mut nb = NaiveBayes::with_tokenizer(CollocationTokenizer::new(WhitespaceTokenizer, collocations));
+    nb.add_document("hot dog with mustard", "meat");
+    nb.add_document("hot soup", "veggie");
+    nb.add_document("hot tomato soup", "veggie");
+    nb.train();
+
+    // "hot dog" is one word that only the meat document has
+    assert_eq!( nb.vocab_size(), 6 );
+    assert_eq!( nb.classify("hot dog").unwrap(), "meat" );
+    assert_eq!( nb.classify("hot").unwrap(), "veggie" );
+}
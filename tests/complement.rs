@@ -0,0 +1,38 @@
+extern crate classifier;
+use classifier::{NaiveBayes, NaiveBayesVariant};
+
+fn add_examples(nb: &mut NaiveBayes) {
+    // nine ham documents for every spam document
+    for _ in 0..9 {
+        nb.add_document("meeting lunch tomorrow report", "ham");
+    }
+    nb.add_document("winner prize lunch", "spam");
+}
+
+#[test]
+fn complement_test() {
+
+    let mut multinomial = NaiveBayes::new();
+    add_examples(&mut multinomial);
+    multinomial.train();
+
+    let mut complement = NaiveBayes::with_variant(NaiveBayesVariant::Complement);
+    add_examples(&mut complement);
+    complement.train();
+
+    // the multinomial model leans on the majority label
+    assert_eq!( multinomial.classify("prize lunch").unwrap(), "ham" );
+    assert_eq!( complement.classify("prize lunch").unwrap(), "spam" );
+    assert_eq!( complement.classify("meeting report").unwrap(), "ham" );
+
+    // the variant survives exporting and reimporting the classifier
+    let complement2 = NaiveBayes::from_json( &complement.to_json() ).unwrap();
+    assert_eq!( complement2.classify("prize lunch").unwrap(), "spam" );
+
+    // online scoring agrees with the trained model
+    let mut online = NaiveBayes::with_variant(NaiveBayesVariant::Complement);
+    online.set_online(true);
+    add_examples(&mut online);
+    assert_eq!( online.classify("prize lunch").unwrap(), "spam" );
+    assert_eq!( online.classify("meeting report").unwrap(), "ham" );
+}
@@ -0,0 +1,131 @@
+extern crate classifier;
+use std::collections::BTreeMap;
+use classifier::ClassifierError;
+use classifier::dataset;
+
+fn examples() -> Vec<(String, String)> {
+    let examples = [
+        ("sirloin pastrami ham hock pork belly", "meat"),
+        ("ball tip pork chop short ribs", "meat"),
+        ("salami pancetta beef ribs", "meat"),
+        ("ham hock landjaeger pork belly", "meat"),
+        ("okra kale spinach water chestnut", "veggie"),
+        ("green bean okra kale", "veggie"),
+        ("spinach arugula pea sprouts", "veggie"),
+        ("water chestnut pea sprouts arugula", "veggie"),
+        ("apple banana", "fruit"),
+        ("pear plum", "fruit"),
+    ];
+    examples.iter().map(|&(d, l)| (d.to_string(), l.to_string())).collect()
+}
+
+#[test]
+fn train_test_split_test() {
+    let examples = examples();
+    let (train, test) = dataset::train_test_split(&examples, 0.3, 7).unwrap();
+    assert_eq!( train.len(), 7 );
+    assert_eq!( test.len(), 3 );
+
+    // every example ends up in exactly one of the sets
+    let mut all: Vec<_> = train.iter().chain(test.iter()).cloned().collect();
+    all.sort();
+    let mut expected = examples.clone();
+    expected.sort();
+    assert_eq!( all, expected );
+
+    // the same seed gives the same split
+    assert_eq!( dataset::train_test_split(&examples, 0.3, 7).unwrap(), (train, test) );
+}
+
+#[test]
+fn stratified_split_test() {
+    let examples = examples();
+    for seed in 0..20 {
+        let (train, test) = dataset::stratified_train_test_split(&examples, 0.25, seed).unwrap();
+        assert_eq!( train.len() + test.len(), examples.len() );
+
+        // even the rare label shows up on both sides
+        for label in ["meat", "veggie", "fruit"].iter() {
+            assert!( train.iter().any(|(_, l)| l == label) );
+            assert!( test.iter().any(|(_, l)| l == label) );
+        }
+    }
+
+    match dataset::train_test_split(&examples, 1.0, 0) {
+        Err(ClassifierError::InvalidSplitRatio(_)) => {},
+        other => panic!("expected an invalid split ratio error, got {:?}", other),
+    }
+}
+
+fn label_counts(examples: &[(String, String)]) -> Vec<(String, usize)> {
+    let mut counts = BTreeMap::new();
+    for (_, label) in examples.iter() {
+        *counts.entry(label.clone()).or_insert(0) += 1;
+    }
+    counts.into_iter().collect()
+}
+
+#[test]
+fn oversample_test() {
+    let mut examples = examples();
+    examples.push(("pork".to_string(), "meat".to_string()));
+    let balanced = dataset::oversample(&examples, 3);
+    assert_eq!( label_counts(&balanced), vec![("fruit".to_string(), 5), ("meat".to_string(), 5),
+                                             ("veggie".to_string(), 5)] );
+    // every example is kept, two whole copies of the fruit and one more
+    for example in examples.iter() {
+        assert!( balanced.contains(example) );
+    }
+    let apples = balanced.iter().filter(|(document, _)| document == "apple banana").count();
+    assert!( apples == 2 || apples == 3 );
+
+    assert_eq!( dataset::oversample(&examples, 3), balanced );
+    assert_ne!( dataset::oversample(&examples, 4), balanced );
+}
+
+#[test]
+fn undersample_test() {
+    let examples = examples();
+    let balanced = dataset::undersample(&examples, 3);
+    assert_eq!( label_counts(&balanced), vec![("fruit".to_string(), 2), ("meat".to_string(), 2),
+                                             ("veggie".to_string(), 2)] );
+    for example in balanced.iter() {
+        assert!( examples.contains(example) );
+    }
+    let mut distinct = balanced.clone();
+    distinct.sort();
+    distinct.dedup();
+    assert_eq!( distinct.len(), 6 );
+    assert_eq!( dataset::undersample(&examples, 3), balanced );
+
+    let none: Vec<(String, String)> = Vec::new();
+    assert!( dataset::undersample(&none, 3).is_empty() );
+    assert!( dataset::oversample(&none, 3).is_empty() );
+}
+
+#[test]
+fn jsonl_round_trip_test() {
+    let examples = examples();
+    let mut buffer = Vec::new();
+    dataset::to_jsonl(&mut buffer, &examples).unwrap();
+    assert_eq!( buffer.iter().filter(|&&b| b == b'\n').count(), examples.len() );
+
+    let read: Result<Vec<(String, String)>, ClassifierError> = dataset::from_jsonl(&buffer[..]).collect();
+    assert_eq!( read.unwrap(), examples );
+}
+
+#[test]
+fn jsonl_malformed_line_test() {
+    let jsonl = "{\"text\": \"pork belly\", \"label\": \"meat\", \"id\": 7}\n\
+                 \n\
+                 {\"text\": \"kale\"}\n\
+                 {\"text\": \"okra\", \"label\": \"veggie\"}";
+    let records: Vec<_> = dataset::from_jsonl(jsonl.as_bytes()).collect();
+    assert_eq!( records.len(), 3 );
+    assert_eq!( records[0], Ok(("pork belly".to_string(), "meat".to_string())) );
+    match records[1] {
+        Err(ClassifierError::MalformedLine(3, _)) => {},
+        ref other => panic!("expected a malformed line error, got {:?}", other),
+    }
+    assert_eq!( records[2], Ok(("okra".to_string(), "veggie".to_string())) );
+}
@@ -0,0 +1,55 @@
+extern crate classifier;
+use classifier::{ClassifierError, Deduplicator, NaiveBayes};
+
+#[test]
+fn exact_duplicates_test() {
+    let mut deduplicator = Deduplicator::new();
+    assert!(!deduplicator.is_duplicate("pork belly and ribs"));
+    assert!(deduplicator.is_duplicate("pork belly and ribs"));
+    assert!(deduplicator.is_duplicate("  PORK belly\tand ribs\n"));
+    assert!(!deduplicator.is_duplicate("pork belly and short ribs"));
+    assert_eq!((deduplicator.kept(), deduplicator.skipped()), (2, 2));
+}
+
+#[test]
+fn near_duplicates_test() {
+    let mut deduplicator = Deduplicator::new().near_duplicates(0.6, 2).unwrap();
+    let original = "the quick brown fox jumps over the lazy dog by the river bank";
+    assert!(!deduplicator.is_duplicate(original));
+    // one word changed at the end leaves most shingles the same
+    assert!(deduplicator.is_duplicate("the quick brown fox jumps over the lazy dog by the river side"));
+    assert!(!deduplicator.is_duplicate("a slow red hen walks under the busy cat near a quiet lake"));
+    assert!(!deduplicator.is_duplicate("the quick brown fox"));
+    assert_eq!((deduplicator.kept(), deduplicator.skipped()), (3, 1));
+
+    // exact deduplication alone keeps the near duplicate
+    let mut exact = Deduplicator::new();
+    assert!(!exact.is_duplicate(original));
+    assert!(!exact.is_duplicate("the quick brown fox jumps over the lazy dog by the river side"));
+}
+
+#[test]
+fn deduplicate_test() {
+    let examples = [("pork ribs", "meat"), ("kale okra", "veggie"), ("Pork Ribs", "veggie")];
+    let mut deduplicator = Deduplicator::new();
+    assert_eq!(deduplicator.deduplicate(&examples), vec![
+        ("pork ribs".to_string(), "meat".to_string()),
+        ("kale okra".to_string(), "veggie".to_string()),
+    ]);
+    // the deduplicator remembers documents across calls
+    assert!(deduplicator.deduplicate(&[("kale okra", "veggie")]).is_empty());
+    assert_eq!(deduplicator.skipped(), 2);
+
+    let mut nb = NaiveBayes::new();
+    let mut deduplicator = Deduplicator::new();
+    assert_eq!(nb.add_documents_deduplicated(examples.iter().cloned(), &mut deduplicator), 1);
+    nb.train();
+    assert_eq!(nb.label_prior("meat"), Some(0.5));
+}
+
+#[test]
+fn invalid_settings_test() {
+    assert!(matches!(Deduplicator::new().near_duplicates(0.0, 3), Err(ClassifierError::InvalidThreshold(_))));
+    assert!(matches!(Deduplicator::new().near_duplicates(1.5, 3), Err(ClassifierError::InvalidThreshold(_))));
+    assert!(matches!(Deduplicator::new().near_duplicates(0.8, 0), Err(ClassifierError::InvalidNgramRange(0, 0))));
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "disk")]
+extern crate classifier;
+extern crate sled;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use classifier::{ClassifierError, DiskTrainer, NaiveBayes, NaiveBayesVariant};
+
+fn examples() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("sirloin pastrami ham hock pork belly", "meat"),
+        ("ball tip pork chop short ribs pork", "meat"),
+        ("okra kale spinach water chestnut", "veggie"),
+        ("green bean okra kale", "veggie"),
+        ("apple banana pear", "fruit"),
+    ]
+}
+
+// a fresh database path for each test
+fn temp_db(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("classifier-disk-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn sorted(mut posteriors: Vec<(String, f64)>) -> Vec<(String, f64)> {
+    posteriors.sort_by(|a, b| a.0.cmp(&b.0));
+    posteriors
+}
+
+#[test]
+fn disk_trainer_test() {
+    for &variant in [NaiveBayesVariant::Multinomial, NaiveBayesVariant::Bernoulli].iter() {
+        let path = temp_db(&format!("{:?}", variant));
+        let mut trainer = DiskTrainer::with_classifier(&path, NaiveBayes::with_variant(variant)).unwrap();
+        let mut in_memory = NaiveBayes::with_variant(variant);
+        for &(document, label) in examples().iter() {
+            trainer.add_document(document, label).unwrap();
+            in_memory.add_document(document, label);
+        }
+        in_memory.train();
+        let nb = trainer.into_classifier(0.0).unwrap();
+
+        assert_eq!( nb.vocab_size(), in_memory.vocab_size() );
+        for document in ["pork kale", "banana", "okra ribs pear"].iter() {
+            let expected = sorted(in_memory.get_document_posteriors(document).unwrap());
+            let actual = sorted(nb.get_document_posteriors(document).unwrap());
+            for (a, e) in actual.iter().zip(expected.iter()) {
+                assert_eq!( a.0, e.0 );
+                assert!( (a.1 - e.1).abs() < 1e-9 );
+            }
+        }
+        let _ = fs::remove_dir_all(&path);
+    }
+}
+
+#[test]
+fn disk_trainer_min_count_test() {
+    let path = temp_db("min-count");
+    let mut trainer = DiskTrainer::open(&path).unwrap();
+    for &(document, label) in examples().iter() {
+        trainer.add_document(document, label).unwrap();
+    }
+    // only "pork", "okra" and "kale" are seen twice or more
+    let nb = trainer.into_classifier(2.0).unwrap();
+    assert_eq!( nb.vocab_size(), 3 );
+    assert_eq!( nb.classify("pork").unwrap(), "meat" );
+    assert_eq!( nb.classify("kale").unwrap(), "veggie" );
+    let _ = fs::remove_dir_all(&path);
+}
+
+#[test]
+fn disk_trainer_reopen_test() {
+    let path = temp_db("reopen");
+    {
+        let mut trainer = DiskTrainer::open(&path).unwrap();
+        trainer.add_document("sirloin pastrami ham hock", "meat").unwrap();
+        trainer.flush().unwrap();
+    }
+    let mut trainer = DiskTrainer::open(&path).unwrap();
+    trainer.add_document("okra kale spinach", "veggie").unwrap();
+    let nb = trainer.into_classifier(0.0).unwrap();
+    assert_eq!( nb.get_labels().len(), 2 );
+    assert_eq!( nb.classify("pastrami").unwrap(), "meat" );
+    assert_eq!( nb.classify("okra").unwrap(), "veggie" );
+    let _ = fs::remove_dir_all(&path);
+}
+
+#[test]
+fn disk_trainer_corrupt_value_test() {
+    // a word count and a document count cut short on disk
+    for &(key, value) in [(&b"wpork\0meat"[..], &[0u8; 12][..]), (&b"emeat"[..], &[0u8; 3][..])].iter() {
+        let path = temp_db(&format!("corrupt-{}", key.len()));
+        {
+            let db = sled::open(&path).unwrap();
+            db.insert(&b"wbelly\0meat"[..], &[0u8; 16][..]).unwrap();
+            db.insert(key, value).unwrap();
+            db.flush().unwrap();
+        }
+        match DiskTrainer::open(&path).unwrap().into_classifier(0.0) {
+            Err(ClassifierError::Decode(_)) => {},
+            other => panic!("expected a decoding error, got {:?}", other),
+        }
+        let _ = fs::remove_dir_all(&path);
+    }
+}
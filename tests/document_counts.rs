@@ -0,0 +1,82 @@
+extern crate classifier;
+extern crate serde_json;
+use std::collections::HashMap;
+use classifier::NaiveBayes;
+
+fn counts(words: &[(&str, u32)]) -> HashMap<String, u32> {
+    words.iter().map(|&(word, count)| (word.to_string(), count)).collect()
+}
+
+// the classifier's JSON, leaving out the sets of words, which are written
+// in no particular order
+fn encoded(nb: &NaiveBayes) -> serde_json::Value {
+    let mut encoded: serde_json::Value = serde_json::from_str(&nb.to_json()).unwrap();
+    for field in ["vocab", "fixed_vocabulary", "stop_words"].iter() {
+        encoded.as_object_mut().unwrap().remove(*field);
+    }
+    encoded
+}
+
+#[test]
+fn document_counts_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document_counts(&counts(&[("pork", 3), ("ribs", 1), ("belly", 0)]), "meat");
+    nb.add_document_counts(&counts(&[("kale", 2), ("okra", 1)]), "veggie");
+    nb.train();
+
+    let mut expected = NaiveBayes::new();
+    expected.add_document("pork ribs pork pork", "meat");
+    expected.add_document("kale okra kale", "veggie");
+    expected.train();
+    assert_eq!( encoded(&nb), encoded(&expected) );
+    assert_eq!( nb.classify("pork").unwrap(), "meat" );
+
+    // a document with no words adds nothing
+    nb.add_document_counts(&counts(&[("pork", 0)]), "meat");
+    nb.add_document_counts(&HashMap::new(), "meat");
+    nb.train();
+    assert_eq!( encoded(&nb), encoded(&expected) );
+}
+
+#[test]
+fn document_counts_settings_test() {
+    let mut nb = NaiveBayes::new();
+    nb.set_stop_words(vec!["the"]);
+    nb.set_max_word_count(2).unwrap();
+    nb.set_fixed_vocabulary(vec!["pork", "ribs", "kale", "okra"]);
+    nb.add_document_counts(&counts(&[("the", 4), ("pork", 5), ("ribs", 1), ("brisket", 2)]), "meat");
+    nb.add_document_counts(&counts(&[("kale", 1), ("okra", 1)]), "veggie");
+    nb.train();
+
+    let mut expected = NaiveBayes::new();
+    expected.set_stop_words(vec!["the"]);
+    expected.set_max_word_count(2).unwrap();
+    expected.set_fixed_vocabulary(vec!["pork", "ribs", "kale", "okra"]);
+    expected.add_document("the the the the pork pork pork pork pork ribs brisket brisket", "meat");
+    expected.add_document("kale okra", "veggie");
+    expected.train();
+    assert_eq!( encoded(&nb), encoded(&expected) );
+    assert_eq!( nb.vocabulary().collect::<Vec<_>>(), vec!["kale", "okra", "pork", "ribs"] );
+
+    // a document whose words are all left out adds nothing, not even its
+    // label, so it can't make the label more likely
+    nb.add_document_counts(&counts(&[("the", 3), ("brisket", 2)]), "meat");
+    nb.add_document_counts(&counts(&[("the", 1)]), "dessert");
+    nb.train();
+    assert_eq!( encoded(&nb), encoded(&expected) );
+    assert_eq!( nb.get_labels(), vec!["meat", "veggie"] );
+}
+
+#[test]
+fn hashed_document_counts_test() {
+    let mut nb = NaiveBayes::with_hashed_features(8).unwrap();
+    nb.add_document_counts(&counts(&[("pork", 2), ("ribs", 1)]), "meat");
+    nb.add_document_counts(&counts(&[("kale", 1), ("okra", 1)]), "veggie");
+    nb.train();
+
+    let mut expected = NaiveBayes::with_hashed_features(8).unwrap();
+    expected.add_document("pork pork ribs", "meat");
+    expected.add_document("kale okra", "veggie");
+    expected.train();
+    assert_eq!( encoded(&nb), encoded(&expected) );
+}
@@ -0,0 +1,112 @@
+extern crate classifier;
+extern crate serde_json;
+use classifier::{NaiveBayes, ClassifierError};
+
+fn classifier_with_ids() -> NaiveBayes {
+    let mut nb = NaiveBayes::new();
+    nb.add_document_with_id("doc-2", "sirloin pastrami ham hock", "meat").unwrap();
+    nb.add_document_with_id("doc-1", "pork belly ribs", "meat").unwrap();
+    nb.add_document_with_id("doc-3", "okra kale spinach", "veggie").unwrap();
+    nb.add_document("kale carrots beets", "veggie");
+    nb.train();
+    nb
+}
+
+#[test]
+fn documents_for_label_test() {
+    let nb = classifier_with_ids();
+    assert_eq!( nb.documents_for_label("meat"), vec!["doc-1", "doc-2"] );
+    // documents added without an ID are not listed
+    assert_eq!( nb.documents_for_label("veggie"), vec!["doc-3"] );
+    assert!( nb.documents_for_label("fruit").is_empty() );
+    assert_eq!( nb.document_label("doc-3"), Some("veggie") );
+    assert_eq!( nb.document_label("doc-4"), None );
+}
+
+#[test]
+fn remove_document_by_id_test() {
+    let mut nb = classifier_with_ids();
+    nb.remove_document_by_id("doc-1").unwrap();
+    nb.train();
+    assert_eq!( nb.documents_for_label("meat"), vec!["doc-2"] );
+    assert_eq!( nb.classify("pork ribs kale").unwrap(), "veggie" );
+
+    assert_eq!( nb.remove_document_by_id("doc-1"), Err(ClassifierError::UnknownDocumentId("doc-1".to_string())) );
+
+    // a document removed some other way can't be removed again by its ID
+    nb.remove_document("okra kale spinach", "veggie").unwrap();
+    assert_eq!( nb.remove_document_by_id("doc-3"), Err(ClassifierError::DocumentNotFound("veggie".to_string())) );
+    assert_eq!( nb.document_label("doc-3"), Some("veggie") );
+}
+
+#[test]
+fn reused_id_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document_with_id("doc", "pork belly ribs", "veggie").unwrap();
+    nb.add_document("okra kale spinach", "veggie");
+    // adding a document under the same ID replaces the mislabeled one
+    nb.add_document_with_id("doc", "pork belly ribs", "meat").unwrap();
+    nb.train();
+    assert_eq!( nb.document_label("doc"), Some("meat") );
+    assert!( nb.documents_for_label("veggie").is_empty() );
+    assert_eq!( nb.classify("pork").unwrap(), "meat" );
+
+    // the document under the ID can't be replaced once it's been removed
+    // some other way
+    nb.remove_document("pork belly ribs", "meat").unwrap();
+    assert_eq!(
+        nb.add_document_with_id("doc", "pork belly", "meat"),
+        Err(ClassifierError::DocumentNotFound("meat".to_string()))
+    );
+    assert_eq!( nb.document_label("doc"), Some("meat") );
+}
+
+#[test]
+fn featureless_id_test() {
+    let mut nb = NaiveBayes::new();
+    nb.set_stop_words(vec!["the"]);
+    nb.add_document("pork ribs", "meat");
+    // documents without features aren't remembered, whether their label
+    // exists or not
+    let empty: &[&str] = &[];
+    assert_eq!( nb.add_document_tokenized_with_id("x", empty, "meat"), Err(ClassifierError::EmptyDocument) );
+    assert_eq!( nb.add_document_with_id("y", "the", "veggie"), Err(ClassifierError::EmptyDocument) );
+    assert_eq!( nb.document_label("x"), None );
+    assert_eq!( nb.document_label("y"), None );
+    assert_eq!( nb.remove_document_by_id("x"), Err(ClassifierError::UnknownDocumentId("x".to_string())) );
+    nb.train();
+    assert_eq!( nb.get_labels(), vec!["meat"] );
+    assert_eq!( nb.classify("pork").unwrap(), "meat" );
+
+    // and an empty document can't be removed in place of a real one
+    assert_eq!( nb.remove_document("the", "meat"), Err(ClassifierError::DocumentNotFound("meat".to_string())) );
+    assert_eq!( nb.get_labels(), vec!["meat"] );
+}
+
+#[test]
+fn serialized_ids_test() {
+    let nb = classifier_with_ids();
+    let mut decoded = NaiveBayes::from_json(&nb.to_json()).unwrap();
+    assert_eq!( decoded.documents_for_label("meat"), vec!["doc-1", "doc-2"] );
+    decoded.remove_document_by_id("doc-3").unwrap();
+    assert!( decoded.documents_for_label("veggie").is_empty() );
+
+    // classifiers serialized without any IDs still load
+    let mut plain = NaiveBayes::new();
+    plain.add_document("pork belly ribs", "meat");
+    plain.train();
+    let mut encoded: serde_json::Value = serde_json::from_str(&plain.to_json()).unwrap();
+    encoded.as_object_mut().unwrap().remove("documents");
+    let decoded = NaiveBayes::from_json(&encoded.to_string()).unwrap();
+    assert!( decoded.documents_for_label("meat").is_empty() );
+}
+
+#[test]
+fn merged_ids_test() {
+    let mut other = NaiveBayes::new();
+    other.add_document_with_id("doc-4", "bacon sausage", "meat").unwrap();
+    other.add_document_with_id("doc-1", "kale", "veggie").unwrap();
+    let nb = NaiveBayes::merge(&[classifier_with_ids(), other]).unwrap();
+    assert_eq!( nb.documents_for_label("meat"), vec!["doc-1", "doc-2", "doc-4"] );
+    assert_eq!( nb.document_label("doc-1"), Some("meat") );
+}
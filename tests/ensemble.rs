@@ -0,0 +1,135 @@
+extern crate classifier;
+use std::cell::Cell;
+use std::rc::Rc;
+use classifier::{CharNgramTokenizer, ClassifierError, Ensemble, NaiveBayes, TextClassifier, Voting};
+
+fn trained(examples: &[(&str, &str)]) -> NaiveBayes {
+    let mut nb = NaiveBayes::new();
+    for &(document, label) in examples.iter() {
+        nb.add_document(document, label);
+    }
+    nb.train();
+    nb
+}
+
+// three models that disagree about "pork kale": two read it as meat
+fn ensemble(voting: Voting) -> Ensemble {
+    let mut ensemble = Ensemble::new(voting);
+    ensemble.add_model(trained(&[("pork belly", "meat"), ("kale okra", "veggie")]));
+    ensemble.add_model(trained(&[("pork chop", "meat"), ("kale kale kale", "veggie"), ("pork", "meat")]));
+    ensemble.add_model(trained(&[("pork", "meat"), ("kale kale kale spinach", "veggie"), ("okra", "veggie")]));
+    ensemble
+}
+
+#[test]
+fn majority_vote_test() {
+    let ensemble = ensemble(Voting::Majority);
+    let votes: Vec<String> = ensemble.models().iter().map(|model| model.classify("pork kale").unwrap()).collect();
+    assert_eq!( votes, vec!["meat", "meat", "veggie"] );
+    assert_eq!( ensemble.classify("pork kale").unwrap(), "meat" );
+
+    let posteriors = ensemble.get_document_posteriors("pork kale").unwrap();
+    assert_eq!( posteriors[0].0, "meat" );
+    assert!( (posteriors[0].1 - 2.0 / 3.0).abs() < 1e-12 );
+    assert!( (posteriors[1].1 - 1.0 / 3.0).abs() < 1e-12 );
+}
+
+#[test]
+fn average_log_probability_test() {
+    let ensemble = ensemble(Voting::AverageLogProbability);
+    let posteriors = ensemble.get_document_posteriors("pork kale").unwrap();
+
+    // the normalized geometric mean of the models' posteriors
+    let mut means = [0.0; 2];
+    for model in ensemble.models().iter() {
+        for (mean, (_, p)) in means.iter_mut().zip(model.probabilities("pork kale").unwrap()) {
+            *mean += p.ln() / 3.0;
+        }
+    }
+    let total: f64 = means.iter().map(|mean| mean.exp()).sum();
+    for (&mean, (_, p)) in means.iter().zip(posteriors.iter()) {
+        assert!( (mean.exp() / total - p).abs() < 1e-12 );
+    }
+    let best = if posteriors[0].1 > posteriors[1].1 { "meat" } else { "veggie" };
+    assert_eq!( ensemble.classify("pork kale").unwrap(), best );
+    assert_eq!( ensemble.classify("okra").unwrap(), "veggie" );
+}
+
+#[test]
+fn ensemble_training_test() {
+    // models reading documents differently, trained together
+    let mut ensemble = Ensemble::new(Voting::Majority);
+    ensemble.add_model(NaiveBayes::new());
+    ensemble.add_model(NaiveBayes::with_tokenizer(CharNgramTokenizer::new(3, 4).unwrap()));
+    let mut bigrams = NaiveBayes::new();
+    bigrams.set_ngram_range(1, 2).unwrap();
+    ensemble.add_model(bigrams);
+    assert_eq!( ensemble.len(), 3 );
+
+    let model: &mut dyn TextClassifier = &mut ensemble;
+    model.add_document("pork belly ribs", "meat");
+    model.add_document("kale okra spinach", "veggie");
+    model.add_document("pear apple", "fruit");
+    model.train();
+    assert_eq!( model.labels(), vec!["fruit", "meat", "veggie"] );
+    assert_eq!( model.classify("pork ribs").unwrap(), "meat" );
+    assert!( (model.probabilities("kale").unwrap().iter().map(|&(_, p)| p).sum::<f64>() - 1.0).abs() < 1e-9 );
+
+    // a label one model doesn't know
+    ensemble.set_voting(Voting::AverageLogProbability);
+    ensemble.add_model(trained(&[("durian", "exotic"), ("pork", "meat")]));
+    assert_eq!( ensemble.labels(), vec!["exotic", "fruit", "meat", "veggie"] );
+    assert_eq!( ensemble.classify("pork belly").unwrap(), "meat" );
+}
+
+#[test]
+fn empty_ensemble_test() {
+    for &voting in [Voting::Majority, Voting::AverageLogProbability].iter() {
+        let mut ensemble = Ensemble::new(voting);
+        assert!( ensemble.is_empty() );
+        assert_eq!( ensemble.classify("pork"), Err(ClassifierError::Untrained) );
+        assert_eq!( ensemble.get_document_posteriors("pork"), Err(ClassifierError::Untrained) );
+
+        ensemble.add_model(NaiveBayes::new());
+        assert_eq!( ensemble.classify("pork"), Err(ClassifierError::Untrained) );
+    }
+}
+
+// a model that gives every label a probability of NaN, counting how often
+// it's asked for them
+struct NanModel {
+    calls: Rc<Cell<usize>>,
+}
+
+impl TextClassifier for NanModel {
+    fn add_document(&mut self, _: &str, _: &str) {}
+
+    fn train(&mut self) {}
+
+    fn classify(&self, _: &str) -> Result<String, ClassifierError> {
+        panic!("the ensemble should only ask for probabilities");
+    }
+
+    fn probabilities(&self, _: &str) -> Result<Vec<(String, f64)>, ClassifierError> {
+        self.calls.set(self.calls.get() + 1);
+        Ok(vec![("meat".to_string(), f64::NAN), ("veggie".to_string(), f64::NAN)])
+    }
+
+    fn labels(&self) -> Vec<String> {
+        vec!["meat".to_string(), "veggie".to_string()]
+    }
+}
+
+#[test]
+fn nan_probabilities_test() {
+    for &voting in [Voting::Majority, Voting::AverageLogProbability].iter() {
+        let calls = Rc::new(Cell::new(0));
+        let mut ensemble = Ensemble::new(voting);
+        ensemble.add_model(NanModel { calls: calls.clone() });
+        ensemble.add_model(NanModel { calls: calls.clone() });
+        assert!( ensemble.classify("pork").is_ok() );
+        assert_eq!( calls.get(), 2 );
+        assert_eq!( ensemble.get_document_posteriors("pork").unwrap().len(), 2 );
+        assert_eq!( calls.get(), 4 );
+    }
+}
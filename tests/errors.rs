@@ -0,0 +1,52 @@
+extern crate classifier;
+use classifier::{NaiveBayes, ClassifierError};
+
+#[test]
+fn from_json_error_test() {
+
+    // malformed JSON
+    match NaiveBayes::from_json("{ \"vocab\": [") {
+        Err(ClassifierError::Decode(_)) => {},
+        other => panic!("expected a decode error, got {:?}", other),
+    }
+
+    // valid JSON that isn't a classifier
+    match NaiveBayes::from_json("[1, 2, 3]") {
+        Err(ClassifierError::Decode(_)) => {},
+        other => panic!("expected a decode error, got {:?}", other),
+    }
+}
+
+#[test]
+fn invalid_smoothing_test() {
+    let mut nb = NaiveBayes::new();
+    assert_eq!( nb.set_smoothing(0.0), Err(ClassifierError::InvalidSmoothing(0.0)) );
+    assert_eq!( nb.set_smoothing(-1.0), Err(ClassifierError::InvalidSmoothing(-1.0)) );
+    assert!( nb.set_smoothing(0.5).is_ok() );
+}
+
+#[test]
+fn classify_errors_test() {
+
+    // nothing added at all
+    let nb = NaiveBayes::new();
+    assert_eq!( nb.classify("pork"), Err(ClassifierError::Untrained) );
+    assert_eq!( nb.get_document_posteriors("pork"), Err(ClassifierError::Untrained) );
+
+    // documents added but never trained on
+    let mut nb = NaiveBayes::new();
+    nb.add_document("pork belly", "meat");
+    nb.add_document("kale okra", "veggie");
+    assert_eq!( nb.classify("pork"), Err(ClassifierError::Untrained) );
+    assert!( nb.explain("pork").is_err() );
+
+    // trained, but the document has nothing to classify it by
+    nb.train();
+    assert_eq!( nb.classify(""), Err(ClassifierError::EmptyDocument) );
+    assert_eq!( nb.classify_top_k("   ", 2), Err(ClassifierError::EmptyDocument) );
+    assert_eq!( nb.classify("pork"), Ok("meat".to_string()) );
+
+    // every word was dropped from the vocabulary
+    nb.select_top_features(0, Default::default());
+    assert_eq!( nb.classify("pork"), Err(ClassifierError::EmptyVocabulary) );
+}
@@ -0,0 +1,89 @@
+extern crate classifier;
+use classifier::evaluation::{roc_curve, ConfusionMatrix};
+
+fn results() -> Vec<(String, String)> {
+    // (predicted, actual)
+    let results = [
+        ("meat", "meat"),
+        ("meat", "meat"),
+        ("veggie", "meat"),
+        ("veggie", "veggie"),
+        ("meat", "veggie"),
+        ("fruit", "fruit"),
+    ];
+    results.iter().map(|&(p, a)| (p.to_string(), a.to_string())).collect()
+}
+
+#[test]
+fn per_label_test() {
+    let matrix = ConfusionMatrix::from_pairs(&results());
+
+    assert_eq!( matrix.labels(), vec!["fruit", "meat", "veggie"] );
+    assert_eq!( matrix.total(), 6 );
+    assert_eq!( matrix.count("meat", "veggie"), 1 );
+    assert_eq!( matrix.count("fruit", "meat"), 0 );
+
+    assert_eq!( matrix.support("meat"), 3 );
+    assert_eq!( matrix.precision("meat"), 2.0 / 3.0 );
+    assert_eq!( matrix.recall("meat"), 2.0 / 3.0 );
+    assert_eq!( matrix.precision("veggie"), 0.5 );
+    assert_eq!( matrix.recall("veggie"), 0.5 );
+    assert_eq!( matrix.f1("fruit"), 1.0 );
+
+    let report = matrix.report();
+    assert_eq!( report.len(), 3 );
+    assert_eq!( report[2].label, "veggie" );
+    assert_eq!( report[2].support, 2 );
+}
+
+#[test]
+fn averages_test() {
+    let matrix = ConfusionMatrix::from_pairs(&results());
+
+    let macro_precision = (1.0 + 2.0 / 3.0 + 0.5) / 3.0;
+    assert!( (matrix.macro_precision() - macro_precision).abs() < 1e-12 );
+    assert_eq!( matrix.accuracy(), 4.0 / 6.0 );
+    assert_eq!( matrix.micro_precision(), matrix.accuracy() );
+    assert_eq!( matrix.micro_f1(), matrix.accuracy() );
+
+    // an empty matrix scores zero rather than dividing by zero
+    let empty = ConfusionMatrix::new();
+    assert_eq!( empty.accuracy(), 0.0 );
+    assert_eq!( empty.macro_f1(), 0.0 );
+}
+
+#[test]
+fn roc_curve_test() {
+    let scores = [0.9, 0.8, 0.8, 0.4, 0.3, 0.1];
+    let truths = [true, true, false, true, false, false];
+    let curve = roc_curve(&scores, &truths);
+
+    let thresholds: Vec<f64> = curve.points().iter().map(|point| point.threshold).collect();
+    assert_eq!( thresholds, vec![0.9, 0.8, 0.4, 0.3, 0.1] );
+    let tied = curve.points()[1];
+    assert_eq!( (tied.true_positives, tied.false_positives), (2, 1) );
+    assert_eq!( tied.precision, 2.0 / 3.0 );
+    assert_eq!( tied.recall, 2.0 / 3.0 );
+    assert_eq!( tied.false_positive_rate, 1.0 / 3.0 );
+    let last = curve.points()[4];
+    assert_eq!( (last.precision, last.recall, last.false_positive_rate), (0.5, 1.0, 1.0) );
+
+    // 7 of the 9 positive and negative pairs are ordered and one is tied
+    assert!( (curve.auc() - 7.5 / 9.0).abs() < 1e-12 );
+    assert_eq!( curve.best_f1().unwrap().threshold, 0.4 );
+    assert_eq!( curve.threshold_for_precision(1.0).unwrap().threshold, 0.9 );
+    assert_eq!( curve.threshold_for_precision(0.8).unwrap().threshold, 0.9 );
+    assert_eq!( curve.threshold_for_precision(0.7).unwrap().threshold, 0.4 );
+}
+
+#[test]
+fn roc_curve_extremes_test() {
+    assert_eq!( roc_curve(&[3.0, 2.0, 1.0], &[true, false, false]).auc(), 1.0 );
+    assert_eq!( roc_curve(&[3.0, 2.0, 1.0], &[false, true, true]).auc(), 0.0 );
+    assert_eq!( roc_curve(&[1.0, 1.0], &[true, false]).auc(), 0.5 );
+    assert_eq!( roc_curve(&[1.0, 2.0], &[true, true]).auc(), 0.0 );
+
+    let empty = roc_curve(&[], &[]);
+    assert!( empty.points().is_empty() );
+    assert_eq!( empty.best_f1(), None );
+}
@@ -0,0 +1,57 @@
+extern crate classifier;
+use classifier::{NaiveBayes, NaiveBayesVariant};
+
+fn classifier(variant: NaiveBayesVariant, online: bool) -> NaiveBayes {
+    let mut nb = NaiveBayes::with_variant(variant);
+    nb.set_online(online);
+    nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+    nb.add_document("ball tip pork chop short ribs", "meat");
+    nb.add_document("okra kale spinach water chestnut", "veggie");
+    nb.add_document("green bean okra kale arugula", "veggie");
+    nb.train();
+    nb
+}
+
+#[test]
+fn explanation_matches_scores_test() {
+    let variants = [NaiveBayesVariant::Multinomial,
+                    NaiveBayesVariant::Bernoulli,
+                    NaiveBayesVariant::Complement];
+    let document = "pork belly kale kale unknown";
+
+    for &variant in variants.iter() {
+        for &online in [false, true].iter() {
+            let nb = classifier(variant, online);
+            let explanation = nb.explain(document).unwrap();
+
+            // the best explained label is the classification, and the
+            // posteriors are the ones the classifier reports
+            assert_eq!( explanation.labels[0].label, nb.classify(document).unwrap() );
+            for (label, posterior) in nb.get_document_posteriors(document).unwrap() {
+                let explained = explanation.label(&label).unwrap();
+                assert!( (explained.posterior - posterior).abs() < 1e-9 );
+
+                let total = explained.prior + explained.baseline +
+                    explained.contributions.iter().map(|&(_, c)| c).sum::<f64>();
+                assert!( (explained.score - total).abs() < 1e-9 );
+            }
+        }
+    }
+}
+
+#[test]
+fn contributions_test() {
+    let nb = classifier(NaiveBayesVariant::Multinomial, false);
+    let explanation = nb.explain("pork kale kale unknown").unwrap();
+    let meat = explanation.label("meat").unwrap();
+
+    // unknown words are left out, repeated words appear every time
+    let words: Vec<&str> = meat.contributions.iter().map(|(w, _)| w.as_str()).collect();
+    assert_eq!( words, vec!["pork", "kale", "kale"] );
+    assert!( (meat.prior - 0.5f64.ln()).abs() < 1e-12 );
+    assert_eq!( meat.baseline, 0.0 );
+
+    // pork is more likely under meat than under veggie
+    let veggie = explanation.label("veggie").unwrap();
+    assert!( meat.contributions[0].1 > veggie.contributions[0].1 );
+}
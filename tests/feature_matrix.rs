@@ -0,0 +1,40 @@
+#![cfg(feature = "ndarray")]
+extern crate classifier;
+extern crate ndarray;
+use classifier::{FeatureWeighting, NaiveBayes};
+use ndarray::arr2;
+
+fn trained(weighting: FeatureWeighting) -> NaiveBayes {
+    let mut nb = NaiveBayes::new();
+    nb.set_feature_weighting(weighting);
+    nb.add_document("pork belly ribs", "meat");
+    nb.add_document("pork sausage", "meat");
+    nb.add_document("kale okra", "veggie");
+    nb.train();
+    nb
+}
+
+#[test]
+fn count_matrix_test() {
+    let nb = trained(FeatureWeighting::Counts);
+    let (matrix, words) = nb.to_feature_matrix(&["pork ribs pork durian", "", "okra kale okra"]);
+    assert_eq!( words, vec!["belly", "kale", "okra", "pork", "ribs", "sausage"] );
+    assert_eq!( matrix, arr2(&[[0.0, 0.0, 0.0, 2.0, 1.0, 0.0],
+                               [0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+                               [0.0, 1.0, 2.0, 0.0, 0.0, 0.0]]) );
+
+    let (empty, _) = nb.to_feature_matrix::<&str>(&[]);
+    assert_eq!( empty.dim(), (0, 6) );
+}
+
+#[test]
+fn tf_idf_matrix_test() {
+    let nb = trained(FeatureWeighting::TfIdf);
+    let (matrix, words) = nb.to_feature_matrix(&["pork ribs pork"]);
+    let column = |word: &str| words.iter().position(|w| w == word).unwrap();
+    // pork is in 2 of the 3 documents and ribs in 1
+    let idf = |frequency: f64| (4.0 / (1.0 + frequency)).ln() + 1.0;
+    assert!( (matrix[[0, column("pork")]] - 2.0 * idf(2.0)).abs() < 1e-12 );
+    assert!( (matrix[[0, column("ribs")]] - idf(1.0)).abs() < 1e-12 );
+    assert_eq!( matrix[[0, column("kale")]], 0.0 );
+}
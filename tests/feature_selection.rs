@@ -0,0 +1,49 @@
+extern crate classifier;
+use classifier::NaiveBayes;
+use classifier::feature_selection::{chi_squared, mutual_information, FeatureScore};
+
+fn classifier() -> NaiveBayes {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("the pork beef", "meat");
+    nb.add_document("the pork ham", "meat");
+    nb.add_document("the kale okra", "veggie");
+    nb.add_document("the kale leek", "veggie");
+    nb
+}
+
+#[test]
+fn scores_test() {
+    // a word that splits the documents perfectly
+    assert_eq!( chi_squared(2.0, 0.0, 0.0, 2.0), 4.0 );
+    assert_eq!( mutual_information(2.0, 0.0, 0.0, 2.0), 1.0 );
+
+    // a word that is independent of the label
+    assert_eq!( chi_squared(1.0, 1.0, 1.0, 1.0), 0.0 );
+    assert_eq!( mutual_information(1.0, 1.0, 1.0, 1.0), 0.0 );
+    assert_eq!( chi_squared(2.0, 2.0, 0.0, 0.0), 0.0 );
+}
+
+#[test]
+fn feature_scores_test() {
+    let nb = classifier();
+    let scores = nb.feature_scores(FeatureScore::MutualInformation);
+    assert_eq!( scores.len(), 2 * nb.vocab_size() );
+    assert_eq!( scores[0], ("kale".to_string(), "meat".to_string(), 1.0) );
+    assert!( scores.iter().filter(|s| s.0 == "the").all(|s| s.2 == 0.0) );
+}
+
+#[test]
+fn select_top_features_test() {
+    let mut nb = classifier();
+    assert_eq!( nb.vocab_size(), 7 );
+
+    // "kale" and "pork" each appear in every document of one label only
+    assert_eq!( nb.select_top_features(2, FeatureScore::ChiSquared), 5 );
+    assert_eq!( nb.vocab_size(), 2 );
+    nb.train();
+
+    assert_eq!( nb.classify("pork ham").unwrap(), "meat" );
+    assert_eq!( nb.classify("the kale").unwrap(), "veggie" );
+    let top: Vec<String> = nb.top_words_for_label("veggie", 10).into_iter().map(|(w, _)| w).collect();
+    assert_eq!( top, vec!["kale"] );
+}
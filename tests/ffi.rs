@@ -0,0 +1,81 @@
+#![cfg(feature = "ffi")]
+extern crate classifier;
+use std::ffi::{CStr, CString};
+use std::fs;
+use std::ptr;
+use classifier::ffi::*;
+
+fn c(s: &str) -> CString {
+    CString::new(s).unwrap()
+}
+
+// takes a string handed back by the library
+unsafe fn take(s: *mut std::os::raw::c_char) -> String {
+    assert!( !s.is_null() );
+    let owned = CStr::from_ptr(s).to_str().unwrap().to_string();
+    classifier_string_free(s);
+    owned
+}
+
+unsafe fn last_error() -> String {
+    CStr::from_ptr(classifier_last_error()).to_str().unwrap().to_string()
+}
+
+#[test]
+fn ffi_test() {
+    unsafe {
+        let nb = classifier_new();
+        assert_eq!( classifier_add_document(nb, c("sirloin pastrami ham hock").as_ptr(), c("meat").as_ptr()), 0 );
+        assert_eq!( classifier_add_document(nb, c("okra kale spinach").as_ptr(), c("veggie").as_ptr()), 0 );
+        assert!( classifier_classify(nb, c("pastrami").as_ptr()).is_null() );
+        assert_eq!( last_error(), "classifier has not been trained on any documents" );
+
+        assert_eq!( classifier_train(nb), 0 );
+        assert_eq!( take(classifier_classify(nb, c("pastrami").as_ptr())), "meat" );
+
+        let json = classifier_to_json(nb);
+        let copy = classifier_from_json(json);
+        classifier_string_free(json);
+        assert_eq!( take(classifier_classify(copy, c("kale").as_ptr())), "veggie" );
+        classifier_free(copy);
+        classifier_free(nb);
+    }
+}
+
+#[test]
+fn ffi_errors_test() {
+    unsafe {
+        assert_eq!( classifier_train(ptr::null_mut()), -1 );
+        assert_eq!( last_error(), "null classifier" );
+
+        let nb = classifier_new();
+        assert_eq!( classifier_add_document(nb, ptr::null(), c("meat").as_ptr()), -1 );
+        assert_eq!( last_error(), "null string" );
+        let invalid = [0xffu8, 0];
+        assert_eq!( classifier_add_document(nb, invalid.as_ptr() as *const _, c("meat").as_ptr()), -1 );
+        assert!( last_error().starts_with("invalid UTF-8") );
+
+        assert!( classifier_from_json(c("not json").as_ptr()).is_null() );
+        assert!( last_error().starts_with("decoding classifier failed") );
+
+        classifier_free(nb);
+        classifier_free(ptr::null_mut());
+        classifier_string_free(ptr::null_mut());
+    }
+}
+
+// every function of the ffi module is declared in the header
+#[test]
+fn ffi_header_test() {
+    let source = fs::read_to_string("src/ffi.rs").unwrap();
+    let header = fs::read_to_string("include/classifier.h").unwrap();
+    let functions: Vec<&str> = source.lines()
+        .filter_map(|line| line.split("extern \"C\" fn ").nth(1))
+        .map(|rest| rest.split('(').next().unwrap())
+        .collect();
+    assert_eq!( functions.len(), 9 );
+    for function in functions {
+        assert!( header.contains(&format!(" *{}(", function)) || header.contains(&format!(" {}(", function)),
+                 "{} is not declared in include/classifier.h", function );
+    }
+}
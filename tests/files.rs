@@ -0,0 +1,67 @@
+extern crate classifier;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use classifier::{NaiveBayes, Compression, ClassifierError};
+
+fn trained_classifier() -> NaiveBayes {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+    nb.add_document("okra kale spinach water chestnut", "veggie");
+    nb.train();
+    nb
+}
+
+// a fresh directory for each test
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("classifier-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn round_trip(path: &Path, compression: Compression) {
+    trained_classifier().save_to_file_compressed(path, compression).unwrap();
+    let nb = NaiveBayes::load_from_file(path).unwrap();
+    assert_eq!( nb.classify("pork belly").unwrap(), "meat" );
+    assert_eq!( nb.classify("kale").unwrap(), "veggie" );
+}
+
+#[test]
+fn save_load_test() {
+    let dir = temp_dir("save-load");
+    let path = dir.join("model.json");
+
+    round_trip(&path, Compression::None);
+    #[cfg(feature = "gzip")]
+    round_trip(&path, Compression::Gzip);
+    #[cfg(feature = "zstandard")]
+    round_trip(&path, Compression::Zstd);
+
+    // nothing but the model is left in the directory
+    assert_eq!( fs::read_dir(&dir).unwrap().count(), 1 );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn file_errors_test() {
+    let dir = temp_dir("errors");
+
+    match NaiveBayes::load_from_file(dir.join("missing.json")) {
+        Err(ClassifierError::Io(_)) => {},
+        other => panic!("expected an io error, got {:?}", other),
+    }
+
+    let path = dir.join("garbage.json");
+    fs::write(&path, "not a model").unwrap();
+    match NaiveBayes::load_from_file(&path) {
+        Err(ClassifierError::Decode(_)) => {},
+        other => panic!("expected a decode error, got {:?}", other),
+    }
+
+    match trained_classifier().save_to_file(dir.join("no-such-dir").join("model.json")) {
+        Err(ClassifierError::Io(_)) => {},
+        other => panic!("expected an io error, got {:?}", other),
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
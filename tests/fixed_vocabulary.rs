@@ -0,0 +1,48 @@
+extern crate classifier;
+use classifier::{NaiveBayes, UnknownWordPolicy};
+
+#[test]
+fn fixed_vocabulary_test() {
+    let mut nb = NaiveBayes::with_fixed_vocabulary(vec!["pork", "ribs", "kale", "okra"]);
+    nb.add_document("pork ribs for jane.doe@example.com", "meat");
+    nb.add_document("kale okra from 555-0100", "veggie");
+    nb.train();
+
+    // nothing outside of the list is stored
+    let words: Vec<&str> = nb.vocabulary().collect();
+    assert_eq!( words, vec!["kale", "okra", "pork", "ribs"] );
+    let json = nb.to_json();
+    assert!( !json.contains("jane") && !json.contains("555") );
+
+    assert_eq!( nb.classify("pork ribs").unwrap(), "meat" );
+    // words outside of the list are unknown when classifying
+    nb.set_unknown_word_policy(UnknownWordPolicy::PenalizePerToken(1.0)).unwrap();
+    let known = nb.get_document_posteriors("okra").unwrap();
+    let unknown = nb.get_document_posteriors("okra jane.doe@example.com").unwrap();
+    assert!( (known[1].1 - unknown[1].1).abs() < 1e-12 );
+    assert_eq!( nb.classify("okra jane.doe@example.com").unwrap(), "veggie" );
+
+    // the fixed vocabulary survives serialization
+    let mut decoded = NaiveBayes::from_json(&nb.to_json()).unwrap();
+    decoded.add_document("okra and secrets", "veggie");
+    assert_eq!( decoded.vocab_size(), 4 );
+
+    decoded.clear_fixed_vocabulary();
+    decoded.add_document("okra and secrets", "veggie");
+    assert_eq!( decoded.vocab_size(), 6 );
+}
+
+#[test]
+fn fixed_vocabulary_removal_test() {
+    let mut nb = NaiveBayes::new();
+    nb.set_fixed_vocabulary(vec!["pork", "kale"]);
+    nb.add_document_with_id("a", "pork from jane", "meat").unwrap();
+    nb.add_document("kale", "veggie");
+
+    // documents kept by ID only hold their counted words
+    assert!( !nb.to_json().contains("jane") );
+    nb.remove_document_by_id("a").unwrap();
+    nb.add_document("pork belly", "meat");
+    nb.remove_document("pork belly", "meat").unwrap();
+    assert_eq!( nb.get_labels(), vec!["veggie"] );
+}
@@ -0,0 +1,130 @@
+extern crate classifier;
+use classifier::{ClassifierError, FeatureWeighting, FrozenModel, NaiveBayes, NaiveBayesVariant, Preprocessor,
+                 UnknownWordPolicy};
+
+fn add_examples(nb: &mut NaiveBayes) {
+    nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+    nb.add_document("ball tip pork chop short ribs pork", "meat");
+    nb.add_document("Okra kale spinach water chestnut", "veggie");
+    nb.add_document("green bean okra kale", "veggie");
+    nb.add_document("apple banana pear", "fruit");
+}
+
+// checks that the frozen classifier, after a round trip through JSON, gives
+// the same labels and posteriors
+fn assert_same(mut nb: NaiveBayes) {
+    add_examples(&mut nb);
+    nb.train();
+    let model = FrozenModel::from_json(&nb.clone().freeze().to_json()).unwrap();
+
+    assert_eq!( model.vocab_size(), nb.vocab_size() );
+    assert_eq!( model.get_labels(), nb.get_labels() );
+    for document in ["pork kale", "banana", "okra ribs pear pork okra", "kale durian durian", "OKRA water"].iter() {
+        let expected = nb.get_document_posteriors(document).unwrap();
+        let actual = model.get_document_posteriors(document).unwrap();
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert_eq!( a.0, e.0 );
+            assert!( (a.1 - e.1).abs() < 1e-9, "{}: {} != {}", document, a.1, e.1 );
+        }
+        assert_eq!( model.classify(document).unwrap(), nb.classify(document).unwrap() );
+    }
+}
+
+#[test]
+fn frozen_variants_test() {
+    for &variant in [NaiveBayesVariant::Multinomial, NaiveBayesVariant::Bernoulli, NaiveBayesVariant::Complement].iter() {
+        assert_same(NaiveBayes::with_variant(variant));
+
+        let mut nb = NaiveBayes::with_variant(variant);
+        nb.set_unknown_word_policy(UnknownWordPolicy::DefaultProbability).unwrap();
+        assert_same(nb);
+    }
+}
+
+#[test]
+fn frozen_settings_test() {
+    let mut nb = NaiveBayes::new();
+    nb.set_feature_weighting(FeatureWeighting::TfIdf);
+    assert_same(nb);
+
+    let mut nb = NaiveBayes::new();
+    nb.set_ngram_range(1, 2).unwrap();
+    nb.set_stop_words(vec!["water"]);
+    nb.set_preprocessor(Preprocessor::builder().lowercase(true).strip_accents(true).build().unwrap());
+    assert_same(nb);
+
+    assert_same(NaiveBayes::with_hashed_features(16).unwrap());
+}
+
+#[test]
+fn frozen_memory_test() {
+    let mut nb = NaiveBayes::new();
+    for i in 0..2000 {
+        nb.add_document(&format!("pork{} belly{} ribs", i, i), "meat");
+        nb.add_document(&format!("kale{} okra{} spinach", i, i), "veggie");
+    }
+    nb.train();
+    let memory_bytes = nb.stats().memory_bytes;
+    let model = nb.freeze();
+    assert!( model.memory_bytes() * 4 < memory_bytes, "{} vs {}", model.memory_bytes(), memory_bytes );
+    assert_eq!( model.classify("pork17 ribs").unwrap(), "meat" );
+}
+
+#[test]
+fn shrink_to_fit_test() {
+    let mut nb = NaiveBayes::new();
+    for i in 0..2000 {
+        nb.add_document(&format!("pork{} belly ribs", i), "meat");
+        nb.add_document(&format!("kale{} okra spinach", i), "veggie");
+    }
+    nb.train();
+    nb.prune_vocabulary(2, 1.0).unwrap();
+    nb.train();
+    let before = nb.estimated_memory_bytes();
+    let posteriors = nb.get_document_posteriors("pork ribs kale").unwrap();
+
+    nb.shrink_to_fit();
+    assert!( nb.estimated_memory_bytes() < before, "{} vs {}", nb.estimated_memory_bytes(), before );
+    assert_eq!( nb.get_document_posteriors("pork ribs kale").unwrap(), posteriors );
+    // the classifier can still be trained
+    nb.add_document("bacon", "meat");
+    nb.train();
+    assert_eq!( nb.classify("bacon").unwrap(), "meat" );
+}
+
+#[test]
+fn frozen_errors_test() {
+    assert_eq!( NaiveBayes::new().freeze().classify("pork"), Err(ClassifierError::Untrained) );
+
+    let mut nb = NaiveBayes::new();
+    add_examples(&mut nb);
+    let model = nb.freeze();
+    assert_eq!( model.classify(""), Err(ClassifierError::EmptyDocument) );
+    assert!( FrozenModel::from_json("{}").is_err() );
+}
+
+#[test]
+fn quantized_test() {
+    let mut nb = NaiveBayes::new();
+    for i in 0..500 {
+        nb.add_document(&format!("pork{} belly{} ribs", i, i), "meat");
+        nb.add_document(&format!("kale{} okra{} spinach", i, i), "veggie");
+    }
+    nb.train();
+    let full = nb.clone().freeze();
+    let quantized = nb.freeze_quantized();
+    assert!( quantized.is_quantized() && !full.is_quantized() );
+    assert!( quantized.memory_bytes() < full.memory_bytes() * 3 / 4 );
+    assert!( quantized.to_json().len() < full.to_json().len() * 3 / 4 );
+
+    let decoded = FrozenModel::from_json(&quantized.to_json()).unwrap();
+    assert!( decoded.is_quantized() );
+    for document in ["pork17 ribs", "kale3 okra3 ribs", "spinach belly400 durian"].iter() {
+        let expected = full.get_document_posteriors(document).unwrap();
+        for (a, e) in decoded.get_document_posteriors(document).unwrap().iter().zip(expected.iter()) {
+            assert_eq!( a.0, e.0 );
+            assert!( (a.1 - e.1).abs() < 1e-5, "{}: {} != {}", document, a.1, e.1 );
+        }
+        assert_eq!( decoded.classify(document).unwrap(), full.classify(document).unwrap() );
+    }
+}
@@ -0,0 +1,54 @@
+extern crate classifier;
+use classifier::{GaussianNaiveBayes, ClassifierError};
+
+// height (feet), weight (lbs), and foot size (inches)
+fn trained_classifier() -> GaussianNaiveBayes {
+    let mut gnb = GaussianNaiveBayes::new();
+    let examples = vec![
+        (vec![6.0, 180.0, 12.0], "male".to_string()),
+        (vec![5.92, 190.0, 11.0], "male".to_string()),
+        (vec![5.58, 170.0, 12.0], "male".to_string()),
+        (vec![5.92, 165.0, 10.0], "male".to_string()),
+        (vec![5.0, 100.0, 6.0], "female".to_string()),
+        (vec![5.5, 150.0, 8.0], "female".to_string()),
+        (vec![5.42, 130.0, 7.0], "female".to_string()),
+        (vec![5.75, 150.0, 9.0], "female".to_string()),
+    ];
+    gnb.add_samples(&examples).unwrap();
+    gnb.train();
+    gnb
+}
+
+#[test]
+fn gaussian_test() {
+    let gnb = trained_classifier();
+
+    let sample = [6.0, 130.0, 8.0];
+    assert_eq!( gnb.classify(&sample).unwrap(), "female" );
+
+    // the probabilities form a distribution
+    let probs = gnb.get_sample_probabilities(&sample).unwrap();
+    let total: f64 = probs.iter().map(|&(_, p)| p).sum();
+    assert!((total - 1.0).abs() < 1e-9);
+    for &(ref label, p) in probs.iter() {
+        if label == "female" {
+            assert!(p > 0.5);
+        }
+    }
+
+    // export and reimport the classifier
+    let gnb2 = GaussianNaiveBayes::from_json( &gnb.to_json() ).unwrap();
+    assert_eq!( gnb2.classify(&sample).unwrap(), "female" );
+}
+
+#[test]
+fn gaussian_errors_test() {
+    let mut gnb = GaussianNaiveBayes::new();
+    gnb.add_sample(&[1.0, 2.0], "a").unwrap();
+
+    assert_eq!( gnb.add_sample(&[1.0], "a"), Err(ClassifierError::DimensionMismatch(2, 1)) );
+    assert_eq!( gnb.classify(&[1.0, 2.0, 3.0]), Err(ClassifierError::DimensionMismatch(2, 3)) );
+
+    let untrained = GaussianNaiveBayes::new();
+    assert_eq!( untrained.classify(&[]), Err(ClassifierError::Untrained) );
+}
@@ -0,0 +1,42 @@
+extern crate classifier;
+use classifier::{NaiveBayes, ClassifierError};
+
+#[test]
+fn hashed_features_test() {
+    let mut nb = NaiveBayes::with_hashed_features(4).unwrap();
+    for i in 0..50 {
+        nb.add_document(&format!("pork{} beef{} ham{}", i, i, i), "meat");
+        nb.add_document(&format!("kale{} okra{} leek{}", i, i, i), "veggie");
+    }
+    nb.add_document("pork beef ham", "meat");
+    nb.add_document("kale okra leek", "veggie");
+    nb.train();
+
+    // hundreds of distinct words, but never more words than buckets
+    assert!( nb.vocab_size() <= 4 );
+    assert_eq!( nb.get_labels().len(), 2 );
+
+    // words are reported by the bucket they hash into
+    let top: Vec<String> = nb.top_words_for_label("meat", 10).into_iter().map(|(w, _)| w).collect();
+    assert!( top.iter().all(|word| word.starts_with('#')) );
+}
+
+#[test]
+fn hashed_features_classify_test() {
+    let mut nb = NaiveBayes::with_hashed_features(1 << 16).unwrap();
+    nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+    nb.add_document("okra kale spinach water chestnut", "veggie");
+    nb.train();
+    assert_eq!( nb.classify("pork belly").unwrap(), "meat" );
+    assert_eq!( nb.classify("kale").unwrap(), "veggie" );
+
+    // the buckets are part of the model
+    let nb2 = NaiveBayes::from_json(&nb.to_json()).unwrap();
+    assert_eq!( nb2.classify("pork belly").unwrap(), "meat" );
+    assert_eq!( nb2.explain("kale").unwrap().label("veggie").unwrap().contributions, nb.explain("kale").unwrap().label("veggie").unwrap().contributions );
+}
+
+#[test]
+fn invalid_bucket_count_test() {
+    assert_eq!( NaiveBayes::with_hashed_features(0).unwrap_err(), ClassifierError::InvalidBucketCount(0) );
+}
@@ -0,0 +1,59 @@
+extern crate classifier;
+use classifier::{ClassifierError, HierarchicalClassifier};
+
+fn trained_classifier() -> HierarchicalClassifier {
+    let mut hc = HierarchicalClassifier::new();
+    hc.add_document("pork chop pork belly ham hock", "food/meat/pork");
+    hc.add_document("beef brisket ribeye sirloin", "food/meat/beef");
+    hc.add_document("kale okra spinach chard", "food/veggie");
+    hc.add_document("hammer nails saw drill", "tools");
+    hc.add_document("drill bits screwdriver wrench", "tools");
+    hc.train();
+    hc
+}
+
+#[test]
+fn hierarchy_test() {
+    let hc = trained_classifier();
+
+    assert_eq!( hc.get_labels(), vec!["food/meat/beef", "food/meat/pork", "food/veggie", "tools"] );
+    assert_eq!( hc.get_children(""), vec!["food", "tools"] );
+    assert_eq!( hc.get_children("food/meat"), vec!["beef", "pork"] );
+    assert!( hc.get_children("tools").is_empty() );
+
+    assert_eq!( hc.classify("ribeye").unwrap(), "food/meat/beef" );
+    assert_eq!( hc.classify("spinach").unwrap(), "food/veggie" );
+    assert_eq!( hc.classify("drill").unwrap(), "tools" );
+
+    // a prediction at every level, most general first
+    let levels = hc.classify_levels("pork belly").unwrap();
+    let paths: Vec<&str> = levels.iter().map(|(path, _)| path.as_str()).collect();
+    assert_eq!( paths, vec!["food", "food/meat", "food/meat/pork"] );
+    for &(_, posterior) in levels.iter() {
+        assert!( posterior > 0.5 && posterior <= 1.0 );
+    }
+}
+
+#[test]
+fn hierarchy_serialization_test() {
+    let hc = trained_classifier();
+    let hc2 = HierarchicalClassifier::from_json(&hc.to_json()).unwrap();
+    assert_eq!( hc2.classify("brisket").unwrap(), "food/meat/beef" );
+    assert_eq!( hc2.get_labels(), hc.get_labels() );
+}
+
+#[test]
+fn separator_test() {
+    let mut hc = HierarchicalClassifier::new();
+    hc.set_separator(" > ");
+    hc.add_document("pork belly", "food > meat");
+    hc.add_document("kale okra", "food > veggie");
+    hc.train();
+    assert_eq!( hc.classify("kale").unwrap(), "food > veggie" );
+}
+
+#[test]
+fn untrained_hierarchy_test() {
+    assert_eq!( HierarchicalClassifier::new().classify("pork"), Err(ClassifierError::Untrained) );
+    assert_eq!( trained_classifier().classify(""), Err(ClassifierError::EmptyDocument) );
+}
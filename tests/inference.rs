@@ -0,0 +1,117 @@
+extern crate classifier;
+use classifier::{ClassifierError, FeatureWeighting, InferenceModel, NaiveBayes, NaiveBayesVariant,
+                 Preprocessor, UnknownWordPolicy};
+
+fn examples() -> Vec<(String, String)> {
+    let examples = [
+        ("sirloin pastrami ham hock pork belly", "meat"),
+        ("ball tip pork chop short ribs pork", "meat"),
+        ("Okra kale spinach water chestnut", "veggie"),
+        ("green bean okra kale", "veggie"),
+        ("apple banana pear", "fruit"),
+    ];
+    examples.iter().map(|&(d, l)| (d.to_string(), l.to_string())).collect()
+}
+
+fn sorted(mut posteriors: Vec<(String, f64)>) -> Vec<(String, f64)> {
+    posteriors.sort_by(|a, b| a.0.cmp(&b.0));
+    posteriors
+}
+
+// checks that the inference model made from the classifier, after a round
+// trip through bytes, gives the same posteriors
+fn assert_same(nb: &mut NaiveBayes) {
+    nb.add_documents(&examples());
+    nb.train();
+    let model = InferenceModel::from_bytes(&nb.to_inference_model().unwrap().to_bytes()).unwrap();
+
+    assert_eq!( model.vocab_size(), nb.vocab_size() );
+    for document in ["pork kale", "banana", "okra ribs pear pork okra", "kale durian durian", "OKRA water"].iter() {
+        let expected = sorted(nb.get_document_posteriors(document).unwrap());
+        let actual = sorted(model.get_document_posteriors(document).unwrap());
+        assert_eq!( actual.len(), expected.len() );
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert_eq!( a.0, e.0 );
+            assert!( (a.1 - e.1).abs() < 1e-9, "{}: {} != {}", document, a.1, e.1 );
+        }
+        // ties between labels may be broken either way
+        let mut ranked = expected.clone();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1));
+        if ranked[0].1 - ranked[1].1 > 1e-9 {
+            assert_eq!( model.classify(document).unwrap(), nb.classify(document).unwrap() );
+        }
+    }
+}
+
+#[test]
+fn inference_variants_test() {
+    for &variant in [NaiveBayesVariant::Multinomial, NaiveBayesVariant::Bernoulli, NaiveBayesVariant::Complement].iter() {
+        assert_same(&mut NaiveBayes::with_variant(variant));
+
+        let mut nb = NaiveBayes::with_variant(variant);
+        nb.set_feature_weighting(FeatureWeighting::TfIdf);
+        assert_same(&mut nb);
+
+        let mut nb = NaiveBayes::with_variant(variant);
+        nb.set_unknown_word_policy(UnknownWordPolicy::DefaultProbability).unwrap();
+        assert_same(&mut nb);
+    }
+}
+
+#[test]
+fn inference_settings_test() {
+    let mut nb = NaiveBayes::new();
+    nb.set_ngram_range(1, 2).unwrap();
+    nb.set_stop_words(&["water".to_string()]);
+    assert_same(&mut nb);
+
+    let mut nb = NaiveBayes::with_hashed_features(16).unwrap();
+    assert_same(&mut nb);
+
+    let mut nb = NaiveBayes::new();
+    nb.set_preprocessor(Preprocessor::builder().lowercase(true).build().unwrap());
+    assert_same(&mut nb);
+
+    let mut nb = NaiveBayes::new();
+    nb.set_online(true);
+    assert_same(&mut nb);
+}
+
+#[test]
+fn inference_errors_test() {
+    assert_eq!( NaiveBayes::new().to_inference_model().unwrap_err(), ClassifierError::Untrained );
+
+    let mut nb = NaiveBayes::new();
+    nb.set_preprocessor(Preprocessor::builder().strip_accents(true).build().unwrap());
+    nb.add_documents(&examples());
+    nb.train();
+    match nb.to_inference_model() {
+        Err(ClassifierError::UnsupportedSetting(_)) => {},
+        other => panic!("expected an unsupported setting, got {:?}", other),
+    }
+
+    let mut nb = NaiveBayes::new();
+    nb.add_documents(&examples());
+    nb.train();
+    let bytes = nb.to_inference_model().unwrap().to_bytes();
+    let model = InferenceModel::from_bytes(&bytes).unwrap();
+    assert_eq!( model.classify("   ").unwrap_err(), ClassifierError::EmptyDocument );
+    assert_eq!( model.get_labels(), vec!["fruit", "meat", "veggie"] );
+
+    for bad in [&bytes[..bytes.len() - 1], &b"not a model"[..], &[]].iter() {
+        match InferenceModel::from_bytes(bad) {
+            Err(ClassifierError::Decode(_)) => {},
+            other => panic!("expected a decoding error, got {:?}", other),
+        }
+    }
+
+    // a label count far beyond what the rest of the model holds
+    assert_eq!( bytes[23..27], 3u32.to_le_bytes() );
+    let mut huge = bytes[..23].to_vec();
+    huge.extend_from_slice(&u32::MAX.to_le_bytes());
+    huge.extend_from_slice(&bytes[27..]);
+    match InferenceModel::from_bytes(&huge) {
+        Err(ClassifierError::Decode(_)) => {},
+        other => panic!("expected a decoding error, got {:?}", other),
+    }
+}
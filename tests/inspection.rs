@@ -0,0 +1,78 @@
+extern crate classifier;
+use classifier::{NaiveBayes, NaiveBayesVariant};
+
+fn add_examples(nb: &mut NaiveBayes) {
+    nb.add_document("pork pork pork beef salt", "meat");
+    nb.add_document("pork ham salt", "meat");
+    nb.add_document("kale kale okra salt", "veggie");
+    nb.add_document("okra spinach salt", "veggie");
+}
+
+#[test]
+fn top_words_test() {
+    let mut nb = NaiveBayes::new();
+    add_examples(&mut nb);
+    nb.train();
+
+    let top = nb.top_words_for_label("meat", 2);
+    assert_eq!( top.len(), 2 );
+    assert_eq!( top[0].0, "pork" );
+    assert_eq!( top[1].0, "salt" );
+    assert!( top[0].1 > top[1].1 );
+
+    // (4 + 1) / (8 + 7)
+    assert!( (top[0].1 - 5.0 / 15.0).abs() < 1e-12 );
+
+    assert!( nb.top_words_for_label("fruit", 3).is_empty() );
+}
+
+#[test]
+fn discriminative_words_test() {
+    for &variant in [NaiveBayesVariant::Multinomial, NaiveBayesVariant::Complement].iter() {
+        let mut nb = NaiveBayes::with_variant(variant);
+        add_examples(&mut nb);
+        nb.train();
+
+        // "salt" is common to both labels, so it says little about either
+        let words = nb.discriminative_words_for_label("veggie", 10);
+        assert_eq!( words.len(), 4 );
+        assert_eq!( words[0].0, "kale" );
+        assert_eq!( words[3].0, "salt" );
+        assert!( words[0].1 > 0.0 );
+        assert!( words[3].1 < words[2].1 );
+    }
+}
+
+#[test]
+fn probability_getters_test() {
+    let mut nb = NaiveBayes::new();
+    add_examples(&mut nb);
+    nb.train();
+
+    assert_eq!( nb.label_prior("meat"), Some(0.5) );
+    assert_eq!( nb.label_prior("fruit"), None );
+
+    // (4 + 1) / (8 + 7), as in top_words_test
+    assert!( (nb.word_probability("meat", "pork").unwrap() - 5.0 / 15.0).abs() < 1e-12 );
+    // a word never seen with the label only gets the smoothing
+    assert!( (nb.word_probability("meat", "kale").unwrap() - 1.0 / 15.0).abs() < 1e-12 );
+    assert_eq!( nb.word_probability("meat", "plum"), None );
+    assert_eq!( nb.word_probability("fruit", "pork"), None );
+}
+
+#[test]
+fn vocabulary_test() {
+    let mut nb = NaiveBayes::new();
+    add_examples(&mut nb);
+    nb.train();
+
+    let words: Vec<&str> = nb.vocabulary().collect();
+    assert_eq!( words, vec!["beef", "ham", "kale", "okra", "pork", "salt", "spinach"] );
+    assert_eq!( words.len(), nb.vocab_size() );
+
+    let mut exported = Vec::new();
+    nb.export_vocabulary(&mut exported).unwrap();
+    assert_eq!( String::from_utf8(exported).unwrap(), "beef\nham\nkale\nokra\npork\nsalt\nspinach\n" );
+
+    assert_eq!( NaiveBayes::new().vocabulary().count(), 0 );
+}
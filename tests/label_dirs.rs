@@ -0,0 +1,57 @@
+extern crate classifier;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use classifier::ClassifierError;
+use classifier::dataset;
+
+// a fresh corpus directory for each test
+fn corpus_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("classifier-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("meat")).unwrap();
+    fs::create_dir_all(dir.join("veggie")).unwrap();
+    dir
+}
+
+#[test]
+fn from_label_dirs_test() {
+    let dir = corpus_dir("label-dirs");
+    fs::write(dir.join("meat").join("2.txt"), "pork belly").unwrap();
+    fs::write(dir.join("meat").join("1.txt"), "ham hock").unwrap();
+    fs::write(dir.join("veggie").join("1.txt"), "\u{feff}kale okra").unwrap();
+    fs::write(dir.join("veggie").join(".DS_Store"), [0xffu8, 0x00]).unwrap();
+    fs::write(dir.join("README"), "not a label").unwrap();
+
+    let examples = dataset::from_label_dirs(&dir).unwrap();
+    let expected: Vec<(String, String)> = [("ham hock", "meat"), ("pork belly", "meat"), ("kale okra", "veggie")]
+        .iter().map(|&(d, l)| (d.to_string(), l.to_string())).collect();
+    assert_eq!( examples, expected );
+
+    // files in other encodings are refused
+    fs::write(dir.join("veggie").join("2.txt"), b"cr\xe8me br\xfbl\xe9e").unwrap();
+    match dataset::from_label_dirs(&dir) {
+        Err(ClassifierError::Decode(ref reason)) => assert!( reason.contains("2.txt") ),
+        other => panic!("expected a decode error, got {:?}", other),
+    }
+    fs::remove_dir_all(&dir).unwrap();
+
+    match dataset::from_label_dirs(&dir) {
+        Err(ClassifierError::Io(_)) => {},
+        other => panic!("expected an io error, got {:?}", other),
+    }
+}
+
+#[cfg(feature = "encoding-detection")]
+#[test]
+fn detecting_encoding_test() {
+    let dir = corpus_dir("detecting-encoding");
+    fs::write(dir.join("meat").join("1.txt"), "pâté de campagne").unwrap();
+    fs::write(dir.join("veggie").join("1.txt"),
+              &b"la cr\xe8me br\xfbl\xe9e et le caf\xe9 sont d\xe9licieux, tr\xe8s fran\xe7ais"[..]).unwrap();
+
+    let examples = dataset::from_label_dirs_detecting_encoding(&dir).unwrap();
+    assert_eq!( examples[0].0, "pâté de campagne" );
+    assert_eq!( examples[1].0, "la crème brûlée et le café sont délicieux, très français" );
+    fs::remove_dir_all(&dir).unwrap();
+}
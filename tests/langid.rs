@@ -0,0 +1,42 @@
+#![cfg(feature = "langid")]
+extern crate classifier;
+use classifier::ClassifierError;
+use classifier::langid::LanguageIdentifier;
+
+#[test]
+fn pretrained_test() {
+    let identifier = LanguageIdentifier::pretrained();
+    assert_eq!( identifier.languages(), vec!["de", "en", "es", "fr", "it", "nl", "pt"] );
+
+    let examples = [
+        ("my friends are waiting for me at the station", "en"),
+        ("nous avons mangé du fromage avec nos amis", "fr"),
+        ("die kinder haben heute keine schule", "de"),
+        ("mis amigos están esperando en la estación", "es"),
+        ("i miei amici sono andati al mercato", "it"),
+        ("os meus amigos estão à espera na estação", "pt"),
+        ("mijn vrienden wachten op het station", "nl"),
+    ];
+    for &(text, language) in examples.iter() {
+        assert_eq!( identifier.detect(text).unwrap(), language, "{}", text );
+    }
+
+    let probabilities = identifier.probabilities("merci pour le petit déjeuner").unwrap();
+    assert_eq!( probabilities.len(), 7 );
+    assert_eq!( probabilities[0].0, "fr" );
+    assert_eq!( identifier.detect_with_threshold("le chien et le chat", 0.5).unwrap(), Some("fr".to_string()) );
+    assert_eq!( identifier.detect_with_threshold("le chien et le chat", 1.1).unwrap(), None );
+}
+
+#[test]
+fn trainable_test() {
+    let mut identifier = LanguageIdentifier::new();
+    assert_eq!( identifier.detect("hello"), Err(ClassifierError::Untrained) );
+    identifier.add_sample("hyvää huomenta, kiitos paljon", "fi");
+    identifier.add_sample("god morgon, tack så mycket", "sv");
+    identifier.train();
+    assert_eq!( identifier.detect("kiitos").unwrap(), "fi" );
+
+    let decoded = LanguageIdentifier::from_json(&identifier.to_json()).unwrap();
+    assert_eq!( decoded.detect("tack så mycket").unwrap(), "sv" );
+}
@@ -0,0 +1,67 @@
+extern crate classifier;
+use classifier::{LogisticRegression, ClassifierError};
+
+fn trained_classifier() -> LogisticRegression {
+    let mut lr = LogisticRegression::new();
+    lr.add_document("sirloin pastrami ham hock pork belly", "meat");
+    lr.add_document("ball tip pork chop short ribs", "meat");
+    lr.add_document("salami pancetta beef ribs", "meat");
+    lr.add_document("okra kale spinach water chestnut", "veggie");
+    lr.add_document("green bean okra kale", "veggie");
+    lr.add_document("spinach arugula pea sprouts", "veggie");
+    lr.add_document("apple banana pear", "fruit");
+    lr.add_document("pear plum apple", "fruit");
+    lr.train();
+    lr
+}
+
+#[test]
+fn classify_test() {
+    let lr = trained_classifier();
+    assert_eq!( lr.classify("pork belly").unwrap(), "meat" );
+    assert_eq!( lr.classify("kale spinach").unwrap(), "veggie" );
+    assert_eq!( lr.classify("plum").unwrap(), "fruit" );
+
+    let posteriors = lr.get_document_posteriors("pork ribs").unwrap();
+    assert_eq!( posteriors.len(), 3 );
+    assert!( (posteriors.iter().map(|&(_, p)| p).sum::<f64>() - 1.0).abs() < 1e-9 );
+    assert!( posteriors.iter().all(|&(ref label, p)| label == "meat" || p < 0.5) );
+}
+
+#[test]
+fn deterministic_test() {
+    // the same documents and seed always give the same model
+    let posteriors = trained_classifier().get_document_posteriors("pork kale pear").unwrap();
+    assert_eq!( trained_classifier().get_document_posteriors("pork kale pear").unwrap(), posteriors );
+}
+
+#[test]
+fn serialization_test() {
+    let lr = trained_classifier();
+    let lr2 = LogisticRegression::from_json(&lr.to_json()).unwrap();
+    let expected = lr.get_document_posteriors("okra pear").unwrap();
+    for (posterior, expected) in lr2.get_document_posteriors("okra pear").unwrap().iter().zip(expected.iter()) {
+        assert_eq!( posterior.0, expected.0 );
+        assert!( (posterior.1 - expected.1).abs() < 1e-9 );
+    }
+}
+
+#[test]
+fn errors_test() {
+    let mut lr = LogisticRegression::new();
+    assert_eq!( lr.classify("pork"), Err(ClassifierError::Untrained) );
+    lr.add_document("pork", "meat");
+    assert_eq!( lr.classify("pork"), Err(ClassifierError::Untrained) );
+
+    assert_eq!( lr.set_learning_rate(0.0), Err(ClassifierError::InvalidLearningRate(0.0)) );
+    assert_eq!( lr.set_regularization(-1.0), Err(ClassifierError::InvalidRegularization(-1.0)) );
+}
+
+#[test]
+fn empty_document_test() {
+    let mut lr = LogisticRegression::new();
+    lr.add_document("pork belly", "meat");
+    lr.add_document("kale okra", "veggie");
+    lr.train();
+    assert_eq!( lr.classify("  "), Err(ClassifierError::EmptyDocument) );
+}
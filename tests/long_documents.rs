@@ -0,0 +1,41 @@
+extern crate classifier;
+use classifier::{NaiveBayes, NaiveBayesVariant};
+
+fn trained(variant: NaiveBayesVariant, online: bool) -> NaiveBayes {
+    let mut nb = NaiveBayes::with_variant(variant);
+    nb.set_online(online);
+    nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+    nb.add_document("ball tip pork chop short ribs pork", "meat");
+    nb.add_document("okra kale spinach water chestnut", "veggie");
+    nb.add_document("green bean okra kale", "veggie");
+    nb.train();
+    nb
+}
+
+// a document of tens of thousands of tokens, most of them repeats
+fn long_document() -> String {
+    let words = ["pork", "okra", "kale", "ribs", "durian", "pork", "belly", "spinach"];
+    (0..40000).map(|i| words[(i * 7 + i / 3) % words.len()]).collect::<Vec<&str>>().join(" ")
+}
+
+#[test]
+fn long_document_test() {
+    let document = long_document();
+    for &variant in [NaiveBayesVariant::Multinomial, NaiveBayesVariant::Bernoulli, NaiveBayesVariant::Complement].iter() {
+        for &online in [false, true].iter() {
+            let nb = trained(variant, online);
+            // scoring each distinct word once, times its count, matches the
+            // inference model, which adds up every occurrence
+            let model = nb.to_inference_model().unwrap();
+            let mut expected = model.get_document_posteriors(&document).unwrap();
+            let mut actual = nb.get_document_posteriors(&document).unwrap();
+            expected.sort_by(|a, b| a.0.cmp(&b.0));
+            actual.sort_by(|a, b| a.0.cmp(&b.0));
+            for (a, e) in actual.iter().zip(expected.iter()) {
+                assert_eq!( a.0, e.0 );
+                assert!( (a.1 - e.1).abs() < 1e-6, "{:?} {}: {} != {}", variant, a.0, a.1, e.1 );
+            }
+            assert_eq!( nb.classify(&document).unwrap(), model.classify(&document).unwrap() );
+        }
+    }
+}
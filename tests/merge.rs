@@ -0,0 +1,76 @@
+extern crate classifier;
+use classifier::{ClassifierError, NaiveBayes, NaiveBayesVariant};
+
+fn examples() -> Vec<(String, String)> {
+    let examples = [
+        ("sirloin pastrami ham hock pork belly", "meat"),
+        ("ball tip pork chop short ribs", "meat"),
+        ("okra kale spinach water chestnut", "veggie"),
+        ("green bean okra kale", "veggie"),
+        ("apple banana pear", "fruit"),
+    ];
+    examples.iter().map(|&(d, l)| (d.to_string(), l.to_string())).collect()
+}
+
+fn sorted(mut posteriors: Vec<(String, f64)>) -> Vec<(String, f64)> {
+    posteriors.sort_by(|a, b| a.0.cmp(&b.0));
+    posteriors
+}
+
+#[test]
+fn merge_test() {
+    for &variant in [NaiveBayesVariant::Multinomial, NaiveBayesVariant::Bernoulli, NaiveBayesVariant::Complement].iter() {
+        let mut whole = NaiveBayes::new();
+        whole.set_variant(variant);
+        whole.add_documents(&examples());
+        whole.train();
+
+        // each shard sees only some of the documents and labels
+        let shards: Vec<NaiveBayes> = examples().chunks(2).map(|chunk| {
+            let mut nb = NaiveBayes::new();
+            nb.set_variant(variant);
+            nb.add_documents(chunk);
+            nb.train();
+            nb
+        }).collect();
+        let merged = NaiveBayes::merge(&shards).unwrap();
+
+        assert_eq!( merged.vocab_size(), whole.vocab_size() );
+        for document in ["pork kale", "banana", "okra ribs pear"].iter() {
+            let expected = sorted(whole.get_document_posteriors(document).unwrap());
+            let actual = sorted(merged.get_document_posteriors(document).unwrap());
+            for (e, a) in expected.iter().zip(actual.iter()) {
+                assert_eq!( e.0, a.0 );
+                assert!( (e.1 - a.1).abs() < 1e-9 );
+            }
+        }
+    }
+}
+
+#[test]
+fn merge_metadata_test() {
+    let mut a = NaiveBayes::new();
+    a.add_document("pork", "meat");
+    a.set_label_metadata("meat", "id", "1");
+    let mut b = NaiveBayes::new();
+    b.add_document("beef", "meat");
+    b.set_label_metadata("meat", "id", "2");
+    b.set_label_metadata("meat", "color", "red");
+
+    let merged = NaiveBayes::merge(&[a, b]).unwrap();
+    let metadata = merged.get_label_metadata("meat").unwrap();
+    assert_eq!( metadata["id"], "1" );
+    assert_eq!( metadata["color"], "red" );
+}
+
+#[test]
+fn merge_errors_test() {
+    let none: [NaiveBayes; 0] = [];
+    assert!( NaiveBayes::merge(&none).is_err() );
+
+    let a = NaiveBayes::new();
+    let mut b = NaiveBayes::new();
+    b.set_smoothing(0.5).unwrap();
+    assert_eq!( NaiveBayes::merge(&[a, b]).err(),
+                Some(ClassifierError::IncompatibleModels("the smoothing differs".to_string())) );
+}
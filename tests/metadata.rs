@@ -0,0 +1,34 @@
+extern crate classifier;
+use std::collections::HashMap;
+use classifier::NaiveBayes;
+
+#[test]
+fn label_metadata_test() {
+    let mut nb = NaiveBayes::new();
+    nb.set_label_metadata("meat", "display_name", "Meat & Poultry");
+    nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+    nb.add_document("okra kale spinach water chestnut", "veggie");
+    nb.set_label_metadata("meat", "id", "17");
+    nb.set_label_metadata("meat", "id", "42");
+    nb.train();
+
+    let metadata = nb.get_label_metadata("meat").unwrap();
+    assert_eq!( metadata.len(), 2 );
+    assert_eq!( metadata["id"], "42" );
+    assert!( nb.get_label_metadata("veggie").is_none() );
+
+    // the metadata comes with the predicted label
+    let (label, metadata) = nb.classify_with_metadata("pork belly").unwrap();
+    assert_eq!( label, "meat" );
+    assert_eq!( metadata["display_name"], "Meat & Poultry" );
+    assert_eq!( nb.classify_with_metadata("kale").unwrap(), ("veggie".to_string(), HashMap::new()) );
+
+    // and is saved with the classifier
+    let nb2 = NaiveBayes::from_json(&nb.to_json()).unwrap();
+    assert_eq!( nb2.get_label_metadata("meat"), nb.get_label_metadata("meat") );
+
+    assert_eq!( nb.remove_label_metadata("meat", "id"), Some("42".to_string()) );
+    assert_eq!( nb.remove_label_metadata("meat", "id"), None );
+    assert_eq!( nb.remove_label_metadata("meat", "display_name"), Some("Meat & Poultry".to_string()) );
+    assert!( nb.get_label_metadata("meat").is_none() );
+}
@@ -0,0 +1,73 @@
+extern crate classifier;
+use classifier::{NaiveBayes, ClassifierError};
+
+#[test]
+fn bigram_test() {
+
+    let mut nb = NaiveBayes::new();
+    nb.set_ngram_range(1, 2).unwrap();
+
+    // both labels have exactly the same words, only the word order differs
+    nb.add_document("green bean soup", "veggie");
+    nb.add_document("bean green soup", "other");
+
+    nb.train();
+
+    // only the bigrams can tell these apart
+    assert_eq!( nb.classify("green bean").unwrap(), "veggie" );
+    assert_eq!( nb.classify("bean green").unwrap(), "other" );
+
+    // the n-gram range is kept when exporting and reimporting the classifier
+    let nb2 = NaiveBayes::from_json( &nb.to_json() ).unwrap();
+    assert_eq!( nb2.classify("green bean").unwrap(), "veggie" );
+    assert_eq!( nb2.classify("bean green").unwrap(), "other" );
+}
+
+#[test]
+fn invalid_ngram_range_test() {
+    let mut nb = NaiveBayes::new();
+    assert_eq!( nb.set_ngram_range(3, 2), Err(ClassifierError::InvalidNgramRange(3, 2)) );
+    assert_eq!( nb.set_ngram_range(0, 2), Err(ClassifierError::InvalidNgramRange(0, 2)) );
+}
+
+#[test]
+fn skip_gram_test() {
+    let mut nb = NaiveBayes::new();
+    nb.set_skip_grams(2);
+    nb.add_document("pork spare ribs", "meat");
+    nb.add_document("ribs spare pork", "other");
+    nb.train();
+    // 3 words, 2 skip-grams skipping one word, and none skipping two
+    assert_eq!( nb.vocab_size(), 5 );
+
+    // "pork _ ribs" matches the first document even with another word
+    // between the two
+    assert_eq!( nb.classify("pork baby ribs").unwrap(), "meat" );
+    assert_eq!( nb.classify("ribs baby pork").unwrap(), "other" );
+
+    let nb2 = NaiveBayes::from_json( &nb.to_json() ).unwrap();
+    assert_eq!( nb2.classify("pork baby ribs").unwrap(), "meat" );
+    let frozen = nb2.freeze();
+    assert_eq!( frozen.classify("ribs baby pork").unwrap(), "other" );
+    assert_eq!( nb.to_inference_model().unwrap_err(), ClassifierError::UnsupportedSetting("skip-grams".to_string()) );
+}
+
+#[test]
+fn skip_gram_window_test() {
+    let mut nb = NaiveBayes::new();
+    nb.set_skip_grams(2);
+    nb.add_document("pork belly spare ribs", "meat");
+    nb.add_document("kale okra", "veggie");
+    nb.train();
+    // 6 words, "pork _ spare" and "belly _ ribs", and "pork _ _ ribs"
+    assert_eq!( nb.vocab_size(), 9 );
+
+    // skip-grams are made after stop words are dropped
+    let mut nb = NaiveBayes::new();
+    nb.set_skip_grams(1);
+    nb.set_stop_words(vec!["and"]);
+    nb.add_document("pork and belly and ribs", "meat");
+    nb.add_document("kale okra", "veggie");
+    nb.train();
+    assert_eq!( nb.vocab_size(), 6 );
+}
@@ -0,0 +1,50 @@
+extern crate classifier;
+use classifier::{LogisticRegression, NaiveBayes, TrainingObserver};
+
+#[derive(Default)]
+struct Recorder {
+    labels: Vec<String>,
+    progress: Vec<f64>,
+}
+
+impl TrainingObserver for Recorder {
+    fn on_document_added(&mut self, label: &str) {
+        self.labels.push(label.to_string());
+    }
+
+    fn on_train_progress(&mut self, fraction: f64) {
+        self.progress.push(fraction);
+    }
+}
+
+#[test]
+fn naive_bayes_observer_test() {
+    let mut recorder = Recorder::default();
+    let mut nb = NaiveBayes::new();
+    let examples = vec![("pork belly", "meat"), ("kale okra", "veggie"), ("apple pear", "fruit"), ("ham hock", "meat")];
+    nb.add_documents_observed(examples, &mut recorder);
+    assert_eq!( recorder.labels, vec!["meat", "veggie", "fruit", "meat"] );
+
+    nb.train_observed(&mut recorder);
+    assert_eq!( recorder.progress.len(), 3 );
+    assert!( recorder.progress.windows(2).all(|w| w[0] < w[1]) );
+    assert_eq!( *recorder.progress.last().unwrap(), 1.0 );
+    assert_eq!( nb.classify("pork").unwrap(), "meat" );
+
+    // an empty classifier still finishes
+    let mut recorder = Recorder::default();
+    NaiveBayes::new().train_observed(&mut recorder);
+    assert_eq!( recorder.progress, vec![1.0] );
+}
+
+#[test]
+fn logistic_observer_test() {
+    let mut recorder = Recorder::default();
+    let mut lr = LogisticRegression::new();
+    lr.add_document("pork belly", "meat");
+    lr.add_document("kale okra", "veggie");
+    lr.set_epochs(4);
+    lr.train_observed(&mut recorder);
+    assert_eq!( recorder.progress, vec![0.25, 0.5, 0.75, 1.0] );
+    assert_eq!( lr.classify("pork").unwrap(), "meat" );
+}
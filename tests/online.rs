@@ -0,0 +1,49 @@
+extern crate classifier;
+use classifier::NaiveBayes;
+
+#[test]
+fn online_learning_test() {
+
+    let mut nb = NaiveBayes::new();
+    nb.set_online(true);
+
+    // no training needed between adding documents and classifying
+    nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+    nb.add_document("okra kale spinach water chestnut", "veggie");
+    assert_eq!( nb.classify("pork belly").unwrap(), "meat" );
+
+    // a newly added document is picked up right away
+    nb.add_document("salami salami broccoli", "veggie");
+    assert_eq!( nb.classify("salami").unwrap(), "veggie" );
+}
+
+#[test]
+fn online_matches_trained_test() {
+
+    let examples = [
+        ("sirloin pastrami ham hock pork belly ham", "meat"),
+        ("okra kale spinach water chestnut kale", "veggie"),
+        ("ball tip pork chop short ribs", "meat"),
+    ];
+
+    let mut online = NaiveBayes::new();
+    online.set_online(true);
+    let mut trained = NaiveBayes::new();
+
+    for &(document, label) in examples.iter() {
+        online.add_document(document, label);
+        trained.add_document(document, label);
+    }
+    trained.train();
+
+    let document = "ham kale pork water";
+    let mut online_probs = online.get_document_posteriors(document).unwrap();
+    let mut trained_probs = trained.get_document_posteriors(document).unwrap();
+    online_probs.sort_by(|a, b| a.0.cmp(&b.0));
+    trained_probs.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (o, t) in online_probs.iter().zip(trained_probs.iter()) {
+        assert_eq!(o.0, t.0);
+        assert!((o.1 - t.1).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,139 @@
+extern crate classifier;
+extern crate serde_json;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use classifier::{ClassifierError, NaiveBayes};
+
+// the fields of a protobuf message, by field number, with varints as
+// numbers and length-delimited fields as bytes
+#[derive(Debug)]
+enum Value {
+    Varint(u64),
+    Bytes(Vec<u8>),
+}
+
+fn read_varint(bytes: &[u8], i: &mut usize) -> u64 {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let byte = bytes[*i];
+        *i += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte < 0x80 {
+            return value;
+        }
+        shift += 7;
+    }
+}
+
+fn parse(bytes: &[u8]) -> Vec<(u64, Value)> {
+    let mut fields = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let key = read_varint(bytes, &mut i);
+        let value = match key & 7 {
+            0 => Value::Varint(read_varint(bytes, &mut i)),
+            2 => {
+                let len = read_varint(bytes, &mut i) as usize;
+                i += len;
+                Value::Bytes(bytes[i - len..i].to_vec())
+            },
+            wire_type => panic!("unexpected wire type {}", wire_type),
+        };
+        fields.push((key >> 3, value));
+    }
+    fields
+}
+
+fn messages(fields: &[(u64, Value)], field: u64) -> Vec<Vec<(u64, Value)>> {
+    fields.iter().filter_map(|(number, value)| match *value {
+        Value::Bytes(ref bytes) if *number == field => Some(parse(bytes)),
+        _ => None,
+    }).collect()
+}
+
+fn string(fields: &[(u64, Value)], field: u64) -> String {
+    fields.iter().find_map(|(number, value)| match *value {
+        Value::Bytes(ref bytes) if *number == field => Some(String::from_utf8(bytes.clone()).unwrap()),
+        _ => None,
+    }).unwrap()
+}
+
+// the model's graph evaluated by hand: the initializers by name and the
+// metadata
+struct Exported {
+    tensors: HashMap<String, (Vec<u64>, Vec<f32>)>,
+    metadata: HashMap<String, String>,
+    ops: Vec<String>,
+}
+
+fn read_model(bytes: &[u8]) -> Exported {
+    let model = parse(bytes);
+    let graph = messages(&model, 7).remove(0);
+    let tensors = messages(&graph, 5).iter().map(|tensor| {
+        let dims = tensor.iter().filter_map(|(number, value)| match *value {
+            Value::Varint(dim) if *number == 1 => Some(dim),
+            _ => None,
+        }).collect();
+        let raw = tensor.iter().find_map(|(number, value)| match *value {
+            Value::Bytes(ref bytes) if *number == 9 => Some(bytes.clone()),
+            _ => None,
+        }).unwrap();
+        let values = raw.chunks(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])).collect();
+        (string(tensor, 8), (dims, values))
+    }).collect();
+    let metadata = messages(&model, 14).iter().map(|entry| (string(entry, 1), string(entry, 2))).collect();
+    let ops = messages(&graph, 1).iter().map(|node| string(node, 4)).collect();
+    Exported { tensors, metadata, ops }
+}
+
+#[test]
+fn export_onnx_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+    nb.add_document("okra kale spinach water chestnut", "veggie");
+    nb.add_document("apple pear okra", "fruit");
+    nb.train();
+    let path = env::temp_dir().join(format!("classifier-onnx-{}.onnx", std::process::id()));
+    nb.export_onnx(&path).unwrap();
+    let exported = read_model(&fs::read(&path).unwrap());
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!( exported.ops, vec!["MatMul", "Add", "Softmax", "ArgMax"] );
+    let labels: Vec<String> = serde_json::from_str(&exported.metadata["labels"]).unwrap();
+    assert_eq!( labels, nb.get_labels() );
+    let words: Vec<String> = serde_json::from_str(&exported.metadata["vocabulary"]).unwrap();
+    assert_eq!( words, nb.vocabulary().collect::<Vec<_>>() );
+    assert_eq!( exported.metadata["binary_features"], "false" );
+
+    let (ref dims, ref weights) = exported.tensors["weights"];
+    assert_eq!( dims, &vec![words.len() as u64 + 1, 3] );
+    let biases = &exported.tensors["biases"].1;
+    for document in ["pork okra okra", "apple kale durian", "ham"].iter() {
+        // one column per word and a last one for unknown features
+        let mut counts = vec![0.0f32; words.len() + 1];
+        for word in document.split_whitespace() {
+            let column = words.iter().position(|w| w == word).unwrap_or(words.len());
+            counts[column] += 1.0;
+        }
+        let scores: Vec<f32> = (0..labels.len()).map(|label| {
+            biases[label] + counts.iter().enumerate().map(|(row, count)| count * weights[row * labels.len() + label]).sum::<f32>()
+        }).collect();
+        let max = scores.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+        let total: f32 = scores.iter().map(|score| (score - max).exp()).sum();
+        let expected = nb.get_document_posteriors(document).unwrap();
+        for (score, (label, posterior)) in scores.iter().zip(expected.iter()) {
+            assert!( (((score - max).exp() / total) as f64 - posterior).abs() < 1e-5, "{} {}", document, label );
+        }
+        let best = scores.iter().position(|&score| score == max).unwrap();
+        assert_eq!( labels[best], nb.classify(document).unwrap() );
+    }
+}
+
+#[test]
+fn export_untrained_onnx_test() {
+    let path = env::temp_dir().join(format!("classifier-onnx-untrained-{}.onnx", std::process::id()));
+    assert_eq!( NaiveBayes::new().export_onnx(&path), Err(ClassifierError::Untrained) );
+    assert!( !path.exists() );
+}
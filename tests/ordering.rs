@@ -0,0 +1,42 @@
+extern crate classifier;
+use classifier::{GaussianNaiveBayes, NaiveBayes};
+
+fn labels(posteriors: &[(String, f64)]) -> Vec<&str> {
+    posteriors.iter().map(|(label, _)| label.as_str()).collect()
+}
+
+#[test]
+fn label_order_test() {
+    let mut nb = NaiveBayes::new();
+    for &(document, label) in [("zucchini okra", "veggie"), ("pork ribs", "meat"), ("apple pear", "fruit"),
+                               ("bread rice", "grain")].iter() {
+        nb.add_document(document, label);
+    }
+    nb.train();
+
+    let sorted = vec!["fruit", "grain", "meat", "veggie"];
+    assert_eq!( nb.get_labels(), sorted );
+    assert_eq!( labels(&nb.get_document_posteriors("pork okra").unwrap()), sorted );
+    #[allow(deprecated)]
+    let probabilities = nb.get_document_probabilities("pork okra");
+    assert_eq!( labels(&probabilities), sorted );
+}
+
+#[test]
+fn tie_breaking_test() {
+    // both labels score the same, so the first one wins
+    let mut nb = NaiveBayes::new();
+    nb.add_document("b", "second");
+    nb.add_document("a", "first");
+    nb.train();
+    assert_eq!( nb.classify("c").unwrap(), "first" );
+}
+
+#[test]
+fn gaussian_label_order_test() {
+    let mut gnb = GaussianNaiveBayes::new();
+    gnb.add_sample(&[1.0], "b").unwrap();
+    gnb.add_sample(&[2.0], "c").unwrap();
+    gnb.add_sample(&[3.0], "a").unwrap();
+    assert_eq!( gnb.get_labels(), vec!["a", "b", "c"] );
+}
@@ -0,0 +1,78 @@
+extern crate classifier;
+#[macro_use]
+extern crate serde_json;
+use std::env;
+use std::fs;
+use classifier::{ClassifierError, Collocations, NaiveBayes, Pipeline, Preprocessor, TextClassifier, TokenizerStage};
+
+fn assert_same_posteriors(a: &Pipeline, b: &Pipeline, document: &str) {
+    let expected = b.get_document_posteriors(document).unwrap();
+    for ((label, p), (expected_label, e)) in a.get_document_posteriors(document).unwrap().into_iter().zip(expected) {
+        assert_eq!( label, expected_label );
+        assert!( (p - e).abs() < 1e-12, "{}: {} != {}", document, p, e );
+    }
+}
+
+#[test]
+fn pipeline_stages_test() {
+    let mut collocations = Collocations::new();
+    collocations.insert("pork", "belly");
+    let mut pipeline = Pipeline::builder()
+        .preprocessor(Preprocessor::builder().lowercase(true).strip_punctuation(true).build().unwrap())
+        .tokenizer(TokenizerStage::Collocations(collocations))
+        .stop_words(vec!["the"])
+        .ngram_range(1, 2)
+        .build()
+        .unwrap();
+    pipeline.add_document("The Pork Belly ribs!", "meat");
+    pipeline.add_document("the kale and okra", "veggie");
+    pipeline.train();
+
+    let words: Vec<&str> = pipeline.classifier().vocabulary().collect();
+    assert_eq!( words, vec!["and", "and okra", "kale", "kale and", "okra", "pork_belly", "pork_belly ribs", "ribs"] );
+    assert_eq!( pipeline.classify("PORK BELLY").unwrap(), "meat" );
+    assert_eq!( TextClassifier::labels(&pipeline), vec!["meat", "veggie"] );
+}
+
+#[test]
+fn pipeline_serialization_test() {
+    let mut pipeline = Pipeline::builder()
+        .preprocessor(Preprocessor::builder().lowercase(true).build().unwrap())
+        .tokenizer(TokenizerStage::CharNgrams(3, 4))
+        .build()
+        .unwrap();
+    pipeline.add_document("Pork Belly Ribs", "meat");
+    pipeline.add_document("Kale Okra Spinach", "veggie");
+    pipeline.train();
+
+    // the loaded pipeline tokenizes into character n-grams without being told to
+    let loaded = Pipeline::from_json(&pipeline.to_json()).unwrap();
+    assert_eq!( loaded.tokenizer(), &TokenizerStage::CharNgrams(3, 4) );
+    for document in ["porky", "OKRAS and ribs", "spinach"].iter() {
+        assert_same_posteriors(&loaded, &pipeline, document);
+    }
+
+    let path = env::temp_dir().join(format!("classifier-pipeline-{}.json", std::process::id()));
+    pipeline.save_to_file(&path).unwrap();
+    let loaded = Pipeline::load_from_file(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_same_posteriors(&loaded, &pipeline, "kales");
+
+    // the classifier is encoded as a plain classifier alongside the tokenizer
+    let encoded: serde_json::Value = serde_json::from_str(&pipeline.to_json()).unwrap();
+    assert_eq!( encoded["tokenizer"], json!({"CharNgrams": [3, 4]}) );
+    let mut plain = NaiveBayes::from_json(&encoded["classifier"].to_string()).unwrap();
+    assert_eq!( plain.get_labels(), vec!["meat", "veggie"] );
+    plain.train();
+}
+
+#[test]
+fn invalid_pipeline_test() {
+    assert_eq!( Pipeline::builder().tokenizer(TokenizerStage::CharNgrams(4, 3)).build().unwrap_err(),
+                ClassifierError::InvalidNgramRange(4, 3) );
+    assert_eq!( Pipeline::builder().ngram_range(0, 2).build().unwrap_err(), ClassifierError::InvalidNgramRange(0, 2) );
+    assert_eq!( Pipeline::builder().hashed_features(0).build().unwrap_err(), ClassifierError::InvalidBucketCount(0) );
+
+    let encoded = Pipeline::new().to_json().replace("\"Whitespace\"", "{\"CharNgrams\":[0,2]}");
+    assert!( Pipeline::from_json(&encoded).is_err() );
+}
@@ -0,0 +1,61 @@
+extern crate classifier;
+use classifier::{NaiveBayes, NaiveBayesVariant};
+
+fn add_examples(nb: &mut NaiveBayes) {
+    nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+    nb.add_document("ball tip pork chop short ribs", "meat");
+    nb.add_document("okra kale spinach water chestnut", "veggie");
+    nb.add_document("apple banana pear", "fruit");
+}
+
+#[test]
+fn posteriors_sum_to_one_test() {
+    let variants = [NaiveBayesVariant::Multinomial,
+                    NaiveBayesVariant::Bernoulli,
+                    NaiveBayesVariant::Complement];
+
+    for &variant in variants.iter() {
+        let mut nb = NaiveBayes::with_variant(variant);
+        add_examples(&mut nb);
+        nb.train();
+
+        let posteriors = nb.get_document_posteriors("pork belly and some kale").unwrap();
+        assert_eq!( posteriors.len(), 3 );
+
+        let total: f64 = posteriors.iter().map(|&(_, p)| p).sum();
+        assert!( (total - 1.0).abs() < 1e-9 );
+        for &(_, p) in posteriors.iter() {
+            assert!( (0.0..=1.0).contains(&p) );
+        }
+
+        let best = posteriors.iter().fold(("", 0.0), |acc, (l, p)| if *p > acc.1 { (l, *p) } else { acc });
+        assert_eq!( best.0, nb.classify("pork belly and some kale").unwrap() );
+    }
+}
+
+#[test]
+fn long_document_test() {
+    let mut nb = NaiveBayes::new();
+    add_examples(&mut nb);
+    nb.train();
+
+    // the log likelihoods of a long document are far too small to
+    // exponentiate directly
+    let document = vec!["pork"; 5000].join(" ");
+    let posteriors = nb.get_document_posteriors(&document).unwrap();
+    let meat = posteriors.iter().find(|&(l, _)| l == "meat").unwrap().1;
+    assert!( (meat - 1.0).abs() < 1e-9 );
+}
+
+#[test]
+#[allow(deprecated)]
+fn deprecated_probabilities_test() {
+    let mut nb = NaiveBayes::new();
+    add_examples(&mut nb);
+    nb.train();
+
+    // the old values still rank the labels the same way
+    let probabilities = nb.get_document_probabilities("pork belly");
+    let best = probabilities.iter().fold(("", f64::MIN), |acc, (l, p)| if *p > acc.1 { (l, *p) } else { acc });
+    assert_eq!( best.0, "meat" );
+}
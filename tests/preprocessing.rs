@@ -0,0 +1,130 @@
+extern crate classifier;
+use classifier::{ClassifierError, NaiveBayes, Preprocessor, TokenFilter, UnicodeNormalization};
+
+#[test]
+fn preprocessor_test() {
+    assert_eq!( Preprocessor::new().process("Beef Café"), "Beef Café" );
+
+    let lowercase = Preprocessor::builder().lowercase(true).build().unwrap();
+    assert_eq!( lowercase.process("Beef BEEF beef"), "beef beef beef" );
+
+    // "e" followed by a combining acute accent composes to "é"
+    let nfc = Preprocessor::builder().normalization(UnicodeNormalization::Nfc).build().unwrap();
+    assert_eq!( nfc.process("cafe\u{301}"), "caf\u{e9}" );
+
+    // the "fi" ligature is only folded by compatibility normalization
+    let nfkc = Preprocessor::builder().normalization(UnicodeNormalization::Nfkc).build().unwrap();
+    assert_eq!( nfc.process("\u{fb01}let"), "\u{fb01}let" );
+    assert_eq!( nfkc.process("\u{fb01}let"), "filet" );
+
+    let strip = Preprocessor::builder().strip_accents(true).build().unwrap();
+    assert_eq!( strip.process("jalape\u{f1}o cr\u{e8}me cafe\u{301}"), "jalapeno creme cafe" );
+}
+
+#[test]
+fn classifier_preprocessing_test() {
+    let mut nb = NaiveBayes::new();
+    nb.set_preprocessor(Preprocessor::builder().lowercase(true).strip_accents(true).build().unwrap());
+    nb.add_document("Beef Pork Jalape\u{f1}o", "meat");
+    nb.add_document("Kale Okra Spinach", "veggie");
+    nb.train();
+
+    // the same preprocessing is applied when classifying
+    assert_eq!( nb.classify("BEEF").unwrap(), "meat" );
+    assert_eq!( nb.classify("jalapeno").unwrap(), "meat" );
+    assert_eq!( nb.classify("KALE").unwrap(), "veggie" );
+
+    // and it survives serialization
+    let nb2 = NaiveBayes::from_json(&nb.to_json()).unwrap();
+    assert_eq!( nb2.classify("Spinach").unwrap(), "veggie" );
+    assert_eq!( nb2.classify("PORK").unwrap(), "meat" );
+}
+
+#[test]
+fn token_filter_test() {
+    let preprocessor = Preprocessor::builder()
+        .token_filter(TokenFilter::exclude("^[0-9]+$").unwrap())
+        .token_filter(TokenFilter::exclude("^(@|https?://)").unwrap())
+        .build()
+        .unwrap();
+    let tokens: Vec<String> = "2 eggs @chef https://example.com 4ever".split(' ').map(|s| s.to_string()).collect();
+    assert_eq!( preprocessor.filter_tokens(tokens), vec!["eggs", "4ever"] );
+
+    let include = Preprocessor::builder().token_filter(TokenFilter::include("^[a-z]+$").unwrap()).build().unwrap();
+    assert_eq!( include.filter_tokens(vec!["pork".to_string(), "Pork".to_string()]), vec!["pork"] );
+
+    match TokenFilter::exclude("(") {
+        Err(ClassifierError::InvalidPattern(_)) => {},
+        other => panic!("expected an invalid pattern error, got {:?}", other),
+    }
+}
+
+#[test]
+fn classifier_token_filter_test() {
+    let mut nb = NaiveBayes::new();
+    nb.set_preprocessor(Preprocessor::builder().token_filter(TokenFilter::exclude("^@").unwrap()).build().unwrap());
+    nb.add_document("@kale beef pork", "meat");
+    nb.add_document("@kale kale okra", "veggie");
+    nb.train();
+
+    // filtered tokens never make it into the vocabulary
+    assert_eq!( nb.vocab_size(), 4 );
+    assert_eq!( nb.classify("@kale"), Err(ClassifierError::EmptyDocument) );
+
+    // and the filters survive serialization
+    let nb2 = NaiveBayes::from_json(&nb.to_json()).unwrap();
+    assert_eq!( nb2.classify("@kale"), Err(ClassifierError::EmptyDocument) );
+    assert_eq!( nb2.classify("@kale pork").unwrap(), "meat" );
+}
+
+#[test]
+fn strip_punctuation_test() {
+    let preprocessor = Preprocessor::builder().strip_punctuation(true).build().unwrap();
+    let tokens: Vec<String> = ["(pork)", "ribs,", "\u{201c}t-bone\u{201d}", "o'clock", "--", "...", "", "steak!?"]
+        .iter().map(|s| s.to_string()).collect();
+    assert_eq!( preprocessor.filter_tokens(tokens.clone()), vec!["pork", "ribs", "t-bone", "o'clock", "steak"] );
+
+    // without stripping only the empty token is dropped
+    assert_eq!( Preprocessor::new().filter_tokens(tokens.clone()).len(), tokens.len() - 1 );
+
+    let mut nb = NaiveBayes::new();
+    nb.set_preprocessor(Preprocessor::builder().lowercase(true).strip_punctuation(true).build().unwrap());
+    nb.add_document("Pork, belly -- and (ribs)!", "meat");
+    nb.add_document("okra; kale & spinach.", "veggie");
+    nb.train();
+    assert_eq!( nb.vocab_size(), 7 );
+    assert_eq!( nb.classify("ribs?").unwrap(), "meat" );
+    assert_eq!( nb.classify("\"spinach\"").unwrap(), "veggie" );
+
+    // the setting survives serialization
+    let nb2 = NaiveBayes::from_json(&nb.to_json()).unwrap();
+    assert_eq!( nb2.classify("(pork)").unwrap(), "meat" );
+}
+
+#[test]
+fn token_length_test() {
+    let preprocessor = Preprocessor::builder().min_token_len(2).max_token_len(9).build().unwrap();
+    let tokens: Vec<String> = ["a", "ox", "jalape\u{f1}os", "aGVsbG8gd29ybGQ=", "ribs"]
+        .iter().map(|s| s.to_string()).collect();
+    // lengths are counted in characters, not bytes
+    assert_eq!( preprocessor.filter_tokens(tokens), vec!["ox", "jalape\u{f1}os", "ribs"] );
+
+    // punctuation is stripped before the length is checked
+    let stripping = Preprocessor::builder().strip_punctuation(true).min_token_len(2).build().unwrap();
+    assert!( stripping.filter_tokens(vec!["(a)".to_string()]).is_empty() );
+
+    // limits that no token fits are refused, equal ones are not
+    assert_eq!( Preprocessor::builder().min_token_len(5).max_token_len(3).build(),
+                Err(ClassifierError::InvalidTokenLengths(5, 3)) );
+    assert!( Preprocessor::builder().min_token_len(3).max_token_len(3).build().is_ok() );
+
+    let mut nb = NaiveBayes::new();
+    nb.set_preprocessor(Preprocessor::builder().min_token_len(2).max_token_len(12).build().unwrap());
+    nb.add_document("a pork belly https://example.com/a/very/long/url", "meat");
+    nb.add_document("b okra kale", "veggie");
+    nb.train();
+    assert_eq!( nb.vocab_size(), 4 );
+    // the limits apply when classifying as well
+    assert_eq!( nb.classify("a b c"), Err(ClassifierError::EmptyDocument) );
+    assert_eq!( NaiveBayes::from_json(&nb.to_json()).unwrap().classify("b pork").unwrap(), "meat" );
+}
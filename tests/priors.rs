@@ -0,0 +1,65 @@
+extern crate classifier;
+use std::collections::HashMap;
+use classifier::{NaiveBayes, PriorMode, ClassifierError};
+
+// far more meat documents than veggie ones
+fn classifier() -> NaiveBayes {
+    let mut nb = NaiveBayes::new();
+    for _ in 0..9 {
+        nb.add_document("pork beef", "meat");
+    }
+    nb.add_document("kale okra", "veggie");
+    nb
+}
+
+fn posterior(nb: &NaiveBayes, document: &str, label: &str) -> f64 {
+    nb.get_document_posteriors(document).unwrap().into_iter().find(|p| p.0 == label).unwrap().1
+}
+
+#[test]
+fn learned_priors_test() {
+    let mut nb = classifier();
+    nb.train();
+
+    // a document with no known words falls back to the priors
+    assert!( (posterior(&nb, "tofu", "meat") - 0.9).abs() < 1e-9 );
+}
+
+#[test]
+fn uniform_priors_test() {
+    let mut nb = classifier();
+    nb.set_priors(PriorMode::Uniform).unwrap();
+    nb.train();
+    let explanation = nb.explain("tofu").unwrap();
+    assert!( (explanation.label("meat").unwrap().prior - 0.5f64.ln()).abs() < 1e-9 );
+    assert!( (explanation.label("veggie").unwrap().prior - 0.5f64.ln()).abs() < 1e-9 );
+
+    // online classifiers use the priors too
+    nb.set_online(true);
+    assert!( (nb.explain("tofu").unwrap().label("meat").unwrap().prior - 0.5f64.ln()).abs() < 1e-9 );
+}
+
+#[test]
+fn custom_priors_test() {
+    let mut nb = classifier();
+    let mut priors = HashMap::new();
+    priors.insert("meat".to_string(), 0.2);
+    priors.insert("veggie".to_string(), 0.8);
+    nb.set_priors(PriorMode::Custom(priors)).unwrap();
+    nb.train();
+    assert_eq!( nb.classify("tofu").unwrap(), "veggie" );
+    assert!( (posterior(&nb, "tofu", "veggie") - 0.8).abs() < 1e-9 );
+
+    // the priors are part of the model
+    let nb2 = NaiveBayes::from_json(&nb.to_json()).unwrap();
+    assert_eq!( nb2.classify("tofu").unwrap(), "veggie" );
+}
+
+#[test]
+fn invalid_prior_test() {
+    let mut nb = classifier();
+    let mut priors = HashMap::new();
+    priors.insert("meat".to_string(), -1.0);
+    assert_eq!( nb.set_priors(PriorMode::Custom(priors)).unwrap_err(),
+                ClassifierError::InvalidPrior("meat".to_string(), -1.0) );
+}
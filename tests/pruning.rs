@@ -0,0 +1,41 @@
+extern crate classifier;
+use classifier::{NaiveBayes, ClassifierError};
+
+fn classifier() -> NaiveBayes {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("the pork the beef porkk", "meat");
+    nb.add_document("the pork ham", "meat");
+    nb.add_document("the kale okra", "veggie");
+    nb.add_document("the kale spinnach okra", "veggie");
+    nb
+}
+
+#[test]
+fn prune_vocabulary_test() {
+    let mut nb = classifier();
+    assert_eq!( nb.vocab_size(), 8 );
+
+    // "the" is in every document, and the rest of the words seen only
+    // once are typos or too rare to matter
+    assert_eq!( nb.prune_vocabulary(2, 0.9).unwrap(), 5 );
+    assert_eq!( nb.vocab_size(), 3 );
+    nb.train();
+
+    assert_eq!( nb.classify("pork").unwrap(), "meat" );
+    assert_eq!( nb.classify("kale okra").unwrap(), "veggie" );
+    let top: Vec<String> = nb.top_words_for_label("meat", 10).into_iter().map(|(w, _)| w).collect();
+    assert_eq!( top, vec!["pork"] );
+
+    // nothing is left to prune
+    assert_eq!( nb.prune_vocabulary(2, 0.9).unwrap(), 0 );
+}
+
+#[test]
+fn prune_vocabulary_error_test() {
+    let mut nb = classifier();
+    match nb.prune_vocabulary(1, 0.0) {
+        Err(ClassifierError::InvalidDocumentFrequency(_)) => {},
+        other => panic!("expected an invalid document frequency error, got {:?}", other),
+    }
+    assert_eq!( nb.prune_vocabulary(1, 1.0).unwrap(), 0 );
+}
@@ -0,0 +1,109 @@
+extern crate classifier;
+extern crate serde_json;
+use std::collections::HashMap;
+use classifier::{NaiveBayes, ClassifierError, PriorMode};
+
+fn posteriors(nb: &NaiveBayes, document: &str) -> Vec<(String, f64)> {
+    nb.get_document_posteriors(document).unwrap()
+}
+
+fn assert_same_posteriors(a: &[(String, f64)], b: &[(String, f64)]) {
+    assert_eq!( a.len(), b.len() );
+    for (&(ref label_a, p_a), &(ref label_b, p_b)) in a.iter().zip(b.iter()) {
+        assert_eq!( label_a, label_b );
+        assert!( (p_a - p_b).abs() < 1e-12, "{} != {}", p_a, p_b );
+    }
+}
+
+#[test]
+fn rename_label_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("pork belly ribs", "meat");
+    nb.add_document("okra kale spinach", "veggie");
+    nb.set_label_metadata("meat", "owner", "butcher");
+    nb.set_label_smoothing("meat", 0.5).unwrap();
+    nb.train();
+
+    nb.rename_label("meat", "protein").unwrap();
+    assert_eq!( nb.get_labels(), vec!["protein", "veggie"] );
+    assert_eq!( nb.classify("pork ribs").unwrap(), "protein" );
+    assert_eq!( nb.get_label_metadata("protein").unwrap()["owner"], "butcher" );
+    assert!( nb.get_label_metadata("meat").is_none() );
+    let encoded: serde_json::Value = serde_json::from_str(&nb.to_json()).unwrap();
+    assert_eq!( encoded["label_smoothing"], serde_json::json!({"protein": 0.5}) );
+
+    assert_eq!( nb.rename_label("meat", "beef"), Err(ClassifierError::MissingLabel("meat".to_string())) );
+}
+
+#[test]
+fn merge_labels_test() {
+    let documents = [
+        ("pork belly ribs", "pork"),
+        ("beef brisket steak", "beef"),
+        ("chicken thighs wings", "chicken"),
+        ("okra kale spinach", "veggie"),
+        ("kale carrots", "veggie"),
+    ];
+    let mut nb = NaiveBayes::new();
+    let mut expected = NaiveBayes::new();
+    for &(document, label) in documents.iter() {
+        nb.add_document(document, label);
+        let label = if label == "veggie" { label } else { "meat" };
+        expected.add_document(document, label);
+    }
+    nb.train();
+    expected.train();
+
+    nb.merge_labels(&["pork", "beef", "chicken"], "meat").unwrap();
+    assert_eq!( nb.get_labels(), vec!["meat", "veggie"] );
+    assert_same_posteriors(&posteriors(&nb, "pork kale steak"), &posteriors(&expected, "pork kale steak"));
+    assert_same_posteriors(&posteriors(&nb, "wings"), &posteriors(&expected, "wings"));
+}
+
+#[test]
+fn merge_into_existing_label_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document_with_id("a", "pork belly ribs", "meat").unwrap();
+    nb.add_document_with_id("b", "beef brisket", "beef").unwrap();
+    nb.add_document_with_id("c", "okra kale spinach", "veggie").unwrap();
+    let mut priors = HashMap::new();
+    priors.insert("meat".to_string(), 0.25);
+    priors.insert("beef".to_string(), 0.25);
+    priors.insert("veggie".to_string(), 0.5);
+    nb.set_priors(PriorMode::Custom(priors)).unwrap();
+    nb.set_label_metadata("beef", "owner", "rancher");
+    nb.set_label_metadata("meat", "owner", "butcher");
+    nb.set_label_metadata("beef", "color", "red");
+
+    nb.rename_label("beef", "meat").unwrap();
+    assert_eq!( nb.get_labels(), vec!["meat", "veggie"] );
+    assert_eq!( nb.documents_for_label("meat"), vec!["a", "b"] );
+    let metadata = nb.get_label_metadata("meat").unwrap();
+    assert_eq!( (metadata["owner"].as_str(), metadata["color"].as_str()), ("butcher", "red") );
+    let encoded: serde_json::Value = serde_json::from_str(&nb.to_json()).unwrap();
+    assert_eq!( encoded["priors"], serde_json::json!({"Custom": {"meat": 0.5, "veggie": 0.5}}) );
+
+    // a document can be removed by its ID after its label is merged away
+    nb.remove_document_by_id("b").unwrap();
+    nb.train();
+    assert_eq!( nb.classify("pork ribs kale").unwrap(), "meat" );
+}
+
+#[test]
+fn merge_missing_label_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("pork belly ribs", "meat");
+    nb.add_document("okra kale spinach", "veggie");
+    nb.train();
+    let before: serde_json::Value = serde_json::from_str(&nb.to_json()).unwrap();
+
+    assert_eq!( nb.merge_labels(&["meat", "fruit"], "food"), Err(ClassifierError::MissingLabel("fruit".to_string())) );
+    assert_eq!( nb.merge_labels(&["fruit", "meat"], "meat"), Err(ClassifierError::MissingLabel("fruit".to_string())) );
+    // no labels would make an empty one
+    let no_labels: [&str; 0] = [];
+    assert_eq!( nb.merge_labels(&no_labels, "food"), Err(ClassifierError::NoLabels) );
+    assert_eq!( nb.merge_labels(&no_labels, "meat"), Err(ClassifierError::NoLabels) );
+    assert_eq!( nb.get_labels(), vec!["meat", "veggie"] );
+    let after: serde_json::Value = serde_json::from_str(&nb.to_json()).unwrap();
+    assert_eq!( after, before );
+}
@@ -0,0 +1,146 @@
+extern crate classifier;
+extern crate serde_json;
+use classifier::{NaiveBayes, ClassifierError};
+
+// takes the vocabulary out of an encoded classifier, since the order
+// of a set isn't stable
+fn sorted_vocab(encoded: &mut serde_json::Value) -> Vec<String> {
+    let vocab = encoded.as_object_mut().unwrap().remove("vocab").unwrap();
+    let mut vocab: Vec<String> = serde_json::from_value(vocab).unwrap();
+    vocab.sort();
+    vocab
+}
+
+#[test]
+fn remove_document_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("sirloin pastrami ham hock", "meat");
+    nb.add_document("okra kale spinach", "veggie");
+    nb.train();
+    let before = nb.to_json();
+
+    // adding and then removing a document leaves the model as it was
+    nb.add_document("kale kale bacon", "meat");
+    nb.remove_document("kale kale bacon", "meat").unwrap();
+    nb.train();
+    assert_eq!( NaiveBayes::from_json(&nb.to_json()).unwrap().classify("kale").unwrap(), "veggie" );
+    let mut after: serde_json::Value = serde_json::from_str(&nb.to_json()).unwrap();
+    let mut expected: serde_json::Value = serde_json::from_str(&before).unwrap();
+    assert_eq!( sorted_vocab(&mut after), sorted_vocab(&mut expected) );
+    assert_eq!( after, expected );
+
+    // removing a label's last document removes the label
+    nb.remove_document("okra kale spinach", "veggie").unwrap();
+    nb.train();
+    assert_eq!( nb.get_labels(), vec!["meat"] );
+    assert_eq!( nb.classify("kale").unwrap(), "meat" );
+}
+
+#[test]
+fn remove_unknown_document_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("sirloin pastrami ham hock", "meat");
+    nb.train();
+
+    for &(document, label) in [("kale", "meat"), ("ham ham", "meat"), ("ham", "veggie")].iter() {
+        match nb.remove_document(document, label) {
+            Err(ClassifierError::DocumentNotFound(ref l)) if l == label => {},
+            other => panic!("expected a document not found error, got {:?}", other),
+        }
+    }
+
+    // the failed removals changed nothing
+    nb.remove_document("sirloin pastrami ham hock", "meat").unwrap();
+    assert!( nb.get_labels().is_empty() );
+}
+
+#[test]
+fn remove_label_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("sirloin pastrami ham hock", "meat");
+    nb.add_document("okra kale spinach", "veggie");
+    nb.add_document_with_id("fruit-1", "apple kale banana", "fruit").unwrap();
+    nb.set_label_metadata("fruit", "color", "yellow");
+    nb.set_label_smoothing("fruit", 0.5).unwrap();
+    nb.remove_label("fruit").unwrap();
+    nb.train();
+
+    // the model is as if the label's documents had never been added, and
+    // words only they had are gone from the vocabulary
+    let mut expected = NaiveBayes::new();
+    expected.add_document("sirloin pastrami ham hock", "meat");
+    expected.add_document("okra kale spinach", "veggie");
+    expected.train();
+    let mut after: serde_json::Value = serde_json::from_str(&nb.to_json()).unwrap();
+    let mut expected: serde_json::Value = serde_json::from_str(&expected.to_json()).unwrap();
+    assert_eq!( sorted_vocab(&mut after), sorted_vocab(&mut expected) );
+    assert_eq!( after, expected );
+    assert_eq!( nb.get_labels(), vec!["meat", "veggie"] );
+    assert!( nb.get_label_metadata("fruit").is_none() );
+    assert_eq!( nb.document_label("fruit-1"), None );
+
+    assert_eq!( nb.remove_label("fruit"), Err(ClassifierError::MissingLabel("fruit".to_string())) );
+}
+
+#[test]
+fn remove_last_label_test() {
+    // weights that aren't whole binary fractions leave rounding error in
+    // the counts, which mustn't outlive the labels
+    let mut nb = NaiveBayes::new();
+    nb.add_document_weighted("kale okra", "veggie", 0.1).unwrap();
+    nb.add_document_weighted("kale", "greens", 0.2).unwrap();
+    for &label in ["veggie", "greens"].iter() {
+        nb.remove_label(label).unwrap();
+    }
+    nb.train();
+
+    let mut expected = NaiveBayes::new();
+    expected.train();
+    let after: serde_json::Value = serde_json::from_str(&nb.to_json()).unwrap();
+    let expected: serde_json::Value = serde_json::from_str(&expected.to_json()).unwrap();
+    assert_eq!( after, expected );
+    assert_eq!( nb.stats().num_examples, 0.0 );
+}
+
+#[test]
+fn remove_weighted_document_test() {
+    let train = |nb: &mut NaiveBayes| {
+        nb.set_max_word_count(2).unwrap();
+        nb.add_document("sirloin pastrami ham hock", "meat");
+        nb.add_document("okra kale spinach", "veggie");
+    };
+    let mut expected = NaiveBayes::new();
+    train(&mut expected);
+    expected.train();
+
+    // a weighted document with a word over the count limit, removed with
+    // the weight it was added with, leaves the model as if it never was
+    let mut nb = NaiveBayes::new();
+    train(&mut nb);
+    nb.add_document_weighted("kale kale kale bacon", "meat", 2.5).unwrap();
+    assert_eq!( nb.remove_document_weighted("kale kale kale bacon", "meat", 3.0),
+                Err(ClassifierError::DocumentNotFound("meat".to_string())) );
+    assert_eq!( nb.remove_document_weighted("bacon", "meat", 0.0), Err(ClassifierError::InvalidWeight(0.0)) );
+    nb.remove_document_weighted("kale kale kale bacon", "meat", 2.5).unwrap();
+    nb.train();
+    let mut after: serde_json::Value = serde_json::from_str(&nb.to_json()).unwrap();
+    let mut encoded: serde_json::Value = serde_json::from_str(&expected.to_json()).unwrap();
+    assert_eq!( sorted_vocab(&mut after), sorted_vocab(&mut encoded) );
+    assert_eq!( after, encoded );
+
+    // a weight that isn't a whole binary fraction leaves only rounding error
+    nb.add_document_weighted("bacon lardons kale", "fatty", 0.1).unwrap();
+    nb.add_document_weighted("kale", "veggie", 0.3).unwrap();
+    nb.remove_document_weighted("bacon lardons kale", "fatty", 0.1).unwrap();
+    nb.remove_document_weighted("kale", "veggie", 0.3).unwrap();
+    nb.train();
+    assert_eq!( nb.get_labels(), vec!["meat", "veggie"] );
+    let mut words: Vec<&str> = nb.vocabulary().collect();
+    words.sort();
+    assert_eq!( words, vec!["ham", "hock", "kale", "okra", "pastrami", "sirloin", "spinach"] );
+    let posteriors = nb.get_document_posteriors("kale ham").unwrap();
+    for ((label, p), (expected_label, e)) in posteriors.into_iter().zip(expected.get_document_posteriors("kale ham").unwrap()) {
+        assert_eq!( label, expected_label );
+        assert!( (p - e).abs() < 1e-12 );
+    }
+}
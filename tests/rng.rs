@@ -0,0 +1,69 @@
+#![cfg(feature = "rand")]
+
+extern crate classifier;
+extern crate rand;
+use classifier::{dataset, LogisticRegression};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+fn examples() -> Vec<(String, String)> {
+    let examples = [
+        ("sirloin pastrami ham hock pork belly", "meat"),
+        ("ball tip pork chop short ribs", "meat"),
+        ("salami pancetta beef ribs", "meat"),
+        ("ham hock landjaeger pork belly", "meat"),
+        ("okra kale spinach water chestnut", "veggie"),
+        ("green bean okra kale", "veggie"),
+        ("spinach arugula pea sprouts", "veggie"),
+        ("water chestnut pea sprouts arugula", "veggie"),
+    ];
+    examples.iter().map(|&(d, l)| (d.to_string(), l.to_string())).collect()
+}
+
+fn trained_classifier(seed: u64) -> LogisticRegression {
+    let mut lr = LogisticRegression::new();
+    for (document, label) in examples().iter() {
+        lr.add_document(document, label);
+    }
+    lr.train_with_rng(&mut StdRng::seed_from_u64(seed));
+    lr
+}
+
+#[test]
+fn split_with_rng_test() {
+    let examples = examples();
+    let split = dataset::train_test_split_with_rng(&examples, 0.25, &mut StdRng::seed_from_u64(3)).unwrap();
+    assert_eq!( split.0.len(), 6 );
+    assert_eq!( split.1.len(), 2 );
+    assert_eq!( dataset::train_test_split_with_rng(&examples, 0.25, &mut StdRng::seed_from_u64(3)).unwrap(), split );
+
+    let (train, test) = dataset::stratified_train_test_split_with_rng(&examples, 0.25, &mut StdRng::seed_from_u64(3))
+        .unwrap();
+    assert_eq!( test.iter().filter(|&(_, label)| label == "meat").count(), 1 );
+    assert_eq!( test.iter().filter(|&(_, label)| label == "veggie").count(), 1 );
+    assert_eq!( train.len(), 6 );
+}
+
+#[test]
+fn resample_with_rng_test() {
+    let mut examples = examples();
+    examples.truncate(6);
+    let oversampled = dataset::oversample_with_rng(&examples, &mut StdRng::seed_from_u64(5));
+    assert_eq!( oversampled.len(), 8 );
+    assert_eq!( dataset::oversample_with_rng(&examples, &mut StdRng::seed_from_u64(5)), oversampled );
+
+    let undersampled = dataset::undersample_with_rng(&examples, &mut StdRng::seed_from_u64(5));
+    assert_eq!( undersampled.len(), 4 );
+    assert_eq!( undersampled.iter().filter(|&(_, label)| label == "meat").count(), 2 );
+}
+
+#[test]
+fn train_with_rng_test() {
+    let lr = trained_classifier(11);
+    assert_eq!( lr.classify("pork belly").unwrap(), "meat" );
+    assert_eq!( lr.classify("okra spinach").unwrap(), "veggie" );
+
+    // the same seed always gives the same model
+    assert_eq!( trained_classifier(11).get_document_posteriors("pork kale").unwrap(),
+                lr.get_document_posteriors("pork kale").unwrap() );
+}
@@ -0,0 +1,61 @@
+extern crate classifier;
+use classifier::{ClassifierError, NaiveBayes};
+use classifier::sanitize;
+
+#[test]
+fn scrubbers_test() {
+    assert_eq!( sanitize::scrub_emails("write to jane.doe+news@mail.example.co.uk today"), "write to <email> today" );
+    assert_eq!( sanitize::scrub_emails("no @ here, or user@localhost"), "no @ here, or user@localhost" );
+
+    assert_eq!( sanitize::scrub_phone_numbers("call 555-0100 or (555) 010.0199"), "call <phone> or <phone>" );
+    assert_eq!( sanitize::scrub_phone_numbers("+44 20 7946 0958"), "<phone>" );
+    // too few digits to be a phone number
+    assert_eq!( sanitize::scrub_phone_numbers("order 12 of 345"), "order 12 of 345" );
+
+    assert_eq!( sanitize::scrub_credit_cards("card 4111 1111 1111 1111 ok"), "card <credit_card> ok" );
+    assert_eq!( sanitize::scrub_credit_cards("5500-0000-0000-0004"), "<credit_card>" );
+    // the check digit is wrong
+    assert_eq!( sanitize::scrub_credit_cards("4111111111111112"), "4111111111111112" );
+
+    assert_eq!( sanitize::scrub_pii("jane@example.com paid with 4111111111111111, call 555 0100"),
+                "<email> paid with <credit_card>, call <phone>" );
+}
+
+#[test]
+fn sanitizer_test() {
+    let mut nb = NaiveBayes::new();
+    nb.set_sanitizer(sanitize::scrub_pii);
+    nb.add_document("please refund card 4111 1111 1111 1111", "billing");
+    nb.add_document("email jane@example.com about the invoice", "billing");
+    nb.add_document("the app crashes on start", "bug");
+    nb.train();
+
+    let json = nb.to_json();
+    assert!( !json.contains("4111") && !json.contains("jane") );
+    assert!( nb.vocabulary().any(|word| word == "<credit_card>") );
+    // documents are sanitized when classifying too
+    assert_eq!( nb.classify("bob@example.org").unwrap(), "billing" );
+    assert_eq!( nb.freeze().classify("bob@example.org").unwrap(), "billing" );
+
+    let mut decoded = NaiveBayes::from_json(&json).unwrap();
+    decoded.add_document("mail bob@example.org", "billing");
+    assert!( decoded.to_json().contains("bob@example.org") );
+}
+
+#[test]
+fn custom_sanitizer_test() {
+    fn mask_digits(text: &str) -> String {
+        text.chars().map(|c| if c.is_ascii_digit() { '#' } else { c }).collect()
+    }
+
+    let mut nb = NaiveBayes::new();
+    nb.set_sanitizer(mask_digits);
+    nb.add_document("order 1234 shipped", "shipping");
+    nb.add_document("order 99 refunded", "billing");
+    nb.train();
+    assert_eq!( nb.vocabulary().collect::<Vec<_>>(), vec!["##", "####", "order", "refunded", "shipped"] );
+    assert_eq!( nb.to_inference_model().unwrap_err(), ClassifierError::UnsupportedSetting("sanitizer".to_string()) );
+
+    nb.clear_sanitizer();
+    assert!( nb.to_inference_model().is_ok() );
+}
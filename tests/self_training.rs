@@ -0,0 +1,44 @@
+extern crate classifier;
+use classifier::{ClassifierError, NaiveBayes};
+
+fn seeded() -> NaiveBayes {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("pork ribs", "meat");
+    nb.add_document("kale okra", "veggie");
+    nb
+}
+
+#[test]
+fn self_train_test() {
+    let mut nb = seeded();
+    // "brisket" is only learned from the first round, "shank" only from
+    // documents labeled in the second
+    let unlabeled = ["pork ribs brisket", "kale okra chard", "brisket shank", "shank", "pork kale"];
+    let added = nb.self_train(&unlabeled, 0.65, 5).unwrap();
+
+    let labels: Vec<(usize, &str)> = added.iter().map(|(i, label)| (*i, label.as_str())).collect();
+    assert_eq!(&labels[..2], &[(0, "meat"), (1, "veggie")]);
+    assert_eq!(labels[2], (2, "meat"));
+    assert!(!labels.iter().any(|&(i, _)| i == 4));
+    assert_eq!(nb.stats().num_examples, 2.0 + added.len() as f64);
+    assert_eq!(nb.classify("shank").unwrap(), "meat");
+}
+
+#[test]
+fn self_train_iterations_test() {
+    let unlabeled = ["pork ribs brisket", "kale okra chard", "brisket shank"];
+    let mut nb = seeded();
+    assert_eq!(nb.self_train(&unlabeled, 0.7, 1).unwrap(), vec![(0, "meat".to_string()), (1, "veggie".to_string())]);
+    let mut nb = seeded();
+    assert_eq!(nb.self_train(&unlabeled, 0.7, 0).unwrap(), vec![]);
+    // trained either way
+    assert_eq!(nb.classify("pork").unwrap(), "meat");
+    let mut nb = seeded();
+    assert_eq!(nb.self_train(&unlabeled, 1.1, 3).unwrap(), vec![]);
+}
+
+#[test]
+fn self_train_untrained_test() {
+    let mut nb = NaiveBayes::new();
+    assert_eq!(nb.self_train(&["pork"], 0.9, 3), Err(ClassifierError::Untrained));
+}
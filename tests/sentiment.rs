@@ -0,0 +1,50 @@
+#![cfg(feature = "sentiment")]
+
+extern crate classifier;
+
+use classifier::{ClassifierError, Tokenizer, WhitespaceTokenizer};
+use classifier::sentiment::{NegationTokenizer, SentimentAnalyzer};
+
+#[test]
+fn negation_tokenizer_test() {
+    let tokenizer = NegationTokenizer::new(WhitespaceTokenizer);
+    assert_eq!(tokenizer.tokenize("It wasn't great. Still, (fine)!"),
+               vec!["It", "wasn't", "not_great", "Still", "fine"]);
+    assert_eq!(tokenizer.tokenize("never again, never"), vec!["never", "not_again", "never"]);
+    assert!(tokenizer.tokenize("... !").is_empty());
+}
+
+#[test]
+fn pretrained_test() {
+    let analyzer = SentimentAnalyzer::pretrained();
+    assert_eq!(analyzer.classifier().get_labels(), vec!["negative", "positive"]);
+    assert_eq!(analyzer.classify("Great acting and a lovely story").unwrap(), "positive");
+    assert_eq!(analyzer.classify("Rude staff and cold food").unwrap(), "negative");
+    // the same words, negated
+    assert_eq!(analyzer.classify("it was good").unwrap(), "positive");
+    assert_eq!(analyzer.classify("it was not good").unwrap(), "negative");
+
+    let positive = analyzer.score("excellent, i loved it").unwrap();
+    let negative = analyzer.score("awful, a waste of money").unwrap();
+    assert!(positive > 0.0 && positive <= 1.0);
+    assert!((-1.0..0.0).contains(&negative));
+    assert!(analyzer.classify("").is_err());
+}
+
+#[test]
+fn trainable_test() {
+    let mut analyzer = SentimentAnalyzer::new();
+    assert!(matches!(analyzer.classify("fine"), Err(ClassifierError::Untrained)));
+    analyzer.add_example("the update fixed everything", "positive");
+    analyzer.add_example("the update broke everything", "negative");
+    analyzer.train();
+    assert_eq!(analyzer.classify("It FIXED my phone").unwrap(), "positive");
+
+    let path = std::env::temp_dir().join("classifier_sentiment_test.json");
+    analyzer.save_to_file(&path).unwrap();
+    let loaded = SentimentAnalyzer::load_from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.classify("It FIXED my phone").unwrap(), "positive");
+    let decoded = SentimentAnalyzer::from_json(&analyzer.to_json()).unwrap();
+    assert_eq!(decoded.score("broke").unwrap(), analyzer.score("broke").unwrap());
+}
@@ -0,0 +1,103 @@
+extern crate classifier;
+extern crate serde_json;
+use classifier::{ClassifierError, NaiveBayes, NaiveBayesVariant};
+
+fn trained_classifier() -> NaiveBayes {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+    nb.add_document("okra kale spinach water chestnut", "veggie");
+    nb.train();
+    nb
+}
+
+#[test]
+fn writer_reader_test() {
+    let nb = trained_classifier();
+
+    // write the classifier out and read it back in
+    let mut buffer = Vec::new();
+    nb.to_writer(&mut buffer).unwrap();
+    let nb2 = NaiveBayes::from_reader(&buffer[..]).unwrap();
+
+    assert_eq!( nb2.classify("pork belly").unwrap(), "meat" );
+    assert_eq!( nb2.classify("kale").unwrap(), "veggie" );
+}
+
+#[test]
+fn serde_test() {
+    let nb = trained_classifier();
+
+    // the classifier can be used with serde directly
+    let value = serde_json::to_value(&nb).unwrap();
+    let nb2: NaiveBayes = serde_json::from_value(value).unwrap();
+
+    assert_eq!( nb2.classify("pork belly").unwrap(), "meat" );
+    assert_eq!( nb2.classify("kale").unwrap(), "veggie" );
+}
+
+#[test]
+fn variants_round_trip_test() {
+    for &variant in [NaiveBayesVariant::Multinomial, NaiveBayesVariant::Bernoulli, NaiveBayesVariant::Complement].iter() {
+        let mut nb = NaiveBayes::with_variant(variant);
+        nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+        nb.add_document("okra kale spinach water chestnut", "veggie");
+        nb.add_document("apple pear okra", "fruit");
+        nb.train();
+
+        // the log-probabilities used for scoring are rebuilt after decoding
+        let nb2 = NaiveBayes::from_json(&nb.to_json()).unwrap();
+        for document in ["pork belly", "okra okra kale", "pear durian"].iter() {
+            let expected = nb.get_document_posteriors(document).unwrap();
+            for ((label, p), (label2, p2)) in expected.iter().zip(nb2.get_document_posteriors(document).unwrap()) {
+                assert_eq!( *label, label2 );
+                assert!( (p - p2).abs() < 1e-12 );
+            }
+        }
+    }
+}
+
+#[test]
+fn old_format_test() {
+
+    // a model exported before n-gram support was added
+    let encoded = r#"{"vocab":["ham","kale"],"num_examples":2,"smoothing":1.0,"classifications":{"meat":{"label":"meat","num_examples":1,"num_words":1,"probability":0.5,"default_word_probability":0.3333333333333333,"words":{"ham":[1,0.6666666666666666]}},"veggie":{"label":"veggie","num_examples":1,"num_words":1,"probability":0.5,"default_word_probability":0.3333333333333333,"words":{"kale":[1,0.6666666666666666]}}}}"#;
+
+    let nb = NaiveBayes::from_json(encoded).unwrap();
+    assert_eq!( nb.classify("ham").unwrap(), "meat" );
+    assert_eq!( nb.classify("kale").unwrap(), "veggie" );
+}
+
+#[test]
+fn model_version_test() {
+    let mut value = serde_json::to_value(trained_classifier()).unwrap();
+    assert_eq!( value["model_version"], 1 );
+
+    // a model from before versioning is upgraded
+    value.as_object_mut().unwrap().remove("model_version");
+    let nb: NaiveBayes = serde_json::from_value(value.clone()).unwrap();
+    assert_eq!( nb.classify("pastrami").unwrap(), "meat" );
+    assert_eq!( serde_json::to_value(&nb).unwrap()["model_version"], 1 );
+
+    // and one from a newer release is refused
+    value["model_version"] = 2.into();
+    match NaiveBayes::from_json(&value.to_string()) {
+        Err(ClassifierError::Decode(ref reason)) =>
+            assert!( reason.contains("model version 2 is not supported"), "{}", reason ),
+        other => panic!("expected a decode error, got {:?}", other),
+    }
+}
+
+#[test]
+fn words_encoded_test() {
+    let nb = trained_classifier();
+
+    // the encoded model refers to words by the words themselves
+    let value = serde_json::to_value(&nb).unwrap();
+    assert_eq!( value["vocab"].as_array().unwrap().len(), nb.vocab_size() );
+    assert_eq!( value["classifications"]["meat"]["words"]["pastrami"][0], 1.0 );
+    assert_eq!( value["classifications"]["veggie"]["document_counts"]["kale"], 1.0 );
+
+    // and encoding a decoded model gives the same model back
+    let nb2: NaiveBayes = serde_json::from_value(value.clone()).unwrap();
+    assert_eq!( serde_json::to_value(&nb2).unwrap()["classifications"], value["classifications"] );
+}
@@ -0,0 +1,39 @@
+extern crate classifier;
+use std::thread;
+use classifier::{NaiveBayes, NaiveBayesModel};
+
+fn assert_send_sync<S: Send + Sync>() {}
+
+#[test]
+fn send_sync_test() {
+    assert_send_sync::<NaiveBayes>();
+    assert_send_sync::<NaiveBayesModel>();
+}
+
+#[test]
+fn shared_model_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+    nb.add_document("okra kale spinach water chestnut", "veggie");
+    let expected = nb.clone();
+    let model = nb.into_model();
+
+    // every thread classifies with its own clone of the same model
+    let handles: Vec<_> = (0..8).map(|i| {
+        let model = model.clone();
+        thread::spawn(move || {
+            let document = if i % 2 == 0 { "pork belly" } else { "kale" };
+            (document, model.classify(document).unwrap())
+        })
+    }).collect();
+    for handle in handles {
+        let (document, label) = handle.join().unwrap();
+        assert_eq!( label, if document == "kale" { "veggie" } else { "meat" } );
+    }
+
+    // the model is trained when it is made
+    let mut expected = expected;
+    expected.train();
+    assert_eq!( model.get_document_posteriors("ham").unwrap(), expected.get_document_posteriors("ham").unwrap() );
+    assert_eq!( model.classifier().vocab_size(), expected.vocab_size() );
+}
@@ -0,0 +1,109 @@
+extern crate classifier;
+extern crate serde_json;
+use classifier::{ClassifierError, NaiveBayes};
+
+static DOCUMENTS: &[(&str, &str)] = &[
+    ("pork belly ribs pork", "meat"),
+    ("sirloin ribs", "meat"),
+    ("kale okra spinach", "veggie"),
+];
+
+// what scikit-learn's MultinomialNB with alpha=1 fits to the documents,
+// with the words in column order
+fn sklearn_model(words: &[&str]) -> serde_json::Value {
+    let labels = ["meat", "veggie"];
+    let mut log_priors = Vec::new();
+    let mut log_probabilities = Vec::new();
+    for label in labels.iter() {
+        let documents: Vec<&str> = DOCUMENTS.iter().filter(|&&(_, l)| l == *label).map(|&(d, _)| d).collect();
+        log_priors.push((documents.len() as f64 / DOCUMENTS.len() as f64).ln());
+        let counts: Vec<f64> = words.iter()
+            .map(|word| documents.iter().map(|d| d.split_whitespace().filter(|w| w == word).count()).sum::<usize>() as f64)
+            .collect();
+        let total: f64 = counts.iter().sum();
+        log_probabilities.push(counts.iter().map(|c| ((c + 1.0) / (total + words.len() as f64)).ln()).collect::<Vec<_>>());
+    }
+    serde_json::json!({
+        "classes_": labels,
+        "class_log_prior_": log_priors,
+        "feature_log_prob_": log_probabilities,
+        "vocabulary": words,
+    })
+}
+
+fn words() -> Vec<&'static str> {
+    vec!["belly", "kale", "okra", "pork", "ribs", "sirloin", "spinach"]
+}
+
+#[test]
+fn from_sklearn_json_test() {
+    let mut expected = NaiveBayes::new();
+    expected.add_documents(DOCUMENTS);
+    expected.train();
+
+    let nb = NaiveBayes::from_sklearn_json(&sklearn_model(&words()).to_string()).unwrap();
+    assert_eq!( nb.get_labels(), vec!["meat", "veggie"] );
+    assert_eq!( nb.vocabulary().collect::<Vec<_>>(), words() );
+    for document in ["pork kale", "ribs ribs okra", "spinach sirloin belly", "durian pork"].iter() {
+        let posteriors = nb.get_document_posteriors(document).unwrap();
+        for ((label, p), (expected_label, e)) in posteriors.iter().zip(expected.get_document_posteriors(document).unwrap()) {
+            assert_eq!( *label, expected_label );
+            assert!( (p - e).abs() < 1e-9, "{}: {} != {}", document, p, e );
+        }
+    }
+}
+
+#[test]
+fn sklearn_vocabulary_formats_test() {
+    // a CountVectorizer's vocabulary_ maps words to columns, and without
+    // classes_ the labels are numbered
+    let mut model = sklearn_model(&words());
+    model["vocabulary"] = words().iter().enumerate().map(|(i, w)| (w.to_string(), serde_json::json!(i))).collect();
+    model.as_object_mut().unwrap().remove("classes_");
+    let nb = NaiveBayes::from_sklearn_json(&model.to_string()).unwrap();
+    assert_eq!( nb.get_labels(), vec!["0", "1"] );
+    assert_eq!( nb.classify("pork ribs").unwrap(), "0" );
+
+    model["classes_"] = serde_json::json!([3, 7]);
+    let nb = NaiveBayes::from_sklearn_json(&model.to_string()).unwrap();
+    assert_eq!( nb.classify("kale").unwrap(), "7" );
+}
+
+#[test]
+fn invalid_sklearn_json_test() {
+    let mut model = sklearn_model(&words());
+    model["vocabulary"] = serde_json::json!(["belly", "kale"]);
+    assert_eq!( NaiveBayes::from_sklearn_json(&model.to_string()).unwrap_err(), ClassifierError::DimensionMismatch(2, 7) );
+
+    let mut model = sklearn_model(&words());
+    model["vocabulary"] = serde_json::json!({"pork": 0, "kale": 0});
+    assert!( NaiveBayes::from_sklearn_json(&model.to_string()).is_err() );
+
+    // a word given twice, in either vocabulary format
+    let mut duplicated = words();
+    duplicated[1] = "pork";
+    let model = sklearn_model(&duplicated);
+    match NaiveBayes::from_sklearn_json(&model.to_string()) {
+        Err(ClassifierError::Decode(ref reason)) if reason.contains("appears twice") => {},
+        other => panic!("expected a duplicate word, got {:?}", other),
+    }
+    let mut model = sklearn_model(&words());
+    model["vocabulary"] = serde_json::Value::Null;
+    let mut encoded = model.to_string();
+    let columns: Vec<String> = duplicated.iter().enumerate().map(|(i, w)| format!("\"{}\": {}", w, i)).collect();
+    encoded = encoded.replace("\"vocabulary\":null", &format!("\"vocabulary\":{{{}}}", columns.join(", ")));
+    match NaiveBayes::from_sklearn_json(&encoded) {
+        Err(ClassifierError::Decode(ref reason)) if reason.contains("appears twice") => {},
+        other => panic!("expected a duplicate word, got {:?}", other),
+    }
+
+    // every row of feature_log_prob_ has a probability per word
+    let mut model = sklearn_model(&words());
+    model["feature_log_prob_"][1].as_array_mut().unwrap().pop();
+    assert_eq!( NaiveBayes::from_sklearn_json(&model.to_string()).unwrap_err(), ClassifierError::DimensionMismatch(7, 6) );
+
+    let mut model = sklearn_model(&words());
+    model["class_log_prior_"] = serde_json::json!([-0.5]);
+    assert!( NaiveBayes::from_sklearn_json(&model.to_string()).is_err() );
+    assert!( NaiveBayes::from_sklearn_json("{}").is_err() );
+}
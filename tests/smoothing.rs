@@ -0,0 +1,59 @@
+extern crate classifier;
+use classifier::{NaiveBayes, ClassifierError};
+
+fn examples(examples: &[(&str, &str)]) -> Vec<(String, String)> {
+    examples.iter().map(|&(d, l)| (d.to_string(), l.to_string())).collect()
+}
+
+fn classifier() -> NaiveBayes {
+    let mut nb = NaiveBayes::new();
+    nb.add_documents(&examples(&[
+        ("pork pork pork pork pork pork beef", "meat"),
+        ("kale", "veggie"),
+    ]));
+    nb
+}
+
+#[test]
+fn label_smoothing_test() {
+    let mut nb = classifier();
+    nb.train();
+    let kale = nb.top_words_for_label("veggie", 1)[0].1;
+    assert!( (kale - 2.0 / 4.0).abs() < 1e-9 );
+
+    // only the veggie label is smoothed differently
+    nb.set_label_smoothing("veggie", 0.5).unwrap();
+    nb.train();
+    assert!( (nb.top_words_for_label("veggie", 1)[0].1 - 1.5 / 2.5).abs() < 1e-9 );
+    assert!( (nb.top_words_for_label("meat", 1)[0].1 - 7.0 / 10.0).abs() < 1e-9 );
+
+    // the override is part of the model
+    let nb2 = NaiveBayes::from_json(&nb.to_json()).unwrap();
+    assert!( (nb2.top_words_for_label("veggie", 1)[0].1 - 1.5 / 2.5).abs() < 1e-9 );
+
+    nb.clear_label_smoothing("veggie");
+    nb.train();
+    assert!( (nb.top_words_for_label("veggie", 1)[0].1 - kale).abs() < 1e-9 );
+
+    assert_eq!( nb.set_label_smoothing("veggie", 0.0), Err(ClassifierError::InvalidSmoothing(0.0)) );
+}
+
+#[test]
+fn tune_smoothing_test() {
+    let validation = examples(&[("beef", "meat"), ("pork", "meat"), ("kale", "veggie")]);
+
+    // heavy smoothing washes out the word counts
+    let mut nb = classifier();
+    nb.set_smoothing(100.0).unwrap();
+    nb.train();
+    assert_eq!( nb.classify("beef").unwrap(), "veggie" );
+
+    let best = nb.tune_smoothing(&validation, &[100.0, 0.01, 1.0]).unwrap();
+    assert_eq!( best, 0.01 );
+    assert_eq!( nb.classify("beef").unwrap(), "meat" );
+
+    // nothing to compare leaves the smoothing alone
+    assert_eq!( nb.tune_smoothing(&[] as &[(&str, &str)], &[5.0]).unwrap(), 0.01 );
+    assert_eq!( nb.tune_smoothing(&validation, &[1.0, -1.0]), Err(ClassifierError::InvalidSmoothing(-1.0)) );
+    assert_eq!( NaiveBayes::new().tune_smoothing(&validation, &[1.0]), Err(ClassifierError::Untrained) );
+}
@@ -0,0 +1,62 @@
+extern crate classifier;
+use classifier::NaiveBayes;
+
+#[test]
+fn stats_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("pork pork beef salt", "meat");
+    nb.add_document("pork ham", "meat");
+    nb.add_document("kale okra salt", "veggie");
+    nb.set_label_smoothing("veggie", 0.5).unwrap();
+    nb.train();
+
+    let stats = nb.stats();
+    assert_eq!( stats.vocab_size, 6 );
+    assert_eq!( stats.num_labels, 2 );
+    assert_eq!( stats.num_examples, 3.0 );
+    assert_eq!( stats.smoothing, 1.0 );
+    assert_eq!( stats.labels.iter().map(|l| l.label.as_str()).collect::<Vec<_>>(), vec!["meat", "veggie"] );
+
+    let meat = stats.label("meat").unwrap();
+    assert_eq!( meat.examples, 2.0 );
+    assert_eq!( meat.tokens, 6.0 );
+    assert_eq!( meat.distinct_words, 4 );
+    assert_eq!( meat.smoothing, 1.0 );
+    assert_eq!( stats.label("veggie").unwrap().smoothing, 0.5 );
+    assert!( stats.label("fruit").is_none() );
+
+    // more words take up more memory
+    let before = stats.memory_bytes;
+    nb.add_document("spinach arugula watercress chard collards", "veggie");
+    assert!( nb.stats().memory_bytes > before );
+}
+
+#[test]
+fn empty_stats_test() {
+    let stats = NaiveBayes::new().stats();
+    assert_eq!( stats.vocab_size, 0 );
+    assert_eq!( stats.num_labels, 0 );
+    assert!( stats.labels.is_empty() );
+    assert!( stats.memory_bytes > 0 );
+}
+
+#[test]
+fn estimated_memory_bytes_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("pork belly ribs", "meat");
+    nb.train();
+    let base = nb.estimated_memory_bytes();
+    assert_eq!( nb.stats().memory_bytes, base );
+
+    // documents kept by ID, stop words, and label metadata all count
+    let mut with_id = NaiveBayes::new();
+    with_id.add_document_with_id("doc-1", "pork belly ribs", "meat").unwrap();
+    with_id.train();
+    assert!( with_id.estimated_memory_bytes() > base );
+
+    nb.set_stop_words(["the", "a", "and"]);
+    let with_stop_words = nb.estimated_memory_bytes();
+    assert!( with_stop_words > base );
+    nb.set_label_metadata("meat", "description", "cuts of beef, pork, and lamb");
+    assert!( nb.estimated_memory_bytes() > with_stop_words );
+}
@@ -0,0 +1,33 @@
+extern crate classifier;
+use classifier::{NaiveBayes, StemmingTokenizer, WhitespaceTokenizer};
+
+#[test]
+fn custom_stemmer_test() {
+    let stemmer = |word: &str| word.trim_end_matches("ing").trim_end_matches('s').to_string();
+    let mut nb = NaiveBayes::with_tokenizer(StemmingTokenizer::new(WhitespaceTokenizer, stemmer));
+    nb.add_document("grilling steaks", "meat");
+    nb.add_document("steaming carrots", "veggie");
+    nb.train();
+
+    assert_eq!( nb.classify("grill steak").unwrap(), "meat" );
+    assert_eq!( nb.classify("steam carrot").unwrap(), "veggie" );
+}
+
+#[cfg(feature = "stemming")]
+#[test]
+fn snowball_stemmer_test() {
+    use classifier::{SnowballStemmer, StemmingAlgorithm, Tokenizer};
+
+    let tokenizer = StemmingTokenizer::new(WhitespaceTokenizer, SnowballStemmer::english());
+    assert_eq!( tokenizer.tokenize("running runs run"), vec!["run", "run", "run"] );
+
+    let french = StemmingTokenizer::new(WhitespaceTokenizer, SnowballStemmer::new(StemmingAlgorithm::French));
+    assert_eq!( french.tokenize("continuellement"), vec!["continuel"] );
+
+    let mut nb = NaiveBayes::with_tokenizer(tokenizer);
+    nb.add_document("running jumping", "sport");
+    nb.add_document("cooking baking", "kitchen");
+    nb.train();
+    assert_eq!( nb.classify("runs jumps").unwrap(), "sport" );
+    assert_eq!( nb.classify("cooks bakes").unwrap(), "kitchen" );
+}
@@ -0,0 +1,35 @@
+extern crate classifier;
+use classifier::NaiveBayes;
+
+#[test]
+fn stop_words_test() {
+
+    let mut nb = NaiveBayes::new();
+    nb.set_stop_words(&["the".to_string(), "and".to_string()]);
+
+    // "the" and "and" would otherwise make up most of the meat vocabulary
+    nb.add_document("the ham and the pork and the beef and the veal", "meat");
+    nb.add_document("kale spinach okra", "veggie");
+    nb.train();
+
+    assert_eq!( nb.classify("the kale and the spinach").unwrap(), "veggie" );
+
+    // stop words survive exporting and reimporting the classifier
+    let nb2 = NaiveBayes::from_json( &nb.to_json() ).unwrap();
+    assert_eq!( nb2.classify("the kale and the spinach").unwrap(), "veggie" );
+}
+
+#[cfg(feature = "stop-words")]
+#[test]
+fn english_stop_words_test() {
+    use classifier::stop_words;
+
+    let mut nb = NaiveBayes::new();
+    nb.set_stop_words(stop_words::english());
+
+    nb.add_document("the ham and the pork and the beef and the veal", "meat");
+    nb.add_document("kale spinach okra", "veggie");
+    nb.train();
+
+    assert_eq!( nb.classify("the kale and the spinach").unwrap(), "veggie" );
+}
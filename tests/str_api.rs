@@ -0,0 +1,58 @@
+extern crate classifier;
+use classifier::evaluation::ConfusionMatrix;
+use classifier::{dataset, validation, LogisticRegression, NaiveBayes};
+
+// string slices go everywhere owned strings do
+#[test]
+fn naive_bayes_str_test() {
+    let mut nb = NaiveBayes::new();
+    nb.set_stop_words(["the", "a"]);
+    nb.add_documents(&[("the pork belly", "meat"), ("a kale okra", "veggie")]);
+    nb.add_documents_tokenized(&[(vec!["ham", "hock"], "meat")]);
+    nb.add_document_tokenized(&["spinach", "okra"], "veggie");
+    nb.train();
+
+    assert_eq!( nb.classify_tokenized(&["pork", "ham"]).unwrap(), "meat" );
+    assert_eq!( nb.get_document_posteriors_tokenized(&["kale"]).unwrap()[1].0, "veggie" );
+    assert_eq!( nb.classify_tokenized(&["the"]), nb.classify("the") );
+    let labels: Vec<String> = nb.classify_batch(&["pork", "okra"]).into_iter().map(Result::unwrap).collect();
+    assert_eq!( labels, vec!["meat", "veggie"] );
+
+    let mut tuned = nb.clone();
+    assert!( tuned.tune_smoothing(&[("pork", "meat")], &[0.5, 1.0]).is_ok() );
+    assert!( nb.remove_document_tokenized(&["ham", "hock"], "meat").is_ok() );
+}
+
+#[test]
+fn owned_strings_still_work_test() {
+    let stop_words = vec!["the".to_string()];
+    let examples = vec![("the pork belly".to_string(), "meat".to_string()),
+                        ("kale okra".to_string(), "veggie".to_string())];
+    let mut nb = NaiveBayes::new();
+    nb.set_stop_words(&stop_words);
+    nb.add_documents(&examples);
+    nb.train();
+    let tokens = vec!["pork".to_string()];
+    assert_eq!( nb.classify_tokenized(&tokens).unwrap(), "meat" );
+}
+
+#[test]
+fn helpers_str_test() {
+    let examples = [("pork belly", "meat"), ("ham hock", "meat"), ("kale okra", "veggie"), ("okra spinach", "veggie")];
+    let (train, test) = dataset::stratified_train_test_split(&examples, 0.5, 1).unwrap();
+    assert_eq!( (train.len(), test.len()), (2, 2) );
+    assert_eq!( validation::k_fold_cross_validate(&examples, 2).unwrap().folds.len(), 2 );
+
+    let mut jsonl = Vec::new();
+    dataset::to_jsonl(&mut jsonl, &examples).unwrap();
+    assert_eq!( dataset::from_jsonl(&jsonl[..]).count(), 4 );
+
+    let matrix = ConfusionMatrix::from_pairs(&[("meat", "meat"), ("meat", "veggie")]);
+    assert_eq!( matrix.accuracy(), 0.5 );
+
+    let mut lr = LogisticRegression::new();
+    lr.set_stop_words(["the"]);
+    lr.add_documents(&examples);
+    lr.train();
+    assert_eq!( lr.classify_tokenized(&["pork", "belly"]).unwrap(), "meat" );
+}
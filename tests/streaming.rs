@@ -0,0 +1,47 @@
+extern crate classifier;
+use classifier::NaiveBayes;
+use classifier::dataset;
+
+fn examples() -> Vec<(String, String)> {
+    vec![
+        ("sirloin pastrami ham hock pork belly".to_string(), "meat".to_string()),
+        ("okra kale spinach water chestnut".to_string(), "veggie".to_string()),
+        ("ball tip pork chop short ribs".to_string(), "meat".to_string()),
+    ]
+}
+
+#[test]
+fn add_documents_iter_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_documents(&examples());
+    nb.train();
+
+    // documents streamed straight from a JSONL reader give the same model
+    let mut buffer = Vec::new();
+    dataset::to_jsonl(&mut buffer, &examples()).unwrap();
+    let mut streamed = NaiveBayes::new();
+    streamed.add_documents_iter(dataset::from_jsonl(&buffer[..]).map(|record| record.unwrap()));
+    streamed.train();
+    assert_eq!( streamed.to_json().len(), nb.to_json().len() );
+    assert_eq!( streamed.vocab_size(), nb.vocab_size() );
+    assert_eq!( streamed.get_document_posteriors("pork kale").unwrap().len(), 2 );
+
+    // borrowed documents work too
+    let mut borrowed = NaiveBayes::new();
+    borrowed.add_documents_iter(examples().iter().map(|(d, l)| (d.as_str(), l.as_str())));
+    borrowed.train();
+    assert_eq!( borrowed.vocab_size(), nb.vocab_size() );
+}
+
+#[test]
+fn extend_test() {
+    let mut nb = NaiveBayes::new();
+    nb.extend(vec![("pork belly", "meat"), ("kale okra", "veggie")]);
+    nb.extend(Some(("beef ribs", "meat")));
+    nb.train();
+
+    let mut labels = nb.get_labels();
+    labels.sort();
+    assert_eq!( labels, vec!["meat", "veggie"] );
+    assert_eq!( nb.classify("beef").unwrap(), "meat" );
+}
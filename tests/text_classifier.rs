@@ -0,0 +1,44 @@
+extern crate classifier;
+use classifier::{ClassifierError, LogisticRegression, NaiveBayes, TextClassifier};
+
+fn models() -> Vec<Box<dyn TextClassifier>> {
+    vec![Box::new(NaiveBayes::new()), Box::new(LogisticRegression::new())]
+}
+
+// code written once against the trait
+fn fit(model: &mut dyn TextClassifier, examples: &[(&str, &str)]) {
+    for &(document, label) in examples.iter() {
+        model.add_document(document, label);
+    }
+    model.train();
+}
+
+#[test]
+fn shared_interface_test() {
+    let examples = [
+        ("sirloin pastrami ham hock pork belly", "meat"),
+        ("ball tip pork chop short ribs", "meat"),
+        ("okra kale spinach water chestnut", "veggie"),
+        ("green bean okra kale", "veggie"),
+    ];
+    for mut model in models() {
+        fit(&mut *model, &examples);
+        assert_eq!( model.classify("pork chop").unwrap(), "meat" );
+        assert_eq!( model.classify("kale").unwrap(), "veggie" );
+
+        let mut labels = model.labels();
+        labels.sort();
+        assert_eq!( labels, vec!["meat", "veggie"] );
+
+        let probabilities = model.probabilities("okra").unwrap();
+        assert!( (probabilities.iter().map(|&(_, p)| p).sum::<f64>() - 1.0).abs() < 1e-9 );
+    }
+}
+
+#[test]
+fn untrained_test() {
+    for model in models() {
+        assert_eq!( model.classify("pork"), Err(ClassifierError::Untrained) );
+        assert_eq!( model.probabilities("pork"), Err(ClassifierError::Untrained) );
+    }
+}
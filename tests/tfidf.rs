@@ -0,0 +1,38 @@
+extern crate classifier;
+use classifier::{NaiveBayes, FeatureWeighting};
+
+fn add_examples(nb: &mut NaiveBayes) {
+    // "food" shows up in every document, mostly in the meat documents
+    nb.add_document("food food food food food food ham", "meat");
+    nb.add_document("food food food food food food beef", "meat");
+    nb.add_document("food kale spinach", "veggie");
+    nb.add_document("food okra kale", "veggie");
+}
+
+#[test]
+fn tfidf_test() {
+
+    // with raw counts the frequent word drowns out "kale"
+    let mut counts = NaiveBayes::new();
+    add_examples(&mut counts);
+    counts.train();
+    assert_eq!( counts.classify("food food kale").unwrap(), "meat" );
+
+    // with tf-idf the common word counts for less
+    let mut tfidf = NaiveBayes::new();
+    tfidf.set_feature_weighting(FeatureWeighting::TfIdf);
+    add_examples(&mut tfidf);
+    tfidf.train();
+    assert_eq!( tfidf.classify("food food kale").unwrap(), "veggie" );
+
+    // the weighting survives exporting and reimporting the classifier
+    let tfidf2 = NaiveBayes::from_json( &tfidf.to_json() ).unwrap();
+    assert_eq!( tfidf2.classify("food food kale").unwrap(), "veggie" );
+
+    // online scoring agrees with the trained model
+    let mut online = NaiveBayes::new();
+    online.set_feature_weighting(FeatureWeighting::TfIdf);
+    online.set_online(true);
+    add_examples(&mut online);
+    assert_eq!( online.classify("food food kale").unwrap(), "veggie" );
+}
@@ -0,0 +1,25 @@
+extern crate classifier;
+use classifier::{ClassifierError, NaiveBayes};
+
+#[test]
+fn classify_with_threshold_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+    nb.add_document("ball tip pork chop short ribs", "meat");
+    nb.add_document("okra kale spinach water chestnut", "veggie");
+    nb.add_document("green bean okra kale arugula", "veggie");
+    nb.train();
+
+    // a clearly meaty document passes a high threshold
+    assert_eq!( nb.classify_with_threshold("pork belly short ribs", 0.9).unwrap(), Some("meat".to_string()) );
+
+    // a document with no known words is a coin flip, so it is routed away
+    assert_eq!( nb.classify_with_threshold("lorem ipsum", 0.9).unwrap(), None );
+    assert!( nb.classify_with_threshold("lorem ipsum", 0.0).unwrap().is_some() );
+
+    let tokens = vec!["kale".to_string(), "okra".to_string()];
+    assert_eq!( nb.classify_with_threshold_tokenized(&tokens, 0.5).unwrap(), Some("veggie".to_string()) );
+
+    // an untrained classifier has nothing to offer
+    assert_eq!( NaiveBayes::new().classify_with_threshold("kale", 0.0), Err(ClassifierError::Untrained) );
+}
@@ -0,0 +1,93 @@
+extern crate classifier;
+use classifier::{CharNgramTokenizer, ClassifierError, NaiveBayes, Tokenizer, WhitespaceTokenizer};
+
+// splits on commas instead of whitespace
+struct CommaTokenizer;
+
+impl Tokenizer for CommaTokenizer {
+    fn tokenize(&self, document: &str) -> Vec<String> {
+        document.split(',').map(|s| s.trim().to_string()).collect()
+    }
+}
+
+#[test]
+fn whitespace_tokenizer_test() {
+    let tokens = WhitespaceTokenizer.tokenize("pork chop shank");
+    assert_eq!(tokens, vec!["pork", "chop", "shank"]);
+
+    // runs of whitespace give no empty tokens
+    let tokens = WhitespaceTokenizer.tokenize("  pork \t\n chop  shank\n");
+    assert_eq!(tokens, vec!["pork", "chop", "shank"]);
+    assert!(WhitespaceTokenizer.tokenize(" \t ").is_empty());
+}
+
+#[test]
+fn vocabulary_has_only_words_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("  pork   belly\t\tribs ", "meat");
+    nb.add_document("\nokra  kale\n\nspinach\n", "veggie");
+    nb.train();
+    let words: Vec<&str> = nb.vocabulary().collect();
+    assert_eq!( words, vec!["belly", "kale", "okra", "pork", "ribs", "spinach"] );
+}
+
+#[test]
+fn custom_tokenizer_test() {
+
+    // create a new classifier that breaks documents on commas
+    let mut nb = NaiveBayes::with_tokenizer(CommaTokenizer);
+
+    let examples = [
+        ("spring onion, water spinach, fava bean, bush tomato", "veggie"),
+        ("ham hock, ball tip, pork belly, short ribs", "meat"),
+        ("green bean, black-eyed pea, sweet pepper, spring onion", "veggie"),
+        ("pork chop, beef ribs, short loin, ham hock", "meat"),
+    ];
+
+    for &(document, label) in examples.iter() {
+        nb.add_document(document, label);
+    }
+
+    nb.train();
+
+    // "short ribs" is only a single word with this tokenizer
+    let food_sentence = "short ribs, ham hock".to_string();
+    assert_eq!( nb.classify(&food_sentence).unwrap(), "meat" );
+
+    // the tokenizer has to be given back when reimporting the classifier
+    let nb2 = NaiveBayes::from_json_with_tokenizer(&nb.to_json(), CommaTokenizer).unwrap();
+    assert_eq!( nb2.classify(&food_sentence).unwrap(), "meat" );
+}
+
+#[test]
+fn closure_tokenizer_test() {
+
+    // any closure from &str to Vec<String> is a tokenizer
+    let mut nb = NaiveBayes::with_tokenizer(|document: &str| -> Vec<String> {
+        document.chars().filter(|c| !c.is_whitespace()).map(|c| c.to_string()).collect()
+    });
+
+    nb.add_document("牛肉 猪肉 鸡肉", "meat");
+    nb.add_document("白菜 菠菜 芹菜", "veggie");
+
+    nb.train();
+
+    assert_eq!( nb.classify("肉").unwrap(), "meat" );
+    assert_eq!( nb.classify("菜").unwrap(), "veggie" );
+}
+
+#[test]
+fn char_ngram_tokenizer_test() {
+    let tokenizer = CharNgramTokenizer::new(3, 3).unwrap();
+    assert_eq!( tokenizer.tokenize("okra  a"), vec!["<ok", "okr", "kra", "ra>", "<a>"] );
+    assert_eq!( CharNgramTokenizer::new(2, 1), Err(ClassifierError::InvalidNgramRange(2, 1)) );
+    assert_eq!( CharNgramTokenizer::new(0, 3), Err(ClassifierError::InvalidNgramRange(0, 3)) );
+
+    // misspelled words still share most of their n-grams with the training words
+    let mut nb = NaiveBayes::with_tokenizer(CharNgramTokenizer::default());
+    nb.add_document("pastrami sirloin brisket", "meat");
+    nb.add_document("spinach artichoke broccoli", "veggie");
+    nb.train();
+    assert_eq!( nb.classify("pastrammi").unwrap(), "meat" );
+    assert_eq!( nb.classify("brocolli spinnach").unwrap(), "veggie" );
+}
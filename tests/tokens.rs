@@ -0,0 +1,29 @@
+extern crate classifier;
+use classifier::NaiveBayes;
+
+#[test]
+fn borrowed_tokens_test() {
+    let buffer = "pork belly ribs|kale okra spinach|pork kale";
+    let mut nb = NaiveBayes::new();
+    let mut documents = buffer.split('|');
+    nb.add_document_tokens(documents.next().unwrap().split(' '), "meat");
+    nb.add_document_tokens(documents.next().unwrap().split(' '), "veggie");
+    nb.train();
+
+    let query = documents.next().unwrap();
+    assert_eq!( nb.classify_tokens(query.split(' ')).unwrap(), nb.classify(query).unwrap() );
+    assert_eq!( nb.get_document_posteriors_tokens(query.split(' ')).unwrap(),
+                nb.get_document_posteriors(query).unwrap() );
+    assert_eq!( nb.classify_tokens("ribs belly".split(' ')).unwrap(), "meat" );
+}
+
+#[test]
+fn borrowed_tokens_ngrams_test() {
+    let mut nb = NaiveBayes::new();
+    nb.set_ngram_range(1, 2).unwrap();
+    nb.add_document_tokens(vec!["pork", "belly"], "meat");
+    nb.add_document_tokens(vec!["water", "chestnut"], "veggie");
+    nb.train();
+    assert_eq!( nb.classify_tokens(vec!["water", "chestnut"]).unwrap(), "veggie" );
+    assert_eq!( nb.vocab_size(), 6 );
+}
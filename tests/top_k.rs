@@ -0,0 +1,35 @@
+extern crate classifier;
+use classifier::NaiveBayes;
+
+#[test]
+fn classify_top_k_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+    nb.add_document("okra kale spinach water chestnut", "veggie");
+    nb.add_document("apple banana pear", "fruit");
+    nb.add_document("salmon tuna cod", "fish");
+    nb.train();
+
+    let top = nb.classify_top_k("pork belly kale", 3).unwrap();
+    assert_eq!( top.len(), 3 );
+    assert_eq!( top[0].0, "meat" );
+    assert_eq!( top[1].0, "veggie" );
+    assert!( top[0].1 >= top[1].1 && top[1].1 >= top[2].1 );
+
+    // asking for more labels than there are gives all of them
+    assert_eq!( nb.classify_top_k("pork", 10).unwrap().len(), 4 );
+    assert!( nb.classify_top_k("pork", 0).unwrap().is_empty() );
+}
+
+#[test]
+fn ties_test() {
+    let mut nb = NaiveBayes::new();
+    for label in ["delta", "alpha", "charlie", "bravo"].iter() {
+        nb.add_document("same words", label);
+    }
+    nb.train();
+
+    // every label is equally likely, so they come back in order of name
+    let top: Vec<String> = nb.classify_top_k("same", 4).unwrap().into_iter().map(|(l, _)| l).collect();
+    assert_eq!( top, vec!["alpha", "bravo", "charlie", "delta"] );
+}
@@ -0,0 +1,73 @@
+#![cfg(feature = "tracing")]
+
+extern crate classifier;
+extern crate tracing;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use classifier::{LogisticRegression, NaiveBayes};
+use tracing::{Event, Metadata, Subscriber};
+use tracing::span::{Attributes, Id, Record};
+
+// records the names of the spans opened and the number of events
+#[derive(Default, Clone)]
+struct Recorder {
+    spans: Arc<Mutex<Vec<String>>>,
+    events: Arc<AtomicU64>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl Subscriber for Recorder {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes) -> Id {
+        self.spans.lock().unwrap().push(span.metadata().name().to_string());
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event) {
+        self.events.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn naive_bayes_spans_test() {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let mut nb = NaiveBayes::new();
+        nb.add_document("pork belly", "meat");
+        nb.add_document("kale okra", "veggie");
+        nb.train();
+        nb.classify("pork").unwrap();
+        nb.get_document_posteriors("kale").unwrap();
+    });
+    assert_eq!( *recorder.spans.lock().unwrap(),
+                vec!["add_document", "add_document", "train", "classify", "posteriors"] );
+}
+
+#[test]
+fn logistic_spans_test() {
+    let recorder = Recorder::default();
+    tracing::subscriber::with_default(recorder.clone(), || {
+        let mut lr = LogisticRegression::new();
+        lr.add_document("pork belly", "meat");
+        lr.add_document("kale okra", "veggie");
+        lr.set_epochs(3);
+        lr.train();
+        lr.classify("pork").unwrap();
+    });
+    let spans = recorder.spans.lock().unwrap();
+    assert_eq!( spans[..3], ["add_document", "add_document", "train"] );
+    assert!( spans.contains(&"classify".to_string()) );
+    // an event for each epoch
+    assert_eq!( recorder.events.load(Ordering::Relaxed), 3 );
+}
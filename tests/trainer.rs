@@ -0,0 +1,42 @@
+extern crate classifier;
+use classifier::{NaiveBayes, NaiveBayesModel, NaiveBayesTrainer};
+
+fn trainer() -> NaiveBayesTrainer {
+    let mut trainer = NaiveBayesTrainer::new();
+    trainer.add_document("sirloin pastrami ham hock pork belly", "meat");
+    trainer.add_document("okra kale spinach water chestnut", "veggie");
+    trainer
+}
+
+#[test]
+fn trainer_model_test() {
+    let model = trainer().into_model();
+    assert_eq!( model.classify("pork belly").unwrap(), "meat" );
+
+    let mut labels = model.get_labels();
+    labels.sort();
+    assert_eq!( labels, vec!["meat", "veggie"] );
+
+    // the model goes back to being a trainer to learn more
+    let mut trainer = model.into_trainer();
+    trainer.add_document("tofu tempeh", "vegan");
+    let model = trainer.into_model();
+    assert_eq!( model.classify("tofu").unwrap(), "vegan" );
+}
+
+#[test]
+fn model_serialization_test() {
+    let model = trainer().into_model();
+
+    // a saved model loads as either a model or a classifier
+    let encoded = model.to_json();
+    let model2 = NaiveBayesModel::from_json(&encoded).unwrap();
+    let nb = NaiveBayes::from_json(&encoded).unwrap();
+    assert_eq!( model2.classify("kale").unwrap(), "veggie" );
+    assert_eq!( nb.classify("kale").unwrap(), "veggie" );
+
+    let mut buffer = Vec::new();
+    model.to_writer(&mut buffer).unwrap();
+    let model3 = NaiveBayesModel::from_reader(&buffer[..]).unwrap();
+    assert_eq!( model3.classify("ham").unwrap(), "meat" );
+}
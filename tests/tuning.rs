@@ -0,0 +1,77 @@
+extern crate classifier;
+use classifier::{ClassifierError, FeatureWeighting, NaiveBayes, NaiveBayesVariant};
+use classifier::tuning::{self, Configuration, ParameterGrid};
+
+fn examples() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("the ribs and the pork", "meat"),
+        ("kale and okra", "veggie"),
+        ("the ribs and the bacon", "meat"),
+        ("kale and spinach", "veggie"),
+        ("the the ribs", "meat"),
+        ("the the the the kale", "veggie"),
+    ]
+}
+
+#[test]
+fn configurations_test() {
+    let grid = ParameterGrid::new();
+    assert_eq!(grid.configurations(), vec![Configuration {
+        smoothing: 1.0,
+        ngram_range: (1, 1),
+        stop_words: Vec::new(),
+        weighting: FeatureWeighting::Counts,
+    }]);
+
+    let grid = grid.smoothing(&[0.5, 1.0, 2.0]).stop_words(&["the"]).weightings(&[FeatureWeighting::TfIdf]);
+    let configurations = grid.configurations();
+    assert_eq!(configurations.len(), 6);
+    assert_eq!(configurations[1].smoothing, 0.5);
+    assert_eq!(configurations[1].stop_words, vec!["the"]);
+    assert!(ParameterGrid::new().smoothing(&[]).configurations().is_empty());
+}
+
+#[test]
+fn grid_search_test() {
+    let grid = ParameterGrid::new().smoothing(&[0.1, 1.0]).stop_words(&["the", "and"]);
+    let report = tuning::grid_search(&examples(), &grid, 3).unwrap();
+    assert_eq!(report.results.len(), 4);
+    for (result, configuration) in report.results.iter().zip(grid.configurations()) {
+        assert_eq!(result.configuration, configuration);
+        assert_eq!(result.report.folds.len(), 3);
+    }
+
+    let best = report.best().unwrap();
+    assert!(report.results.iter().all(|result| result.report.average.accuracy <= best.report.average.accuracy));
+    assert_eq!(best.configuration.stop_words, vec!["the", "and"]);
+
+    // the best configuration can be given to a classifier
+    let mut nb = NaiveBayes::new();
+    best.configuration.apply(&mut nb).unwrap();
+    for &(document, label) in examples().iter() {
+        nb.add_document(document, label);
+    }
+    nb.train();
+    assert_eq!(nb.classify("the the the the ribs").unwrap(), "meat");
+
+    assert_eq!(tuning::grid_search_parallel(&examples(), &grid, 3).unwrap(), report);
+}
+
+#[test]
+fn grid_search_with_test() {
+    let grid = ParameterGrid::new().ngram_ranges(&[(1, 1), (1, 2)]);
+    let report = tuning::grid_search_with(&examples(), &grid, 2, true, || {
+        NaiveBayes::with_variant(NaiveBayesVariant::Bernoulli)
+    }).unwrap();
+    assert_eq!(report.results.len(), 2);
+    assert!(tuning::grid_search(&examples(), &ParameterGrid::new().weightings(&[]), 2).unwrap().best().is_none());
+}
+
+#[test]
+fn grid_search_errors_test() {
+    let grid = ParameterGrid::new().smoothing(&[1.0, -1.0]);
+    assert_eq!(tuning::grid_search(&examples(), &grid, 2), Err(ClassifierError::InvalidSmoothing(-1.0)));
+    let grid = ParameterGrid::new().ngram_ranges(&[(2, 1)]);
+    assert_eq!(tuning::grid_search(&examples(), &grid, 2), Err(ClassifierError::InvalidNgramRange(2, 1)));
+    assert_eq!(tuning::grid_search(&examples(), &ParameterGrid::new(), 1), Err(ClassifierError::InvalidFoldCount(1)));
+}
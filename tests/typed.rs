@@ -0,0 +1,117 @@
+extern crate classifier;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde_json;
+use classifier::{ClassifierError, NaiveBayes, TypedNaiveBayes};
+
+// labels need no string conversions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum Food {
+    Veggie,
+    Meat,
+    Fruit,
+}
+
+fn trained() -> TypedNaiveBayes<Food> {
+    let mut nb = TypedNaiveBayes::new();
+    nb.add_document("pork belly ribs", &Food::Meat);
+    nb.add_document("ham hock", &Food::Meat);
+    nb.add_document_tokenized(&["kale", "okra"], &Food::Veggie);
+    nb.train();
+    nb
+}
+
+#[test]
+fn classify_test() {
+    let nb = trained();
+    assert_eq!( nb.classify("pork ham").unwrap(), Food::Meat );
+    assert_eq!( nb.classify_tokenized(&["okra"]).unwrap(), Food::Veggie );
+    // in the order the labels were first added
+    assert_eq!( nb.get_labels().unwrap(), vec![Food::Meat, Food::Veggie] );
+
+    let posteriors = nb.get_document_posteriors("kale").unwrap();
+    assert_eq!( posteriors[1].0, Food::Veggie );
+    assert!( posteriors[1].1 > posteriors[0].1 );
+    assert_eq!( nb.classify(""), Err(ClassifierError::EmptyDocument) );
+}
+
+#[test]
+fn serialization_test() {
+    let nb = trained();
+    let decoded: TypedNaiveBayes<Food> = TypedNaiveBayes::from_json(&nb.to_json()).unwrap();
+    assert_eq!( decoded.classify("ribs").unwrap(), Food::Meat );
+    assert_eq!( decoded.get_labels(), nb.get_labels() );
+
+    let mut encoded: serde_json::Value = serde_json::from_str(&nb.to_json()).unwrap();
+    assert_eq!( encoded["labels"], serde_json::json!(["Meat", "Veggie"]) );
+    encoded["labels"] = serde_json::json!(["Meat", "Meat"]);
+    assert!( TypedNaiveBayes::<Food>::from_json(&encoded.to_string()).is_err() );
+}
+
+#[test]
+fn from_classifier_test() {
+    let mut untyped = NaiveBayes::new();
+    untyped.set_smoothing(0.5).unwrap();
+    let mut nb: TypedNaiveBayes<Food> = TypedNaiveBayes::from_classifier(untyped).unwrap();
+    nb.add_document("pork", &Food::Meat);
+    nb.add_document("kale", &Food::Veggie);
+    nb.train();
+    assert_eq!( nb.classifier().get_labels(), vec!["00000000000000000000", "00000000000000000001"] );
+    assert_eq!( nb.classify("pork").unwrap(), Food::Meat );
+
+    // the labels of a classifier with documents stand for no typed label
+    let mut untyped = NaiveBayes::new();
+    untyped.add_document("apple pear", "fruit");
+    assert_eq!( TypedNaiveBayes::<Food>::from_classifier(untyped).unwrap_err(),
+                ClassifierError::InvalidLabel("fruit".to_string()) );
+}
+
+#[test]
+fn relabel_test() {
+    let mut nb = trained();
+    nb.rename_label(&Food::Veggie, &Food::Fruit).unwrap();
+    assert_eq!( nb.get_labels().unwrap(), vec![Food::Meat, Food::Fruit] );
+    assert_eq!( nb.classify("okra").unwrap(), Food::Fruit );
+
+    nb.merge_labels(&[Food::Meat, Food::Fruit], &Food::Meat).unwrap();
+    assert_eq!( nb.get_labels().unwrap(), vec![Food::Meat] );
+    assert_eq!( nb.classify("okra").unwrap(), Food::Meat );
+
+    let mut nb = trained();
+    nb.remove_label(&Food::Meat).unwrap();
+    nb.train();
+    assert_eq!( nb.get_labels().unwrap(), vec![Food::Veggie] );
+    assert_eq!( nb.classify("okra").unwrap(), Food::Veggie );
+    assert!( nb.remove_label(&Food::Meat).is_err() );
+    assert!( nb.rename_label(&Food::Fruit, &Food::Meat).is_err() );
+
+    // a label whose documents are all removed is gone, and comes back in
+    // its place
+    nb.remove_document_tokenized(&["kale", "okra"], &Food::Veggie).unwrap();
+    assert!( nb.get_labels().unwrap().is_empty() );
+    nb.add_document("pork", &Food::Meat);
+    nb.add_document("kale", &Food::Veggie);
+    nb.train();
+    assert_eq!( nb.get_labels().unwrap(), vec![Food::Meat, Food::Veggie] );
+}
+
+#[test]
+fn tie_test() {
+    // more labels than one digit can number, all fitting equally well
+    let mut nb: TypedNaiveBayes<usize> = TypedNaiveBayes::new();
+    for label in [2, 10, 7, 1, 0, 3, 4, 5, 6, 8, 9, 11].iter() {
+        nb.add_document("pork", label);
+    }
+    nb.train();
+    assert_eq!( nb.classify("pork").unwrap(), 2 );
+    assert_eq!( nb.get_labels().unwrap(), vec![2, 10, 7, 1, 0, 3, 4, 5, 6, 8, 9, 11] );
+}
+
+#[test]
+fn string_labels_test() {
+    let mut nb: TypedNaiveBayes = TypedNaiveBayes::new();
+    nb.add_document("pork", &"meat".to_string());
+    nb.add_document("kale", &"veggie".to_string());
+    nb.train();
+    assert_eq!( nb.classify("kale").unwrap(), "veggie" );
+}
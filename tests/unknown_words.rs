@@ -0,0 +1,59 @@
+extern crate classifier;
+use classifier::{ClassifierError, NaiveBayes, UnknownWordPolicy};
+
+fn trained_classifier() -> NaiveBayes {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("pork", "meat");
+    nb.add_document("kale okra spinach chard leek beet kale", "veggie");
+    nb.train();
+    nb
+}
+
+#[test]
+fn default_probability_test() {
+    let mut nb = trained_classifier();
+    let document = "kale lorem ipsum dolor";
+
+    // only kale counts by default
+    assert_eq!( nb.classify(document).unwrap(), "veggie" );
+
+    // scored as unseen words, the unknown words favor the label with fewer
+    // words, which gives unseen words more probability
+    nb.set_unknown_word_policy(UnknownWordPolicy::DefaultProbability).unwrap();
+    assert_eq!( nb.classify(document).unwrap(), "meat" );
+
+    // the policy is kept when the classifier is saved
+    let nb2 = NaiveBayes::from_json(&nb.to_json()).unwrap();
+    assert_eq!( nb2.classify(document).unwrap(), "meat" );
+
+    // the explanation lists the unknown words after the known ones
+    let explanation = nb.explain(document).unwrap();
+    let words: Vec<&str> = explanation.labels[0].contributions.iter().map(|(w, _)| w.as_str()).collect();
+    assert_eq!( words, vec!["kale", "lorem", "ipsum", "dolor"] );
+}
+
+#[test]
+fn penalize_per_token_test() {
+    let mut nb = trained_classifier();
+    let document = "kale lorem ipsum";
+    let ignored = nb.explain(document).unwrap();
+
+    nb.set_unknown_word_policy(UnknownWordPolicy::PenalizePerToken(2.0)).unwrap();
+    let penalized = nb.explain(document).unwrap();
+
+    // every label loses the same amount, so the posteriors don't change
+    for label in ignored.labels.iter() {
+        let other = penalized.label(&label.label).unwrap();
+        assert!( (label.score - 4.0 - other.score).abs() < 1e-9 );
+        assert!( (label.posterior - other.posterior).abs() < 1e-9 );
+    }
+    assert_eq!( nb.classify(document).unwrap(), "veggie" );
+}
+
+#[test]
+fn invalid_penalty_test() {
+    let mut nb = NaiveBayes::new();
+    assert_eq!( nb.set_unknown_word_policy(UnknownWordPolicy::PenalizePerToken(-1.0)),
+                Err(ClassifierError::InvalidPenalty(-1.0)) );
+    assert!( nb.set_unknown_word_policy(UnknownWordPolicy::PenalizePerToken(0.0)).is_ok() );
+}
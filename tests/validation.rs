@@ -0,0 +1,55 @@
+extern crate classifier;
+use classifier::{NaiveBayes, ClassifierError};
+use classifier::validation;
+
+fn examples() -> Vec<(String, String)> {
+    let examples = [
+        ("sirloin pastrami ham hock pork belly", "meat"),
+        ("ball tip pork chop short ribs", "meat"),
+        ("salami pancetta beef ribs", "meat"),
+        ("ham hock landjaeger pork belly", "meat"),
+        ("okra kale spinach water chestnut", "veggie"),
+        ("green bean okra kale", "veggie"),
+        ("spinach arugula pea sprouts", "veggie"),
+        ("water chestnut pea sprouts arugula", "veggie"),
+    ];
+    examples.iter().map(|&(d, l)| (d.to_string(), l.to_string())).collect()
+}
+
+#[test]
+fn k_fold_test() {
+    let report = validation::k_fold_cross_validate(&examples(), 4).unwrap();
+    assert_eq!(report.folds.len(), 4);
+    assert!(report.average.accuracy > 0.5);
+    for fold in report.folds.iter() {
+        assert!(fold.accuracy >= 0.0 && fold.accuracy <= 1.0);
+        assert!(fold.f1 >= 0.0 && fold.f1 <= 1.0);
+    }
+}
+
+#[test]
+fn stratified_k_fold_test() {
+    let report = validation::stratified_k_fold_cross_validate(&examples(), 4).unwrap();
+    assert_eq!(report.folds.len(), 4);
+    assert!(report.average.accuracy > 0.5);
+    assert!(report.average.precision > 0.5);
+    assert!(report.average.recall > 0.5);
+}
+
+#[test]
+fn cross_validate_with_test() {
+    let report = validation::cross_validate_with(&examples(), 2, true, || {
+        let mut nb = NaiveBayes::new();
+        nb.set_smoothing(0.5).unwrap();
+        nb
+    }).unwrap();
+    assert_eq!(report.folds.len(), 2);
+}
+
+#[test]
+fn invalid_fold_count_test() {
+    assert_eq!( validation::k_fold_cross_validate(&examples(), 1),
+                Err(ClassifierError::InvalidFoldCount(1)) );
+    assert_eq!( validation::k_fold_cross_validate(&examples(), 9),
+                Err(ClassifierError::InvalidFoldCount(9)) );
+}
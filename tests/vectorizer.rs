@@ -0,0 +1,73 @@
+extern crate classifier;
+use classifier::{ClassifierError, NaiveBayes, NaiveBayesVariant, UnknownWordPolicy};
+
+fn trained(variant: NaiveBayesVariant, online: bool) -> NaiveBayes {
+    let mut nb = NaiveBayes::with_variant(variant);
+    nb.set_online(online);
+    nb.set_unknown_word_policy(UnknownWordPolicy::DefaultProbability).unwrap();
+    nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+    nb.add_document("okra kale spinach water chestnut", "veggie");
+    nb.add_document("apple pear okra", "fruit");
+    nb.train();
+    nb
+}
+
+#[test]
+fn vectorizer_matches_classifier_test() {
+    for &variant in [NaiveBayesVariant::Multinomial, NaiveBayesVariant::Bernoulli, NaiveBayesVariant::Complement].iter() {
+        for &online in [false, true].iter() {
+            let nb = trained(variant, online);
+            let vectorizer = nb.vectorizer();
+            for document in ["pork belly pork belly", "okra okra kale durian", "pear durian durian"].iter() {
+                let vector = vectorizer.vectorize(document);
+                assert_eq!( vectorizer.classify(&vector).unwrap(), nb.classify(document).unwrap() );
+                let expected = nb.get_document_posteriors(document).unwrap();
+                for ((label, p), (label2, p2)) in expected.iter().zip(vectorizer.get_posteriors(&vector).unwrap()) {
+                    assert_eq!( *label, label2 );
+                    assert!( (p - p2).abs() < 1e-12 );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn vector_counts_test() {
+    let nb = trained(NaiveBayesVariant::Multinomial, false);
+    let vectorizer = nb.vectorizer();
+
+    let vector = vectorizer.vectorize("pork pork pork okra durian durian");
+    assert_eq!( vector.distinct_words(), 2 );
+    assert_eq!( vector.unknown_features(), 2 );
+    assert_eq!( vector, vectorizer.vectorize_tokenized(&["okra", "pork", "durian", "pork", "durian", "pork"]) );
+
+    let empty = vectorizer.vectorize("");
+    assert!( empty.is_empty() );
+    assert_eq!( vectorizer.classify(&empty), Err(ClassifierError::EmptyDocument) );
+
+    let untrained = NaiveBayes::new();
+    let vector = untrained.vectorizer().vectorize("pork");
+    assert_eq!( untrained.vectorizer().classify(&vector), Err(ClassifierError::Untrained) );
+}
+
+#[test]
+fn sparse_vector_test() {
+    let nb = trained(NaiveBayesVariant::Multinomial, false);
+    let vectorizer = nb.vectorizer();
+    let mapping = vectorizer.vocabulary_mapping();
+    assert_eq!( mapping.len(), 13 );
+    assert_eq!( vectorizer.dimension(), 13 );
+    let id = |word: &str| mapping.iter().find(|&&(_, w)| w == word).unwrap().0;
+
+    let vector = vectorizer.vectorize("pork pork okra durian");
+    let sparse = vector.to_sparse();
+    assert_eq!( sparse.len(), 2 );
+    assert!( sparse.contains(&(id("pork"), 2.0)) && sparse.contains(&(id("okra"), 1.0)) );
+
+    let mut shuffled = vec![(id("okra"), 1.0), (id("pork"), 1.5), (99, 1.0), (id("pork"), 0.5), (id("kale"), 0.0)];
+    shuffled.reverse();
+    let rebuilt = vectorizer.from_sparse(&shuffled);
+    assert_eq!( rebuilt, vector );
+    assert_eq!( vectorizer.classify(&rebuilt).unwrap(), nb.classify("pork pork okra durian").unwrap() );
+    assert!( vectorizer.from_sparse(&[]).is_empty() );
+}
@@ -0,0 +1,16 @@
+#![cfg(feature = "wasm")]
+extern crate classifier;
+use classifier::WasmClassifier;
+
+#[test]
+fn wasm_classifier_test() {
+    let mut nb = WasmClassifier::new();
+    nb.add_document("sirloin pastrami ham hock pork belly", "meat");
+    nb.add_document("okra kale spinach water chestnut", "veggie");
+    nb.train();
+    assert_eq!( nb.classify("pork belly").unwrap(), "meat" );
+
+    let copy = WasmClassifier::from_json(&nb.to_json()).unwrap();
+    assert_eq!( copy.classify("kale").unwrap(), "veggie" );
+    assert_eq!( copy.classifier().vocab_size(), nb.classifier().vocab_size() );
+}
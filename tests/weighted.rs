@@ -0,0 +1,53 @@
+extern crate classifier;
+use classifier::{NaiveBayes, NaiveBayesVariant, ClassifierError};
+
+#[test]
+fn weighted_document_test() {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("beef", "meat");
+    nb.add_document_weighted("beef", "veggie", 0.5).unwrap();
+    nb.add_document_weighted("kale", "veggie", 2.5).unwrap();
+    nb.train();
+
+    // a weight of 0.5 counts for half of a document
+    let explanation = nb.explain("beef").unwrap();
+    assert!( (explanation.label("meat").unwrap().prior - 0.25f64.ln()).abs() < 1e-9 );
+    assert!( (explanation.label("veggie").unwrap().prior - 0.75f64.ln()).abs() < 1e-9 );
+
+    // word counts are weighted too, (2.5 + 1) / (3 + 2) for "kale"
+    let words = nb.top_words_for_label("veggie", 1);
+    assert_eq!( words[0].0, "kale" );
+    assert!( (words[0].1 - 3.5 / 5.0).abs() < 1e-9 );
+}
+
+#[test]
+fn weights_match_repeats_test() {
+    // a document with a weight of 3 is the same as adding it 3 times
+    for &variant in [NaiveBayesVariant::Multinomial, NaiveBayesVariant::Bernoulli, NaiveBayesVariant::Complement].iter() {
+        let mut weighted = NaiveBayes::with_variant(variant);
+        weighted.add_document_weighted("pork beef pork", "meat", 3.0).unwrap();
+        weighted.add_document("kale okra", "veggie");
+        weighted.train();
+
+        let mut repeated = NaiveBayes::with_variant(variant);
+        for _ in 0..3 {
+            repeated.add_document("pork beef pork", "meat");
+        }
+        repeated.add_document("kale okra", "veggie");
+        repeated.train();
+
+        let mut expected = repeated.get_document_posteriors("pork okra").unwrap();
+        let mut posteriors = weighted.get_document_posteriors("pork okra").unwrap();
+        expected.sort_by(|a, b| a.0.cmp(&b.0));
+        posteriors.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!( posteriors, expected );
+    }
+}
+
+#[test]
+fn invalid_weight_test() {
+    let mut nb = NaiveBayes::new();
+    assert_eq!( nb.add_document_weighted("beef", "meat", 0.0), Err(ClassifierError::InvalidWeight(0.0)) );
+    assert!( nb.add_document_weighted("beef", "meat", -1.0).is_err() );
+    assert!( nb.get_labels().is_empty() );
+}
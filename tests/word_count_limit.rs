@@ -0,0 +1,71 @@
+extern crate classifier;
+use classifier::{NaiveBayes, ClassifierError};
+
+fn assert_close(expected: &[(String, f64)], actual: &[(String, f64)]) {
+    for (&(ref label, p), &(ref actual_label, actual_p)) in expected.iter().zip(actual.iter()) {
+        assert_eq!( label, actual_label );
+        assert!( (p - actual_p).abs() < 1e-9 );
+    }
+}
+
+fn spam_classifier() -> NaiveBayes {
+    let mut nb = NaiveBayes::new();
+    nb.add_document("free money now click", "spam");
+    nb.add_document("win free prizes click here", "spam");
+    nb.add_document("meeting notes for the project review", "ham");
+    nb.add_document("lunch meeting moved to the afternoon", "ham");
+    nb
+}
+
+#[test]
+fn max_word_count_test() {
+    let document = "free free free free free free free free meeting project review notes";
+
+    let mut nb = spam_classifier();
+    nb.train();
+    assert_eq!( nb.classify(document).unwrap(), "spam" );
+
+    // counting "free" at most twice lets the rest of the document decide
+    let mut capped = spam_classifier();
+    capped.set_max_word_count(2).unwrap();
+    capped.train();
+    assert_eq!( capped.classify(document).unwrap(), "ham" );
+    assert_eq!( capped.classify("free free").unwrap(), "spam" );
+
+    // the limit is kept by serialization and by frozen models
+    let decoded = NaiveBayes::from_json(&capped.to_json()).unwrap();
+    let expected = capped.get_document_posteriors(document).unwrap();
+    assert_close(&expected, &decoded.get_document_posteriors(document).unwrap());
+    assert_close(&expected, &decoded.freeze().get_document_posteriors(document).unwrap());
+    assert_eq!( capped.to_inference_model().unwrap_err(),
+                ClassifierError::UnsupportedSetting("word count limits above 1".to_string()) );
+}
+
+#[test]
+fn word_presence_test() {
+    let mut nb = spam_classifier();
+    nb.set_max_word_count(1).unwrap();
+    // repeated words in training count once, so this adds one "free"
+    nb.add_document("free free free free free lunch", "ham");
+    nb.train();
+    assert_eq!( nb.get_document_posteriors("free").unwrap(), nb.get_document_posteriors("free free free").unwrap() );
+
+    // a document is removed with the counts it was added with
+    nb.remove_document("free free free free free lunch", "ham").unwrap();
+    nb.train();
+    assert_eq!( nb.classify("free").unwrap(), "spam" );
+
+    // a limit of 1 can be reproduced by an inference model
+    let model = nb.to_inference_model().unwrap();
+    assert_eq!( model.classify("free free free meeting").unwrap(), nb.classify("free free free meeting").unwrap() );
+
+    nb.clear_max_word_count();
+    nb.train();
+    assert!( nb.get_document_posteriors("free").unwrap() != nb.get_document_posteriors("free free free").unwrap() );
+}
+
+#[test]
+fn invalid_max_word_count_test() {
+    let mut nb = NaiveBayes::new();
+    assert_eq!( nb.set_max_word_count(0), Err(ClassifierError::InvalidWordCount(0)) );
+}